@@ -0,0 +1,85 @@
+-- Add migration script here
+
+-- 编辑审核工作流迁移
+-- 目标：文章在发布前需经过审核，状态流转为
+--   draft -> in_review -> changes_requested -> approved -> published
+-- 设计原则：审核状态与文章本身分开存储，每一次状态流转都记录操作人和审核意见
+
+-- ================================
+-- 第一部分：数据库结构
+-- ================================
+
+-- 文章当前的审核状态（每篇文章最多一条记录，没有记录时视为 draft）
+CREATE TABLE IF NOT EXISTS post_reviews (
+    post_id     UUID PRIMARY KEY REFERENCES posts(id) ON DELETE CASCADE,
+    -- 当前审核状态
+    status      TEXT NOT NULL DEFAULT 'draft'
+        CHECK (status IN ('draft', 'in_review', 'changes_requested', 'approved', 'published')),
+    -- 指定的审核人，审核人被删除时置空，由其他审核人接手
+    reviewer_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER set_post_reviews_timestamp
+    BEFORE UPDATE
+    ON post_reviews
+    FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+CREATE INDEX IF NOT EXISTS idx_post_reviews_status ON post_reviews (status);
+CREATE INDEX IF NOT EXISTS idx_post_reviews_reviewer_id ON post_reviews (reviewer_id);
+
+-- 审核状态流转记录（包含每次流转时的审核意见）
+CREATE TABLE IF NOT EXISTS post_review_transitions (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    post_id     UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    from_status TEXT NOT NULL,
+    to_status   TEXT NOT NULL,
+    -- 执行流转的用户（作者提交 / 审核人审核 / 发布者发布）
+    actor_id    UUID REFERENCES users(id) ON DELETE SET NULL,
+    -- 审核意见或提交说明
+    comment     TEXT,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_review_transitions_post_id ON post_review_transitions (post_id);
+
+-- 已发布的文章直接标记为 published，避免撤回后重新发布时被审核流程卡住历史数据
+INSERT INTO post_reviews (post_id, status)
+SELECT id, 'published'
+FROM posts
+WHERE published_at IS NOT NULL
+ON CONFLICT (post_id) DO NOTHING;
+
+-- ================================
+-- 第二部分：审核相关权限
+-- ================================
+
+INSERT INTO permissions (id, name, description)
+VALUES
+    (gen_random_uuid(), 'post:submit_review', '提交文章审核（持有该权限但没有 post:review 的角色必须经审核才能发布）'),
+    (gen_random_uuid(), 'post:review', '审核文章：指派审核人、通过或要求修改')
+ON CONFLICT (name) DO NOTHING;
+
+-- author、moderator: 发布前必须提交审核
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name IN ('author', 'moderator')
+  AND p.name = 'post:submit_review'
+  AND NOT EXISTS (
+    SELECT 1 FROM role_permissions rp
+    WHERE rp.role_id = r.id AND rp.permission_id = p.id
+  );
+
+-- editor、admin、superadmin: 可以提交审核，也可以审核他人的文章
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name IN ('editor', 'admin', 'superadmin')
+  AND p.name IN ('post:submit_review', 'post:review')
+  AND NOT EXISTS (
+    SELECT 1 FROM role_permissions rp
+    WHERE rp.role_id = r.id AND rp.permission_id = p.id
+  );
@@ -0,0 +1,45 @@
+-- Add migration script here
+
+-- 草稿评审意见（行内评论）
+-- 目标：被分享草稿的协作者可以直接在草稿上留下意见，而不是通过聊天工具沟通
+-- 设计原则：
+--   1. 顶层评论锚定到一段文本范围（字符偏移）或一个标题
+--   2. 回复只挂在顶层评论下，形成单层讨论串，回复继承顶层评论的锚点
+--   3. 解决/重新打开作用于整个讨论串（顶层评论）
+
+CREATE TABLE IF NOT EXISTS draft_comments (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    post_id        UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    author_id      UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- 回复所属的顶层评论，顶层评论为 NULL
+    parent_id      UUID REFERENCES draft_comments(id) ON DELETE CASCADE,
+    -- 锚点类型：'range' 文本范围 / 'heading' 标题；回复为 NULL
+    anchor_type    TEXT CHECK (anchor_type IN ('range', 'heading')),
+    -- 文本范围锚点：基于 Markdown 原文的字符偏移 [start, end)
+    anchor_start   INTEGER,
+    anchor_end     INTEGER,
+    -- 标题锚点：标题文本
+    anchor_heading TEXT,
+    -- 创建评论时被引用的原文片段，原文修改后仍可定位上下文
+    quoted_text    TEXT,
+    body           TEXT NOT NULL,
+    resolved_at    TIMESTAMPTZ,
+    resolved_by    UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- 顶层评论必须有锚点，回复不能有锚点
+    CONSTRAINT draft_comments_anchor_check CHECK (
+        (parent_id IS NULL AND anchor_type IS NOT NULL)
+        OR (parent_id IS NOT NULL AND anchor_type IS NULL)
+    )
+);
+
+CREATE TRIGGER set_draft_comments_timestamp
+    BEFORE UPDATE
+    ON draft_comments
+    FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+CREATE INDEX IF NOT EXISTS idx_draft_comments_post_id ON draft_comments (post_id);
+CREATE INDEX IF NOT EXISTS idx_draft_comments_parent_id ON draft_comments (parent_id);
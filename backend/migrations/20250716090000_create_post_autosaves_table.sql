@@ -0,0 +1,28 @@
+-- Add migration script here
+
+-- 草稿自动保存
+-- 目标：编辑器每隔几秒自动保存，但不覆盖用户最后一次显式保存的内容
+-- 设计原则：
+--   1. 每个用户对每篇文章只有一个自动保存槽位，后一次自动保存覆盖前一次
+--   2. 自动保存只属于保存它的用户，分享草稿的查看者永远看不到
+--   3. 显式保存文章后删除该用户的自动保存；过期的自动保存由后台任务定期清理
+
+CREATE TABLE IF NOT EXISTS post_autosaves (
+    post_id    UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    title      TEXT NOT NULL,
+    content    TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (post_id, user_id)
+);
+
+CREATE TRIGGER set_post_autosaves_timestamp
+    BEFORE UPDATE
+    ON post_autosaves
+    FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+-- 清理任务按更新时间查找过期的自动保存
+CREATE INDEX IF NOT EXISTS idx_post_autosaves_updated_at ON post_autosaves (updated_at);
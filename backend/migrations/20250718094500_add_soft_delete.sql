@@ -0,0 +1,32 @@
+-- Add migration script here
+
+-- 回收站：文章、分类、标签改为软删除
+-- 目标：误删的内容可以从回收站恢复，超过保留期后再由后台任务彻底清除
+-- 设计原则：
+--   1. deleted_at 不为 NULL 表示已移入回收站，所有现有的查询都会排除这些记录
+--   2. 软删除时保留文章与分类/标签的关联，恢复后关联随之恢复
+--   3. 彻底清除时走原有的 ON DELETE CASCADE，关联记录自动删除
+--   4. 名称和 slug 只在未删除的记录之间唯一：回收站中的 "rust" 标签不妨碍重新创建 "rust"，
+--      恢复时如果名称或 slug 已被占用则拒绝恢复
+
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE tags ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- 回收站列表和清理任务只关心已删除的记录
+CREATE INDEX IF NOT EXISTS idx_posts_deleted_at ON posts (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_categories_deleted_at ON categories (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_tags_deleted_at ON tags (deleted_at) WHERE deleted_at IS NOT NULL;
+
+-- 原有的列级 UNIQUE 约束改为只覆盖未删除记录的部分唯一索引
+ALTER TABLE posts DROP CONSTRAINT IF EXISTS posts_slug_key;
+ALTER TABLE categories DROP CONSTRAINT IF EXISTS categories_name_key;
+ALTER TABLE categories DROP CONSTRAINT IF EXISTS categories_slug_key;
+ALTER TABLE tags DROP CONSTRAINT IF EXISTS tags_name_key;
+ALTER TABLE tags DROP CONSTRAINT IF EXISTS tags_slug_key;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_posts_slug_live ON posts (slug) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_categories_name_live ON categories (name) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_categories_slug_live ON categories (slug) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_tags_name_live ON tags (name) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_tags_slug_live ON tags (slug) WHERE deleted_at IS NULL;
@@ -0,0 +1,30 @@
+-- Add migration script here
+
+-- 草稿预览链接
+-- 目标：把草稿分享给没有账号的外部审阅者或客户，只读预览
+-- 设计原则：
+--   1. 链接令牌由服务端签名（包含链接 ID 与过期时间），数据库只保存链接元数据，不保存令牌
+--   2. 链接必须有过期时间，作者可以随时撤销（revoked_at），撤销与过期的链接都不可用
+--   3. 每次通过链接访问都记录到 draft_access_logs，访问者没有账号，因此 accessed_by 为空
+
+CREATE TABLE IF NOT EXISTS post_preview_links (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    post_id      UUID        NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    created_by   UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    label        TEXT,                      -- 备注，例如 "客户审阅"
+    expires_at   TIMESTAMPTZ NOT NULL,
+    revoked_at   TIMESTAMPTZ,
+    use_count    INTEGER     NOT NULL DEFAULT 0,
+    last_used_at TIMESTAMPTZ,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_preview_links_post_id ON post_preview_links (post_id);
+
+-- 通过预览链接的访问没有对应的用户，记录使用的链接
+ALTER TABLE draft_access_logs
+    ALTER COLUMN accessed_by DROP NOT NULL,
+    ADD COLUMN IF NOT EXISTS preview_link_id UUID REFERENCES post_preview_links(id) ON DELETE SET NULL;
+
+COMMENT ON TABLE post_preview_links IS '草稿预览链接表：签名、可过期、可撤销的匿名只读预览';
+COMMENT ON COLUMN draft_access_logs.preview_link_id IS '通过预览链接访问时使用的链接，此时 accessed_by 为空';
@@ -0,0 +1,60 @@
+-- Add migration script here
+
+-- 草稿分享按接收人设置角色和有效期
+-- 目标：posts.draft_shared_with 是一个 UUID 数组，分享只能整体开/关且永久有效
+-- 设计原则：
+--   1. 每个接收人一条记录，角色为 viewer（只读）、commenter（可评论）、editor（可编辑草稿内容）
+--   2. expires_at 为空表示永久有效；过期的分享在所有查询中视为不存在
+--   3. 现有数组中的分享迁移为 commenter 角色，与原来的实际权限（查看 + 评论）一致
+--   4. 文章查询不再附带接收人列表，只在草稿和分享相关的路径按需查询 draft_shares；
+--      提供 is_draft_shared_with() 函数，按主键判断草稿是否有效地分享给了某个用户
+
+CREATE TABLE IF NOT EXISTS draft_shares (
+    post_id    UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role       TEXT NOT NULL DEFAULT 'viewer' CHECK (role IN ('viewer', 'commenter', 'editor')),
+    expires_at TIMESTAMPTZ,
+    shared_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (post_id, user_id)
+);
+
+CREATE TRIGGER set_draft_shares_timestamp
+    BEFORE UPDATE
+    ON draft_shares
+    FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
+
+-- "分享给我的草稿" 按接收人查询
+CREATE INDEX IF NOT EXISTS idx_draft_shares_user_id ON draft_shares (user_id);
+
+-- 迁移现有分享数据（忽略已不存在的用户）
+INSERT INTO draft_shares (post_id, user_id, role, shared_by)
+SELECT p.id, u.id, 'commenter', p.author_id
+FROM posts p
+CROSS JOIN LATERAL unnest(p.draft_shared_with) AS s(user_id)
+JOIN users u ON u.id = s.user_id
+WHERE p.draft_shared_with IS NOT NULL
+ON CONFLICT (post_id, user_id) DO NOTHING;
+
+ALTER TABLE posts DROP COLUMN IF EXISTS draft_shared_with;
+
+-- 草稿是否分享给了该用户且分享未过期
+CREATE OR REPLACE FUNCTION is_draft_shared_with(p_post_id UUID, p_user_id UUID)
+    RETURNS BOOLEAN
+    LANGUAGE sql
+    STABLE
+AS
+$$
+SELECT EXISTS(
+    SELECT 1
+    FROM draft_shares
+    WHERE post_id = p_post_id
+      AND user_id = p_user_id
+      AND (expires_at IS NULL OR expires_at > NOW())
+)
+$$;
+
+COMMENT ON TABLE draft_shares IS '草稿分享表：每个接收人的角色（viewer/commenter/editor）和可选的有效期';
@@ -0,0 +1,19 @@
+-- Add migration script here
+
+-- 用户通知偏好
+-- 设计原则：
+--   1. 每个用户最多一行，没有记录时视为接收所有通知
+--   2. 每种通知一个布尔列，新增通知类型时追加列并默认开启
+
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id      UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    draft_shared BOOLEAN NOT NULL DEFAULT TRUE, -- 有人分享草稿给我时发送邮件
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER set_notification_preferences_timestamp
+    BEFORE UPDATE
+    ON notification_preferences
+    FOR EACH ROW
+EXECUTE FUNCTION trigger_set_timestamp();
@@ -0,0 +1,42 @@
+-- Add migration script here
+
+-- 文章封禁记录与申诉
+-- 设计原则：
+--   1. posts.is_banned 仍然是文章是否被封禁的唯一判断依据，post_bans 记录每一次封禁的经过
+--   2. 每篇文章同一时间最多一条生效中的封禁记录（lifted_at 为空），解封时填写 lifted_by / lifted_at
+--   3. 作者对每次封禁只能申诉一次，由拥有 post:ban 权限的版主维持封禁或解封
+
+CREATE TABLE IF NOT EXISTS post_bans (
+    id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    post_id    UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    banned_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    reason     TEXT,
+    banned_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    lifted_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    lifted_at  TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_post_bans_active ON post_bans (post_id) WHERE lifted_at IS NULL;
+
+-- 为已经被封禁的文章补一条没有版主和原因的记录
+INSERT INTO post_bans (post_id, banned_at)
+SELECT id, updated_at
+FROM posts
+WHERE is_banned = TRUE;
+
+CREATE TABLE IF NOT EXISTS post_ban_appeals (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    ban_id          UUID NOT NULL UNIQUE REFERENCES post_bans(id) ON DELETE CASCADE,
+    post_id         UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    appellant_id    UUID REFERENCES users(id) ON DELETE SET NULL,
+    message         TEXT NOT NULL,
+    status          VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'upheld', 'unbanned')),
+    resolved_by     UUID REFERENCES users(id) ON DELETE SET NULL,
+    resolution_note TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at     TIMESTAMPTZ
+);
+
+-- 版主申诉队列按状态和提交时间查询
+CREATE INDEX IF NOT EXISTS idx_post_ban_appeals_status ON post_ban_appeals (status, created_at);
@@ -0,0 +1,45 @@
+-- Add migration script here
+
+-- 读者举报
+-- 设计原则：
+--   1. 游客和登录用户都可以举报已发布的文章，举报人用 reporter_key 标识：
+--      登录用户为 "user:{id}"，游客为客户端 IP 的带密钥哈希，不保存原始 IP
+--   2. 同一举报人对同一篇文章同时只能有一条待处理的举报，并按 reporter_key 限制举报频率
+--   3. 版主按文章聚合处理举报：驳回（dismissed）或封禁文章（actioned）
+
+CREATE TABLE IF NOT EXISTS post_reports (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    post_id      UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    reporter_id  UUID REFERENCES users(id) ON DELETE SET NULL,
+    reporter_key TEXT NOT NULL,
+    category     VARCHAR(20) NOT NULL DEFAULT 'other'
+        CHECK (category IN ('spam', 'abuse', 'illegal', 'copyright', 'other')),
+    details      TEXT,
+    status       VARCHAR(20) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'dismissed', 'actioned')),
+    resolved_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    resolved_at  TIMESTAMPTZ,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_post_reports_open_per_reporter
+    ON post_reports (post_id, reporter_key) WHERE status = 'open';
+
+-- 频率限制按举报人和时间查询
+CREATE INDEX IF NOT EXISTS idx_post_reports_reporter_key ON post_reports (reporter_key, created_at);
+
+-- 举报队列按文章聚合待处理的举报
+CREATE INDEX IF NOT EXISTS idx_post_reports_open ON post_reports (post_id) WHERE status = 'open';
+
+-- 处理举报的权限，与 post:ban 一样只授予管理员
+INSERT INTO permissions (id, name, description)
+VALUES
+    (gen_random_uuid(), 'post:report:review', '查看和处理读者举报')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name IN ('admin', 'superadmin')
+  AND p.name = 'post:report:review'
+ON CONFLICT DO NOTHING;
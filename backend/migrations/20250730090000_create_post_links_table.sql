@@ -0,0 +1,19 @@
+-- Add migration script here
+
+-- 文章之间的维基链接（[[slug]] / [[slug|text]]）
+-- 设计原则：
+--   1. 保存文章或更新正文时，按正文中的维基链接整体替换该文章的出链
+--   2. 链接按目标 slug 记录，与渲染时按 slug 解析的规则一致：
+--      目标文章之后才创建时，已有的链接自动生效
+--   3. "哪些文章链接到这里"只统计已发布、未封禁且未删除的来源文章，在查询时过滤
+
+CREATE TABLE IF NOT EXISTS post_links (
+    source_post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    target_slug    VARCHAR(255) NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (source_post_id, target_slug)
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_links_target_slug ON post_links (target_slug);
+
+-- 已有文章的正文在首次保存后才会建立链接，这里不做回填
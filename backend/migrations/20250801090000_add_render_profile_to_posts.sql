@@ -0,0 +1,6 @@
+-- Add migration script here
+-- 文章的 Markdown 渲染方案
+-- 设计原则：
+-- 1. 只保存方案名称，方案的具体内容（启用的扩展、是否允许 HTML 等）由配置文件定义
+-- 2. 为空时使用配置中的默认方案，已有文章无需迁移数据
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS render_profile VARCHAR(50);
@@ -0,0 +1,16 @@
+-- Add migration script here
+-- 使用允许原始 HTML 的渲染方案的权限
+-- 设计原则：
+-- 1. 原始 HTML 渲染后按白名单清理，但仍只开放给可信的作者
+-- 2. 默认授予管理员和编辑，其他资深作者可以通过角色管理单独授予
+INSERT INTO permissions (id, name, description)
+VALUES
+    (gen_random_uuid(), 'post:raw_html', '在文章中使用经过清理的原始 HTML')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name IN ('admin', 'superadmin', 'editor')
+  AND p.name = 'post:raw_html'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,35 @@
+-- Add migration script here
+-- 文章模板：周报、发布说明等固定格式文章的起始骨架
+-- 设计原则：
+-- 1. owner_id 为空表示全局模板，所有作者可用；否则只有创建者本人可见
+-- 2. 默认分类和标签以数组保存，使用模板时忽略已删除的分类和标签，不需要级联维护
+-- 3. 同一作者的模板名称不能重复，全局模板之间名称也不能重复
+CREATE TABLE IF NOT EXISTS post_templates (
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id      UUID REFERENCES users(id) ON DELETE CASCADE,
+    name          VARCHAR(100) NOT NULL,
+    title_pattern VARCHAR(255) NOT NULL DEFAULT '',  -- 标题模式，支持 {date}、{year}、{month}、{day}、{week}
+    content       TEXT NOT NULL DEFAULT '',
+    category_ids  UUID[] NOT NULL DEFAULT '{}',
+    tag_ids       UUID[] NOT NULL DEFAULT '{}',
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_post_templates_owner_name
+    ON post_templates (owner_id, name) WHERE owner_id IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_post_templates_global_name
+    ON post_templates (name) WHERE owner_id IS NULL;
+
+-- 管理全局模板的权限，默认授予管理员和编辑
+INSERT INTO permissions (id, name, description)
+VALUES
+    (gen_random_uuid(), 'post:template:manage_global', '管理所有作者可用的全局文章模板')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name IN ('admin', 'superadmin', 'editor')
+  AND p.name = 'post:template:manage_global'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,64 @@
+-- Add migration script here
+-- 文章作者表：支持多人合写的文章
+-- 设计原则：
+-- 1. 每篇文章有且只有一个主作者，posts.author_id 始终等于主作者，保留以兼容现有查询
+-- 2. 触发器在 posts.author_id 变化时同步主作者记录，原主作者降为合著者
+-- 3. position 决定合著者的展示顺序，主作者总是排在最前
+CREATE TABLE IF NOT EXISTS post_authors (
+    post_id    UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    position   INTEGER NOT NULL DEFAULT 0,
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (post_id, user_id)
+);
+
+-- 每篇文章只能有一个主作者
+CREATE UNIQUE INDEX IF NOT EXISTS idx_post_authors_primary ON post_authors (post_id) WHERE is_primary;
+-- 按用户统计和筛选合写的文章
+CREATE INDEX IF NOT EXISTS idx_post_authors_user_id ON post_authors (user_id);
+
+-- 迁移现有文章的作者
+INSERT INTO post_authors (post_id, user_id, position, is_primary)
+SELECT id, author_id, 0, TRUE
+FROM posts
+WHERE author_id IS NOT NULL
+ON CONFLICT (post_id, user_id) DO NOTHING;
+
+-- posts.author_id 变化时同步主作者记录
+CREATE OR REPLACE FUNCTION sync_post_primary_author()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    UPDATE post_authors
+    SET is_primary = FALSE
+    WHERE post_id = NEW.id
+      AND is_primary
+      AND user_id IS DISTINCT FROM NEW.author_id;
+
+    IF NEW.author_id IS NOT NULL THEN
+        INSERT INTO post_authors (post_id, user_id, position, is_primary)
+        VALUES (NEW.id, NEW.author_id, 0, TRUE)
+        ON CONFLICT (post_id, user_id) DO UPDATE SET is_primary = TRUE, position = 0;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sync_post_primary_author
+    AFTER INSERT OR UPDATE OF author_id
+    ON posts
+    FOR EACH ROW
+EXECUTE FUNCTION sync_post_primary_author();
+
+-- 用户是否是文章的作者（主作者或合著者）
+CREATE OR REPLACE FUNCTION is_post_author(p_post_id UUID, p_user_id UUID)
+    RETURNS BOOLEAN
+    LANGUAGE sql
+    STABLE
+AS
+$$
+SELECT EXISTS(SELECT 1 FROM post_authors WHERE post_id = p_post_id AND user_id = p_user_id)
+$$;
+
+COMMENT ON TABLE post_authors IS '文章作者表：主作者和按 position 排序的合著者';
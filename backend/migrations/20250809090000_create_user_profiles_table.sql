@@ -0,0 +1,36 @@
+-- Add migration script here
+-- 用户资料表：显示名称、简介、头像、个人网站和社交链接，用于作者署名和个人资料
+-- 设计原则：
+-- 1. 资料与账户信息分表存储，users 表只保留登录相关的字段
+-- 2. 简介保存 Markdown 原文和渲染后的 HTML，读取时无需重复渲染
+-- 3. 上传的头像保存在数据库中，avatar_url 指向头像接口；也可以直接填写外部图片地址
+CREATE TABLE IF NOT EXISTS user_profiles (
+    user_id      UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    display_name VARCHAR(50),
+    bio          TEXT,
+    bio_html     TEXT,
+    avatar_url   VARCHAR(500),
+    website      VARCHAR(500),
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- 社交链接，每个平台最多一条，position 决定展示顺序
+CREATE TABLE IF NOT EXISTS user_social_links (
+    user_id  UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    platform VARCHAR(30) NOT NULL,
+    url      VARCHAR(500) NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, platform)
+);
+
+-- 用户上传的头像，每个用户只保留最新的一张
+CREATE TABLE IF NOT EXISTS user_avatars (
+    user_id      UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    content_type VARCHAR(50) NOT NULL,
+    data         BYTEA NOT NULL,
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE user_profiles IS '用户资料表：作者署名和个人资料页展示的信息';
+COMMENT ON TABLE user_social_links IS '用户社交链接表：每个平台一条，按 position 排序';
+COMMENT ON TABLE user_avatars IS '用户上传的头像';
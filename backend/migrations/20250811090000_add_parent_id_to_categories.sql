@@ -0,0 +1,43 @@
+-- Add migration script here
+-- 分类层级：分类可以有一个父分类，用于文档类博客的多级栏目
+-- 设计原则：
+-- 1. parent_id 为空表示顶级分类，现有分类全部保持为顶级分类
+-- 2. 触发器拒绝形成循环的父子关系，服务层会先给出更友好的提示
+-- 3. 彻底删除父分类时子分类变为顶级分类；软删除和合并时由服务层重新指定父分类
+ALTER TABLE categories
+    ADD COLUMN IF NOT EXISTS parent_id UUID REFERENCES categories(id) ON DELETE SET NULL;
+
+ALTER TABLE categories
+    ADD CONSTRAINT categories_parent_not_self CHECK (parent_id IS NULL OR parent_id <> id);
+
+-- 查询子分类和构建分类树
+CREATE INDEX IF NOT EXISTS idx_categories_parent_id ON categories (parent_id);
+
+-- 修改父分类时检查新的父分类不是自己的后代
+CREATE OR REPLACE FUNCTION prevent_category_cycle()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    IF NEW.parent_id IS NOT NULL AND EXISTS (
+        WITH RECURSIVE ancestors AS (
+            SELECT id, parent_id FROM categories WHERE id = NEW.parent_id
+            UNION
+            SELECT c.id, c.parent_id
+            FROM categories c
+            JOIN ancestors a ON c.id = a.parent_id
+        )
+        SELECT 1 FROM ancestors WHERE id = NEW.id
+    ) THEN
+        RAISE EXCEPTION '无效的父分类：分类 % 不能移到自己的子分类下', NEW.id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER prevent_category_cycle
+    BEFORE INSERT OR UPDATE OF parent_id
+    ON categories
+    FOR EACH ROW
+EXECUTE FUNCTION prevent_category_cycle();
+
+COMMENT ON COLUMN categories.parent_id IS '父分类，为空表示顶级分类';
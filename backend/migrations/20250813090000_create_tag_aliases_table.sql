@@ -0,0 +1,18 @@
+-- Add migration script here
+-- 标签别名表：把 "Rust"、"rust-lang" 这类写法统一指向同一个规范标签
+-- 设计原则：
+-- 1. 合并标签时，源标签的名称自动成为目标标签的别名，源标签原有的别名也一并转移
+-- 2. 别名的 slug 全局唯一；服务层保证别名不会与现有标签的 slug 重复
+-- 3. 规范标签被彻底删除时别名随之删除；软删除期间别名不参与解析
+CREATE TABLE IF NOT EXISTS tag_aliases (
+    id         UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    tag_id     UUID        NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    name       TEXT        NOT NULL,
+    slug       TEXT        NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- 列出某个标签的全部别名
+CREATE INDEX IF NOT EXISTS idx_tag_aliases_tag_id ON tag_aliases (tag_id);
+
+COMMENT ON TABLE tag_aliases IS '标签别名表：按名称或 slug 查找标签时解析到规范标签';
@@ -0,0 +1,7 @@
+-- Add migration script here
+-- 启用 pg_trgm 扩展：查找相似的标签和分类时计算名称的三元组相似度
+-- 设计原则：
+-- 1. 名称先在应用层统一为小写、去掉标点、中文转为拼音，再交给 similarity() 比较
+--    （pg_trgm 不会从中文字符中提取三元组，直接比较原始名称没有意义）
+-- 2. 比较的是临时传入的规范化名称，不需要在 tags / categories 上建立三元组索引
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
@@ -0,0 +1,58 @@
+-- Add migration script here
+-- 标签和分类推荐模型的版本号：推荐服务缓存训练好的模型，版本号变化时才重新训练
+-- 设计原则：
+-- 1. 版本号是一个序列，影响训练结果的表发生变化时由语句级触发器递增
+--    （文章的标题、内容、删除状态，标签和分类的名称、删除状态，标签别名，文章与标签/分类的关联）
+-- 2. 序列不受事务回滚影响，回滚只会让缓存多失效一次，不会让缓存的模型过期
+-- 3. 用序列而不是单行计数表，避免所有写入这些表的事务在同一行上排队
+CREATE SEQUENCE IF NOT EXISTS taxonomy_version_seq;
+
+-- 先取一次值，之后每次递增都能从 last_value 上看出来
+SELECT nextval('taxonomy_version_seq');
+
+CREATE OR REPLACE FUNCTION bump_taxonomy_version()
+    RETURNS TRIGGER AS
+$$
+BEGIN
+    PERFORM nextval('taxonomy_version_seq');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER bump_taxonomy_version
+    AFTER INSERT OR DELETE OR UPDATE OF title, content, deleted_at
+    ON posts
+    FOR EACH STATEMENT
+EXECUTE FUNCTION bump_taxonomy_version();
+
+CREATE TRIGGER bump_taxonomy_version
+    AFTER INSERT OR DELETE OR UPDATE OF name, deleted_at
+    ON tags
+    FOR EACH STATEMENT
+EXECUTE FUNCTION bump_taxonomy_version();
+
+CREATE TRIGGER bump_taxonomy_version
+    AFTER INSERT OR DELETE OR UPDATE OF name, deleted_at
+    ON categories
+    FOR EACH STATEMENT
+EXECUTE FUNCTION bump_taxonomy_version();
+
+CREATE TRIGGER bump_taxonomy_version
+    AFTER INSERT OR DELETE OR UPDATE
+    ON tag_aliases
+    FOR EACH STATEMENT
+EXECUTE FUNCTION bump_taxonomy_version();
+
+CREATE TRIGGER bump_taxonomy_version
+    AFTER INSERT OR DELETE OR UPDATE
+    ON post_tags
+    FOR EACH STATEMENT
+EXECUTE FUNCTION bump_taxonomy_version();
+
+CREATE TRIGGER bump_taxonomy_version
+    AFTER INSERT OR DELETE OR UPDATE
+    ON post_categories
+    FOR EACH STATEMENT
+EXECUTE FUNCTION bump_taxonomy_version();
+
+COMMENT ON SEQUENCE taxonomy_version_seq IS '标签和分类推荐模型的版本号，训练数据变化时递增';
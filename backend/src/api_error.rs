@@ -1,34 +1,49 @@
 use anyhow::Error as AnyhowError;
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use config::ConfigError;
 use serde_json::json;
 
+use crate::utils::VersionError;
+
 // 这个结构体用于在 handler 层捕获 anyhow::Error 并转换为响应
 pub struct ApiError(AnyhowError);
 
 // 辅助函数，用于检查错误链中是否包含特定关键词
+// 只匹配每一层错误的 Display 消息：anyhow 的 Debug 输出在开启 RUST_BACKTRACE 时会带上调用栈，
+// 栈中的函数名（如 get_post_by_id_with_permission）会导致误判
 fn error_chain_contains(error: &AnyhowError, keyword: &str) -> bool {
-    // 检查顶层错误消息
-    if format!("{:?}", error).to_lowercase().contains(keyword) {
-        return true;
-    }
-    // 遍历 source 链
-    let mut current_source = error.source();
-    while let Some(source) = current_source {
-        if format!("{:?}", source).to_lowercase().contains(keyword) {
-            return true;
-        }
-        current_source = source.source()
-    }
-    false
+    error
+        .chain()
+        .any(|cause| cause.to_string().to_lowercase().contains(keyword))
 }
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         // 记录完整的错误信息（使用Debug格式）以供调试
         tracing::error!("API层捕获到错误(debug): {:?}", self.0);
 
+        // --- 0. 乐观并发控制的版本错误 (428 / 412) ---
+        if let Some(version_error) = self.0.downcast_ref::<VersionError>() {
+            return match version_error {
+                VersionError::Missing => (
+                    StatusCode::PRECONDITION_REQUIRED,
+                    Json(json!({ "error": format!("{}", version_error) })),
+                )
+                    .into_response(),
+                // 返回服务器当前版本，前端据此提示用户合并修改
+                VersionError::Stale { current_version } => (
+                    StatusCode::PRECONDITION_FAILED,
+                    [(header::ETAG, format!("\"{}\"", current_version))],
+                    Json(json!({
+                        "error": format!("{}", version_error),
+                        "current_version": current_version,
+                    })),
+                )
+                    .into_response(),
+            };
+        }
+
         // --- 1. 认证 (401) 和授权 (403) 错误 ---
         if error_chain_contains(&self.0, "bearer token")
             || error_chain_contains(&self.0, "无效或过期的 token")
@@ -53,6 +68,15 @@ impl IntoResponse for ApiError {
                 .into_response();
         }
 
+        // 对 "过于频繁" 这类频率限制错误使用 429
+        if error_chain_contains(&self.0, "过于频繁") {
+            return (
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(json!({ "error": format!("{}", self.0) })),
+            )
+                .into_response();
+        }
+
         // --- 2. 数据库约束和冲突错误 ---
         if let Some(db_error) = self.0.downcast_ref::<sqlx::Error>() {
             if let Some(db_error_inner) = db_error.as_database_error() {
@@ -129,3 +153,42 @@ impl From<AnyhowError> for ApiError {
         ApiError(error.into())
     }
 } */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+
+    // Display 是业务错误消息，Debug 则像开启 RUST_BACKTRACE 后那样带有调用栈
+    struct ErrorWithBacktrace;
+
+    impl fmt::Display for ErrorWithBacktrace {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "未找到 ID 为 42 的帖子")
+        }
+    }
+
+    impl fmt::Debug for ErrorWithBacktrace {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "未找到 ID 为 42 的帖子\n\nStack backtrace:\n   0: backend::services::post::PostService::get_post_by_id_with_permission"
+            )
+        }
+    }
+
+    impl std::error::Error for ErrorWithBacktrace {}
+
+    #[test]
+    fn test_status_depends_on_messages_not_backtraces() {
+        let error = AnyhowError::new(ErrorWithBacktrace).context("获取文章失败");
+        assert!(!error_chain_contains(&error, "permission"));
+        assert_eq!(ApiError(error).into_response().status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_keywords_match_any_layer_of_the_chain() {
+        let error = anyhow::anyhow!("您只能编辑自己的帖子").context("更新文章失败");
+        assert_eq!(ApiError(error).into_response().status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -107,6 +107,16 @@ impl AuthUser {
         self.0.username.clone()
     }
 
+    // 获取用户角色
+    pub fn roles(&self) -> &[String] {
+        &self.0.roles
+    }
+
+    // 获取用户权限
+    pub fn permissions(&self) -> &[String] {
+        &self.0.permissions
+    }
+
     // 检查权限
     pub fn require_permission(&self, permission: &str) -> Result<(), ApiError> {
         if self.0.permissions.contains(&permission.to_string()) {
@@ -1,4 +1,4 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use config::{Config as ConfigRs, Environment, File};
 use serde::Deserialize;
 
@@ -10,6 +10,7 @@ pub struct EmailConfig {
     pub smtp_user: String,
     pub smtp_pass: String,
     pub from_address: String,
+    pub admin_base_url: String, // 后台管理前端地址，用于邮件中的链接
 }
 // 认证相关配置结构体
 #[derive(Debug, Deserialize, Clone)]
@@ -42,6 +43,141 @@ pub struct DraftPolicy {
     pub audit_draft_access: bool,
 }
 
+// 自动保存配置
+#[derive(Debug, Deserialize, Clone)]
+pub struct AutosaveConfig {
+    pub retention_days: i32,         // 自动保存保留天数，超过后被清理
+    pub purge_interval_minutes: u64, // 后台清理任务的执行间隔 分钟
+}
+
+// 回收站配置
+#[derive(Debug, Deserialize, Clone)]
+pub struct TrashConfig {
+    pub retention_days: i32,         // 回收站保留天数，超过后被彻底删除
+    pub purge_interval_minutes: u64, // 后台清理任务的执行间隔 分钟
+}
+
+// 读者举报配置
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReportConfig {
+    pub max_reports_per_hour: i64, // 每个举报人（用户或 IP）每小时最多提交的举报数
+    pub trust_forwarded_for: bool, // 是否信任 X-Forwarded-For 中的客户端 IP，仅在反向代理之后开启
+}
+
+// Markdown 渲染配置
+#[derive(Debug, Deserialize, Clone)]
+pub struct MarkdownConfig {
+    pub highlight_languages: Vec<String>, // 允许服务端高亮的代码语言，其余语言按普通代码块输出
+    pub line_numbers: bool,               // 是否把高亮代码块按行包裹，供前端显示行号
+    #[serde(default = "default_shortcodes")]
+    pub shortcodes: Vec<ShortcodeConfig>, // 允许使用的嵌入短代码，如 {{< youtube id >}}
+    pub default_profile: String,          // 文章未指定渲染方案时使用的方案
+    #[serde(default = "default_render_profiles")]
+    pub profiles: Vec<RenderProfileConfig>, // 可供文章选择的渲染方案
+}
+
+// Markdown 渲染方案配置，未列出的开关默认关闭
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RenderProfileConfig {
+    pub name: String, // 方案名称，保存在文章的 render_profile 字段中
+    #[serde(default)]
+    pub strikethrough: bool, // 删除线 ~~text~~
+    #[serde(default)]
+    pub table: bool, // 表格
+    #[serde(default)]
+    pub tasklist: bool, // 任务列表 - [ ]
+    #[serde(default)]
+    pub footnotes: bool, // 脚注
+    #[serde(default)]
+    pub description_lists: bool, // 描述列表
+    #[serde(default)]
+    pub math: bool, // $...$ 和 $$...$$ 数学公式
+    #[serde(default)]
+    pub wikilinks: bool, // [[slug]] 维基链接
+    #[serde(default)]
+    pub autolink: bool, // 自动识别裸露的网址和邮箱
+    #[serde(default)]
+    pub smart_punctuation: bool, // 智能标点：弯引号、破折号、省略号
+    #[serde(default)]
+    pub hard_breaks: bool, // 段落内的换行渲染为 <br />
+    #[serde(default)]
+    pub sanitized_html: bool, // 允许原始 HTML，渲染后按白名单清理；使用者需要 post:raw_html 权限
+    #[serde(default)]
+    pub allowed_roles: Vec<String>, // 可以使用此方案的角色，为空表示所有作者都可以使用
+}
+
+// 嵌入短代码配置
+#[derive(Debug, Deserialize, Clone)]
+pub struct ShortcodeConfig {
+    pub name: String,       // 短代码名称
+    pub id_pattern: String, // 参数必须完整匹配的正则表达式
+    pub template: String,   // 嵌入的 HTML 模板，{id} 会被替换为校验通过的参数
+}
+
+// 内置的渲染方案：
+// standard 为默认方案；casual 适合随笔，保留换行并识别网址；
+// trusted 允许经过白名单清理的原始 HTML，只开放给拥有 post:raw_html 权限的作者
+pub fn default_render_profiles() -> Vec<RenderProfileConfig> {
+    let standard = RenderProfileConfig {
+        name: "standard".to_string(),
+        strikethrough: true,
+        table: true,
+        tasklist: true,
+        footnotes: true,
+        description_lists: true,
+        math: true,
+        wikilinks: true,
+        ..Default::default()
+    };
+    vec![
+        RenderProfileConfig {
+            name: "casual".to_string(),
+            autolink: true,
+            smart_punctuation: true,
+            hard_breaks: true,
+            ..standard.clone()
+        },
+        RenderProfileConfig {
+            name: "trusted".to_string(),
+            sanitized_html: true,
+            ..standard.clone()
+        },
+        standard,
+    ]
+}
+
+// 内置的嵌入短代码：YouTube、Vimeo 视频，GitHub Gist 和推文
+// Gist 和推文的官方嵌入方式依赖脚本，这里只输出占位元素，由前端加载
+pub fn default_shortcodes() -> Vec<ShortcodeConfig> {
+    let shortcode = |name: &str, id_pattern: &str, template: &str| ShortcodeConfig {
+        name: name.to_string(),
+        id_pattern: id_pattern.to_string(),
+        template: template.to_string(),
+    };
+    vec![
+        shortcode(
+            "youtube",
+            "[A-Za-z0-9_-]{11}",
+            r#"<div class="embed embed-youtube"><iframe src="https://www.youtube-nocookie.com/embed/{id}" title="YouTube" loading="lazy" allow="encrypted-media; picture-in-picture" allowfullscreen></iframe></div>"#,
+        ),
+        shortcode(
+            "vimeo",
+            "[0-9]{1,12}",
+            r#"<div class="embed embed-vimeo"><iframe src="https://player.vimeo.com/video/{id}" title="Vimeo" loading="lazy" allow="picture-in-picture" allowfullscreen></iframe></div>"#,
+        ),
+        shortcode(
+            "gist",
+            "[A-Za-z0-9-]{1,39}/[0-9a-f]{20,32}",
+            r#"<div class="embed embed-gist" data-gist="{id}"><a href="https://gist.github.com/{id}">GitHub Gist</a></div>"#,
+        ),
+        shortcode(
+            "tweet",
+            "[0-9]{1,20}",
+            r#"<blockquote class="embed embed-tweet twitter-tweet"><a href="https://twitter.com/i/status/{id}">Tweet</a></blockquote>"#,
+        ),
+    ]
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
     pub database: DatabaseConfig,
@@ -49,6 +185,10 @@ pub struct AppConfig {
     pub auth: AuthConfig,
     pub email: EmailConfig,
     pub draft_policy: DraftPolicy,
+    pub autosave: AutosaveConfig,
+    pub trash: TrashConfig,
+    pub reports: ReportConfig,
+    pub markdown: MarkdownConfig,
 }
 
 impl AppConfig {
@@ -76,10 +216,30 @@ impl AppConfig {
             .set_default("email.smtp_user","")?
             .set_default("email.smtp_pass","")?
             .set_default("email.from_address","np-reply@localhost.com")?
+            .set_default("email.admin_base_url","http://localhost:5173")?
             // 草稿策略默认值
             .set_default("draft_policy.mode", "private")?
             .set_default("draft_policy.admin_access_all_drafts", false)?
             .set_default("draft_policy.audit_draft_access", true)?
+            // 自动保存默认值
+            .set_default("autosave.retention_days", 30)?
+            .set_default("autosave.purge_interval_minutes", 60)?
+            // 回收站默认值
+            .set_default("trash.retention_days", 30)?
+            .set_default("trash.purge_interval_minutes", 60)?
+            // 读者举报默认值
+            .set_default("reports.max_reports_per_hour", 5)?
+            .set_default("reports.trust_forwarded_for", false)?
+            // Markdown 渲染默认值
+            .set_default(
+                "markdown.highlight_languages",
+                vec![
+                    "rust", "rs", "python", "py", "javascript", "js", "java", "go", "c", "cpp",
+                    "sql", "bash", "sh", "json", "yaml", "html", "css", "diff",
+                ],
+            )?
+            .set_default("markdown.line_numbers", false)?
+            .set_default("markdown.default_profile", "standard")?
             // .set_default(...)? // 其他默认值
 
             // 从环境变量加载配置
@@ -101,6 +261,14 @@ impl AppConfig {
             .try_deserialize::<AppConfig>()
             .context("反序列化配置到 AppConfig 结构体失败")?; // 添加上下文
 
+        // tokio::time::interval 遇到 0 会 panic，后台清理任务将悄无声息地停止
+        if app_config.autosave.purge_interval_minutes == 0 {
+            bail!("配置 autosave.purge_interval_minutes 必须大于 0");
+        }
+        if app_config.trash.purge_interval_minutes == 0 {
+            bail!("配置 trash.purge_interval_minutes 必须大于 0");
+        }
+
         Ok(app_config) // 返回包含配置的 Result::Ok
     }
 }
\ No newline at end of file
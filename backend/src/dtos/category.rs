@@ -1,10 +1,12 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
 // 用于创建新分类的请求体结构
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateCategoryPayload {
     pub name: Option<String>,
+    #[serde(default)]
+    pub parent_id: Option<Uuid>, // 父分类，不提供时创建顶级分类
 }
 
 // 用于更新分类的请求体结构
@@ -13,6 +15,37 @@ pub struct UpdateCategoryPayload {
     // 所有字段都是可选的，表示只更新提供的字段
     // slug通常根据name变化自动更新
     pub name: Option<String>,
+    // 不提供时保持原父分类，null 表示移为顶级分类
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub parent_id: Option<Option<Uuid>>,
+}
+
+// 区分字段缺失和显式的 null：缺失时为 None（由 serde(default) 提供），null 时为 Some(None)
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// 分类树节点，子分类按名称排序
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryTreeNode {
+    #[serde(flatten)]
+    pub category: crate::models::Category,
+    pub children: Vec<CategoryTreeNode>,
+}
+
+/// 按分类筛选文章的查询参数
+#[derive(Debug, Deserialize)]
+pub struct CategoryPostsQuery {
+    #[serde(default)]
+    pub include_descendants: bool, // 是否包括子分类（所有后代分类）下的文章
 }
 
 // === 管理员专用的DTO ===
@@ -23,6 +56,8 @@ pub struct MergeCategoriesPayload {
     pub target_category_id: Uuid,        // 目标分类ID（保留的分类）
     pub source_category_ids: Vec<Uuid>,  // 源分类ID列表（将被合并删除的分类）
     pub new_target_name: Option<String>, // 可选：给目标分类一个新名称
+    #[serde(default)]
+    pub child_categories: ChildCategoriesHandling, // 源分类的子分类如何处理
 }
 
 /// 分类合并响应
@@ -40,6 +75,8 @@ pub struct MergeCategoriesResponse {
 pub struct BatchDeleteCategoriesPayload {
     pub category_ids: Vec<Uuid>,
     pub handle_orphaned_posts: Option<OrphanedPostsHandling>, // 如何处理失去分类的文章
+    #[serde(default)]
+    pub child_categories: ChildCategoriesHandling, // 被删除分类的子分类如何处理
 }
 
 /// 批量删除分类响应
@@ -48,6 +85,8 @@ pub struct BatchDeleteCategoriesResponse {
     pub deleted_count: usize,
     pub affected_post_count: usize,
     pub orphaned_post_count: usize, // 完全失去分类的文章数
+    #[serde(default)]
+    pub auto_assigned_post_count: usize, // 按推荐归入其他分类的孤儿文章数
     pub operation_summary: String,
 }
 
@@ -62,6 +101,16 @@ pub enum OrphanedPostsHandling {
     AutoSuggestCategories,
 }
 
+/// 删除或合并分类时子分类的处理策略
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChildCategoriesHandling {
+    /// 删除时移到最近的未被删除的上级分类下，合并时移到目标分类下
+    #[default]
+    Reparent,
+    /// 存在子分类时拒绝操作
+    Refuse,
+}
+
 /// 分类合并预览请求
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MergeCategoriesPreviewPayload {
@@ -99,5 +148,6 @@ pub struct CategoryUsageStats {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SimilarCategoryGroup {
     pub categories: Vec<crate::models::Category>,
-    pub similarity_reason: String, // 例如："名称相似"、"同义词"等
+    pub similarity_reason: String, // 例如："名称拼写相近"
+    pub confidence: f64,           // 0 到 1，越高越可能是重复分类
 }
@@ -0,0 +1,73 @@
+use crate::models::DraftComment;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 评论锚点：锚定到 Markdown 原文的一段字符范围 [start, end)，或一个标题
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CommentAnchor {
+    Range { start: i32, end: i32 },
+    Heading { text: String },
+}
+
+/// 创建评论DTO
+/// 顶层评论必须提供 anchor；回复提供 parent_id，且不能再设置 anchor
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateDraftCommentPayload {
+    pub body: String,
+    pub parent_id: Option<Uuid>,
+    pub anchor: Option<CommentAnchor>,
+}
+
+/// 评论DTO，顶层评论携带其全部回复
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DraftCommentDto {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub author_id: Uuid,
+    pub author_username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<CommentAnchor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quoted_text: Option<String>,
+    pub body: String,
+    pub is_resolved: bool,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub replies: Vec<DraftCommentDto>,
+}
+
+impl From<DraftComment> for DraftCommentDto {
+    fn from(comment: DraftComment) -> Self {
+        let anchor = match comment.anchor_type.as_deref() {
+            Some("range") => Some(CommentAnchor::Range {
+                start: comment.anchor_start.unwrap_or(0),
+                end: comment.anchor_end.unwrap_or(0),
+            }),
+            Some("heading") => Some(CommentAnchor::Heading {
+                text: comment.anchor_heading.clone().unwrap_or_default(),
+            }),
+            _ => None,
+        };
+
+        Self {
+            id: comment.id,
+            post_id: comment.post_id,
+            author_id: comment.author_id,
+            author_username: comment.author_username,
+            anchor,
+            quoted_text: comment.quoted_text,
+            body: comment.body,
+            is_resolved: comment.resolved_at.is_some(),
+            resolved_at: comment.resolved_at,
+            resolved_by: comment.resolved_by,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+            replies: Vec::new(),
+        }
+    }
+}
@@ -3,8 +3,12 @@ use serde::{Deserialize, Serialize};
 pub mod admin;
 pub mod auth;
 pub mod category;
+pub mod draft_comment;
 pub mod post;
+pub mod post_template;
+pub mod report;
 pub mod tag;
+pub mod trash;
 pub mod user;
 
 // 用于接收分页查询参数的结构体
@@ -1,4 +1,4 @@
-use crate::models::{Category, Tag};
+use crate::models::{BanAppealStatus, Category, DraftShareRole, ReviewStatus, Tag, UserProfile};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -6,7 +6,10 @@ use uuid::Uuid;
 /// 用于创建新文章的数据结构（DTO - Data Transfer Object）
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CreatePostPayload {
+    // 使用模板时标题和正文可以为空，由模板补全
+    #[serde(default)]
     pub title: String,
+    #[serde(default)]
     pub content: String,
     // 帖子的分类 ID 列表 (Option<Vec<Uuid>> 表示可以不提供，或者提供一个空的 Vec)
     pub category_ids: Option<Vec<Uuid>>,
@@ -17,7 +20,11 @@ pub struct CreatePostPayload {
     pub draft_shared_with: Option<Vec<Uuid>>, // 分享给哪些用户
     #[serde(default)]
     pub is_draft_public: Option<bool>, // 是否允许有权限的编辑查看
-                                       // 注意：创建时通常不需要 slug 和 published_at，slug 应自动生成，published_at 应为 None
+    // 注意：创建时通常不需要 slug 和 published_at，slug 应自动生成，published_at 应为 None
+    #[serde(default)]
+    pub render_profile: Option<String>, // Markdown 渲染方案，不提供时使用默认方案
+    #[serde(default)]
+    pub template_id: Option<Uuid>, // 使用的文章模板，未提供的字段由模板补全
 }
 
 /// 用于更新文章的数据结构（DTO）
@@ -42,41 +49,218 @@ pub struct UpdatePostPayload {
     pub draft_shared_with: Option<Vec<Uuid>>, // 更新分享给的用户列表
     #[serde(default)]
     pub is_draft_public: Option<bool>, // 更新是否允许编辑查看
+
+    #[serde(default)]
+    pub render_profile: Option<String>, // 更改 Markdown 渲染方案，不提供时保持不变
 }
 
 /// 草稿分享操作DTO
+/// 提交的分享列表会整体替换草稿当前的分享设置
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ShareDraftPayload {
-    pub shared_with: Vec<Uuid>,  // 要分享给的用户ID列表
+    #[serde(default)]
+    pub shared_with: Vec<Uuid>, // 要分享给的用户ID列表（兼容旧接口，按 commenter 角色分享）
+    #[serde(default)]
+    pub shares: Vec<DraftShareEntry>, // 按接收人指定角色和有效期的分享
     pub is_public: bool,         // 是否设为公开（允许有权限的编辑查看）
     pub message: Option<String>, // 分享时的消息
 }
 
+/// 单个接收人的草稿分享设置
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DraftShareEntry {
+    pub user_id: Uuid,
+    pub role: DraftShareRole,
+    pub expires_at: Option<DateTime<Utc>>, // 为空表示永久有效
+}
+
+/// "分享给我的草稿" 列表项DTO
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SharedDraftDto {
+    pub post_id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub author_id: Option<Uuid>,
+    pub author_username: Option<String>,
+    pub role: DraftShareRole,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub shared_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>, // 草稿最近一次修改的时间
+}
+
+/// 自动保存DTO：编辑器中当前的标题和正文
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AutosavePayload {
+    pub title: String,
+    pub content: String,
+}
+
+/// 标签和分类推荐DTO：编辑器中尚未保存的标题和正文，不提供时使用文章已保存的内容
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SuggestTaxonomyPayload {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+/// 创建草稿预览链接DTO
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreatePreviewLinkPayload {
+    pub label: Option<String>,         // 备注，例如 "客户审阅"
+    pub expires_in_hours: Option<i64>, // 有效期（小时），不传使用默认值
+}
+
+/// 草稿预览链接DTO
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreviewLinkDto {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub label: Option<String>,
+    pub token: String, // 访问 /blog/preview/{token} 使用的签名令牌
+    pub expires_at: DateTime<Utc>,
+    pub use_count: i32,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// 文章封禁操作DTO
 #[derive(Debug, Deserialize, Serialize)]
 pub struct BanPostPayload {
     pub reason: Option<String>, // 封禁原因
 }
 
+/// 文章封禁记录DTO（展示给作者和版主）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostBanDto {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub reason: Option<String>,
+    pub banned_by: Option<Uuid>,
+    pub banned_by_username: Option<String>,
+    pub banned_at: DateTime<Utc>,
+    pub appeal_status: Option<BanAppealStatus>, // 为空表示尚未申诉
+}
+
+/// 作者提交封禁申诉DTO
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateBanAppealPayload {
+    pub message: String, // 申诉理由
+}
+
+/// 版主对申诉的处理结论
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BanAppealDecision {
+    Uphold, // 维持封禁
+    Unban,  // 解封文章
+}
+
+/// 处理封禁申诉DTO
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ResolveBanAppealPayload {
+    pub decision: BanAppealDecision,
+    pub note: Option<String>, // 处理说明，会通过邮件告知作者
+}
+
+/// 封禁申诉队列的查询参数
+#[derive(Debug, Deserialize, Default)]
+pub struct BanAppealQuery {
+    pub status: Option<BanAppealStatus>, // 默认只看待处理的申诉
+}
+
+/// 封禁申诉DTO（版主申诉队列）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BanAppealDto {
+    pub id: Uuid,
+    pub ban_id: Uuid,
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub appellant_id: Option<Uuid>,
+    pub appellant_username: Option<String>,
+    pub message: String,
+    pub status: BanAppealStatus,
+    pub ban_reason: Option<String>,
+    pub banned_at: DateTime<Utc>,
+    pub resolved_by: Option<Uuid>,
+    pub resolution_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// 提交审核DTO
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct SubmitReviewPayload {
+    pub reviewer_id: Option<Uuid>, // 可选：提交时直接指定审核人
+    pub comment: Option<String>,   // 提交说明
+}
+
+/// 指派审核人DTO
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AssignReviewerPayload {
+    pub reviewer_id: Uuid,
+}
+
+/// 审核结论
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approve,        // 通过审核
+    RequestChanges, // 要求修改
+}
+
+/// 审核文章DTO
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReviewPostPayload {
+    pub decision: ReviewDecision,
+    pub comment: Option<String>, // 审核意见，要求修改时必填
+}
+
+/// 审核状态流转记录DTO
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewTransitionDto {
+    pub id: Uuid,
+    pub from_status: String,
+    pub to_status: String,
+    pub actor_id: Option<Uuid>,
+    pub actor_username: Option<String>,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 文章审核详情DTO
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostReviewDto {
+    pub post_id: Uuid,
+    pub status: ReviewStatus,
+    pub reviewer: Option<UserBasicDto>,
+    pub transitions: Vec<ReviewTransitionDto>,
+}
+
 /// 草稿访问日志DTO
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DraftAccessLogDto {
     pub id: Uuid,
     pub post_id: Uuid,
     pub post_title: String,
-    pub accessed_by: Uuid,
-    pub accessed_by_username: String,
+    pub accessed_by: Option<Uuid>,
+    pub accessed_by_username: Option<String>,
     pub access_type: String,
     pub access_reason: Option<String>,
+    pub preview_link_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
 /// 用户基本信息DTO，用于在文章中显示作者信息
+/// 公开接口（博客展示、预览链接）不返回邮箱
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct UserBasicDto {
     pub id: Uuid,
     pub username: String,
-    pub email: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(flatten)]
+    pub profile: UserProfile, // 显示名称、简介、头像等署名信息
 }
 
 /// 用于在获取单个帖子详情时，同时返回帖子的基本信息及其关联的分类和标签信息
@@ -89,7 +273,8 @@ pub struct PostDetailDto {
     pub content_markdown: String, // 用于存放原始 Markdown
     pub content_html: String,     // 用于存放渲染后的 HTML
     pub author_id: Option<Uuid>,  // 保留以兼容现有代码
-    pub author: Option<UserBasicDto>, // 新增作者详细信息
+    pub author: Option<UserBasicDto>, // 主作者，保留以兼容现有代码
+    pub authors: Vec<UserBasicDto>,   // 全部作者，主作者在前，合著者按顺序排列
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub published_at: Option<DateTime<Utc>>,
@@ -105,10 +290,23 @@ pub struct PostDetailDto {
     pub is_draft_public: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_accessing_others_draft: Option<bool>, // 标识是否在访问他人的草稿
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub share_role: Option<DraftShareRole>, // 当前用户通过分享获得的角色
 
     // 文章封禁状态
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_banned: Option<bool>, // 是否被封禁
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ban: Option<PostBanDto>, // 生效中的封禁记录，仅作者和版主可见
+
+    // 维基链接
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub broken_wiki_links: Option<Vec<BrokenWikiLinkDto>>, // 无法解析的链接，仅可编辑的用户可见
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backlinks: Option<Vec<PostLinkDto>>, // 链接到本文的已发布文章，仅详情接口返回
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub render_profile: Option<String>, // Markdown 渲染方案，为空表示默认方案
 
     // 当前用户对此文章的操作权限
     pub can_edit: bool,    // 是否可以编辑
@@ -119,20 +317,102 @@ pub struct PostDetailDto {
     pub can_unban: bool,   // 是否可以解封
 }
 
+/// 无法解析的维基链接的原因
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WikiLinkIssue {
+    Missing,     // 没有这个 slug 的文章
+    Unpublished, // 目标文章未发布或已被封禁
+}
+
+/// 无法解析的维基链接，渲染时按普通文本输出
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrokenWikiLinkDto {
+    pub slug: String,
+    pub issue: WikiLinkIssue,
+}
+
+/// 链接到当前文章的文章
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostLinkDto {
+    pub id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// 设置文章作者DTO
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SetPostAuthorsPayload {
+    pub author_ids: Vec<Uuid>, // 第一个为主作者，其余为按顺序排列的合著者
+}
+
+/// 批量操作的动作，一次请求只执行一种动作
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BulkPostAction {
+    Publish,
+    Unpublish,
+    Delete, // 移入回收站
+    AddTags { tag_ids: Vec<Uuid> },
+    RemoveTags { tag_ids: Vec<Uuid> },
+    SetCategories { category_ids: Vec<Uuid> }, // 整体替换文章的分类
+    Ban { reason: Option<String> },
+}
+
+/// 批量操作文章DTO
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BulkPostPayload {
+    pub post_ids: Vec<Uuid>,
+    pub action: BulkPostAction,
+}
+
+/// 单篇文章的批量操作结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkPostItemResult {
+    pub post_id: Uuid,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>, // 失败原因
+}
+
+/// 批量操作响应，results 与请求中的 post_ids 顺序一致（重复的 ID 只保留一次）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkPostResponse {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkPostItemResult>,
+}
+
 /// 分类的简化 DTO,不想在 PostDetailDto 中暴露完整的 Category 模型
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CategoryDto {
     pub id: Uuid,
     pub name: String,
     pub slug: String,
+    pub parent_id: Option<Uuid>,
+    // 从顶级分类到父分类的路径，不包括当前分类；顶级分类为空
+    #[serde(default)]
+    pub breadcrumbs: Vec<CategoryBreadcrumbDto>,
 }
 
+/// 分类面包屑中的一级
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryBreadcrumbDto {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+}
+
+// 面包屑需要查询上级分类，这里只转换分类本身
 impl From<Category> for CategoryDto {
     fn from(category: Category) -> Self {
         Self {
             id: category.id,
             name: category.name,
             slug: category.slug,
+            parent_id: category.parent_id,
+            breadcrumbs: Vec::new(),
         }
     }
 }
@@ -154,3 +434,26 @@ impl From<Tag> for TagDto {
         }
     }
 }
+
+/// 推荐的标签
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagSuggestionDto {
+    pub tag: TagDto,
+    pub score: f64,           // 0 到 1，越高越相关
+    pub reasons: Vec<String>, // 推荐理由
+}
+
+/// 推荐的分类
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategorySuggestionDto {
+    pub category: CategoryDto,
+    pub score: f64,
+    pub reasons: Vec<String>,
+}
+
+/// 文章的标签和分类推荐，按分数从高到低排列，不包括文章已有的标签和分类
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaxonomySuggestionsResponse {
+    pub tags: Vec<TagSuggestionDto>,
+    pub categories: Vec<CategorySuggestionDto>,
+}
@@ -0,0 +1,62 @@
+use crate::models::PostTemplate;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 创建文章模板DTO
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct CreatePostTemplatePayload {
+    pub name: String,
+    #[serde(default)]
+    pub title_pattern: String, // 标题模式，如 "周报 {year} 第 {week} 周"
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub category_ids: Vec<Uuid>, // 默认分类
+    #[serde(default)]
+    pub tag_ids: Vec<Uuid>, // 默认标签
+    #[serde(default)]
+    pub global: bool, // 是否为全局模板，需要 post:template:manage_global 权限
+}
+
+/// 更新文章模板DTO，未提供的字段保持不变
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UpdatePostTemplatePayload {
+    pub name: Option<String>,
+    pub title_pattern: Option<String>,
+    pub content: Option<String>,
+    pub category_ids: Option<Vec<Uuid>>,
+    pub tag_ids: Option<Vec<Uuid>>,
+}
+
+/// 文章模板DTO
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostTemplateDto {
+    pub id: Uuid,
+    pub name: String,
+    pub title_pattern: String,
+    pub content: String,
+    pub category_ids: Vec<Uuid>,
+    pub tag_ids: Vec<Uuid>,
+    pub is_global: bool,
+    pub owner_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PostTemplate> for PostTemplateDto {
+    fn from(template: PostTemplate) -> Self {
+        Self {
+            id: template.id,
+            is_global: template.owner_id.is_none(),
+            owner_id: template.owner_id,
+            name: template.name,
+            title_pattern: template.title_pattern,
+            content: template.content,
+            category_ids: template.category_ids,
+            tag_ids: template.tag_ids,
+            created_at: template.created_at,
+            updated_at: template.updated_at,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::dtos::post::PostBanDto;
+use crate::models::ReportCategory;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 读者举报文章DTO
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct CreateReportPayload {
+    #[serde(default)]
+    pub category: ReportCategory, // 举报分类，不填视为其他
+    pub details: Option<String>, // 补充说明
+}
+
+/// 举报提交结果DTO（不回显举报人信息）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportReceiptDto {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub category: ReportCategory,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 按分类统计的举报数量
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportCategoryCount {
+    pub category: ReportCategory,
+    pub count: i64,
+}
+
+/// 举报队列中的文章（按文章聚合待处理的举报）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportedPostDto {
+    pub post_id: Uuid,
+    pub title: String,
+    pub slug: String,
+    pub author_id: Option<Uuid>,
+    pub author_username: Option<String>,
+    pub is_banned: bool,
+    pub report_count: i64,
+    pub categories: Vec<ReportCategoryCount>,
+    pub first_reported_at: DateTime<Utc>,
+    pub last_reported_at: DateTime<Utc>,
+}
+
+/// 单条举报DTO（版主查看，游客举报不含举报人）
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostReportDto {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub category: ReportCategory,
+    pub details: Option<String>,
+    pub reporter_id: Option<Uuid>,
+    pub reporter_username: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 版主处理举报的结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportResolutionDto {
+    pub post_id: Uuid,
+    pub resolved_reports: u64, // 本次关闭的举报数量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ban: Option<PostBanDto>, // 封禁文章时返回新的封禁记录
+}
@@ -14,6 +14,12 @@ pub struct UpdateTagPayload {
                               // slug 通常不直接由用户更新，而是根据 name 的变化在后端自动更新
 }
 
+/// 为标签添加别名的请求体结构
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateTagAliasPayload {
+    pub name: String, // 别名的 slug 同样由后端根据名称生成
+}
+
 // === 管理员专用的DTO ===
 
 /// 标签合并请求
@@ -47,6 +53,8 @@ pub struct BatchDeleteTagsResponse {
     pub deleted_count: usize,
     pub affected_post_count: usize,
     pub orphaned_post_count: usize, // 完全失去标签的文章数
+    #[serde(default)]
+    pub auto_assigned_post_count: usize, // 添加了推荐标签的孤儿文章数
     pub operation_summary: String,
 }
 
@@ -0,0 +1,11 @@
+use crate::models::{TrashedCategory, TrashedPost, TrashedTag};
+use serde::{Deserialize, Serialize};
+
+/// 回收站内容DTO，只包含当前用户有权恢复的条目
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TrashDto {
+    pub posts: Vec<TrashedPost>,
+    pub categories: Vec<TrashedCategory>,
+    pub tags: Vec<TrashedTag>,
+    pub retention_days: i32, // 条目在回收站中保留的天数
+}
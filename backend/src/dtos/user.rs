@@ -1,12 +1,36 @@
+use crate::models::{SocialLink, UserProfile, UserPublic};
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use serde::Serialize;
+use uuid::Uuid;
 
 // 用于“更新用户个人资料”接口的请求体
 // 所有字段都是可选的，允许用户只更新部分信息
 #[derive(Debug, Deserialize, Default)]
+// 资料字段传空字符串表示清除；social_links 传入时整体替换
 pub struct UpdateProfilePayload {
     pub username: Option<String>,
     pub email: Option<String>,
+    pub display_name: Option<String>,
+    pub bio: Option<String>, // Markdown 格式
+    pub avatar_url: Option<String>,
+    pub website: Option<String>,
+    pub social_links: Option<Vec<SocialLink>>,
+}
+
+// "我" 的个人资料：账户信息和公开展示的资料
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MyProfileResponse {
+    #[serde(flatten)]
+    pub user: UserPublic,
+    #[serde(flatten)]
+    pub profile: UserProfile,
+}
+
+// 用于“更新通知偏好”接口的请求体，未提供的项保持原值
+#[derive(Debug, Deserialize, Default)]
+pub struct UpdateNotificationPreferencesPayload {
+    pub draft_shared: Option<bool>,
 }
 
 // 用于“修改密码”接口的请求体
@@ -47,6 +71,18 @@ pub struct UserStatsResponse {
     pub total_posts: i64,
     pub published_posts: i64,
     pub draft_posts: i64,
+    pub co_authored_posts: i64, // 作为合著者参与的文章数，已计入上面的统计
     pub total_categories: i64,
     pub total_tags: i64,
 }
+
+// 作者公开资料，只包含可以公开展示的字段，不包含邮箱等私有信息
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuthorProfileDto {
+    pub id: Uuid,
+    pub username: String,
+    pub joined_at: DateTime<Utc>,
+    pub published_post_count: i64, // 已发布的文章数（包括合写的文章）
+    #[serde(flatten)]
+    pub profile: UserProfile,
+}
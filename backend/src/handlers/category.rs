@@ -1,6 +1,6 @@
 use axum::{
     extract::{Json, Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, header},
     response::IntoResponse,
 };
 
@@ -8,9 +8,11 @@ use crate::api_error::ApiError;
 use crate::auth::AuthUser;
 use crate::dtos::category::{
     BatchDeleteCategoriesPayload, CreateCategoryPayload, MergeCategoriesPayload,
-    MergeCategoriesPreviewPayload, UpdateCategoryPayload,
+    MergeCategoriesPreviewPayload, OrphanedPostsHandling, UpdateCategoryPayload,
 };
 use crate::handlers::AppState;
+use crate::utils::{etag_header, expected_version};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// 创建新分类的 Handler
@@ -41,6 +43,17 @@ pub async fn list_categories_handler(
     Ok(Json(categories))
 }
 
+/// 获取分类树的 Handler
+pub async fn get_category_tree_handler(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    tracing::info!("接收到获取分类树请求");
+    let tree = state.category_service.get_category_tree().await?;
+    tracing::info!("成功获取分类树，共 {} 个顶级分类", tree.len());
+
+    Ok(Json(tree))
+}
+
 /// 获取单个分类的Handler（可以用过 ID 或 Slug）
 pub async fn get_category_handler(
     State(state): State<AppState>,
@@ -62,7 +75,9 @@ pub async fn get_category_handler(
     };
     tracing::info!("成功获取分类：{:?}", category);
 
-    Ok(Json(category))
+    // 通过 ETag 返回当前版本，更新时需要在 If-Match 中带回
+    let etag = etag_header(category.updated_at);
+    Ok(([(header::ETAG, etag)], Json(category)))
 }
 
 /// 更新分类的 Handler
@@ -70,16 +85,23 @@ pub async fn update_category_handler(
     auth_user: AuthUser,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<UpdateCategoryPayload>,
 ) -> Result<impl IntoResponse, ApiError> {
     // 授权检查
     auth_user.require_permission("category:manage")?;
 
     tracing::info!("接收到更新分类请求：ID:{},Payload:{:?}", id, payload);
-    let updated_category = state.category_service.update_category(id, payload).await?;
+    // 乐观并发控制：必须携带读取时的版本
+    let expected_version = expected_version(&headers)?;
+    let updated_category = state
+        .category_service
+        .update_category(id, payload, expected_version)
+        .await?;
     tracing::info!("分类更新成功: {:?}", updated_category);
 
-    Ok(Json(updated_category))
+    let etag = etag_header(updated_category.updated_at);
+    Ok(([(header::ETAG, etag)], Json(updated_category)))
 }
 
 /// 删除分类的 Handler
@@ -113,7 +135,11 @@ pub async fn merge_categories_handler(
 
     let result = state
         .category_service
-        .merge_categories(payload.target_category_id, &payload.source_category_ids)
+        .merge_categories(
+            payload.target_category_id,
+            &payload.source_category_ids,
+            payload.child_categories,
+        )
         .await?;
 
     tracing::info!("分类合并成功：{:?}", result);
@@ -138,6 +164,7 @@ pub async fn merge_categories_enhanced_handler(
             payload.target_category_id,
             &payload.source_category_ids,
             payload.new_target_name.as_deref(),
+            payload.child_categories,
         )
         .await?;
 
@@ -181,14 +208,31 @@ pub async fn batch_delete_categories_handler(
     // 检查是否需要处理孤儿文章
     let handle_orphaned = matches!(
         payload.handle_orphaned_posts,
-        Some(crate::dtos::category::OrphanedPostsHandling::AddUncategorizedCategory)
+        Some(OrphanedPostsHandling::AddUncategorizedCategory)
+            | Some(OrphanedPostsHandling::AutoSuggestCategories)
     );
 
     if handle_orphaned {
+        // 自动推荐时先为孤儿文章找到合适的分类，没有推荐的仍归入"未分类"
+        let suggested_categories = match payload.handle_orphaned_posts {
+            Some(OrphanedPostsHandling::AutoSuggestCategories) => {
+                state
+                    .taxonomy_suggestion_service
+                    .suggest_categories_for_orphans(&payload.category_ids)
+                    .await?
+            }
+            _ => HashMap::new(),
+        };
+
         // 使用增强版批量删除
         let response = state
             .category_service
-            .batch_delete_categories_enhanced(&payload.category_ids, true)
+            .batch_delete_categories_enhanced(
+                &payload.category_ids,
+                true,
+                payload.child_categories,
+                &suggested_categories,
+            )
             .await?;
 
         tracing::info!("增强版批量删除分类成功：{}", response.operation_summary);
@@ -197,7 +241,7 @@ pub async fn batch_delete_categories_handler(
         // 使用简单版批量删除
         let deleted_count = state
             .category_service
-            .batch_delete_categories(&payload.category_ids)
+            .batch_delete_categories(&payload.category_ids, payload.child_categories)
             .await?;
 
         tracing::info!("批量删除分类成功：删除了 {} 个分类", deleted_count);
@@ -207,6 +251,7 @@ pub async fn batch_delete_categories_handler(
             deleted_count,
             affected_post_count: 0, // 简单版本不统计受影响的文章
             orphaned_post_count: 0,
+            auto_assigned_post_count: 0,
             operation_summary: format!("成功删除 {} 个分类", deleted_count),
         };
 
@@ -0,0 +1,84 @@
+use crate::api_error::ApiError;
+use crate::auth::AuthUser;
+use crate::dtos::draft_comment::CreateDraftCommentPayload;
+use crate::handlers::AppState;
+use axum::extract::{Json, Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use uuid::Uuid;
+
+// 获取草稿评审意见列表处理器
+pub async fn list_draft_comments_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    // 非作者访问需要 post:draft:access_shared 权限，并通过草稿分享检查
+    let can_access_shared = auth_user
+        .require_permission("post:draft:access_shared")
+        .is_ok();
+
+    let comments = state
+        .draft_comment_service
+        .list_comments(id, auth_user.user_id(), can_access_shared)
+        .await?;
+    Ok(Json(comments))
+}
+
+// 添加评审意见 / 回复处理器
+pub async fn create_draft_comment_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateDraftCommentPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    let can_access_shared = auth_user
+        .require_permission("post:draft:access_shared")
+        .is_ok();
+
+    let comment = state
+        .draft_comment_service
+        .create_comment(id, auth_user.user_id(), can_access_shared, payload)
+        .await?;
+    Ok((StatusCode::CREATED, Json(comment)))
+}
+
+// 解决评审意见处理器
+pub async fn resolve_draft_comment_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let can_access_shared = auth_user
+        .require_permission("post:draft:access_shared")
+        .is_ok();
+
+    let comment = state
+        .draft_comment_service
+        .set_comment_resolved(id, comment_id, auth_user.user_id(), can_access_shared, true)
+        .await?;
+    Ok(Json(comment))
+}
+
+// 重新打开评审意见处理器
+pub async fn unresolve_draft_comment_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let can_access_shared = auth_user
+        .require_permission("post:draft:access_shared")
+        .is_ok();
+
+    let comment = state
+        .draft_comment_service
+        .set_comment_resolved(
+            id,
+            comment_id,
+            auth_user.user_id(),
+            can_access_shared,
+            false,
+        )
+        .await?;
+    Ok(Json(comment))
+}
@@ -1,8 +1,12 @@
 pub mod admin;
 pub mod auth;
 pub mod category;
+pub mod draft_comment;
 pub mod post;
+pub mod post_template;
+pub mod report;
 pub mod tag;
+pub mod trash;
 pub mod user;
 
 pub use post::{
@@ -51,7 +55,9 @@ pub use user::{
 };
 
 use crate::services::{
-    AdminService, AuthService, CategoryService, PostService, TagService, UserService,
+    AdminService, AuthService, AutosaveService, CategoryService, DraftCommentService,
+    NotificationService, PostService, PostTemplateService, PreviewLinkService, ReportService,
+    TagService, TaxonomySuggestionService, TrashService, UserService,
 };
 
 use std::sync::Arc;
@@ -64,4 +70,12 @@ pub struct AppState {
     pub auth_service: Arc<AuthService>,
     pub admin_service: Arc<AdminService>,
     pub user_service: Arc<UserService>,
+    pub draft_comment_service: Arc<DraftCommentService>,
+    pub autosave_service: Arc<AutosaveService>,
+    pub trash_service: Arc<TrashService>,
+    pub preview_link_service: Arc<PreviewLinkService>,
+    pub notification_service: Arc<NotificationService>,
+    pub report_service: Arc<ReportService>,
+    pub post_template_service: Arc<PostTemplateService>,
+    pub taxonomy_suggestion_service: Arc<TaxonomySuggestionService>,
 }
@@ -1,11 +1,18 @@
 use crate::api_error::ApiError;
 use crate::auth::{AuthUser, OptionalAuth};
 use crate::dtos::Pagination;
-use crate::dtos::post::{BanPostPayload, CreatePostPayload, ShareDraftPayload, UpdatePostPayload};
+use crate::dtos::category::CategoryPostsQuery;
+use crate::dtos::post::{
+    AssignReviewerPayload, AutosavePayload, BanAppealQuery, BanPostPayload, BulkPostPayload,
+    CreateBanAppealPayload, CreatePostPayload, CreatePreviewLinkPayload, ResolveBanAppealPayload,
+    ReviewPostPayload, SetPostAuthorsPayload, ShareDraftPayload, SubmitReviewPayload,
+    SuggestTaxonomyPayload, UpdatePostPayload,
+};
 use crate::handlers::AppState;
+use crate::utils::{etag_header, expected_version};
 use anyhow::Result;
 use axum::extract::{Json, Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode, header};
 use axum::response::IntoResponse;
 use uuid::Uuid;
 // 定义应用状态，包含服务实例
@@ -15,21 +22,63 @@ use uuid::Uuid;
 // ApiError 实现了 From<anyhow::Error>,所以可以在 service 调用后用 '?'
 // 创建文章处理器
 pub async fn create_post_handler(
-    auth_user: AuthUser,                    // 要求认证用户
-    State(state): State<AppState>,          // 从状态中提取PostService
-    Json(payload): Json<CreatePostPayload>, // 从请求体解析 JSON
+    auth_user: AuthUser,                        // 要求认证用户
+    State(state): State<AppState>,              // 从状态中提取PostService
+    Json(mut payload): Json<CreatePostPayload>, // 从请求体解析 JSON
 ) -> Result<impl IntoResponse, ApiError> {
     // 授权检查： 要求 "post:create" 权限
     auth_user.require_permission("post:create")?;
 
     // 从认证信息中获取作者 ID// 从认证信息中获取作者 ID
     let author_id = auth_user.user_id();
+    // 使用模板时，由模板补全未提供的标题、正文、分类和标签
+    if let Some(template_id) = payload.template_id {
+        payload = state
+            .post_template_service
+            .apply_template(template_id, author_id, payload)
+            .await?;
+    }
+    // 受限的渲染方案只开放给指定角色，允许 HTML 的方案需要 post:raw_html 权限
+    state
+        .post_service
+        .ensure_can_use_render_profile(
+            None,
+            payload.render_profile.as_deref(),
+            auth_user.roles(),
+            auth_user.permissions(),
+        )
+        .await?;
 
     // 返回 Result<impl IntoResponse, ApiError>
     let post_detail = state.post_service.create_post(author_id, payload).await?; //调用服务层方法
     Ok((StatusCode::CREATED, Json(post_detail))) // 成功返回 201 CREATED 和 JSON 数据
 }
 
+// 复制文章处理器：复制为当前用户的新草稿
+pub async fn clone_post_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:create")?;
+    // 与查看详情一致：分享给自己或公开的草稿需要 post:draft:access_shared 权限
+    let can_access_shared = auth_user
+        .require_permission("post:draft:access_shared")
+        .is_ok();
+
+    let post_detail = state
+        .post_service
+        .clone_post(
+            id,
+            auth_user.user_id(),
+            can_access_shared,
+            auth_user.roles(),
+            auth_user.permissions(),
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(post_detail)))
+}
+
 // 获取文章列表处理器（管理界面专用）
 pub async fn list_posts_handler(
     auth_user: AuthUser, // 要求认证用户
@@ -92,6 +141,45 @@ pub async fn list_published_posts_handler(
     Ok(Json(paginated_response))
 }
 
+// 获取作者公开资料处理器（博客展示界面专用，支持游客和认证用户）
+pub async fn get_author_profile_handler(
+    _optional_auth: OptionalAuth,
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let profile = state.post_service.get_author_profile(&username).await?;
+    Ok(Json(profile))
+}
+
+// 获取作者已发布文章列表处理器（博客展示界面专用，支持游客和认证用户）
+pub async fn list_author_posts_handler(
+    _optional_auth: OptionalAuth,
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+    Query(pagination): Query<Pagination>,
+) -> Result<impl IntoResponse, ApiError> {
+    let paginated_response = state
+        .post_service
+        .list_published_posts_by_author(&username, pagination)
+        .await?;
+    Ok(Json(paginated_response))
+}
+
+// 获取分类下已发布文章列表处理器（博客展示界面专用，支持游客和认证用户）
+pub async fn list_category_posts_handler(
+    _optional_auth: OptionalAuth,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<CategoryPostsQuery>,
+    Query(pagination): Query<Pagination>,
+) -> Result<impl IntoResponse, ApiError> {
+    let paginated_response = state
+        .post_service
+        .list_published_posts_by_category(&slug, query.include_descendants, pagination)
+        .await?;
+    Ok(Json(paginated_response))
+}
+
 // 获取单篇文章处理器（管理界面专用，需要认证）
 pub async fn get_post_handler(
     auth_user: AuthUser, // 管理界面需要认证
@@ -108,6 +196,10 @@ pub async fn get_post_handler(
         // 如果不是管理员，需要验证是否有管理自己文章的权限
         auth_user.require_permission("post:manage_own")?;
     }
+    // 是否可以查看他人分享给自己的草稿
+    let can_access_shared = auth_user
+        .require_permission("post:draft:access_shared")
+        .is_ok();
 
     // 尝试解析为UUID
     let post_detail = match Uuid::parse_str(&id_or_slug) {
@@ -118,13 +210,12 @@ pub async fn get_post_handler(
                 .get_post_by_id_with_permission(id, user_id, can_read_any)
                 .await?;
 
-            // 如果不是管理员，检查文章所有权
-            if !can_read_any {
-                if let Some(author_id) = state.post_service.get_post_author(id).await? {
-                    if author_id != user_id {
-                        return Err(ApiError::from(anyhow::anyhow!("您只能查看自己的文章")));
-                    }
-                }
+            // 如果不是管理员，检查文章所有权（分享给自己的草稿除外，合著者视同作者）
+            if !(can_read_any || (can_access_shared && post.share_role.is_some()))
+                && post.author_id.is_some()
+                && !state.post_service.is_post_author(id, user_id).await?
+            {
+                return Err(ApiError::from(anyhow::anyhow!("您只能查看自己的文章")));
             }
 
             post
@@ -136,19 +227,20 @@ pub async fn get_post_handler(
                 .get_post_by_slug_with_permission(&id_or_slug, user_id, can_read_any)
                 .await?;
 
-            // 如果不是管理员，检查文章所有权
-            if !can_read_any {
-                if let Some(author_id) = state.post_service.get_post_author(post.id).await? {
-                    if author_id != user_id {
-                        return Err(ApiError::from(anyhow::anyhow!("您只能查看自己的文章")));
-                    }
-                }
+            // 如果不是管理员，检查文章所有权（分享给自己的草稿除外，合著者视同作者）
+            if !(can_read_any || (can_access_shared && post.share_role.is_some()))
+                && post.author_id.is_some()
+                && !state.post_service.is_post_author(post.id, user_id).await?
+            {
+                return Err(ApiError::from(anyhow::anyhow!("您只能查看自己的文章")));
             }
 
             post
         }
     };
-    Ok(Json(post_detail))
+    // 通过 ETag 返回当前版本，更新时需要在 If-Match 中带回
+    let etag = etag_header(post_detail.updated_at);
+    Ok(([(header::ETAG, etag)], Json(post_detail)))
 }
 
 // 获取单篇已发布文章处理器（博客展示界面专用，支持游客和认证用户）
@@ -175,6 +267,7 @@ pub async fn update_post_handler(
     auth_user: AuthUser,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<UpdatePostPayload>,
 ) -> Result<impl IntoResponse, ApiError> {
     let user_id = auth_user.user_id();
@@ -185,21 +278,51 @@ pub async fn update_post_handler(
         // 如果有超级权限，直接执行修改
         tracing::info!("用户 {} (管理员/编辑) 正在编辑帖子 {}", user_id, id);
     } else {
-        // 如果没有超级权限，检查用户是否有编辑自己帖子的权限，并且是否是帖子的所有者
-        auth_user.require_permission("post:edit_own")?;
-
-        let post_author = state.post_service.get_post_author(id).await?;
-
-        if post_author != Some(user_id) {
-            // 如果帖子的作者不是当前用户，则禁止操作
-            tracing::warn!("权限不足：用户 {} 尝试编辑不属于自己的帖子 {}", user_id, id);
-            return Err(ApiError::from(anyhow::anyhow!("您只能编辑自己的帖子")));
+        if state.post_service.is_post_author(id, user_id).await? {
+            // 如果没有超级权限，检查用户是否有编辑自己帖子的权限（合著者视同作者）
+            auth_user.require_permission("post:edit_own")?;
+            tracing::info!("用户 {} 正在编辑自己的帖子 {}", user_id, id);
+        } else {
+            // 不是作者：只有草稿的 editor 分享接收人可以编辑（不需要 post:draft:edit_any）
+            auth_user.require_permission("post:draft:access_shared")?;
+            if let Err(e) = state
+                .post_service
+                .ensure_can_edit_shared_draft(id, user_id, &payload)
+                .await
+            {
+                tracing::warn!("权限不足：用户 {} 尝试编辑不属于自己的帖子 {}", user_id, id);
+                return Err(ApiError::from(e));
+            }
+            tracing::info!("用户 {} 正在通过 editor 分享编辑草稿 {}", user_id, id);
         }
-        tracing::info!("用户 {} 正在编辑自己的帖子 {}", user_id, id);
     }
 
-    let update_post_detail = state.post_service.update_post(id, payload).await?;
-    Ok(Json(update_post_detail))
+    // 修改渲染方案或正文时检查渲染方案的使用权限
+    if payload.render_profile.is_some() || payload.content.is_some() {
+        state
+            .post_service
+            .ensure_can_use_render_profile(
+                Some(id),
+                payload.render_profile.as_deref(),
+                auth_user.roles(),
+                auth_user.permissions(),
+            )
+            .await?;
+    }
+
+    // 乐观并发控制：必须携带读取时的版本
+    let expected_version = expected_version(&headers)?;
+    let update_post_detail = state
+        .post_service
+        .update_post(id, payload, expected_version, user_id)
+        .await?;
+    // 显式保存后，该用户的自动保存已经过时
+    // 文章已经保存成功，清理失败只记录日志，否则客户端会重试一次已经完成的保存
+    if let Err(e) = state.autosave_service.discard(id, user_id).await {
+        tracing::warn!("清理文章 {} 的自动保存失败: {:?}", id, e);
+    }
+    let etag = etag_header(update_post_detail.updated_at);
+    Ok(([(header::ETAG, etag)], Json(update_post_detail)))
 }
 
 // 删除文章处理器
@@ -265,8 +388,15 @@ pub async fn publish_post_handler(
         tracing::info!("用户 {} 正在发布自己的文章 {}", user_id, id);
     }
 
+    // 持有 post:submit_review 但没有 post:review 的角色必须经过审核才能发布
+    let requires_review = auth_user.require_permission("post:submit_review").is_ok()
+        && auth_user.require_permission("post:review").is_err();
+
     // 执行发布操作
-    state.post_service.publish_post(id).await?;
+    state
+        .post_service
+        .publish_post(id, user_id, requires_review)
+        .await?;
     Ok(Json(serde_json::json!({"message": "文章发布成功"})))
 }
 
@@ -298,10 +428,52 @@ pub async fn unpublish_post_handler(
     }
 
     // 执行撤回操作
-    state.post_service.unpublish_post(id).await?;
+    state.post_service.unpublish_post(id, user_id).await?;
     Ok(Json(serde_json::json!({"message": "文章撤回成功"})))
 }
 
+// 设置文章作者处理器
+// 主作者（post:manage_own）可以管理合著者，更换主作者需要 post:manage_any
+pub async fn set_post_authors_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SetPostAuthorsPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = auth_user.user_id();
+    let can_manage_any = auth_user.require_permission("post:manage_any").is_ok();
+    if !can_manage_any {
+        auth_user.require_permission("post:manage_own")?;
+    }
+
+    let authors = state
+        .post_service
+        .set_post_authors(id, user_id, can_manage_any, payload)
+        .await?;
+    Ok(Json(authors))
+}
+
+// 批量操作文章处理器
+// 每篇文章单独做与单篇接口相同的权限检查，返回逐篇结果
+pub async fn bulk_posts_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Json(payload): Json<BulkPostPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = auth_user.user_id();
+    tracing::info!(
+        "用户 {} 正在批量操作 {} 篇文章",
+        user_id,
+        payload.post_ids.len()
+    );
+
+    let response = state
+        .post_service
+        .bulk_update_posts(user_id, auth_user.permissions(), payload)
+        .await?;
+    Ok(Json(response))
+}
+
 // 草稿分享处理器
 pub async fn share_draft_handler(
     auth_user: AuthUser,
@@ -323,12 +495,27 @@ pub async fn share_draft_handler(
     })))
 }
 
+// 获取分享给我的草稿列表处理器（包含每篇草稿的分享角色）
+pub async fn list_shared_with_me_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Query(pagination): Query<Pagination>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:draft:access_shared")?;
+
+    let drafts = state
+        .post_service
+        .list_shared_with_me(auth_user.user_id(), pagination)
+        .await?;
+    Ok(Json(drafts))
+}
+
 // 封禁文章处理器
 pub async fn ban_post_handler(
     auth_user: AuthUser,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(_payload): Json<BanPostPayload>, // 封禁原因等信息
+    Json(payload): Json<BanPostPayload>, // 封禁原因等信息
 ) -> Result<impl IntoResponse, ApiError> {
     let user_id = auth_user.user_id();
 
@@ -339,11 +526,15 @@ pub async fn ban_post_handler(
     }
 
     // 调用服务层封禁文章
-    state.post_service.ban_post(id, user_id, can_ban).await?;
+    let ban = state
+        .post_service
+        .ban_post(id, user_id, can_ban, payload)
+        .await?;
 
     Ok(Json(serde_json::json!({
         "message": "文章已被封禁",
-        "post_id": id
+        "post_id": id,
+        "ban": ban
     })))
 }
 
@@ -369,3 +560,270 @@ pub async fn unban_post_handler(
         "post_id": id
     })))
 }
+
+// 作者对封禁提交申诉处理器
+pub async fn appeal_ban_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateBanAppealPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    let appeal = state
+        .post_service
+        .appeal_ban(id, auth_user.user_id(), payload)
+        .await?;
+    Ok((StatusCode::CREATED, Json(appeal)))
+}
+
+// 封禁申诉队列处理器（仅拥有 post:ban 权限的版主）
+pub async fn list_ban_appeals_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<BanAppealQuery>,
+    Query(pagination): Query<Pagination>,
+) -> Result<impl IntoResponse, ApiError> {
+    let can_ban = auth_user.require_permission("post:ban").is_ok();
+    if !can_ban {
+        return Err(ApiError::from(anyhow::anyhow!("您没有权限处理封禁申诉")));
+    }
+
+    let appeals = state
+        .post_service
+        .list_ban_appeals(can_ban, query.status, pagination)
+        .await?;
+    Ok(Json(appeals))
+}
+
+// 处理封禁申诉处理器：维持封禁或解封
+pub async fn resolve_ban_appeal_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(appeal_id): Path<Uuid>,
+    Json(payload): Json<ResolveBanAppealPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    let can_ban = auth_user.require_permission("post:ban").is_ok();
+    if !can_ban {
+        return Err(ApiError::from(anyhow::anyhow!("您没有权限处理封禁申诉")));
+    }
+
+    let appeal = state
+        .post_service
+        .resolve_ban_appeal(appeal_id, auth_user.user_id(), can_ban, payload)
+        .await?;
+    Ok(Json(appeal))
+}
+
+// 提交审核处理器
+pub async fn submit_review_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SubmitReviewPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:submit_review")?;
+
+    let review = state
+        .post_service
+        .submit_for_review(id, auth_user.user_id(), payload)
+        .await?;
+    Ok(Json(review))
+}
+
+// 指派审核人处理器
+pub async fn assign_reviewer_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AssignReviewerPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:review")?;
+
+    let review = state
+        .post_service
+        .assign_reviewer(id, auth_user.user_id(), payload.reviewer_id)
+        .await?;
+    Ok(Json(review))
+}
+
+// 审核文章处理器（通过 / 要求修改）
+pub async fn review_post_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ReviewPostPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:review")?;
+
+    let review = state
+        .post_service
+        .review_post(id, auth_user.user_id(), payload)
+        .await?;
+    Ok(Json(review))
+}
+
+// 获取文章审核详情处理器
+pub async fn get_post_review_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let can_review = auth_user.require_permission("post:review").is_ok();
+
+    let review = state
+        .post_service
+        .get_post_review(id, auth_user.user_id(), can_review)
+        .await?;
+    Ok(Json(review))
+}
+
+// 待审核文章列表处理器
+pub async fn list_pending_reviews_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Query(pagination): Query<Pagination>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:review")?;
+
+    let paginated_response = state
+        .post_service
+        .list_pending_reviews(auth_user.user_id(), pagination)
+        .await?;
+    Ok(Json(paginated_response))
+}
+
+// 自动保存处理器：写入当前用户对文章的自动保存槽位，不影响文章已保存的内容
+pub async fn autosave_post_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AutosavePayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    // 与编辑文章相同的权限要求
+    let can_edit_any = auth_user.require_permission("post:edit_any").is_ok();
+    if !can_edit_any {
+        auth_user.require_permission("post:edit_own")?;
+    }
+
+    let autosave = state
+        .autosave_service
+        .save(id, auth_user.user_id(), can_edit_any, payload)
+        .await?;
+    Ok(Json(autosave))
+}
+
+// 获取自动保存处理器：只能取回自己的自动保存，用于恢复未保存的编辑
+pub async fn get_autosave_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let autosave = state.autosave_service.get(id, auth_user.user_id()).await?;
+    Ok(Json(autosave))
+}
+
+// 标签和分类推荐处理器：编辑器可以提交尚未保存的标题和正文
+pub async fn suggest_taxonomy_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<SuggestTaxonomyPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    // 与编辑文章相同的权限要求
+    let can_edit_any = auth_user.require_permission("post:edit_any").is_ok();
+    if !can_edit_any {
+        auth_user.require_permission("post:edit_own")?;
+    }
+
+    let suggestions = state
+        .taxonomy_suggestion_service
+        .suggest_for_post(id, auth_user.user_id(), can_edit_any, payload)
+        .await?;
+    Ok(Json(suggestions))
+}
+
+// 丢弃自动保存处理器
+pub async fn discard_autosave_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    state
+        .autosave_service
+        .discard(id, auth_user.user_id())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 创建草稿预览链接处理器：生成给没有账号的外部审阅者使用的签名链接
+pub async fn create_preview_link_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreatePreviewLinkPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    // 与编辑文章相同的权限要求
+    let can_edit_any = auth_user.require_permission("post:edit_any").is_ok();
+    if !can_edit_any {
+        auth_user.require_permission("post:edit_own")?;
+    }
+
+    let link = state
+        .preview_link_service
+        .create_link(id, auth_user.user_id(), can_edit_any, payload)
+        .await?;
+    Ok((StatusCode::CREATED, Json(link)))
+}
+
+// 获取文章有效预览链接列表处理器
+pub async fn list_preview_links_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let can_edit_any = auth_user.require_permission("post:edit_any").is_ok();
+    if !can_edit_any {
+        auth_user.require_permission("post:edit_own")?;
+    }
+
+    let links = state
+        .preview_link_service
+        .list_links(id, auth_user.user_id(), can_edit_any)
+        .await?;
+    Ok(Json(links))
+}
+
+// 撤销预览链接处理器
+pub async fn revoke_preview_link_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path((id, link_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let can_edit_any = auth_user.require_permission("post:edit_any").is_ok();
+    if !can_edit_any {
+        auth_user.require_permission("post:edit_own")?;
+    }
+
+    state
+        .preview_link_service
+        .revoke_link(id, link_id, auth_user.user_id(), can_edit_any)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 通过预览链接查看草稿处理器（公开访问，只读）
+pub async fn get_preview_post_handler(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let post_id = state.preview_link_service.resolve(&token).await?;
+    let post_detail = state.post_service.get_preview_post(post_id).await?;
+
+    // 预览内容不应被缓存或被搜索引擎收录
+    Ok((
+        [
+            (header::CACHE_CONTROL, "no-store"),
+            (header::HeaderName::from_static("x-robots-tag"), "noindex"),
+        ],
+        Json(post_detail),
+    ))
+}
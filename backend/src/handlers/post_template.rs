@@ -0,0 +1,90 @@
+use crate::api_error::ApiError;
+use crate::auth::AuthUser;
+use crate::dtos::post_template::{CreatePostTemplatePayload, UpdatePostTemplatePayload};
+use crate::handlers::AppState;
+use axum::extract::{Json, Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use uuid::Uuid;
+
+// 管理全局模板需要的权限
+const MANAGE_GLOBAL_PERMISSION: &str = "post:template:manage_global";
+
+// 列出可用的文章模板处理器：自己的模板和全局模板
+pub async fn list_post_templates_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:create")?;
+    let templates = state
+        .post_template_service
+        .list_templates(auth_user.user_id())
+        .await?;
+    Ok(Json(templates))
+}
+
+// 获取单个文章模板处理器
+pub async fn get_post_template_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:create")?;
+    let template = state
+        .post_template_service
+        .get_template(id, auth_user.user_id())
+        .await?;
+    Ok(Json(template))
+}
+
+// 创建文章模板处理器，创建全局模板需要 post:template:manage_global 权限
+pub async fn create_post_template_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Json(payload): Json<CreatePostTemplatePayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:create")?;
+    let can_manage_global = auth_user
+        .require_permission(MANAGE_GLOBAL_PERMISSION)
+        .is_ok();
+    let template = state
+        .post_template_service
+        .create_template(auth_user.user_id(), can_manage_global, payload)
+        .await?;
+    Ok((StatusCode::CREATED, Json(template)))
+}
+
+// 更新文章模板处理器
+pub async fn update_post_template_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdatePostTemplatePayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:create")?;
+    let can_manage_global = auth_user
+        .require_permission(MANAGE_GLOBAL_PERMISSION)
+        .is_ok();
+    let template = state
+        .post_template_service
+        .update_template(id, auth_user.user_id(), can_manage_global, payload)
+        .await?;
+    Ok(Json(template))
+}
+
+// 删除文章模板处理器
+pub async fn delete_post_template_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:create")?;
+    let can_manage_global = auth_user
+        .require_permission(MANAGE_GLOBAL_PERMISSION)
+        .is_ok();
+    state
+        .post_template_service
+        .delete_template(id, auth_user.user_id(), can_manage_global)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
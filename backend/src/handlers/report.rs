@@ -0,0 +1,95 @@
+use crate::api_error::ApiError;
+use crate::auth::{AuthUser, OptionalAuth};
+use crate::dtos::Pagination;
+use crate::dtos::post::BanPostPayload;
+use crate::dtos::report::CreateReportPayload;
+use crate::handlers::AppState;
+use axum::Extension;
+use axum::extract::{ConnectInfo, Json, Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use std::net::SocketAddr;
+use uuid::Uuid;
+
+// 读者举报文章处理器（公开接口，游客也可以举报）
+pub async fn report_post_handler(
+    optional_auth: OptionalAuth,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    Json(payload): Json<CreateReportPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok());
+    let peer_ip = connect_info.map(|Extension(ConnectInfo(addr))| addr.ip());
+
+    let receipt = state
+        .report_service
+        .report_post(
+            id,
+            optional_auth.context().user_id(),
+            forwarded_for,
+            peer_ip,
+            payload,
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(receipt)))
+}
+
+// 举报队列处理器（需要 post:report:review 权限）
+pub async fn list_reported_posts_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Query(pagination): Query<Pagination>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:report:review")?;
+    let posts = state.report_service.list_reported_posts(pagination).await?;
+    Ok(Json(posts))
+}
+
+// 获取文章待处理举报的处理器
+pub async fn list_post_reports_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(post_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:report:review")?;
+    let reports = state.report_service.list_reports_for_post(post_id).await?;
+    Ok(Json(reports))
+}
+
+// 驳回举报处理器
+pub async fn dismiss_post_reports_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(post_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:report:review")?;
+    let resolution = state
+        .report_service
+        .dismiss_reports(post_id, auth_user.user_id())
+        .await?;
+    Ok(Json(resolution))
+}
+
+// 从举报队列封禁文章处理器（同时需要 post:ban 权限）
+pub async fn ban_reported_post_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(post_id): Path<Uuid>,
+    Json(payload): Json<BanPostPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("post:report:review")?;
+    let can_ban = auth_user.require_permission("post:ban").is_ok();
+    if !can_ban {
+        return Err(ApiError::from(anyhow::anyhow!("您没有权限封禁文章")));
+    }
+
+    let resolution = state
+        .report_service
+        .ban_reported_post(post_id, auth_user.user_id(), can_ban, payload)
+        .await?;
+    Ok(Json(resolution))
+}
@@ -1,13 +1,14 @@
 use crate::api_error::ApiError;
 use crate::auth::AuthUser;
 use crate::dtos::tag::{
-    BatchDeleteTagsPayload, CreateTagPayload, MergeTagsPayload, MergeTagsPreviewPayload,
-    UpdateTagPayload,
+    BatchDeleteTagsPayload, CreateTagAliasPayload, CreateTagPayload, MergeTagsPayload,
+    MergeTagsPreviewPayload, OrphanedPostsHandling, UpdateTagPayload,
 };
 use crate::handlers::AppState;
+use crate::utils::{etag_header, expected_version};
 use axum::{
     extract::{Json, Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, header},
     response::IntoResponse,
 };
 use uuid::Uuid;
@@ -25,7 +26,13 @@ pub async fn create_tag_handler(
     tracing::info!("接收到创建标签请求：{:?}", payload);
 
     // 调用 TagService 的 create_tag 方法
-    let tag = state.tag_service.create_tag(payload).await?;
+    let (tag, created) = state.tag_service.create_tag(payload).await?;
+
+    if !created {
+        // 名称已是现有标签或其别名，返回 200 OK 和规范标签
+        tracing::info!("标签已存在，返回规范标签：{:?}", tag);
+        return Ok((StatusCode::OK, Json(tag)));
+    }
 
     tracing::info!("标签创建成功：{:?}", tag);
 
@@ -64,7 +71,9 @@ pub async fn get_tag_handler(
     };
     tracing::info!("成功获取标签：{:?}", tag);
 
-    Ok(Json(tag))
+    // 通过 ETag 返回当前版本，更新时需要在 If-Match 中带回
+    let etag = etag_header(tag.updated_at);
+    Ok(([(header::ETAG, etag)], Json(tag)))
 }
 
 /// 更新标签的 Handler
@@ -72,16 +81,23 @@ pub async fn update_tag_handler(
     auth_user: AuthUser,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<UpdateTagPayload>,
 ) -> Result<impl IntoResponse, ApiError> {
     // 授权检查
     auth_user.require_permission("tag:manage")?;
 
     tracing::info!("接收到更新标签请求：ID：{},Payload: {:?}", id, payload);
-    let updated_tag = state.tag_service.update_tag(id, payload).await?;
+    // 乐观并发控制：必须携带读取时的版本
+    let expected_version = expected_version(&headers)?;
+    let updated_tag = state
+        .tag_service
+        .update_tag(id, payload, expected_version)
+        .await?;
     tracing::info!("标签更新成功：{:?}", updated_tag);
 
-    Ok(Json(updated_tag))
+    let etag = etag_header(updated_tag.updated_at);
+    Ok(([(header::ETAG, etag)], Json(updated_tag)))
 }
 
 pub async fn delete_tag_handler(
@@ -100,6 +116,59 @@ pub async fn delete_tag_handler(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// 获取标签别名列表的 Handler
+pub async fn list_tag_aliases_handler(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    tracing::info!("接收到获取标签别名请求：ID: {}", id);
+
+    let aliases = state.tag_service.list_tag_aliases(id).await?;
+
+    Ok(Json(aliases))
+}
+
+/// 为标签添加别名的 Handler
+pub async fn create_tag_alias_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateTagAliasPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    // 授权检查 - 别名影响所有作者创建标签的结果，需要标签管理权限
+    auth_user.require_permission("tag:manage")?;
+
+    tracing::info!("接收到添加标签别名请求：ID: {}, Payload: {:?}", id, payload);
+
+    let alias = state.tag_service.add_tag_alias(id, payload).await?;
+
+    tracing::info!("标签别名添加成功：{:?}", alias);
+
+    Ok((StatusCode::CREATED, Json(alias)))
+}
+
+/// 删除标签别名的 Handler
+pub async fn delete_tag_alias_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path((id, alias_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    // 授权检查
+    auth_user.require_permission("tag:manage")?;
+
+    tracing::info!(
+        "接收到删除标签别名请求：标签 ID: {}, 别名 ID: {}",
+        id,
+        alias_id
+    );
+
+    state.tag_service.remove_tag_alias(id, alias_id).await?;
+
+    tracing::info!("标签别名删除成功：{}", alias_id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 // === 管理员专用的标签管理功能 ===
 
 /// 合并标签：将多个标签合并为一个目标标签
@@ -180,6 +249,21 @@ pub async fn batch_delete_tags_handler(
 
     tracing::info!("接收到批量删除标签请求：{:?}", payload);
 
+    if let Some(OrphanedPostsHandling::AutoSuggestTags) = payload.handle_orphaned_posts {
+        // 为失去全部标签的文章推荐新标签后再删除
+        let suggested_tags = state
+            .taxonomy_suggestion_service
+            .suggest_tags_for_orphans(&payload.tag_ids)
+            .await?;
+        let response = state
+            .tag_service
+            .batch_delete_tags_enhanced(&payload.tag_ids, &suggested_tags)
+            .await?;
+
+        tracing::info!("增强版批量删除标签成功：{}", response.operation_summary);
+        return Ok(Json(response).into_response());
+    }
+
     let deleted_count = state
         .tag_service
         .batch_delete_tags(&payload.tag_ids)
@@ -187,7 +271,7 @@ pub async fn batch_delete_tags_handler(
 
     tracing::info!("批量删除标签成功：删除了 {} 个标签", deleted_count);
 
-    Ok(Json(serde_json::json!({ "deleted_count": deleted_count })))
+    Ok(Json(serde_json::json!({ "deleted_count": deleted_count })).into_response())
 }
 
 /// 获取标签使用统计
@@ -0,0 +1,76 @@
+use crate::api_error::ApiError;
+use crate::auth::AuthUser;
+use crate::handlers::AppState;
+use axum::extract::{Json, Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use uuid::Uuid;
+
+// 获取回收站列表处理器
+// 返回内容按权限过滤：文章按 post:delete_any / post:delete_own，分类和标签按对应的管理权限
+pub async fn list_trash_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let can_delete_any_post = auth_user.require_permission("post:delete_any").is_ok();
+    let can_delete_own_post = auth_user.require_permission("post:delete_own").is_ok();
+    let can_manage_categories = auth_user.require_permission("category:manage").is_ok();
+    let can_manage_tags = auth_user.require_permission("tag:manage").is_ok();
+
+    if !(can_delete_any_post || can_delete_own_post || can_manage_categories || can_manage_tags) {
+        return Err(ApiError::from(anyhow::anyhow!("没有查看回收站的权限")));
+    }
+
+    let trash = state
+        .trash_service
+        .list_trash(
+            auth_user.user_id(),
+            can_delete_any_post,
+            can_delete_own_post,
+            can_manage_categories,
+            can_manage_tags,
+        )
+        .await?;
+    Ok(Json(trash))
+}
+
+// 恢复文章处理器
+pub async fn restore_post_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    // 与删除使用同一组权限：能删除的人才能恢复
+    let can_delete_any = auth_user.require_permission("post:delete_any").is_ok();
+    if !can_delete_any {
+        auth_user.require_permission("post:delete_own")?;
+    }
+
+    state
+        .trash_service
+        .restore_post(id, auth_user.user_id(), can_delete_any)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 恢复分类处理器
+pub async fn restore_category_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("category:manage")?;
+    state.trash_service.restore_category(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 恢复标签处理器
+pub async fn restore_tag_handler(
+    auth_user: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    auth_user.require_permission("tag:manage")?;
+    state.trash_service.restore_tag(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,12 +1,14 @@
-use crate::dtos::user::ChangePasswordPayload;
+use crate::dtos::user::{ChangePasswordPayload, UpdateNotificationPreferencesPayload};
 use crate::{
     api_error::ApiError, auth::AuthUser, dtos::user::UpdateProfilePayload, handlers::AppState,
 };
+use anyhow::anyhow;
 use axum::{
-    extract::{Json, State},
-    http::StatusCode,
+    extract::{Json, Multipart, Path, State},
+    http::{StatusCode, header},
     response::IntoResponse,
 };
+use uuid::Uuid;
 
 // 获取 "我" 的个人资料
 pub async fn get_my_profile_handler(
@@ -32,6 +34,61 @@ pub async fn update_my_profile_handler(
     Ok(Json(updated_profile))
 }
 
+// 上传 "我" 的头像，请求体为 multipart/form-data，文件放在 avatar 字段中
+pub async fn upload_my_avatar_handler(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = auth_user.user_id();
+    let mut avatar = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| anyhow!("无效的上传请求: {}", e))?
+    {
+        if field.name() == Some("avatar") {
+            let data = field
+                .bytes()
+                .await
+                .map_err(|e| anyhow!("无效的上传请求: {}", e))?;
+            avatar = Some(data);
+            break;
+        }
+    }
+    let avatar = avatar.ok_or_else(|| anyhow!("无效的上传请求：缺少 avatar 字段"))?;
+
+    let profile = state.user_service.upload_avatar(user_id, &avatar).await?;
+    Ok(Json(profile))
+}
+
+// 删除 "我" 上传的头像
+pub async fn delete_my_avatar_handler(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = auth_user.user_id();
+    let profile = state.user_service.delete_avatar(user_id).await?;
+    Ok(Json(profile))
+}
+
+// 获取用户上传的头像，公开访问
+pub async fn get_user_avatar_handler(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let avatar = state.user_service.get_avatar(user_id).await?;
+    // 头像地址带有版本参数，更换头像后地址随之改变，可以长期缓存
+    Ok((
+        [
+            (header::CONTENT_TYPE, avatar.content_type),
+            (header::CACHE_CONTROL, "public, max-age=86400".to_string()),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        avatar.data,
+    ))
+}
+
 // 删除 "我" 的账户
 pub async fn delete_my_account_handler(
     State(state): State<AppState>,
@@ -67,3 +124,27 @@ pub async fn get_my_stats_handler(
     let stats = state.user_service.get_user_stats(user_id).await?;
     Ok(Json(stats))
 }
+
+// 获取 "我" 的通知偏好
+pub async fn get_my_notification_preferences_handler(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = auth_user.user_id();
+    let preferences = state.notification_service.get_preferences(user_id).await?;
+    Ok(Json(preferences))
+}
+
+// 更新 "我" 的通知偏好
+pub async fn update_my_notification_preferences_handler(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(payload): Json<UpdateNotificationPreferencesPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = auth_user.user_id();
+    let preferences = state
+        .notification_service
+        .update_preferences(user_id, payload)
+        .await?;
+    Ok(Json(preferences))
+}
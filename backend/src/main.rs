@@ -2,18 +2,25 @@ use anyhow::{Context, Result};
 use backend::config::AppConfig;
 use backend::handlers::AppState;
 use backend::repositories::{
-    CategoryRepository, LoginAttemptRepository, OneTimeTokenRepository, PermissionRepository,
-    PostRepository, PostgresCategoryRepository, PostgresLoginAttemptRepository,
-    PostgresOneTimeTokenRepository, PostgresPermissionRepository, PostgresPostRepository,
-    PostgresRoleRepository, PostgresTagRepository, PostgresUserRepository, RoleRepository,
-    TagRepository, UserRepository,
+    CategoryRepository, DraftCommentRepository, LoginAttemptRepository,
+    NotificationPreferenceRepository, OneTimeTokenRepository, PermissionRepository,
+    PostAutosaveRepository, PostPreviewLinkRepository, PostReportRepository, PostRepository,
+    PostTemplateRepository, PostgresCategoryRepository, PostgresDraftCommentRepository,
+    PostgresLoginAttemptRepository, PostgresNotificationPreferenceRepository,
+    PostgresOneTimeTokenRepository, PostgresPermissionRepository, PostgresPostAutosaveRepository,
+    PostgresPostPreviewLinkRepository, PostgresPostReportRepository, PostgresPostRepository,
+    PostgresPostTemplateRepository, PostgresRoleRepository, PostgresTagRepository,
+    PostgresUserRepository, RoleRepository, TagRepository, UserRepository,
 };
 use backend::routes::create_router;
 use backend::services::{
-    AdminService, AuthService, CategoryService, EmailService, PostService, TagService, UserService,
+    AdminService, AuthService, AutosaveService, CategoryService, DraftCommentService, EmailService,
+    NotificationService, PostService, PostTemplateService, PreviewLinkService, ReportService,
+    TagService, TaxonomySuggestionService, TrashService, UserService,
 };
 use sqlx::PgPool;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
@@ -60,6 +67,19 @@ async fn main() -> Result<()> {
         Arc::new(PostgresLoginAttemptRepository::new(db_pool.clone()));
     let one_time_token_repo: Arc<dyn OneTimeTokenRepository> =
         Arc::new(PostgresOneTimeTokenRepository::new(db_pool.clone()));
+    let draft_comment_repo: Arc<dyn DraftCommentRepository> =
+        Arc::new(PostgresDraftCommentRepository::new(db_pool.clone()));
+    let autosave_repo: Arc<dyn PostAutosaveRepository> =
+        Arc::new(PostgresPostAutosaveRepository::new(db_pool.clone()));
+    let preview_link_repo: Arc<dyn PostPreviewLinkRepository> =
+        Arc::new(PostgresPostPreviewLinkRepository::new(db_pool.clone()));
+    let notification_preference_repo: Arc<dyn NotificationPreferenceRepository> = Arc::new(
+        PostgresNotificationPreferenceRepository::new(db_pool.clone()),
+    );
+    let report_repo: Arc<dyn PostReportRepository> =
+        Arc::new(PostgresPostReportRepository::new(db_pool.clone()));
+    let post_template_repo: Arc<dyn PostTemplateRepository> =
+        Arc::new(PostgresPostTemplateRepository::new(db_pool.clone()));
 
     // -- 实例化所有的 Services ----
     let tag_service = Arc::new(TagService::new(tag_repo.clone()));
@@ -80,6 +100,12 @@ async fn main() -> Result<()> {
         permission_repo.clone(),
     ));
     let user_service = Arc::new(UserService::new(user_repo.clone()));
+    let notification_service = Arc::new(NotificationService::new(
+        notification_preference_repo.clone(),
+        user_repo.clone(),
+        email_service.clone(),
+        &config.email,
+    ));
 
     //  -- 创建 PostService 实例 ---
     let post_service = Arc::new(PostService::new(
@@ -87,8 +113,73 @@ async fn main() -> Result<()> {
         category_repo.clone(),
         tag_repo.clone(),
         user_repo.clone(),
+        notification_service.clone(),
+        &config.markdown,
+    ));
+    let draft_comment_service = Arc::new(DraftCommentService::new(
+        draft_comment_repo.clone(),
+        post_repo.clone(),
+    ));
+    let autosave_service = Arc::new(AutosaveService::new(
+        autosave_repo.clone(),
+        post_repo.clone(),
+        &config.autosave,
+    ));
+    let trash_service = Arc::new(TrashService::new(
+        post_repo.clone(),
+        category_repo.clone(),
+        tag_repo.clone(),
+        &config.trash,
+    ));
+    let preview_link_service = Arc::new(PreviewLinkService::new(
+        preview_link_repo.clone(),
+        post_repo.clone(),
+        &config.auth,
+    ));
+    let report_service = Arc::new(ReportService::new(
+        report_repo.clone(),
+        post_repo.clone(),
+        post_service.clone(),
+        &config.reports,
+        &config.auth,
+    ));
+    let post_template_service = Arc::new(PostTemplateService::new(
+        post_template_repo.clone(),
+        category_repo.clone(),
+        tag_repo.clone(),
+    ));
+    let taxonomy_suggestion_service = Arc::new(TaxonomySuggestionService::new(
+        post_repo.clone(),
+        tag_repo.clone(),
+        category_repo.clone(),
     ));
 
+    // -- 后台任务：定期清理过期的自动保存 ---
+    let purge_service = autosave_service.clone();
+    let purge_interval = Duration::from_secs(config.autosave.purge_interval_minutes * 60);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(purge_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = purge_service.purge_expired().await {
+                tracing::warn!("清理过期自动保存失败: {:?}", e);
+            }
+        }
+    });
+
+    // -- 后台任务：定期彻底删除回收站中超过保留期的条目 ---
+    let trash_purge_service = trash_service.clone();
+    let trash_purge_interval = Duration::from_secs(config.trash.purge_interval_minutes * 60);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(trash_purge_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = trash_purge_service.purge_expired().await {
+                tracing::warn!("清理回收站失败: {:?}", e);
+            }
+        }
+    });
+
     // 创建 AppState
     let app_state = AppState {
         post_service,
@@ -97,6 +188,14 @@ async fn main() -> Result<()> {
         auth_service,
         admin_service,
         user_service,
+        draft_comment_service,
+        autosave_service,
+        trash_service,
+        preview_link_service,
+        notification_service,
+        report_service,
+        post_template_service,
+        taxonomy_suggestion_service,
     };
 
     // 创建 Axum 路由
@@ -110,9 +209,13 @@ async fn main() -> Result<()> {
         .await
         .context(format!("绑定端口 {} 失败", addr))?;
 
-    axum::serve(listener, app.into_make_service())
-        .await
-        .context("启动Axum 服务器失败")?;
+    // 携带客户端地址，游客举报的频率限制需要用到
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await
+    .context("启动Axum 服务器失败")?;
 
     Ok(())
 }
@@ -9,6 +9,7 @@ pub struct Category {
     pub id: Uuid,
     pub name: String,
     pub slug: String,
+    pub parent_id: Option<Uuid>, // 父分类，为空表示顶级分类
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
\ No newline at end of file
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 草稿评审意见（行内评论）模型
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct DraftComment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub author_id: Uuid,
+    pub author_username: String,     // 关联查询得到的评论作者用户名
+    pub parent_id: Option<Uuid>,     // 回复所属的顶层评论
+    pub anchor_type: Option<String>, // 'range' / 'heading'，回复为 None
+    pub anchor_start: Option<i32>,
+    pub anchor_end: Option<i32>,
+    pub anchor_heading: Option<String>,
+    pub quoted_text: Option<String>, // 创建时引用的原文片段
+    pub body: String,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
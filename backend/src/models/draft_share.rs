@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 草稿分享角色
+// viewer 只读，commenter 可以添加评审意见，editor 还可以编辑草稿内容
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DraftShareRole {
+    Viewer,
+    Commenter,
+    Editor,
+}
+
+impl DraftShareRole {
+    // 数据库中存储的字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DraftShareRole::Viewer => "viewer",
+            DraftShareRole::Commenter => "commenter",
+            DraftShareRole::Editor => "editor",
+        }
+    }
+
+    // 从数据库字符串解析，未知值按最小权限视为只读
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "commenter" => DraftShareRole::Commenter,
+            "editor" => DraftShareRole::Editor,
+            _ => DraftShareRole::Viewer,
+        }
+    }
+
+    // 是否可以添加评审意见
+    pub fn can_comment(&self) -> bool {
+        matches!(self, DraftShareRole::Commenter | DraftShareRole::Editor)
+    }
+
+    // 是否可以编辑草稿内容
+    pub fn can_edit(&self) -> bool {
+        matches!(self, DraftShareRole::Editor)
+    }
+}
+
+// 草稿分享模型（每个接收人一条记录）
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct DraftShare {
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    pub role: String,
+    pub expires_at: Option<DateTime<Utc>>, // 为空表示永久有效
+    pub shared_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,13 +1,31 @@
 pub mod category;
+pub mod draft_comment;
+pub mod draft_share;
+pub mod notification_preference;
 pub mod permission;
 pub mod post;
+pub mod post_autosave;
+pub mod post_ban;
+pub mod post_preview_link;
+pub mod post_report;
+pub mod post_template;
 pub mod role;
 pub mod tag;
+pub mod trash;
 pub mod user;
 
 pub use category::Category;
+pub use draft_comment::DraftComment;
+pub use draft_share::{DraftShare, DraftShareRole};
+pub use notification_preference::NotificationPreferences;
 pub use permission::Permission;
-pub use post::{DraftAccessLog, Post};
+pub use post::{DraftAccessLog, Post, PostReview, ReviewStatus};
+pub use post_autosave::PostAutosave;
+pub use post_ban::{BanAppealStatus, PostBan, PostBanAppeal};
+pub use post_preview_link::PostPreviewLink;
+pub use post_report::{PostReport, ReportCategory};
+pub use post_template::PostTemplate;
 pub use role::Role;
-pub use tag::Tag;
-pub use user::{User, UserPublic};
+pub use tag::{Tag, TagAlias};
+pub use trash::{TrashedCategory, TrashedPost, TrashedTag};
+pub use user::{SocialLink, User, UserAvatar, UserProfile, UserPublic};
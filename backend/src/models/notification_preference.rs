@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 用户通知偏好模型（没有记录的用户按默认值全部开启）
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct NotificationPreferences {
+    pub user_id: Uuid,
+    pub draft_shared: bool, // 有人分享草稿给我时是否发送邮件
+}
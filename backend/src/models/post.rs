@@ -17,13 +17,17 @@ pub struct Post {
 
     // 草稿分享相关字段
     #[sqlx(default)]
-    pub draft_shared_with: Option<Vec<Uuid>>, // 分享给哪些用户（UUID数组）
+    pub draft_shared_with: Option<Vec<Uuid>>, // 当前有效的分享接收人（由 draft_shares 表汇总）
     #[sqlx(default)]
     pub is_draft_public: Option<bool>, // 是否允许有权限的编辑查看
     
     // 文章封禁状态
     #[sqlx(default)]
     pub is_banned: Option<bool>, // 是否被管理员封禁
+
+    // Markdown 渲染方案，为空时使用默认方案
+    #[sqlx(default)]
+    pub render_profile: Option<String>,
 }
 
 // 草稿访问日志模型
@@ -31,8 +35,55 @@ pub struct Post {
 pub struct DraftAccessLog {
     pub id: Uuid,
     pub post_id: Uuid,
-    pub accessed_by: Uuid,
-    pub access_type: String,           // 'view', 'edit', 'delete'
+    pub accessed_by: Option<Uuid>,     // 通过预览链接访问时为空
+    pub access_type: String,           // 'view', 'edit', 'delete', 'preview_link'
     pub access_reason: Option<String>, // 访问原因说明
+    pub preview_link_id: Option<Uuid>, // 通过预览链接访问时使用的链接
     pub created_at: DateTime<Utc>,
 }
+
+// 文章审核状态
+// 流转顺序：draft -> in_review -> changes_requested -> approved -> published
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    Draft,
+    InReview,
+    ChangesRequested,
+    Approved,
+    Published,
+}
+
+impl ReviewStatus {
+    // 数据库中存储的字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewStatus::Draft => "draft",
+            ReviewStatus::InReview => "in_review",
+            ReviewStatus::ChangesRequested => "changes_requested",
+            ReviewStatus::Approved => "approved",
+            ReviewStatus::Published => "published",
+        }
+    }
+
+    // 从数据库字符串解析，未知值视为草稿
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "in_review" => ReviewStatus::InReview,
+            "changes_requested" => ReviewStatus::ChangesRequested,
+            "approved" => ReviewStatus::Approved,
+            "published" => ReviewStatus::Published,
+            _ => ReviewStatus::Draft,
+        }
+    }
+}
+
+// 文章当前审核状态模型
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct PostReview {
+    pub post_id: Uuid,
+    pub status: String,
+    pub reviewer_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 文章自动保存模型（每个用户对每篇文章一个槽位）
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct PostAutosave {
+    pub post_id: Uuid,
+    pub user_id: Uuid,
+    pub title: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>, // 最近一次自动保存的时间
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 文章封禁记录模型（每次封禁一条，解封时填写 lifted_*）
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct PostBan {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub banned_by: Option<Uuid>,
+    pub reason: Option<String>,
+    pub banned_at: DateTime<Utc>,
+    pub lifted_by: Option<Uuid>,
+    pub lifted_at: Option<DateTime<Utc>>, // 为空表示封禁仍然生效
+}
+
+// 封禁申诉状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BanAppealStatus {
+    Pending,  // 等待版主处理
+    Upheld,   // 维持封禁
+    Unbanned, // 已解封
+}
+
+impl BanAppealStatus {
+    // 数据库中存储的字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BanAppealStatus::Pending => "pending",
+            BanAppealStatus::Upheld => "upheld",
+            BanAppealStatus::Unbanned => "unbanned",
+        }
+    }
+
+    // 从数据库字符串解析，未知值视为待处理
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "upheld" => BanAppealStatus::Upheld,
+            "unbanned" => BanAppealStatus::Unbanned,
+            _ => BanAppealStatus::Pending,
+        }
+    }
+}
+
+// 封禁申诉模型（每次封禁最多一条）
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct PostBanAppeal {
+    pub id: Uuid,
+    pub ban_id: Uuid,
+    pub post_id: Uuid,
+    pub appellant_id: Option<Uuid>,
+    pub message: String,
+    pub status: String,
+    pub resolved_by: Option<Uuid>,
+    pub resolution_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 草稿预览链接模型
+// 令牌本身不入库，由链接 ID 和过期时间签名生成
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct PostPreviewLink {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub created_by: Uuid,
+    pub label: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>, // 不为空表示已被撤销
+    pub use_count: i32,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
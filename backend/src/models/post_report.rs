@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 举报分类
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportCategory {
+    Spam,      // 垃圾广告
+    Abuse,     // 辱骂、骚扰
+    Illegal,   // 违法内容
+    Copyright, // 侵犯版权
+    #[default]
+    Other, // 其他
+}
+
+impl ReportCategory {
+    // 数据库中存储的字符串
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportCategory::Spam => "spam",
+            ReportCategory::Abuse => "abuse",
+            ReportCategory::Illegal => "illegal",
+            ReportCategory::Copyright => "copyright",
+            ReportCategory::Other => "other",
+        }
+    }
+
+    // 从数据库字符串解析，未知值视为其他
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "spam" => ReportCategory::Spam,
+            "abuse" => ReportCategory::Abuse,
+            "illegal" => ReportCategory::Illegal,
+            "copyright" => ReportCategory::Copyright,
+            _ => ReportCategory::Other,
+        }
+    }
+}
+
+// 读者举报模型
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct PostReport {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub reporter_id: Option<Uuid>, // 游客举报时为空
+    #[serde(skip_serializing)]
+    pub reporter_key: String, // 登录用户为 "user:{id}"，游客为 IP 的带密钥哈希
+    pub category: String,
+    pub details: Option<String>,
+    pub status: String, // open | dismissed | actioned
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 文章模板模型
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct PostTemplate {
+    pub id: Uuid,
+    pub owner_id: Option<Uuid>, // 为空表示全局模板
+    pub name: String,
+    pub title_pattern: String, // 标题模式，使用模板时替换其中的日期占位符
+    pub content: String,
+    pub category_ids: Vec<Uuid>, // 默认分类
+    pub tag_ids: Vec<Uuid>,      // 默认标签
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -11,3 +11,13 @@ pub struct Tag {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// 标签别名：按名称或 slug 查找标签时解析到 `tag_id` 指向的规范标签
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct TagAlias {
+    pub id: Uuid,
+    pub tag_id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+// 回收站中的文章
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct TrashedPost {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub author_id: Option<Uuid>,
+    pub deleted_at: DateTime<Utc>, // 移入回收站的时间
+}
+
+// 回收站中的分类
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct TrashedCategory {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+// 回收站中的标签
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct TrashedTag {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub deleted_at: DateTime<Utc>,
+}
@@ -26,3 +26,29 @@ pub struct UserPublic {
     pub created_at: DateTime<Utc>,
     pub roles: Vec<String>, // 用户拥有的角色名称列表
 }
+
+// 用户资料，用于 /me 和文章的作者署名
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UserProfile {
+    pub display_name: Option<String>,
+    pub bio: Option<String>,      // 简介的 Markdown 原文
+    pub bio_html: Option<String>, // 渲染后的简介
+    pub avatar_url: Option<String>,
+    pub website: Option<String>,
+    pub social_links: Vec<SocialLink>,
+}
+
+// 社交链接，platform 为小写的平台名称，如 github
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SocialLink {
+    pub platform: String,
+    pub url: String,
+}
+
+// 用户上传的头像
+#[derive(Debug, Clone, FromRow)]
+pub struct UserAvatar {
+    pub content_type: String,
+    pub data: Vec<u8>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,7 +1,11 @@
-use crate::models::Category;
+use crate::models::{Category, TrashedCategory};
+use crate::repositories::similarity::find_similar_name_clusters;
+use crate::utils::VersionError;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// 分类使用统计
@@ -15,7 +19,8 @@ pub struct CategoryUsageStats {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SimilarCategoryGroup {
     pub categories: Vec<Category>,
-    pub similarity_reason: String, // 例如："名称相似"、"同义词"等
+    pub similarity_reason: String, // 例如："名称拼写相近"
+    pub confidence: f64,           // 0 到 1，越高越可能是重复分类
 }
 
 #[async_trait]
@@ -23,7 +28,8 @@ pub trait CategoryRepository: Send + Sync {
     /// 创建一个新的分类
     /// name: 分类名称
     /// slug： 根据名称生成 URL slug
-    async fn create(&self, name: &str, slug: &str) -> Result<Category>;
+    /// parent_id: 父分类，为 None 时创建顶级分类
+    async fn create(&self, name: &str, slug: &str, parent_id: Option<Uuid>) -> Result<Category>;
 
     /// 根据 ID 获取分类
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Category>>;
@@ -34,25 +40,36 @@ pub trait CategoryRepository: Send + Sync {
     /// 获取所有分类列表
     async fn list(&self) -> Result<Vec<Category>>;
 
+    /// 获取这些分类的直接子分类（不包括回收站中的分类）
+    async fn list_children(&self, parent_ids: &[Uuid]) -> Result<Vec<Category>>;
+
+    /// 获取分类自身及其所有后代分类的 ID（不包括回收站中的分类）
+    async fn get_descendant_ids(&self, id: Uuid) -> Result<Vec<Uuid>>;
+
     /// 更新分类信息
     /// id: 要更新的分类的 ID
     /// name_opt: 可选的新名称。如果Some，则更新名称
     /// new_slug_opt: 可选的新 slug。如果Some，则更新slug
     /// 通常 slug 的更新与 name的更新联动，并在Service层处理
+    /// parent_opt: 可选的新父分类。Some(None) 表示移为顶级分类，循环检查在 Service 层处理
     /// Repository 层只负责执行数据库操作
+    /// expected_version: 客户端读取时的 updated_at，为 None 时不校验版本
     async fn update(
         &self,
         id: Uuid,
         name_opt: Option<&str>,
         new_slug_opt: Option<&str>,
+        parent_opt: Option<Option<Uuid>>,
+        expected_version: Option<DateTime<Utc>>,
     ) -> Result<Category>;
 
-    /// 根据 ID 删除分类
+    /// 根据 ID 删除分类，子分类移到被删除分类的父分类下
     async fn delete(&self, id: Uuid) -> Result<()>;
 
     // === 管理员专用功能 ===
 
     /// 合并分类：将source_category_ids中的所有分类的文章关联转移到target_category_id，然后删除源分类
+    /// 源分类的子分类移到目标分类下
     async fn merge_categories(
         &self,
         target_category_id: Uuid,
@@ -74,15 +91,19 @@ pub trait CategoryRepository: Send + Sync {
         source_category_ids: &[Uuid],
     ) -> Result<(Vec<Category>, usize, usize)>;
 
-    /// 批量删除分类
+    /// 批量删除分类，子分类移到最近的未被删除的上级分类下
     async fn batch_delete(&self, category_ids: &[Uuid]) -> Result<usize>;
 
-    /// 增强版批量删除：支持孤儿文章处理
+    /// 增强版批量删除：支持孤儿文章处理，子分类的处理同 batch_delete
+    ///
+    /// 孤儿文章优先归入 `suggested_categories` 中推荐的分类（文章 ID -> 分类 ID），
+    /// 没有推荐的归入"未分类"
     async fn batch_delete_enhanced(
         &self,
         category_ids: &[Uuid],
         handle_orphaned: bool,
-    ) -> Result<(usize, usize, usize)>;
+        suggested_categories: &HashMap<Uuid, Vec<Uuid>>,
+    ) -> Result<(usize, usize, usize, usize)>;
 
     /// 获取分类使用统计
     async fn get_usage_stats(&self) -> Result<Vec<CategoryUsageStats>>;
@@ -93,6 +114,17 @@ pub trait CategoryRepository: Send + Sync {
     /// 获取单个分类的文章关联信息
     async fn get_category_post_info(&self, category_id: Uuid) -> Result<(usize, Vec<String>)>;
 
+    // === 回收站 ===
+
+    /// 获取回收站中的分类
+    async fn list_deleted(&self) -> Result<Vec<TrashedCategory>>;
+
+    /// 从回收站恢复分类
+    async fn restore(&self, id: Uuid) -> Result<()>;
+
+    /// 彻底删除在回收站中超过保留天数的分类，返回删除的数量
+    async fn purge_deleted(&self, retention_days: i32) -> Result<u64>;
+
     // 以后可能用到的方法
     // ///检查具有给定名称的分类是否存在
     // async fn name_exists(&self,name:&str)->Result<bool>;
@@ -113,19 +145,20 @@ impl PostgresCategoryRepository {
 
 #[async_trait]
 impl CategoryRepository for PostgresCategoryRepository {
-    async fn create(&self, name: &str, slug: &str) -> Result<Category> {
+    async fn create(&self, name: &str, slug: &str, parent_id: Option<Uuid>) -> Result<Category> {
         let category_id = Uuid::new_v4();
         // created_at 和 updated_at 将使用数据库的 DEFAULT NOW()
         let category = sqlx::query_as!(
             Category,
             r#"
-            insert into categories (id,name,slug)
-            values ($1,$2,$3)
-            returning id,name,slug,created_at,updated_at
+            insert into categories (id,name,slug,parent_id)
+            values ($1,$2,$3,$4)
+            returning id,name,slug,parent_id,created_at,updated_at
             "#,
             category_id,
             name,
-            slug
+            slug,
+            parent_id
         )
         .fetch_one(&self.pool)
         .await
@@ -138,9 +171,9 @@ impl CategoryRepository for PostgresCategoryRepository {
         let category = sqlx::query_as!(
             Category,
             r#"
-            select id,name,slug,created_at,updated_at
+            select id,name,slug,parent_id,created_at,updated_at
             from categories
-            where id = $1
+            where id = $1 and deleted_at is null
             "#,
             id
         )
@@ -155,9 +188,9 @@ impl CategoryRepository for PostgresCategoryRepository {
         let category = sqlx::query_as!(
             Category,
             r#"
-            select id,name,slug,created_at,updated_at
+            select id,name,slug,parent_id,created_at,updated_at
             from categories
-            where slug = $1
+            where slug = $1 and deleted_at is null
             "#,
             slug
         )
@@ -172,8 +205,9 @@ impl CategoryRepository for PostgresCategoryRepository {
         let categories = sqlx::query_as!(
             Category,
             r#"
-            select id,name,slug,created_at,updated_at
+            select id,name,slug,parent_id,created_at,updated_at
             from categories
+            where deleted_at is null
             order by name  -- 按名称字幕顺序排序
             "#
         )
@@ -184,11 +218,54 @@ impl CategoryRepository for PostgresCategoryRepository {
         Ok(categories)
     }
 
+    async fn list_children(&self, parent_ids: &[Uuid]) -> Result<Vec<Category>> {
+        let children = sqlx::query_as!(
+            Category,
+            r#"
+            select id,name,slug,parent_id,created_at,updated_at
+            from categories
+            where parent_id = any($1) and deleted_at is null
+            order by name
+            "#,
+            parent_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("查询子分类失败")?;
+
+        Ok(children)
+    }
+
+    async fn get_descendant_ids(&self, id: Uuid) -> Result<Vec<Uuid>> {
+        // 使用 UNION 去重，即使数据中出现循环也能结束
+        let ids = sqlx::query_scalar!(
+            r#"
+            WITH RECURSIVE descendants AS (
+                SELECT id FROM categories WHERE id = $1 AND deleted_at IS NULL
+                UNION
+                SELECT c.id
+                FROM categories c
+                JOIN descendants d ON c.parent_id = d.id
+                WHERE c.deleted_at IS NULL
+            )
+            SELECT id AS "id!" FROM descendants
+            "#,
+            id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context(format!("查询分类 {} 的后代分类失败", id))?;
+
+        Ok(ids)
+    }
+
     async fn update(
         &self,
         id: Uuid,
         name_opt: Option<&str>,
         new_slug_opt: Option<&str>,
+        parent_opt: Option<Option<Uuid>>,
+        expected_version: Option<DateTime<Utc>>,
     ) -> Result<Category> {
         // 1. 先获取当前分类数据，以便只更新提供的字段，或者基于当前值进行更新
         let mut current_category = self
@@ -207,32 +284,53 @@ impl CategoryRepository for PostgresCategoryRepository {
         if let Some(s) = new_slug_opt {
             current_category.slug = s.to_string();
         }
+        if let Some(parent_id) = parent_opt {
+            current_category.parent_id = parent_id;
+        }
         // updated_at 将由数据库触发器自动更新，无需在此设置 current_category.updated_at
-        // 3.执行SQL update
+        // 3.执行SQL update，版本不一致时不更新任何行
         let updated_category = sqlx::query_as!(
             Category,
             r#"
             update categories
-            set name = $1,slug = $2
-            where id = $3
-            returning id,name,slug,created_at,updated_at
+            set name = $1,slug = $2,parent_id = $3
+            where id = $4 and deleted_at is null and ($5::timestamptz is null or updated_at = $5)
+            returning id,name,slug,parent_id,created_at,updated_at
             "#,
             current_category.name,
             current_category.slug,
-            id
+            current_category.parent_id,
+            id,
+            expected_version
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
         .context(format!("数据库层面更新 Category (ID: {}) 失败", id))?;
 
-        Ok(updated_category)
+        match updated_category {
+            Some(category) => Ok(category),
+            None => {
+                // 未更新任何行：分类在此期间已被其他请求修改（或删除）
+                let latest = self
+                    .get_by_id(id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("更新目标 Category (ID: {}) 未找到", id))?;
+                Err(VersionError::stale(latest.updated_at).into())
+            }
+        }
     }
 
     async fn delete(&self, id: Uuid) -> Result<()> {
-        let result = sqlx::query!("delete from categories where id = $1", id)
-            .execute(&self.pool)
-            .await
-            .context(format!("删除 Category (ID: {})", id))?;
+        let mut txn = self.pool.begin().await.context("开始删除分类事务失败")?;
+
+        // 软删除：分类移入回收站，文章关联保留以便恢复
+        let result = sqlx::query!(
+            "update categories set deleted_at = NOW() where id = $1 and deleted_at is null",
+            id
+        )
+        .execute(&mut *txn)
+        .await
+        .context(format!("删除 Category (ID: {})", id))?;
 
         if result.rows_affected() == 0 {
             return Err(anyhow::anyhow!(
@@ -241,6 +339,9 @@ impl CategoryRepository for PostgresCategoryRepository {
             ));
         }
 
+        reparent_to_nearest_ancestor(&mut txn, &[id]).await?;
+
+        txn.commit().await.context("提交删除分类事务失败")?;
         Ok(())
     }
 
@@ -253,6 +354,8 @@ impl CategoryRepository for PostgresCategoryRepository {
     ) -> Result<()> {
         let mut txn = self.pool.begin().await.context("开始事务失败")?;
 
+        reparent_to(&mut txn, source_category_ids, target_category_id).await?;
+
         for &source_id in source_category_ids {
             // 转移文章关联（避免重复）
             sqlx::query!(
@@ -304,6 +407,8 @@ impl CategoryRepository for PostgresCategoryRepository {
         let mut total_duplicates_removed = 0;
         let merged_category_count = source_category_ids.len();
 
+        reparent_to(&mut txn, source_category_ids, target_category_id).await?;
+
         for &source_id in source_category_ids {
             // 获取受影响的文章数
             let affected_posts = sqlx::query_scalar!(
@@ -404,9 +509,9 @@ impl CategoryRepository for PostgresCategoryRepository {
         let categories = sqlx::query_as!(
             Category,
             r#"
-            SELECT id, name, slug, created_at, updated_at
+            SELECT id, name, slug, parent_id, created_at, updated_at
             FROM categories
-            WHERE id = ANY($1)
+            WHERE id = ANY($1) AND deleted_at IS NULL
             "#,
             &all_category_ids
         )
@@ -451,11 +556,20 @@ impl CategoryRepository for PostgresCategoryRepository {
     }
 
     async fn batch_delete(&self, category_ids: &[Uuid]) -> Result<usize> {
-        let result = sqlx::query!("DELETE FROM categories WHERE id = ANY($1)", category_ids)
-            .execute(&self.pool)
-            .await
-            .context("批量删除分类失败")?;
+        let mut txn = self.pool.begin().await.context("开始批量删除事务失败")?;
 
+        // 软删除：分类移入回收站
+        let result = sqlx::query!(
+            "UPDATE categories SET deleted_at = NOW() WHERE id = ANY($1) AND deleted_at IS NULL",
+            category_ids
+        )
+        .execute(&mut *txn)
+        .await
+        .context("批量删除分类失败")?;
+
+        reparent_to_nearest_ancestor(&mut txn, category_ids).await?;
+
+        txn.commit().await.context("提交批量删除事务失败")?;
         Ok(result.rows_affected() as usize)
     }
 
@@ -463,7 +577,8 @@ impl CategoryRepository for PostgresCategoryRepository {
         &self,
         category_ids: &[Uuid],
         handle_orphaned: bool,
-    ) -> Result<(usize, usize, usize)> {
+        suggested_categories: &HashMap<Uuid, Vec<Uuid>>,
+    ) -> Result<(usize, usize, usize, usize)> {
         let mut txn = self.pool.begin().await.context("开始批量删除事务失败")?;
 
         // 统计受影响的文章数
@@ -482,6 +597,7 @@ impl CategoryRepository for PostgresCategoryRepository {
 
         // 如果需要处理孤儿文章
         let mut orphaned_posts = 0;
+        let mut auto_assigned_posts = 0;
         if handle_orphaned {
             // 1. 先删除分类关联，然后查找孤儿文章
             sqlx::query!(
@@ -497,8 +613,11 @@ impl CategoryRepository for PostgresCategoryRepository {
                 r#"
                 SELECT p.id
                 FROM posts p
-                WHERE NOT EXISTS (
-                    SELECT 1 FROM post_categories pc WHERE pc.post_id = p.id
+                WHERE p.deleted_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1 FROM post_categories pc
+                    JOIN categories c ON c.id = pc.category_id
+                    WHERE pc.post_id = p.id AND c.deleted_at IS NULL
                 )
                 "#
             )
@@ -508,13 +627,36 @@ impl CategoryRepository for PostgresCategoryRepository {
 
             orphaned_posts = orphaned_post_ids.len();
 
-            if !orphaned_post_ids.is_empty() {
-                // 3. 确保存在"未分类"分类
+            // 3. 有推荐分类的孤儿文章归入推荐的分类
+            let mut uncategorized_post_ids = Vec::new();
+            for post_id in orphaned_post_ids {
+                match suggested_categories.get(&post_id) {
+                    Some(suggested) if !suggested.is_empty() => {
+                        sqlx::query!(
+                            r#"
+                            INSERT INTO post_categories (post_id, category_id)
+                            SELECT $1, UNNEST($2::uuid[])
+                            ON CONFLICT DO NOTHING
+                            "#,
+                            post_id,
+                            suggested
+                        )
+                        .execute(&mut *txn)
+                        .await
+                        .context(format!("将孤儿文章 {} 归入推荐分类失败", post_id))?;
+                        auto_assigned_posts += 1;
+                    }
+                    _ => uncategorized_post_ids.push(post_id),
+                }
+            }
+
+            if !uncategorized_post_ids.is_empty() {
+                // 4. 确保存在"未分类"分类
                 let uncategorized_category = sqlx::query!(
                     r#"
                     INSERT INTO categories (id, name, slug, created_at, updated_at)
                     VALUES (gen_random_uuid(), '未分类', 'uncategorized', NOW(), NOW())
-                    ON CONFLICT (slug) DO UPDATE SET updated_at = NOW()
+                    ON CONFLICT (slug) WHERE deleted_at IS NULL DO UPDATE SET updated_at = NOW()
                     RETURNING id
                     "#
                 )
@@ -522,8 +664,8 @@ impl CategoryRepository for PostgresCategoryRepository {
                 .await
                 .context("创建或获取未分类分类失败")?;
 
-                // 4. 将孤儿文章关联到"未分类"分类
-                for post_id in orphaned_post_ids {
+                // 5. 将其余孤儿文章关联到"未分类"分类
+                for post_id in &uncategorized_post_ids {
                     sqlx::query!(
                         "INSERT INTO post_categories (post_id, category_id) VALUES ($1, $2)",
                         post_id,
@@ -534,7 +676,10 @@ impl CategoryRepository for PostgresCategoryRepository {
                     .context(format!("将孤儿文章 {} 关联到未分类失败", post_id))?;
                 }
 
-                tracing::info!("已将 {} 篇孤儿文章关联到'未分类'分类", orphaned_posts);
+                tracing::info!(
+                    "已将 {} 篇孤儿文章关联到'未分类'分类",
+                    uncategorized_post_ids.len()
+                );
             }
         } else {
             // 不处理孤儿文章，直接删除分类关联
@@ -547,28 +692,40 @@ impl CategoryRepository for PostgresCategoryRepository {
             .context("删除分类关联失败")?;
         }
 
-        // 删除分类
-        let result = sqlx::query!("DELETE FROM categories WHERE id = ANY($1)", category_ids)
-            .execute(&mut *txn)
-            .await
-            .context("删除分类失败")?;
+        // 删除分类（软删除，移入回收站）
+        let result = sqlx::query!(
+            "UPDATE categories SET deleted_at = NOW() WHERE id = ANY($1) AND deleted_at IS NULL",
+            category_ids
+        )
+        .execute(&mut *txn)
+        .await
+        .context("删除分类失败")?;
 
         let deleted_count = result.rows_affected() as usize;
 
+        reparent_to_nearest_ancestor(&mut txn, category_ids).await?;
+
         txn.commit().await.context("提交批量删除事务失败")?;
 
-        Ok((deleted_count, affected_posts, orphaned_posts))
+        Ok((
+            deleted_count,
+            affected_posts,
+            orphaned_posts,
+            auto_assigned_posts,
+        ))
     }
 
     async fn get_usage_stats(&self) -> Result<Vec<CategoryUsageStats>> {
         let stats = sqlx::query!(
             r#"
             SELECT 
-                c.id, c.name, c.slug, c.created_at, c.updated_at,
+                c.id, c.name, c.slug, c.parent_id, c.created_at, c.updated_at,
                 COUNT(pc.post_id)::int8 as post_count
             FROM categories c
             LEFT JOIN post_categories pc ON c.id = pc.category_id
-            GROUP BY c.id, c.name, c.slug, c.created_at, c.updated_at
+                AND EXISTS (SELECT 1 FROM posts p WHERE p.id = pc.post_id AND p.deleted_at IS NULL)
+            WHERE c.deleted_at IS NULL
+            GROUP BY c.id, c.name, c.slug, c.parent_id, c.created_at, c.updated_at
             ORDER BY post_count DESC, c.name ASC
             "#
         )
@@ -583,6 +740,7 @@ impl CategoryRepository for PostgresCategoryRepository {
                     id: row.id,
                     name: row.name,
                     slug: row.slug,
+                    parent_id: row.parent_id,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 },
@@ -595,19 +753,24 @@ impl CategoryRepository for PostgresCategoryRepository {
 
     async fn find_similar_categories(&self) -> Result<Vec<SimilarCategoryGroup>> {
         let categories = self.list().await?;
-        let mut similar_groups = Vec::new();
-
-        // 简单的相似性检测：名称相似度检查
-        for i in 0..categories.len() {
-            for j in (i + 1)..categories.len() {
-                if is_similar(&categories[i].name, &categories[j].name) {
-                    similar_groups.push(SimilarCategoryGroup {
-                        categories: vec![categories[i].clone(), categories[j].clone()],
-                        similarity_reason: "名称相似".to_string(),
-                    });
-                }
-            }
-        }
+        let names: Vec<(Uuid, String)> =
+            categories.iter().map(|c| (c.id, c.name.clone())).collect();
+        let clusters = find_similar_name_clusters(&self.pool, &names).await?;
+
+        let categories_by_id: HashMap<Uuid, Category> =
+            categories.into_iter().map(|c| (c.id, c)).collect();
+        let similar_groups = clusters
+            .into_iter()
+            .map(|cluster| SimilarCategoryGroup {
+                categories: cluster
+                    .ids
+                    .iter()
+                    .filter_map(|id| categories_by_id.get(id).cloned())
+                    .collect(),
+                similarity_reason: cluster.reason,
+                confidence: cluster.confidence,
+            })
+            .collect();
 
         Ok(similar_groups)
     }
@@ -616,8 +779,9 @@ impl CategoryRepository for PostgresCategoryRepository {
         let post_count = sqlx::query_scalar!(
             r#"
             SELECT COUNT(*)::int as count
-            FROM post_categories
-            WHERE category_id = $1
+            FROM post_categories pc
+            JOIN posts p ON p.id = pc.post_id
+            WHERE pc.category_id = $1 AND p.deleted_at IS NULL
             "#,
             category_id
         )
@@ -631,7 +795,7 @@ impl CategoryRepository for PostgresCategoryRepository {
             SELECT p.title
             FROM posts p
             JOIN post_categories pc ON p.id = pc.post_id
-            WHERE pc.category_id = $1
+            WHERE pc.category_id = $1 AND p.deleted_at IS NULL
             ORDER BY p.created_at DESC
             LIMIT 5
             "#,
@@ -643,66 +807,129 @@ impl CategoryRepository for PostgresCategoryRepository {
 
         Ok((post_count, sample_titles))
     }
-}
 
-// 检查两个分类名称是否相似
-fn is_similar(name1: &str, name2: &str) -> bool {
-    let name1_lower = name1.to_lowercase();
-    let name2_lower = name2.to_lowercase();
+    // === 回收站功能实现 ===
 
-    // 检查是否一个是另一个的子字符串
-    if name1_lower.contains(&name2_lower) || name2_lower.contains(&name1_lower) {
-        return true;
+    async fn list_deleted(&self) -> Result<Vec<TrashedCategory>> {
+        let items = sqlx::query_as!(
+            TrashedCategory,
+            r#"
+            SELECT id, name, slug, deleted_at as "deleted_at!"
+            FROM categories
+            WHERE deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取回收站中的分类失败")?;
+
+        Ok(items)
     }
 
-    // 检查编辑距离
-    let distance = levenshtein_distance(&name1_lower, &name2_lower);
-    let max_len = name1_lower.len().max(name2_lower.len());
+    async fn restore(&self, id: Uuid) -> Result<()> {
+        // 名称和 slug 只在未删除的分类之间唯一，删除后可能已被新建的分类占用
+        let conflict = sqlx::query_scalar!(
+            r#"
+            SELECT live.name
+            FROM categories trashed
+            JOIN categories live ON live.name = trashed.name OR live.slug = trashed.slug
+            WHERE trashed.id = $1 AND trashed.deleted_at IS NOT NULL
+              AND live.deleted_at IS NULL AND live.id <> trashed.id
+            LIMIT 1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("检查分类 (ID: {}) 的名称冲突失败", id))?;
+        if let Some(name) = conflict {
+            return Err(anyhow::anyhow!(
+                "分类 '{}' 已存在，请先重命名或删除它再从回收站恢复",
+                name
+            ));
+        }
 
-    // 如果编辑距离小于最大长度的30%，认为相似
-    if max_len > 0 {
-        let similarity_threshold = 0.3;
-        (distance as f64) / (max_len as f64) < similarity_threshold
-    } else {
-        false
-    }
-}
+        let result = sqlx::query!(
+            "UPDATE categories SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .context(format!("恢复分类 (ID: {}) 失败", id))?;
 
-// 计算编辑距离
-fn levenshtein_distance(a: &str, b: &str) -> usize {
-    let a_chars: Vec<char> = a.chars().collect();
-    let b_chars: Vec<char> = b.chars().collect();
-    let a_len = a_chars.len();
-    let b_len = b_chars.len();
+        if result.rows_affected() == 0 {
+            return Err(anyhow::anyhow!("回收站中未找到分类 (ID: {})", id));
+        }
 
-    if a_len == 0 {
-        return b_len;
-    }
-    if b_len == 0 {
-        return a_len;
+        Ok(())
     }
 
-    let mut matrix = vec![vec![0; b_len + 1]; a_len + 1];
+    async fn purge_deleted(&self, retention_days: i32) -> Result<u64> {
+        // 文章关联通过 ON DELETE CASCADE 自动删除
+        let result = sqlx::query!(
+            "DELETE FROM categories WHERE deleted_at <= NOW() - make_interval(days => $1)",
+            retention_days
+        )
+        .execute(&self.pool)
+        .await
+        .context("清理回收站中的过期分类失败")?;
 
-    for i in 0..=a_len {
-        matrix[i][0] = i;
-    }
-    for j in 0..=b_len {
-        matrix[0][j] = j;
+        Ok(result.rows_affected())
     }
+}
 
-    for i in 1..=a_len {
-        for j in 1..=b_len {
-            let cost = if a_chars[i - 1] == b_chars[j - 1] {
-                0
-            } else {
-                1
-            };
-            matrix[i][j] = (matrix[i - 1][j] + 1)
-                .min(matrix[i][j - 1] + 1)
-                .min(matrix[i - 1][j - 1] + cost);
-        }
-    }
+// 把被删除分类的子分类移到最近的未被删除的上级分类下，没有时成为顶级分类
+async fn reparent_to_nearest_ancestor(
+    txn: &mut Transaction<'_, Postgres>,
+    removed_ids: &[Uuid],
+) -> Result<u64> {
+    // chain 沿着被删除的上级分类逐级向上，最后一行的 ancestor_id 是保留下来的上级分类
+    let result = sqlx::query!(
+        r#"
+        WITH RECURSIVE chain AS (
+            SELECT c.id AS child_id, c.parent_id AS ancestor_id
+            FROM categories c
+            WHERE c.parent_id = ANY($1) AND NOT (c.id = ANY($1)) AND c.deleted_at IS NULL
+            UNION ALL
+            SELECT chain.child_id, p.parent_id
+            FROM chain
+            JOIN categories p ON p.id = chain.ancestor_id
+            WHERE p.id = ANY($1)
+        )
+        UPDATE categories c
+        SET parent_id = chain.ancestor_id
+        FROM chain
+        WHERE c.id = chain.child_id
+          AND (chain.ancestor_id IS NULL OR NOT (chain.ancestor_id = ANY($1)))
+        "#,
+        removed_ids
+    )
+    .execute(&mut **txn)
+    .await
+    .context("重新指定子分类的父分类失败")?;
+
+    Ok(result.rows_affected())
+}
 
-    matrix[a_len][b_len]
+// 把源分类的子分类移到目标分类下，调用方需保证目标分类不是源分类的后代
+async fn reparent_to(
+    txn: &mut Transaction<'_, Postgres>,
+    source_ids: &[Uuid],
+    target_id: Uuid,
+) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE categories
+        SET parent_id = $2
+        WHERE parent_id = ANY($1) AND NOT (id = ANY($1)) AND id <> $2 AND deleted_at IS NULL
+        "#,
+        source_ids,
+        target_id
+    )
+    .execute(&mut **txn)
+    .await
+    .context("将子分类移到目标分类下失败")?;
+
+    Ok(result.rows_affected())
 }
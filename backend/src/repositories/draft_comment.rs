@@ -0,0 +1,152 @@
+use crate::dtos::draft_comment::CommentAnchor;
+use crate::models::DraftComment;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// 草稿评审意见的数据库操作
+#[async_trait]
+pub trait DraftCommentRepository: Send + Sync {
+    // 创建评论（顶层评论带锚点，回复带 parent_id）
+    async fn create(
+        &self,
+        post_id: Uuid,
+        author_id: Uuid,
+        parent_id: Option<Uuid>,
+        anchor: Option<&CommentAnchor>,
+        quoted_text: Option<&str>,
+        body: &str,
+    ) -> Result<DraftComment>;
+    // 根据 id 获取评论
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<DraftComment>>;
+    // 获取文章的全部评论（顶层评论和回复，按创建时间排序）
+    async fn list_for_post(&self, post_id: Uuid) -> Result<Vec<DraftComment>>;
+    // 设置评论的解决状态，resolved_by 为 None 表示重新打开
+    async fn set_resolved(&self, id: Uuid, resolved_by: Option<Uuid>) -> Result<DraftComment>;
+}
+
+#[derive(Clone)]
+pub struct PostgresDraftCommentRepository {
+    pool: PgPool,
+}
+
+impl PostgresDraftCommentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DraftCommentRepository for PostgresDraftCommentRepository {
+    async fn create(
+        &self,
+        post_id: Uuid,
+        author_id: Uuid,
+        parent_id: Option<Uuid>,
+        anchor: Option<&CommentAnchor>,
+        quoted_text: Option<&str>,
+        body: &str,
+    ) -> Result<DraftComment> {
+        let (anchor_type, anchor_start, anchor_end, anchor_heading) = match anchor {
+            Some(CommentAnchor::Range { start, end }) => {
+                (Some("range"), Some(*start), Some(*end), None)
+            }
+            Some(CommentAnchor::Heading { text }) => {
+                (Some("heading"), None, None, Some(text.as_str()))
+            }
+            None => (None, None, None, None),
+        };
+
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO draft_comments
+                (post_id, author_id, parent_id, anchor_type, anchor_start, anchor_end,
+                 anchor_heading, quoted_text, body)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+            post_id,
+            author_id,
+            parent_id,
+            anchor_type,
+            anchor_start,
+            anchor_end,
+            anchor_heading,
+            quoted_text,
+            body
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context(format!("创建草稿评论失败，post_id: {}", post_id))?;
+
+        self.get_by_id(id)
+            .await?
+            .context(format!("创建草稿评论后未找到记录，id: {}", id))
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<DraftComment>> {
+        let comment = sqlx::query_as!(
+            DraftComment,
+            r#"
+            SELECT
+                c.id, c.post_id, c.author_id, u.username as author_username, c.parent_id,
+                c.anchor_type, c.anchor_start, c.anchor_end, c.anchor_heading, c.quoted_text,
+                c.body, c.resolved_at, c.resolved_by, c.created_at, c.updated_at
+            FROM draft_comments c
+            JOIN users u ON c.author_id = u.id
+            WHERE c.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("获取草稿评论失败，id: {}", id))?;
+
+        Ok(comment)
+    }
+
+    async fn list_for_post(&self, post_id: Uuid) -> Result<Vec<DraftComment>> {
+        let comments = sqlx::query_as!(
+            DraftComment,
+            r#"
+            SELECT
+                c.id, c.post_id, c.author_id, u.username as author_username, c.parent_id,
+                c.anchor_type, c.anchor_start, c.anchor_end, c.anchor_heading, c.quoted_text,
+                c.body, c.resolved_at, c.resolved_by, c.created_at, c.updated_at
+            FROM draft_comments c
+            JOIN users u ON c.author_id = u.id
+            WHERE c.post_id = $1
+            ORDER BY c.created_at ASC
+            "#,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context(format!("获取草稿评论列表失败，post_id: {}", post_id))?;
+
+        Ok(comments)
+    }
+
+    async fn set_resolved(&self, id: Uuid, resolved_by: Option<Uuid>) -> Result<DraftComment> {
+        let resolved_at = resolved_by.map(|_| Utc::now());
+        let result = sqlx::query!(
+            "UPDATE draft_comments SET resolved_at = $1, resolved_by = $2 WHERE id = $3",
+            resolved_at,
+            resolved_by,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .context(format!("更新草稿评论解决状态失败，id: {}", id))?;
+
+        if result.rows_affected() == 0 {
+            anyhow::bail!("未找到 ID 为 {} 的评论", id);
+        }
+
+        self.get_by_id(id)
+            .await?
+            .context(format!("更新后未找到草稿评论，id: {}", id))
+    }
+}
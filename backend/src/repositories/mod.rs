@@ -1,17 +1,32 @@
 pub mod category;
+pub mod draft_comment;
+pub mod notification_preference;
 pub mod permission;
 pub mod post;
+pub mod post_autosave;
+pub mod post_preview_link;
+pub mod post_report;
+pub mod post_template;
 pub mod role;
+pub mod similarity;
 pub mod tag;
 pub mod user;
 pub mod login_attempt;
 pub mod one_time_token;
 
 pub use category::{CategoryRepository, PostgresCategoryRepository};
+pub use draft_comment::{DraftCommentRepository, PostgresDraftCommentRepository};
 pub use login_attempt::{LoginAttemptRepository, PostgresLoginAttemptRepository};
+pub use notification_preference::{
+    NotificationPreferenceRepository, PostgresNotificationPreferenceRepository,
+};
 pub use one_time_token::{OneTimeTokenRepository, PostgresOneTimeTokenRepository};
 pub use permission::{PermissionRepository, PostgresPermissionRepository};
 pub use post::{PostRepository, PostgresPostRepository};
+pub use post_autosave::{PostAutosaveRepository, PostgresPostAutosaveRepository};
+pub use post_preview_link::{PostPreviewLinkRepository, PostgresPostPreviewLinkRepository};
+pub use post_report::{PostReportRepository, PostgresPostReportRepository};
+pub use post_template::{PostTemplateRepository, PostgresPostTemplateRepository};
 pub use role::{PostgresRoleRepository, RoleRepository};
 pub use tag::{PostgresTagRepository, TagRepository};
 pub use user::{PostgresUserRepository, UserRepository};
@@ -0,0 +1,70 @@
+use crate::models::NotificationPreferences;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// 用户通知偏好的数据库操作
+#[async_trait]
+pub trait NotificationPreferenceRepository: Send + Sync {
+    // 获取用户的通知偏好，没有记录时返回默认值；用户不存在时返回 None
+    async fn get(&self, user_id: Uuid) -> Result<Option<NotificationPreferences>>;
+    // 写入用户的通知偏好，未提供的项保持原值
+    async fn upsert(
+        &self,
+        user_id: Uuid,
+        draft_shared: Option<bool>,
+    ) -> Result<NotificationPreferences>;
+}
+
+#[derive(Clone)]
+pub struct PostgresNotificationPreferenceRepository {
+    pool: PgPool,
+}
+
+impl PostgresNotificationPreferenceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NotificationPreferenceRepository for PostgresNotificationPreferenceRepository {
+    async fn get(&self, user_id: Uuid) -> Result<Option<NotificationPreferences>> {
+        sqlx::query_as!(
+            NotificationPreferences,
+            r#"
+            SELECT u.id AS user_id, COALESCE(np.draft_shared, TRUE) AS "draft_shared!"
+            FROM users u
+            LEFT JOIN notification_preferences np ON np.user_id = u.id
+            WHERE u.id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("获取通知偏好失败")
+    }
+
+    async fn upsert(
+        &self,
+        user_id: Uuid,
+        draft_shared: Option<bool>,
+    ) -> Result<NotificationPreferences> {
+        sqlx::query_as!(
+            NotificationPreferences,
+            r#"
+            INSERT INTO notification_preferences (user_id, draft_shared)
+            VALUES ($1, COALESCE($2, TRUE))
+            ON CONFLICT (user_id) DO UPDATE
+                SET draft_shared = COALESCE($2, notification_preferences.draft_shared)
+            RETURNING user_id, draft_shared
+            "#,
+            user_id,
+            draft_shared
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("更新通知偏好失败")
+    }
+}
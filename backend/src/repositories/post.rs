@@ -1,13 +1,30 @@
 use crate::dtos::post::{
-    CategoryDto, CreatePostPayload, DraftAccessLogDto, ShareDraftPayload, TagDto, UpdatePostPayload,
+    BanAppealDto, BulkPostAction, CategoryBreadcrumbDto, CategoryDto, CreatePostPayload,
+    DraftAccessLogDto, DraftShareEntry, PostBanDto, PostLinkDto, ReviewTransitionDto,
+    SharedDraftDto, TagDto, UpdatePostPayload, UserBasicDto,
 };
-use crate::models::Post;
+use crate::models::{
+    BanAppealStatus, DraftShareRole, Post, PostBan, PostBanAppeal, PostReview, TrashedPost,
+    UserProfile,
+};
+use crate::utils::VersionError;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// 训练标签和分类推荐模型所需的文章数据
+#[derive(Debug, Clone)]
+pub struct PostTaxonomyDocument {
+    pub id: Uuid,
+    pub title: String,
+    pub content: String,
+    pub tag_ids: Vec<Uuid>,      // 未删除的标签
+    pub category_ids: Vec<Uuid>, // 未删除的分类
+}
+
 // 定义仓库操作的 trait
 #[async_trait]
 pub trait PostRepository: Send + Sync {
@@ -20,6 +37,8 @@ pub trait PostRepository: Send + Sync {
     ) -> Result<Post>;
     async fn get_by_id(&self, id: Uuid) -> Result<Option<Post>>;
     async fn get_by_slug(&self, slug: &str) -> Result<Option<Post>>;
+    // slug 是否已被占用（包括回收站中的文章）
+    async fn slug_exists(&self, slug: &str) -> Result<bool>;
     async fn list(&self, limit: i64, offset: i64) -> Result<(Vec<Post>, i64)>;
     // 新增：按作者ID过滤的文章列表
     async fn list_by_author(
@@ -41,20 +60,26 @@ pub trait PostRepository: Send + Sync {
     async fn get_published_by_slug(&self, slug: &str, include_banned: bool)
     -> Result<Option<Post>>;
 
+    /// expected_version 为客户端读取时的 updated_at，与当前版本不一致时返回 VersionError::Stale；
+    /// 为 None 时不校验版本
+    /// 标题或正文有变化时，处于 in_review / approved 的审核状态在同一事务中退回 draft，由 actor_id 记录流转
     async fn update(
         &self,
         id: Uuid,
         payload: &UpdatePostPayload,
         new_slug: Option<&str>,
+        expected_version: Option<DateTime<Utc>>,
+        actor_id: Uuid,
     ) -> Result<Post>;
 
     async fn delete(&self, id: Uuid) -> Result<()>;
 
-    // 新增：发布文章
-    async fn publish(&self, id: Uuid) -> Result<()>;
+    // 新增：发布文章，同一事务内把审核状态流转为 published
+    // require_approved 为 true 时（必须经过审核的角色），文章必须处于 approved 状态
+    async fn publish(&self, id: Uuid, actor_id: Uuid, require_approved: bool) -> Result<()>;
 
-    // 新增：撤回文章
-    async fn unpublish(&self, id: Uuid) -> Result<()>;
+    // 新增：撤回文章，同一事务内把审核状态流转回 draft
+    async fn unpublish(&self, id: Uuid, actor_id: Uuid) -> Result<()>;
 
     // 获取帖子的完整分类和标签对象
     async fn get_categories_for_post(&self, post_id: Uuid) -> Result<Vec<CategoryDto>>;
@@ -67,6 +92,44 @@ pub trait PostRepository: Send + Sync {
     // 获取帖子的作者id
     async fn get_author_id(&self, post_id: Uuid) -> Result<Option<Uuid>>;
 
+    // ================================
+    // 文章作者相关方法
+    // ================================
+
+    // 用户是否是文章的作者（主作者或合著者）
+    async fn is_author(&self, post_id: Uuid, user_id: Uuid) -> Result<bool>;
+
+    // 获取文章的作者列表，主作者在前，合著者按 position 排序
+    async fn list_authors(&self, post_id: Uuid) -> Result<Vec<UserBasicDto>>;
+
+    // 整体替换文章的作者列表，第一个为主作者（同步到 posts.author_id）
+    async fn set_authors(&self, post_id: Uuid, author_ids: &[Uuid]) -> Result<()>;
+
+    // 获取作者（主作者或合著者）已发布且未被封禁的文章，按发布时间倒序
+    async fn list_published_by_author(
+        &self,
+        author_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64)>;
+
+    // 统计作者（主作者或合著者）已发布且未被封禁的文章数量
+    async fn count_published_by_author(&self, author_id: Uuid) -> Result<i64>;
+
+    // 获取属于任一指定分类的已发布且未被封禁的文章，按发布时间倒序
+    async fn list_published_by_categories(
+        &self,
+        category_ids: &[Uuid],
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64)>;
+
+    // 获取所有未删除文章的标题、正文及其标签和分类，用于训练推荐模型
+    async fn list_taxonomy_documents(&self) -> Result<Vec<PostTaxonomyDocument>>;
+
+    // 推荐模型训练数据的版本号，文章、标签、分类或别名变化后会增大
+    async fn taxonomy_version(&self) -> Result<i64>;
+
     // ================================
     // 草稿权限和分享相关方法
     // ================================
@@ -74,8 +137,33 @@ pub trait PostRepository: Send + Sync {
     // 检查用户是否可以访问草稿
     async fn can_access_draft(&self, post_id: Uuid, user_id: Uuid) -> Result<bool>;
 
-    // 分享草稿给指定用户
-    async fn share_draft(&self, post_id: Uuid, payload: &ShareDraftPayload) -> Result<()>;
+    // 分享草稿给指定用户（整体替换现有的分享设置）
+    async fn share_draft(
+        &self,
+        post_id: Uuid,
+        shared_by: Uuid,
+        shares: &[DraftShareEntry],
+        is_public: bool,
+    ) -> Result<()>;
+
+    // 获取用户对草稿的有效分享角色（未分享或已过期时返回 None）
+    async fn get_draft_share_role(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<DraftShareRole>>;
+
+    // 批量获取草稿当前有效的分享接收人（按分享时间排序），没有分享的草稿不出现在结果中
+    async fn get_draft_share_user_ids(&self, post_ids: &[Uuid])
+    -> Result<HashMap<Uuid, Vec<Uuid>>>;
+
+    // 获取分享给用户的草稿列表（只包含有效的分享）
+    async fn list_shared_with_user(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<SharedDraftDto>, i64)>;
 
     // 获取用户可以访问的草稿列表（包括自己的和分享给自己的）
     async fn list_accessible_drafts(
@@ -118,14 +206,128 @@ pub trait PostRepository: Send + Sync {
     // 文章封禁相关方法
     // ================================
 
-    // 封禁文章
-    async fn ban_post(&self, post_id: Uuid) -> Result<()>;
+    // 封禁文章并记录版主和原因，返回新的封禁记录
+    async fn ban_post(
+        &self,
+        post_id: Uuid,
+        moderator_id: Uuid,
+        reason: Option<&str>,
+    ) -> Result<PostBan>;
 
-    // 解封文章
-    async fn unban_post(&self, post_id: Uuid) -> Result<()>;
+    // 解封文章，同时结束生效中的封禁记录，待处理的申诉一并标记为已解封
+    async fn unban_post(&self, post_id: Uuid, moderator_id: Uuid) -> Result<()>;
+
+    // 获取文章生效中的封禁记录
+    async fn get_active_ban(&self, post_id: Uuid) -> Result<Option<PostBanDto>>;
+
+    // 提交封禁申诉
+    async fn create_ban_appeal(
+        &self,
+        ban_id: Uuid,
+        post_id: Uuid,
+        appellant_id: Uuid,
+        message: &str,
+    ) -> Result<PostBanAppeal>;
+
+    // 获取封禁申诉
+    async fn get_ban_appeal(&self, appeal_id: Uuid) -> Result<Option<BanAppealDto>>;
+
+    // 获取指定状态的封禁申诉列表（按提交时间先后）
+    async fn list_ban_appeals(
+        &self,
+        status: BanAppealStatus,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<BanAppealDto>, i64)>;
+
+    // 处理待处理的申诉，lift_ban 为 true 时在同一事务中解封文章
+    // 返回 false 表示申诉已被处理过
+    async fn resolve_ban_appeal(
+        &self,
+        appeal_id: Uuid,
+        moderator_id: Uuid,
+        status: BanAppealStatus,
+        note: Option<&str>,
+        lift_ban: bool,
+    ) -> Result<bool>;
 
     // 检查文章是否被封禁
     async fn is_banned(&self, post_id: Uuid) -> Result<bool>;
+
+    // ================================
+    // 文章审核相关方法
+    // ================================
+
+    // 获取文章当前的审核状态（没有记录表示从未进入审核流程）
+    async fn get_review(&self, post_id: Uuid) -> Result<Option<PostReview>>;
+
+    // 流转审核状态并记录本次流转，仅当当前状态仍为 from_status 时才生效
+    async fn transition_review(
+        &self,
+        post_id: Uuid,
+        from_status: &str,
+        to_status: &str,
+        actor_id: Uuid,
+        reviewer_id: Option<Uuid>,
+        comment: Option<&str>,
+    ) -> Result<()>;
+
+    // 指派审核人
+    async fn assign_reviewer(&self, post_id: Uuid, reviewer_id: Uuid) -> Result<()>;
+
+    // 获取文章的审核流转记录（按时间顺序）
+    async fn get_review_transitions(&self, post_id: Uuid) -> Result<Vec<ReviewTransitionDto>>;
+
+    // 获取待审核文章列表：指派给该审核人或尚未指派审核人的文章
+    async fn list_pending_reviews(
+        &self,
+        reviewer_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64)>;
+
+    // ================================
+    // 回收站相关方法
+    // ================================
+
+    // 获取回收站中的文章，author_id 为 None 时返回所有作者的文章
+    async fn list_deleted(&self, author_id: Option<Uuid>) -> Result<Vec<TrashedPost>>;
+
+    // 获取回收站中的单篇文章
+    async fn get_deleted_by_id(&self, id: Uuid) -> Result<Option<TrashedPost>>;
+
+    // 从回收站恢复文章
+    async fn restore(&self, id: Uuid) -> Result<()>;
+
+    // 彻底删除在回收站中超过保留天数的文章，返回删除的数量
+    async fn purge_deleted(&self, retention_days: i32) -> Result<u64>;
+
+    // ================================
+    // 维基链接相关方法
+    // ================================
+
+    // 用正文中的维基链接整体替换文章的出链
+    async fn replace_links(&self, source_post_id: Uuid, target_slugs: &[String]) -> Result<()>;
+
+    // 获取链接到指定 slug 的已发布文章（不含文章自身）
+    async fn list_backlinks(&self, target_slug: &str, post_id: Uuid) -> Result<Vec<PostLinkDto>>;
+
+    // 一次查询解析多个维基链接目标：返回存在的 slug 及其能否渲染为链接（已发布且未被封禁）
+    async fn get_wiki_link_targets(&self, slugs: &[String]) -> Result<HashMap<String, bool>>;
+
+    // ================================
+    // 批量操作相关方法
+    // ================================
+
+    // 在同一个事务中对多篇文章执行同一个动作，任何一篇失败则全部回滚
+    // 调用方负责权限和前置条件检查；require_approved 为 true 时批量发布会在事务中再次确认审核已通过
+    async fn bulk_apply(
+        &self,
+        post_ids: &[Uuid],
+        action: &BulkPostAction,
+        actor_id: Uuid,
+        require_approved: bool,
+    ) -> Result<()>;
 }
 
 // Postgres的具体实现
@@ -175,6 +377,45 @@ impl PostgresPostRepository {
         Ok(())
     }
 
+    // 辅助函数：在事务中按用户ID列表同步草稿分享（兼容 draft_shared_with 字段）
+    // 新接收人按 commenter 角色分享，已有接收人保留原角色，不在列表中的分享被移除
+    async fn sync_draft_shares(
+        txn: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+        shared_by: Option<Uuid>,
+        user_ids: &[Uuid],
+    ) -> Result<()> {
+        sqlx::query!(
+            "delete from draft_shares where post_id = $1 and user_id <> all($2)",
+            post_id,
+            user_ids
+        )
+        .execute(&mut **txn)
+        .await
+        .context("Failed to delete old draft shares")?;
+
+        for user_id in user_ids {
+            // 忽略不存在的用户
+            sqlx::query!(
+                r#"
+                insert into draft_shares (post_id, user_id, role, shared_by)
+                select $1, id, 'commenter', $3 from users where id = $2
+                on conflict (post_id, user_id) do nothing
+                "#,
+                post_id,
+                user_id,
+                shared_by
+            )
+            .execute(&mut **txn)
+            .await
+            .context(format!(
+                "Failed to share post {} with user {}",
+                post_id, user_id
+            ))?;
+        }
+        Ok(())
+    }
+
     // 辅助函数：在事务中管理帖子的标签关联
     async fn manage_post_tags(
         txn: &mut Transaction<'_, Postgres>,
@@ -204,6 +445,114 @@ impl PostgresPostRepository {
         }
         Ok(())
     }
+
+    // 辅助函数：在事务中解封文章，结束生效中的封禁记录并关闭待处理的申诉
+    async fn lift_ban(
+        txn: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+        moderator_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query!(
+            "UPDATE posts SET is_banned = false WHERE id = $1 AND deleted_at IS NULL",
+            post_id
+        )
+        .execute(&mut **txn)
+        .await
+        .context(format!("解封文章失败，post_id: {}", post_id))?;
+
+        sqlx::query!(
+            "UPDATE post_bans SET lifted_by = $2, lifted_at = NOW() WHERE post_id = $1 AND lifted_at IS NULL",
+            post_id,
+            moderator_id
+        )
+        .execute(&mut **txn)
+        .await
+        .context("结束封禁记录失败")?;
+
+        sqlx::query!(
+            r#"
+            UPDATE post_ban_appeals
+            SET status = 'unbanned', resolved_by = $2, resolved_at = NOW()
+            WHERE post_id = $1 AND status = 'pending'
+            "#,
+            post_id,
+            moderator_id
+        )
+        .execute(&mut **txn)
+        .await
+        .context("关闭待处理的封禁申诉失败")?;
+
+        Ok(())
+    }
+
+    // 辅助函数：在事务中锁定并返回文章当前的审核状态（没有记录视为 draft）
+    async fn lock_review_status(
+        txn: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+    ) -> Result<String> {
+        let status = sqlx::query_scalar!(
+            "SELECT status FROM post_reviews WHERE post_id = $1 FOR UPDATE",
+            post_id
+        )
+        .fetch_optional(&mut **txn)
+        .await
+        .context(format!("获取文章审核状态失败，post_id: {}", post_id))?
+        .unwrap_or_else(|| "draft".to_string());
+        Ok(status)
+    }
+
+    // 辅助函数：在事务中确认文章已通过审核，必须经过审核的角色发布文章前调用
+    async fn ensure_review_approved(
+        txn: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+    ) -> Result<()> {
+        let status = Self::lock_review_status(txn, post_id).await?;
+        if status != "approved" {
+            anyhow::bail!("只能发布已通过审核的文章，当前审核状态为 {}", status);
+        }
+        Ok(())
+    }
+
+    // 辅助函数：在事务中把文章的审核状态设置为 to_status，状态有变化时记录流转
+    async fn set_review_status(
+        txn: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+        to_status: &str,
+        actor_id: Uuid,
+    ) -> Result<()> {
+        let from_status = Self::lock_review_status(txn, post_id).await?;
+        if from_status == to_status {
+            return Ok(());
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO post_reviews (post_id, status)
+            VALUES ($1, $2)
+            ON CONFLICT (post_id) DO UPDATE SET status = EXCLUDED.status
+            "#,
+            post_id,
+            to_status
+        )
+        .execute(&mut **txn)
+        .await
+        .context(format!("更新文章审核状态失败，post_id: {}", post_id))?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO post_review_transitions (post_id, from_status, to_status, actor_id)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            post_id,
+            from_status,
+            to_status,
+            actor_id
+        )
+        .execute(&mut **txn)
+        .await
+        .context(format!("记录文章审核流转失败，post_id: {}", post_id))?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -228,9 +577,9 @@ impl PostRepository for PostgresPostRepository {
         let post = sqlx::query_as!(
             Post,
             r#"
-            insert into posts (id,slug,title,content,created_at,updated_at,published_at,author_id,draft_shared_with,is_draft_public,is_banned)
+            insert into posts (id,slug,title,content,created_at,updated_at,published_at,author_id,is_draft_public,is_banned,render_profile)
             values ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
-            returning id,slug,title,content,created_at,updated_at,published_at,author_id,draft_shared_with,is_draft_public,is_banned
+            returning id,slug,title,content,created_at,updated_at,published_at,author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile
             "#,
             post_id,
             slug,
@@ -240,14 +589,21 @@ impl PostRepository for PostgresPostRepository {
             now,
             None::<DateTime<Utc>>,
             author_id,
-            payload.draft_shared_with.as_deref(),
             payload.is_draft_public,
-            false // 默认不封禁
+            false, // 默认不封禁
+            payload.render_profile
         )
         .fetch_one(&mut *txn) // 在事务中执行
         .await
         .context("未能将帖子基本信息插入到posts表中")?; // 使用 ？ 和 context
 
+        // 分享给指定用户（如果提供了 draft_shared_with）
+        if let Some(user_ids) = payload.draft_shared_with.as_deref() {
+            Self::sync_draft_shares(&mut txn, post.id, Some(author_id), user_ids)
+                .await
+                .context("Failed to share draft during creation")?;
+        }
+
         // 2. 关联分类(如果提供了 category_ids)
         // is_update is false for creation
         Self::manage_post_categories(&mut txn, post.id, &payload.category_ids, false)
@@ -273,7 +629,7 @@ impl PostRepository for PostgresPostRepository {
         let post = sqlx::query_as!(
             Post,
             r#"
-            select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,draft_shared_with,is_draft_public,is_banned from posts where id = $1
+            select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile from posts where id = $1 and deleted_at is null
             "#,
             id
         )
@@ -287,9 +643,9 @@ impl PostRepository for PostgresPostRepository {
         let post = sqlx::query_as!(
             Post,
             r#"
-            select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,draft_shared_with,is_draft_public,is_banned
+            select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile
             from posts 
-            where slug = $1
+            where slug = $1 and deleted_at is null
             "#,
             slug
         )
@@ -299,17 +655,29 @@ impl PostRepository for PostgresPostRepository {
         Ok(post)
     }
 
+    async fn slug_exists(&self, slug: &str) -> Result<bool> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM posts WHERE slug = $1) AS "exists!""#,
+            slug
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context(format!("检查 slug ({}) 是否被占用失败", slug))?;
+        Ok(exists)
+    }
+
     async fn list(&self, limit: i64, offset: i64) -> Result<(Vec<Post>, i64)> {
         // --- 查询当前页的帖子列表,实际中应只查询已发布的帖子 ---
         let posts = sqlx::query_as!(
             Post,
             r#"
-            SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at,author_id,draft_shared_with,is_draft_public,is_banned 
+            SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at,author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile 
             FROM posts 
-            -- WHERE published_at IS NOT NULL AND published_at <= NOW() -- 过滤已发布的
+            WHERE deleted_at IS NULL -- 排除回收站中的文章
+            -- AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL -- 过滤已发布的
             ORDER BY created_at DESC -- 或者 ORDER BY published_at DESC
             limit $1 offset $2
-            "#,   // 注意: 在实际应用中可能需要加上 WHERE published_at IS NOT NULL AND published_at <= NOW() ORDER BY published_at DESC
+            "#,   // 注意: 在实际应用中可能需要加上 WHERE published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL ORDER BY published_at DESC
             limit,
             offset
         )
@@ -322,7 +690,8 @@ impl PostRepository for PostgresPostRepository {
         let total_count_result = sqlx::query!(
             r#"
             select count(*) as "count!" from posts
-             -- WHERE published_at IS NOT NULL AND published_at <= NOW() -- 同样需要过滤
+            where deleted_at is null
+             -- AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL -- 同样需要过滤
             "#
         )
         .fetch_one(&self.pool)
@@ -347,10 +716,10 @@ impl PostRepository for PostgresPostRepository {
         let posts = sqlx::query_as!(
             Post,
             r#"
-            SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at, author_id,draft_shared_with,is_draft_public,is_banned 
+            SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at, author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile 
             FROM posts 
-            WHERE author_id = $1
-            -- WHERE published_at IS NOT NULL AND published_at <= NOW() -- 过滤已发布的
+            WHERE author_id = $1 AND deleted_at IS NULL
+            -- WHERE published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL -- 过滤已发布的
             ORDER BY created_at DESC -- 或者 ORDER BY published_at DESC
             limit $2 offset $3
             "#,
@@ -366,8 +735,8 @@ impl PostRepository for PostgresPostRepository {
         let total_count_result = sqlx::query!(
             r#"
             select count(*) as "count!" from posts
-            WHERE author_id = $1
-             -- WHERE published_at IS NOT NULL AND published_at <= NOW() -- 同样需要过滤
+            WHERE author_id = $1 AND deleted_at IS NULL
+             -- WHERE published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL -- 同样需要过滤
             "#,
             author_id
         )
@@ -392,9 +761,9 @@ impl PostRepository for PostgresPostRepository {
             sqlx::query_as!(
                 Post,
                 r#"
-                SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at, author_id,draft_shared_with,is_draft_public,is_banned 
+                SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at, author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile 
                 FROM posts 
-                WHERE published_at IS NOT NULL AND published_at <= NOW()
+                WHERE published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL
                 ORDER BY published_at DESC
                 limit $1 offset $2
                 "#,
@@ -409,9 +778,9 @@ impl PostRepository for PostgresPostRepository {
             sqlx::query_as!(
                 Post,
                 r#"
-                SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at, author_id,draft_shared_with,is_draft_public,is_banned 
+                SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at, author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile 
                 FROM posts 
-                WHERE published_at IS NOT NULL AND published_at <= NOW() AND (is_banned = false OR is_banned IS NULL)
+                WHERE published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL AND (is_banned = false OR is_banned IS NULL)
                 ORDER BY published_at DESC
                 limit $1 offset $2
                 "#,
@@ -428,7 +797,7 @@ impl PostRepository for PostgresPostRepository {
             sqlx::query_scalar!(
                 r#"
                 select count(*) from posts
-                WHERE published_at IS NOT NULL AND published_at <= NOW()
+                WHERE published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL
                 "#
             )
             .fetch_one(&self.pool)
@@ -438,7 +807,7 @@ impl PostRepository for PostgresPostRepository {
             sqlx::query_scalar!(
                 r#"
                 select count(*) from posts
-                WHERE published_at IS NOT NULL AND published_at <= NOW() AND (is_banned = false OR is_banned IS NULL)
+                WHERE published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL AND (is_banned = false OR is_banned IS NULL)
                 "#
             )
             .fetch_one(&self.pool)
@@ -456,9 +825,9 @@ impl PostRepository for PostgresPostRepository {
             sqlx::query_as!(
                 Post,
                 r#"
-                select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,draft_shared_with,is_draft_public,is_banned 
+                select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile 
                 from posts 
-                where id = $1 AND published_at IS NOT NULL AND published_at <= NOW()
+                where id = $1 AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL
                 "#,
                 id
             )
@@ -469,9 +838,9 @@ impl PostRepository for PostgresPostRepository {
             sqlx::query_as!(
                 Post,
                 r#"
-                select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,draft_shared_with,is_draft_public,is_banned 
+                select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile 
                 from posts 
-                where id = $1 AND published_at IS NOT NULL AND published_at <= NOW() AND (is_banned = false OR is_banned IS NULL)
+                where id = $1 AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL AND (is_banned = false OR is_banned IS NULL)
                 "#,
                 id
             )
@@ -491,9 +860,9 @@ impl PostRepository for PostgresPostRepository {
             sqlx::query_as!(
                 Post,
                 r#"
-                select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,draft_shared_with,is_draft_public,is_banned
+                select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile
                 from posts 
-                where slug = $1 AND published_at IS NOT NULL AND published_at <= NOW()
+                where slug = $1 AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL
                 "#,
                 slug
             )
@@ -504,9 +873,9 @@ impl PostRepository for PostgresPostRepository {
             sqlx::query_as!(
                 Post,
                 r#"
-                select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,draft_shared_with,is_draft_public,is_banned
+                select id,slug,title,content,created_at as "created_at!",updated_at as "updated_at!",published_at,author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile
                 from posts 
-                where slug = $1 AND published_at IS NOT NULL AND published_at <= NOW() AND (is_banned = false OR is_banned IS NULL)
+                where slug = $1 AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL AND (is_banned = false OR is_banned IS NULL)
                 "#,
                 slug
             )
@@ -522,6 +891,8 @@ impl PostRepository for PostgresPostRepository {
         id: Uuid,
         payload: &UpdatePostPayload,
         new_slug_opt: Option<&str>,
+        expected_version: Option<DateTime<Utc>>,
+        actor_id: Uuid,
     ) -> Result<Post> {
         // 开启数据库事务
         let mut txn = self
@@ -530,6 +901,22 @@ impl PostRepository for PostgresPostRepository {
             .await
             .context("Failed to begin transaction for updating post")?;
 
+        // 0. 锁定帖子行并校验版本，防止并发编辑互相覆盖
+        if let Some(expected) = expected_version {
+            let current_version = sqlx::query_scalar!(
+                r#"select updated_at as "updated_at!" from posts where id = $1 and deleted_at is null for update"#,
+                id
+            )
+            .fetch_optional(&mut *txn)
+            .await
+            .context(format!("更新操作：锁定 Post (id: {}) 失败", id))?
+            .ok_or_else(|| anyhow::anyhow!("更新目标 Post (id: {}) 未找到，无法继续", id))?;
+
+            if current_version != expected {
+                return Err(VersionError::stale(current_version).into());
+            }
+        }
+
         // 1. 获取当前帖子数据 current_post,准备更新值的逻辑
         let current_post = self
             .get_by_id(id)
@@ -564,11 +951,12 @@ impl PostRepository for PostgresPostRepository {
             published_at_to_update = current_post.published_at;
         }
 
-        // 处理草稿分享字段
-        let draft_shared_with_to_update = payload
-            .draft_shared_with
-            .as_deref()
-            .unwrap_or(current_post.draft_shared_with.as_deref().unwrap_or(&[]));
+        // 处理草稿分享字段：只有提供了 draft_shared_with 时才同步分享列表
+        if let Some(user_ids) = payload.draft_shared_with.as_deref() {
+            Self::sync_draft_shares(&mut txn, id, current_post.author_id, user_ids)
+                .await
+                .context("Failed to manage draft shares during update")?;
+        }
         let is_draft_public_to_update = payload
             .is_draft_public
             .unwrap_or(current_post.is_draft_public.unwrap_or(false));
@@ -578,23 +966,32 @@ impl PostRepository for PostgresPostRepository {
             Post,
             r#"
             update posts
-            set title = $1,content = $2,slug = $3,updated_at = $4,published_at = $5,draft_shared_with = $6,is_draft_public = $7
-            where id = $8
-            returning id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at,author_id,draft_shared_with,is_draft_public,is_banned
+            set title = $1,content = $2,slug = $3,updated_at = $4,published_at = $5,is_draft_public = $6,
+                render_profile = COALESCE($8, render_profile)
+            where id = $7
+            returning id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at,author_id,NULL::uuid[] as draft_shared_with,is_draft_public,is_banned,render_profile
             "#,
             title_to_update,
             content_to_update,
             slug_to_update,
             now,
             published_at_to_update,
-            draft_shared_with_to_update,
             is_draft_public_to_update,
-            id
+            id,
+            payload.render_profile
         )
             .fetch_one(&mut *txn)
             .await
             .context(format!("数据库层面更新 Post (id: {}) 失败", id))?;
 
+        // 审核通过的是修改前的内容：标题或正文变化后需要重新提交审核
+        if title_to_update != current_post.title || content_to_update != current_post.content {
+            let review_status = Self::lock_review_status(&mut txn, id).await?;
+            if review_status == "in_review" || review_status == "approved" {
+                Self::set_review_status(&mut txn, id, "draft", actor_id).await?;
+            }
+        }
+
         // 3. 更新分类关联（如果payload中提供了category_ids）
         // is_update is true
         if payload.category_ids.is_some() {
@@ -627,12 +1024,15 @@ impl PostRepository for PostgresPostRepository {
     }
 
     async fn delete(&self, id: Uuid) -> Result<()> {
-        // 删除帖子时，由于 post_categories 和 post_tags 表设置了 ON DELETE CASCADE,
-        // 中间表的关联记录会自动被删除。所以这里只需要删除 posts 表的记录。
-        let result = sqlx::query!("delete from posts where id = $1", id)
-            .execute(&self.pool)
-            .await
-            .context(format!("删除 Post (id: {})", id))?;
+        // 软删除：只标记 deleted_at，文章移入回收站，分类和标签关联保留以便恢复
+        // 彻底删除由 purge_deleted 完成，届时 post_categories 和 post_tags 通过 ON DELETE CASCADE 自动清理
+        let result = sqlx::query!(
+            "update posts set deleted_at = NOW() where id = $1 and deleted_at is null",
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .context(format!("删除 Post (id: {})", id))?;
 
         if result.rows_affected() == 0 {
             anyhow::bail!("尝试删除 Post (id: {}) 时未找到记录", id);
@@ -642,20 +1042,66 @@ impl PostRepository for PostgresPostRepository {
 
     // 实现获取帖子关联的分类和标签的方法
     async fn get_categories_for_post(&self, post_id: Uuid) -> Result<Vec<CategoryDto>> {
-        let categories = sqlx::query_as!(
-            CategoryDto,
+        let mut categories: Vec<CategoryDto> = sqlx::query!(
             r#"
-            select c.id,c.name,c.slug
+            select c.id,c.name,c.slug,c.parent_id
             from categories c
             inner join post_categories pc on c.id = pc.category_id
-            where pc.post_id = $1
+            where pc.post_id = $1 and c.deleted_at is null
             order by c.name
             "#,
             post_id
         )
         .fetch_all(&self.pool)
         .await
-        .context(format!("Failed to fetch categories for post {}", post_id))?;
+        .context(format!("Failed to fetch categories for post {}", post_id))?
+        .into_iter()
+        .map(|row| CategoryDto {
+            id: row.id,
+            name: row.name,
+            slug: row.slug,
+            parent_id: row.parent_id,
+            breadcrumbs: Vec::new(),
+        })
+        .collect();
+
+        // 一次查询所有分类的上级分类，depth 越大离顶级分类越近
+        let category_ids: Vec<Uuid> = categories.iter().map(|c| c.id).collect();
+        let ancestors = sqlx::query!(
+            r#"
+            WITH RECURSIVE ancestors AS (
+                SELECT c.id AS leaf_id, c.parent_id AS ancestor_id, 1 AS depth
+                FROM categories c
+                WHERE c.id = ANY($1)
+                UNION ALL
+                SELECT a.leaf_id, p.parent_id, a.depth + 1
+                FROM ancestors a
+                JOIN categories p ON p.id = a.ancestor_id
+                WHERE a.depth < 64
+            )
+            SELECT a.leaf_id AS "leaf_id!", c.id, c.name, c.slug
+            FROM ancestors a
+            JOIN categories c ON c.id = a.ancestor_id AND c.deleted_at IS NULL
+            ORDER BY a.depth DESC
+            "#,
+            &category_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context(format!(
+            "Failed to fetch category breadcrumbs for post {}",
+            post_id
+        ))?;
+
+        for ancestor in ancestors {
+            if let Some(category) = categories.iter_mut().find(|c| c.id == ancestor.leaf_id) {
+                category.breadcrumbs.push(CategoryBreadcrumbDto {
+                    id: ancestor.id,
+                    name: ancestor.name,
+                    slug: ancestor.slug,
+                });
+            }
+        }
 
         Ok(categories)
     }
@@ -667,7 +1113,7 @@ impl PostRepository for PostgresPostRepository {
             select t.id,t.name,t.slug
             from tags t
             inner join post_tags pt on t.id = pt.tag_id
-            where pt.post_id = $1
+            where pt.post_id = $1 and t.deleted_at is null
             order by t.name
             "#,
             post_id
@@ -680,25 +1126,30 @@ impl PostRepository for PostgresPostRepository {
     }
 
     async fn get_author_id(&self, post_id: Uuid) -> Result<Option<Uuid>> {
-        let result = sqlx::query!("select author_id from posts where id = $1", post_id)
-            .fetch_optional(&self.pool)
-            .await
-            .context(format!(
-                "从数据库获取 Post id 为 {} 的 author id失败",
-                post_id
-            ))?;
+        let result = sqlx::query!(
+            "select author_id from posts where id = $1 and deleted_at is null",
+            post_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!(
+            "从数据库获取 Post id 为 {} 的 author id失败",
+            post_id
+        ))?;
         Ok(result.and_then(|record| record.author_id))
     }
 
     // 新增：发布文章
-    async fn publish(&self, id: Uuid) -> Result<()> {
+    async fn publish(&self, id: Uuid, actor_id: Uuid, require_approved: bool) -> Result<()> {
         let now = Utc::now();
+        let mut txn = self.pool.begin().await.context("开启发布文章事务失败")?;
+
         let result = sqlx::query!(
-            "UPDATE posts SET published_at = $1, updated_at = $1 WHERE id = $2",
+            "UPDATE posts SET published_at = $1, updated_at = $1 WHERE id = $2 AND deleted_at IS NULL",
             now,
             id
         )
-        .execute(&self.pool)
+        .execute(&mut *txn)
         .await
         .context(format!("发布文章 (id: {}) 失败", id))?;
 
@@ -706,19 +1157,28 @@ impl PostRepository for PostgresPostRepository {
             anyhow::bail!("尝试发布文章 (id: {}) 时未找到记录", id);
         }
 
+        // 在锁定的审核记录上检查，避免检查之后文章被修改、审核状态被重置
+        if require_approved {
+            Self::ensure_review_approved(&mut txn, id).await?;
+        }
+        Self::set_review_status(&mut txn, id, "published", actor_id).await?;
+        txn.commit().await.context("提交发布文章事务失败")?;
+
         tracing::info!("文章 {} 已发布", id);
         Ok(())
     }
 
     // 新增：撤回文章
-    async fn unpublish(&self, id: Uuid) -> Result<()> {
+    async fn unpublish(&self, id: Uuid, actor_id: Uuid) -> Result<()> {
         let now = Utc::now();
+        let mut txn = self.pool.begin().await.context("开启撤回文章事务失败")?;
+
         let result = sqlx::query!(
-            "UPDATE posts SET published_at = NULL, updated_at = $1 WHERE id = $2",
+            "UPDATE posts SET published_at = NULL, updated_at = $1 WHERE id = $2 AND deleted_at IS NULL",
             now,
             id
         )
-        .execute(&self.pool)
+        .execute(&mut *txn)
         .await
         .context(format!("撤回文章 (id: {}) 失败", id))?;
 
@@ -726,47 +1186,262 @@ impl PostRepository for PostgresPostRepository {
             anyhow::bail!("尝试撤回文章 (id: {}) 时未找到记录", id);
         }
 
+        Self::set_review_status(&mut txn, id, "draft", actor_id).await?;
+        txn.commit().await.context("提交撤回文章事务失败")?;
+
         tracing::info!("文章 {} 已撤回", id);
         Ok(())
     }
 
-    // 检查用户是否可以访问草稿
-    async fn can_access_draft(&self, post_id: Uuid, user_id: Uuid) -> Result<bool> {
-        let result = sqlx::query!(
+    async fn is_author(&self, post_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let is_author = sqlx::query_scalar!(
+            r#"SELECT is_post_author($1, $2) AS "is_author!""#,
+            post_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context(format!(
+            "检查用户 {} 是否是文章 {} 的作者失败",
+            user_id, post_id
+        ))?;
+        Ok(is_author)
+    }
+
+    async fn list_authors(&self, post_id: Uuid) -> Result<Vec<UserBasicDto>> {
+        // 署名资料由调用方按需补充
+        let rows = sqlx::query!(
             r#"
-            SELECT 
-                author_id,
-                draft_shared_with,
-                is_draft_public,
-                published_at
-            FROM posts 
-            WHERE id = $1
+            SELECT u.id, u.username, u.email
+            FROM post_authors pa
+            JOIN users u ON u.id = pa.user_id
+            WHERE pa.post_id = $1
+            ORDER BY pa.is_primary DESC, pa.position, pa.created_at
             "#,
             post_id
         )
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await
-        .context(format!("检查草稿访问权限失败，post_id: {}", post_id))?;
-
-        let Some(post) = result else {
-            return Ok(false); // 文章不存在
-        };
-
-        // 如果文章已发布，那么不是草稿，任何人都可以访问
-        if post.published_at.is_some() {
-            return Ok(true);
-        }
-
-        // 1. 如果是作者本人，直接允许访问
-        if post.author_id == Some(user_id) {
-            return Ok(true);
-        }
+        .context(format!("获取文章 {} 的作者列表失败", post_id))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| UserBasicDto {
+                id: row.id,
+                username: row.username,
+                email: Some(row.email),
+                profile: UserProfile::default(),
+            })
+            .collect())
+    }
 
-        // 2. 检查是否被分享给该用户
-        if let Some(shared_users) = post.draft_shared_with {
-            if shared_users.contains(&user_id) {
-                return Ok(true);
-            }
+    async fn list_published_by_author(
+        &self,
+        author_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64)> {
+        let posts = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at, author_id, NULL::uuid[] as draft_shared_with, is_draft_public, is_banned, render_profile
+            FROM posts
+            WHERE is_post_author(id, $1)
+              AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL
+              AND (is_banned = false OR is_banned IS NULL)
+            ORDER BY published_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            author_id,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context(format!("查询作者 {} 的已发布文章失败", author_id))?;
+
+        let total = self.count_published_by_author(author_id).await?;
+
+        Ok((posts, total))
+    }
+
+    async fn count_published_by_author(&self, author_id: Uuid) -> Result<i64> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM posts
+            WHERE is_post_author(id, $1)
+              AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL
+              AND (is_banned = false OR is_banned IS NULL)
+            "#,
+            author_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context(format!("统计作者 {} 的已发布文章失败", author_id))
+    }
+
+    async fn list_published_by_categories(
+        &self,
+        category_ids: &[Uuid],
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64)> {
+        let posts = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", published_at, author_id, NULL::uuid[] as draft_shared_with, is_draft_public, is_banned, render_profile
+            FROM posts
+            WHERE EXISTS (SELECT 1 FROM post_categories pc WHERE pc.post_id = posts.id AND pc.category_id = ANY($1))
+              AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL
+              AND (is_banned = false OR is_banned IS NULL)
+            ORDER BY published_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            category_ids,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("按分类查询已发布文章失败")?;
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM posts
+            WHERE EXISTS (SELECT 1 FROM post_categories pc WHERE pc.post_id = posts.id AND pc.category_id = ANY($1))
+              AND published_at IS NOT NULL AND published_at <= NOW() AND deleted_at IS NULL
+              AND (is_banned = false OR is_banned IS NULL)
+            "#,
+            category_ids
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("按分类统计已发布文章失败")?;
+
+        Ok((posts, total))
+    }
+
+    async fn list_taxonomy_documents(&self) -> Result<Vec<PostTaxonomyDocument>> {
+        let documents = sqlx::query_as!(
+            PostTaxonomyDocument,
+            r#"
+            SELECT p.id, p.title, p.content,
+                ARRAY(
+                    SELECT pt.tag_id FROM post_tags pt
+                    JOIN tags t ON t.id = pt.tag_id
+                    WHERE pt.post_id = p.id AND t.deleted_at IS NULL
+                ) AS "tag_ids!",
+                ARRAY(
+                    SELECT pc.category_id FROM post_categories pc
+                    JOIN categories c ON c.id = pc.category_id
+                    WHERE pc.post_id = p.id AND c.deleted_at IS NULL
+                ) AS "category_ids!"
+            FROM posts p
+            WHERE p.deleted_at IS NULL
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取推荐模型的训练文章失败")?;
+
+        Ok(documents)
+    }
+
+    async fn taxonomy_version(&self) -> Result<i64> {
+        sqlx::query_scalar!(r#"SELECT last_value AS "version!" FROM taxonomy_version_seq"#)
+            .fetch_one(&self.pool)
+            .await
+            .context("获取推荐模型版本号失败")
+    }
+
+    async fn set_authors(&self, post_id: Uuid, author_ids: &[Uuid]) -> Result<()> {
+        let Some((primary_id, co_author_ids)) = author_ids.split_first() else {
+            anyhow::bail!("文章至少需要一位作者");
+        };
+        let mut txn = self.pool.begin().await.context("开启作者设置事务失败")?;
+
+        sqlx::query!(
+            "DELETE FROM post_authors WHERE post_id = $1 AND user_id <> ALL($2)",
+            post_id,
+            author_ids
+        )
+        .execute(&mut *txn)
+        .await
+        .context(format!("移除文章 {} 的作者失败", post_id))?;
+
+        // 主作者记录由 sync_post_primary_author 触发器维护
+        let result = sqlx::query!(
+            "UPDATE posts SET author_id = $2 WHERE id = $1 AND deleted_at IS NULL",
+            post_id,
+            primary_id
+        )
+        .execute(&mut *txn)
+        .await
+        .context(format!("更新文章 {} 的主作者失败", post_id))?;
+        if result.rows_affected() == 0 {
+            anyhow::bail!("尝试设置文章 (id: {}) 的作者时未找到记录", post_id);
+        }
+
+        for (index, user_id) in co_author_ids.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                INSERT INTO post_authors (post_id, user_id, position, is_primary)
+                VALUES ($1, $2, $3, FALSE)
+                ON CONFLICT (post_id, user_id) DO UPDATE
+                SET position = EXCLUDED.position, is_primary = FALSE
+                "#,
+                post_id,
+                user_id,
+                index as i32 + 1
+            )
+            .execute(&mut *txn)
+            .await
+            .context(format!("添加文章 {} 的合著者 {} 失败", post_id, user_id))?;
+        }
+
+        txn.commit().await.context("提交作者设置事务失败")?;
+        tracing::info!("文章 {} 的作者已更新为 {:?}", post_id, author_ids);
+        Ok(())
+    }
+
+    // 检查用户是否可以访问草稿
+    async fn can_access_draft(&self, post_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            SELECT 
+                author_id,
+                is_draft_shared_with(id, $2) as "is_shared_to_user!",
+                is_draft_public,
+                published_at
+            FROM posts 
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            post_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("检查草稿访问权限失败，post_id: {}", post_id))?;
+
+        let Some(post) = result else {
+            return Ok(false); // 文章不存在
+        };
+
+        // 如果文章已发布，那么不是草稿，任何人都可以访问
+        if post.published_at.is_some() {
+            return Ok(true);
+        }
+
+        // 1. 如果是作者本人，直接允许访问
+        if post.author_id == Some(user_id) {
+            return Ok(true);
+        }
+
+        // 2. 检查是否被分享给该用户（只包含未过期的分享）
+        if post.is_shared_to_user {
+            return Ok(true);
         }
 
         // 3. 检查是否设为公开（这个需要结合用户权限检查）
@@ -779,28 +1454,34 @@ impl PostRepository for PostgresPostRepository {
     }
 
     // 分享草稿给指定用户
-    async fn share_draft(&self, post_id: Uuid, payload: &ShareDraftPayload) -> Result<()> {
+    async fn share_draft(
+        &self,
+        post_id: Uuid,
+        shared_by: Uuid,
+        shares: &[DraftShareEntry],
+        is_public: bool,
+    ) -> Result<()> {
         // 首先检查文章是否存在且为草稿
         let is_draft = self.is_draft(post_id).await?;
         if !is_draft {
             anyhow::bail!("只能分享草稿文章");
         }
 
+        let mut txn = self.pool.begin().await.context("开启草稿分享事务失败")?;
+
         let result = sqlx::query!(
             r#"
             UPDATE posts 
             SET 
-                draft_shared_with = $1,
-                is_draft_public = $2,
-                updated_at = $3
-            WHERE id = $4 AND published_at IS NULL
+                is_draft_public = $1,
+                updated_at = $2
+            WHERE id = $3 AND published_at IS NULL AND deleted_at IS NULL
             "#,
-            &payload.shared_with,
-            payload.is_public,
+            is_public,
             Utc::now(),
             post_id
         )
-        .execute(&self.pool)
+        .execute(&mut *txn)
         .await
         .context(format!("分享草稿失败，post_id: {}", post_id))?;
 
@@ -808,14 +1489,148 @@ impl PostRepository for PostgresPostRepository {
             anyhow::bail!("草稿分享失败：文章不存在或已发布");
         }
 
-        tracing::info!(
-            "草稿 {} 已分享给 {} 个用户",
-            post_id,
-            payload.shared_with.len()
-        );
+        // 整体替换分享设置
+        sqlx::query!("DELETE FROM draft_shares WHERE post_id = $1", post_id)
+            .execute(&mut *txn)
+            .await
+            .context(format!("清除草稿 {} 的旧分享失败", post_id))?;
+
+        for share in shares {
+            sqlx::query!(
+                r#"
+                INSERT INTO draft_shares (post_id, user_id, role, expires_at, shared_by)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (post_id, user_id) DO UPDATE
+                    SET role = EXCLUDED.role, expires_at = EXCLUDED.expires_at
+                "#,
+                post_id,
+                share.user_id,
+                share.role.as_str(),
+                share.expires_at,
+                shared_by
+            )
+            .execute(&mut *txn)
+            .await
+            .context(format!(
+                "分享草稿 {} 给用户 {} 失败",
+                post_id, share.user_id
+            ))?;
+        }
+
+        txn.commit().await.context("提交草稿分享事务失败")?;
+
+        tracing::info!("草稿 {} 已分享给 {} 个用户", post_id, shares.len());
         Ok(())
     }
 
+    // 获取用户对草稿的有效分享角色
+    async fn get_draft_share_role(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<DraftShareRole>> {
+        let role = sqlx::query_scalar!(
+            r#"
+            SELECT role FROM draft_shares
+            WHERE post_id = $1 AND user_id = $2
+            AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+            post_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("获取草稿 {} 的分享角色失败", post_id))?;
+
+        Ok(role.as_deref().map(DraftShareRole::from_db))
+    }
+
+    async fn get_draft_share_user_ids(
+        &self,
+        post_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<Uuid>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT post_id, user_id FROM draft_shares
+            WHERE post_id = ANY($1)
+            AND (expires_at IS NULL OR expires_at > NOW())
+            ORDER BY created_at
+            "#,
+            post_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取草稿的分享接收人失败")?;
+
+        let mut shares: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for row in rows {
+            shares.entry(row.post_id).or_default().push(row.user_id);
+        }
+        Ok(shares)
+    }
+
+    // 获取分享给用户的草稿列表
+    async fn list_shared_with_user(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<SharedDraftDto>, i64)> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                p.id, p.title, p.slug, p.author_id, u.username as "author_username?",
+                p.updated_at, ds.role, ds.expires_at, ds.created_at as shared_at
+            FROM draft_shares ds
+            JOIN posts p ON p.id = ds.post_id
+            LEFT JOIN users u ON u.id = p.author_id
+            WHERE ds.user_id = $1
+            AND (ds.expires_at IS NULL OR ds.expires_at > NOW())
+            AND p.published_at IS NULL AND p.deleted_at IS NULL
+            ORDER BY p.updated_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            user_id,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取分享给用户的草稿列表失败")?;
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM draft_shares ds
+            JOIN posts p ON p.id = ds.post_id
+            WHERE ds.user_id = $1
+            AND (ds.expires_at IS NULL OR ds.expires_at > NOW())
+            AND p.published_at IS NULL AND p.deleted_at IS NULL
+            "#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("获取分享给用户的草稿总数失败")?;
+
+        let drafts = rows
+            .into_iter()
+            .map(|row| SharedDraftDto {
+                post_id: row.id,
+                title: row.title,
+                slug: row.slug,
+                author_id: row.author_id,
+                author_username: row.author_username,
+                role: DraftShareRole::from_db(&row.role),
+                expires_at: row.expires_at,
+                shared_at: row.shared_at,
+                updated_at: row.updated_at,
+            })
+            .collect();
+
+        Ok((drafts, total))
+    }
+
     // 获取用户可以访问的草稿列表（包括自己的和分享给自己的）
     async fn list_accessible_drafts(
         &self,
@@ -832,12 +1647,12 @@ impl PostRepository for PostgresPostRepository {
             r#"
             SELECT 
                 id, slug, title, content, author_id, created_at, updated_at, published_at,
-                draft_shared_with, is_draft_public, is_banned
+                NULL::uuid[] as draft_shared_with, is_draft_public, is_banned, render_profile
             FROM posts 
-            WHERE published_at IS NULL 
+            WHERE published_at IS NULL AND deleted_at IS NULL
             AND (
-                author_id = $1  -- 自己的草稿
-                OR $1 = ANY(draft_shared_with)  -- 分享给自己的草稿
+                is_post_author(id, $1)  -- 自己的草稿（包括合写的草稿）
+                OR is_draft_shared_with(id, $1)  -- 分享给自己的草稿
                 OR (is_draft_public = true)  -- 公开的草稿
             )
             ORDER BY updated_at DESC
@@ -856,10 +1671,10 @@ impl PostRepository for PostgresPostRepository {
             r#"
             SELECT COUNT(*) as count
             FROM posts 
-            WHERE published_at IS NULL 
+            WHERE published_at IS NULL AND deleted_at IS NULL
             AND (
-                author_id = $1  
-                OR $1 = ANY(draft_shared_with)  
+                is_post_author(id, $1)
+                OR is_draft_shared_with(id, $1)  
                 OR (is_draft_public = true)
             )
             "#,
@@ -920,13 +1735,14 @@ impl PostRepository for PostgresPostRepository {
                     dal.post_id,
                     p.title as post_title,
                     dal.accessed_by,
-                    u.username as accessed_by_username,
+                    u.username as "accessed_by_username?",
                     dal.access_type,
                     dal.access_reason,
+                    dal.preview_link_id,
                     dal.created_at
                 FROM draft_access_logs dal
                 JOIN posts p ON dal.post_id = p.id
-                JOIN users u ON dal.accessed_by = u.id
+                LEFT JOIN users u ON dal.accessed_by = u.id
                 WHERE dal.post_id = $1
                 ORDER BY dal.created_at DESC
                 LIMIT $2 OFFSET $3
@@ -948,13 +1764,14 @@ impl PostRepository for PostgresPostRepository {
                     dal.post_id,
                     p.title as post_title,
                     dal.accessed_by,
-                    u.username as accessed_by_username,
+                    u.username as "accessed_by_username?",
                     dal.access_type,
                     dal.access_reason,
+                    dal.preview_link_id,
                     dal.created_at
                 FROM draft_access_logs dal
                 JOIN posts p ON dal.post_id = p.id
-                JOIN users u ON dal.accessed_by = u.id
+                LEFT JOIN users u ON dal.accessed_by = u.id
                 ORDER BY dal.created_at DESC
                 LIMIT $1 OFFSET $2
                 "#,
@@ -971,10 +1788,13 @@ impl PostRepository for PostgresPostRepository {
 
     // 检查文章是否为草稿
     async fn is_draft(&self, post_id: Uuid) -> Result<bool> {
-        let result = sqlx::query!("SELECT published_at FROM posts WHERE id = $1", post_id)
-            .fetch_optional(&self.pool)
-            .await
-            .context(format!("检查文章是否为草稿失败，post_id: {}", post_id))?;
+        let result = sqlx::query!(
+            "SELECT published_at FROM posts WHERE id = $1 AND deleted_at IS NULL",
+            post_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("检查文章是否为草稿失败，post_id: {}", post_id))?;
 
         match result {
             Some(record) => Ok(record.published_at.is_none()),
@@ -1005,13 +1825,14 @@ impl PostRepository for PostgresPostRepository {
             r#"
             SELECT DISTINCT 
                 id, slug, title, content, created_at AS "created_at!", updated_at AS "updated_at!", 
-                published_at, author_id, draft_shared_with, is_draft_public, is_banned
+                published_at, author_id, NULL::uuid[] as draft_shared_with, is_draft_public, is_banned, render_profile
             FROM posts 
-            WHERE 
-                author_id = $1  -- 自己的所有文章
+            WHERE deleted_at IS NULL AND (
+                is_post_author(id, $1)  -- 自己的所有文章（包括合写的文章）
                 OR published_at IS NOT NULL  -- 他人的已发布文章
-                OR (published_at IS NULL AND $1 = ANY(draft_shared_with))  -- 分享给自己的草稿
+                OR (published_at IS NULL AND is_draft_shared_with(id, $1))  -- 分享给自己的草稿
                 OR (published_at IS NULL AND is_draft_public = true)  -- 公开的草稿
+            )
             ORDER BY created_at DESC
             LIMIT $2 OFFSET $3
             "#,
@@ -1028,11 +1849,12 @@ impl PostRepository for PostgresPostRepository {
             r#"
             SELECT COUNT(DISTINCT id) as "count!"
             FROM posts 
-            WHERE 
-                author_id = $1  -- 自己的所有文章
+            WHERE deleted_at IS NULL AND (
+                is_post_author(id, $1)  -- 自己的所有文章（包括合写的文章）
                 OR published_at IS NOT NULL  -- 他人的已发布文章
-                OR (published_at IS NULL AND $1 = ANY(draft_shared_with))  -- 分享给自己的草稿
+                OR (published_at IS NULL AND is_draft_shared_with(id, $1))  -- 分享给自己的草稿
                 OR (published_at IS NULL AND is_draft_public = true)  -- 公开的草稿
+            )
             "#,
             user_id
         )
@@ -1046,37 +1868,713 @@ impl PostRepository for PostgresPostRepository {
     }
 
     // 封禁文章
-    async fn ban_post(&self, post_id: Uuid) -> Result<()> {
-        sqlx::query!("UPDATE posts SET is_banned = true WHERE id = $1", post_id)
-            .execute(&self.pool)
-            .await
-            .context(format!("封禁文章失败，post_id: {}", post_id))?;
+    async fn ban_post(
+        &self,
+        post_id: Uuid,
+        moderator_id: Uuid,
+        reason: Option<&str>,
+    ) -> Result<PostBan> {
+        let mut tx = self.pool.begin().await.context("开启封禁事务失败")?;
+
+        sqlx::query!(
+            "UPDATE posts SET is_banned = true WHERE id = $1 AND deleted_at IS NULL",
+            post_id
+        )
+        .execute(&mut *tx)
+        .await
+        .context(format!("封禁文章失败，post_id: {}", post_id))?;
+
+        let ban = sqlx::query_as!(
+            PostBan,
+            r#"
+            INSERT INTO post_bans (post_id, banned_by, reason)
+            VALUES ($1, $2, $3)
+            RETURNING id, post_id, banned_by, reason, banned_at, lifted_by, lifted_at
+            "#,
+            post_id,
+            moderator_id,
+            reason
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .context(format!("记录封禁信息失败，post_id: {}", post_id))?;
+
+        tx.commit().await.context("提交封禁事务失败")?;
 
         tracing::info!("文章已被封禁，post_id: {}", post_id);
-        Ok(())
+        Ok(ban)
     }
 
     // 解封文章
-    async fn unban_post(&self, post_id: Uuid) -> Result<()> {
-        sqlx::query!("UPDATE posts SET is_banned = false WHERE id = $1", post_id)
-            .execute(&self.pool)
-            .await
-            .context(format!("解封文章失败，post_id: {}", post_id))?;
+    async fn unban_post(&self, post_id: Uuid, moderator_id: Uuid) -> Result<()> {
+        let mut tx = self.pool.begin().await.context("开启解封事务失败")?;
+        Self::lift_ban(&mut tx, post_id, moderator_id).await?;
+        tx.commit().await.context("提交解封事务失败")?;
 
         tracing::info!("文章已被解封，post_id: {}", post_id);
         Ok(())
     }
 
+    // 获取文章生效中的封禁记录
+    async fn get_active_ban(&self, post_id: Uuid) -> Result<Option<PostBanDto>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                b.id, b.post_id, b.reason, b.banned_by, u.username as "banned_by_username?",
+                b.banned_at, a.status as "appeal_status?"
+            FROM post_bans b
+            LEFT JOIN users u ON u.id = b.banned_by
+            LEFT JOIN post_ban_appeals a ON a.ban_id = b.id
+            WHERE b.post_id = $1 AND b.lifted_at IS NULL
+            "#,
+            post_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("获取文章封禁记录失败")?;
+
+        Ok(row.map(|row| PostBanDto {
+            id: row.id,
+            post_id: row.post_id,
+            reason: row.reason,
+            banned_by: row.banned_by,
+            banned_by_username: row.banned_by_username,
+            banned_at: row.banned_at,
+            appeal_status: row.appeal_status.as_deref().map(BanAppealStatus::from_db),
+        }))
+    }
+
+    // 提交封禁申诉
+    async fn create_ban_appeal(
+        &self,
+        ban_id: Uuid,
+        post_id: Uuid,
+        appellant_id: Uuid,
+        message: &str,
+    ) -> Result<PostBanAppeal> {
+        sqlx::query_as!(
+            PostBanAppeal,
+            r#"
+            INSERT INTO post_ban_appeals (ban_id, post_id, appellant_id, message)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, ban_id, post_id, appellant_id, message, status, resolved_by,
+                      resolution_note, created_at, resolved_at
+            "#,
+            ban_id,
+            post_id,
+            appellant_id,
+            message
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("提交封禁申诉失败")
+    }
+
+    // 获取封禁申诉
+    async fn get_ban_appeal(&self, appeal_id: Uuid) -> Result<Option<BanAppealDto>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                a.id, a.ban_id, a.post_id, p.title as post_title, a.appellant_id,
+                u.username as "appellant_username?", a.message, a.status, b.reason as ban_reason,
+                b.banned_at, a.resolved_by, a.resolution_note, a.created_at, a.resolved_at
+            FROM post_ban_appeals a
+            JOIN post_bans b ON b.id = a.ban_id
+            JOIN posts p ON p.id = a.post_id
+            LEFT JOIN users u ON u.id = a.appellant_id
+            WHERE a.id = $1
+            "#,
+            appeal_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("获取封禁申诉失败")?;
+
+        Ok(row.map(|row| BanAppealDto {
+            id: row.id,
+            ban_id: row.ban_id,
+            post_id: row.post_id,
+            post_title: row.post_title,
+            appellant_id: row.appellant_id,
+            appellant_username: row.appellant_username,
+            message: row.message,
+            status: BanAppealStatus::from_db(&row.status),
+            ban_reason: row.ban_reason,
+            banned_at: row.banned_at,
+            resolved_by: row.resolved_by,
+            resolution_note: row.resolution_note,
+            created_at: row.created_at,
+            resolved_at: row.resolved_at,
+        }))
+    }
+
+    // 获取指定状态的封禁申诉列表
+    async fn list_ban_appeals(
+        &self,
+        status: BanAppealStatus,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<BanAppealDto>, i64)> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                a.id, a.ban_id, a.post_id, p.title as post_title, a.appellant_id,
+                u.username as "appellant_username?", a.message, a.status, b.reason as ban_reason,
+                b.banned_at, a.resolved_by, a.resolution_note, a.created_at, a.resolved_at
+            FROM post_ban_appeals a
+            JOIN post_bans b ON b.id = a.ban_id
+            JOIN posts p ON p.id = a.post_id
+            LEFT JOIN users u ON u.id = a.appellant_id
+            WHERE a.status = $1 AND p.deleted_at IS NULL
+            ORDER BY a.created_at ASC
+            LIMIT $2 OFFSET $3
+            "#,
+            status.as_str(),
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取封禁申诉列表失败")?;
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM post_ban_appeals a
+            JOIN posts p ON p.id = a.post_id
+            WHERE a.status = $1 AND p.deleted_at IS NULL
+            "#,
+            status.as_str()
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("获取封禁申诉总数失败")?;
+
+        let appeals = rows
+            .into_iter()
+            .map(|row| BanAppealDto {
+                id: row.id,
+                ban_id: row.ban_id,
+                post_id: row.post_id,
+                post_title: row.post_title,
+                appellant_id: row.appellant_id,
+                appellant_username: row.appellant_username,
+                message: row.message,
+                status: BanAppealStatus::from_db(&row.status),
+                ban_reason: row.ban_reason,
+                banned_at: row.banned_at,
+                resolved_by: row.resolved_by,
+                resolution_note: row.resolution_note,
+                created_at: row.created_at,
+                resolved_at: row.resolved_at,
+            })
+            .collect();
+
+        Ok((appeals, total))
+    }
+
+    // 处理待处理的申诉
+    async fn resolve_ban_appeal(
+        &self,
+        appeal_id: Uuid,
+        moderator_id: Uuid,
+        status: BanAppealStatus,
+        note: Option<&str>,
+        lift_ban: bool,
+    ) -> Result<bool> {
+        let mut tx = self.pool.begin().await.context("开启申诉处理事务失败")?;
+
+        let post_id = sqlx::query_scalar!(
+            r#"
+            UPDATE post_ban_appeals
+            SET status = $2, resolved_by = $3, resolution_note = $4, resolved_at = NOW()
+            WHERE id = $1 AND status = 'pending'
+            RETURNING post_id
+            "#,
+            appeal_id,
+            status.as_str(),
+            moderator_id,
+            note
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .context("更新封禁申诉状态失败")?;
+
+        let Some(post_id) = post_id else {
+            return Ok(false);
+        };
+
+        if lift_ban {
+            Self::lift_ban(&mut tx, post_id, moderator_id).await?;
+        }
+
+        tx.commit().await.context("提交申诉处理事务失败")?;
+        Ok(true)
+    }
+
     // 检查文章是否被封禁
     async fn is_banned(&self, post_id: Uuid) -> Result<bool> {
-        let result = sqlx::query!("SELECT is_banned FROM posts WHERE id = $1", post_id)
-            .fetch_optional(&self.pool)
-            .await
-            .context(format!("检查文章封禁状态失败，post_id: {}", post_id))?;
+        let result = sqlx::query!(
+            "SELECT is_banned FROM posts WHERE id = $1 AND deleted_at IS NULL",
+            post_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("检查文章封禁状态失败，post_id: {}", post_id))?;
 
         match result {
             Some(record) => Ok(record.is_banned),
             None => anyhow::bail!("文章不存在"),
         }
     }
+
+    // 获取文章当前的审核状态
+    async fn get_review(&self, post_id: Uuid) -> Result<Option<PostReview>> {
+        let review = sqlx::query_as!(
+            PostReview,
+            r#"
+            SELECT post_id, status, reviewer_id, created_at, updated_at
+            FROM post_reviews
+            WHERE post_id = $1
+            "#,
+            post_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("获取文章审核状态失败，post_id: {}", post_id))?;
+
+        Ok(review)
+    }
+
+    // 流转审核状态并记录本次流转
+    async fn transition_review(
+        &self,
+        post_id: Uuid,
+        from_status: &str,
+        to_status: &str,
+        actor_id: Uuid,
+        reviewer_id: Option<Uuid>,
+        comment: Option<&str>,
+    ) -> Result<()> {
+        let mut txn = self.pool.begin().await.context("开启审核流转事务失败")?;
+
+        // 只有当前状态仍为 from_status 时才更新，防止并发操作覆盖彼此的结果
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO post_reviews (post_id, status, reviewer_id)
+            VALUES ($1, $3, $4)
+            ON CONFLICT (post_id) DO UPDATE
+            SET status = EXCLUDED.status,
+                reviewer_id = COALESCE(EXCLUDED.reviewer_id, post_reviews.reviewer_id)
+            WHERE post_reviews.status = $2
+            "#,
+            post_id,
+            from_status,
+            to_status,
+            reviewer_id
+        )
+        .execute(&mut *txn)
+        .await
+        .context(format!("更新文章审核状态失败，post_id: {}", post_id))?;
+
+        if result.rows_affected() == 0 {
+            anyhow::bail!("文章审核状态已被其他操作修改，请刷新后重试");
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO post_review_transitions (post_id, from_status, to_status, actor_id, comment)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            post_id,
+            from_status,
+            to_status,
+            actor_id,
+            comment
+        )
+        .execute(&mut *txn)
+        .await
+        .context(format!("记录文章审核流转失败，post_id: {}", post_id))?;
+
+        txn.commit().await.context("提交审核流转事务失败")?;
+
+        tracing::info!(
+            "文章 {} 审核状态 {} -> {}，操作人 {}",
+            post_id,
+            from_status,
+            to_status,
+            actor_id
+        );
+        Ok(())
+    }
+
+    // 指派审核人
+    async fn assign_reviewer(&self, post_id: Uuid, reviewer_id: Uuid) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO post_reviews (post_id, reviewer_id)
+            VALUES ($1, $2)
+            ON CONFLICT (post_id) DO UPDATE SET reviewer_id = EXCLUDED.reviewer_id
+            "#,
+            post_id,
+            reviewer_id
+        )
+        .execute(&self.pool)
+        .await
+        .context(format!("指派审核人失败，post_id: {}", post_id))?;
+
+        tracing::info!("文章 {} 已指派审核人 {}", post_id, reviewer_id);
+        Ok(())
+    }
+
+    // 获取文章的审核流转记录
+    async fn get_review_transitions(&self, post_id: Uuid) -> Result<Vec<ReviewTransitionDto>> {
+        let transitions = sqlx::query_as!(
+            ReviewTransitionDto,
+            r#"
+            SELECT
+                t.id,
+                t.from_status,
+                t.to_status,
+                t.actor_id,
+                u.username as "actor_username?",
+                t.comment,
+                t.created_at
+            FROM post_review_transitions t
+            LEFT JOIN users u ON t.actor_id = u.id
+            WHERE t.post_id = $1
+            ORDER BY t.created_at ASC
+            "#,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context(format!("获取文章审核流转记录失败，post_id: {}", post_id))?;
+
+        Ok(transitions)
+    }
+
+    // 获取待审核文章列表
+    async fn list_pending_reviews(
+        &self,
+        reviewer_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Post>, i64)> {
+        // 待审核：状态为 in_review，且指派给当前审核人或尚未指派；不包含自己的文章（包括合写的文章）
+        let posts = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT
+                p.id, p.slug, p.title, p.content, p.author_id, p.created_at, p.updated_at,
+                p.published_at, NULL::uuid[] as draft_shared_with, p.is_draft_public, p.is_banned, p.render_profile
+            FROM posts p
+            JOIN post_reviews r ON r.post_id = p.id
+            WHERE r.status = 'in_review'
+            AND (r.reviewer_id = $1 OR r.reviewer_id IS NULL)
+            AND NOT is_post_author(p.id, $1)
+            AND p.deleted_at IS NULL
+            ORDER BY r.updated_at ASC
+            LIMIT $2 OFFSET $3
+            "#,
+            reviewer_id,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取待审核文章列表失败")?;
+
+        let total = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM posts p
+            JOIN post_reviews r ON r.post_id = p.id
+            WHERE r.status = 'in_review'
+            AND (r.reviewer_id = $1 OR r.reviewer_id IS NULL)
+            AND NOT is_post_author(p.id, $1)
+            AND p.deleted_at IS NULL
+            "#,
+            reviewer_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("获取待审核文章总数失败")?;
+
+        Ok((posts, total.count))
+    }
+
+    // 获取回收站中的文章
+    async fn list_deleted(&self, author_id: Option<Uuid>) -> Result<Vec<TrashedPost>> {
+        let posts = sqlx::query_as!(
+            TrashedPost,
+            r#"
+            SELECT id, slug, title, author_id, deleted_at as "deleted_at!"
+            FROM posts
+            WHERE deleted_at IS NOT NULL
+            AND ($1::uuid IS NULL OR author_id = $1)
+            ORDER BY deleted_at DESC
+            "#,
+            author_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取回收站中的文章失败")?;
+
+        Ok(posts)
+    }
+
+    // 获取回收站中的单篇文章
+    async fn get_deleted_by_id(&self, id: Uuid) -> Result<Option<TrashedPost>> {
+        let post = sqlx::query_as!(
+            TrashedPost,
+            r#"
+            SELECT id, slug, title, author_id, deleted_at as "deleted_at!"
+            FROM posts
+            WHERE id = $1 AND deleted_at IS NOT NULL
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("查询回收站中的文章 (id: {}) 失败", id))?;
+
+        Ok(post)
+    }
+
+    // 从回收站恢复文章
+    async fn restore(&self, id: Uuid) -> Result<()> {
+        // slug 只在未删除的文章之间唯一，删除后可能已被新文章占用
+        let conflict = sqlx::query_scalar!(
+            r#"
+            SELECT live.slug
+            FROM posts trashed
+            JOIN posts live ON live.slug = trashed.slug
+            WHERE trashed.id = $1 AND trashed.deleted_at IS NOT NULL
+              AND live.deleted_at IS NULL AND live.id <> trashed.id
+            LIMIT 1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("检查文章 (id: {}) 的 slug 冲突失败", id))?;
+        if let Some(slug) = conflict {
+            anyhow::bail!("slug 为 '{}' 的文章已存在，无法从回收站恢复", slug);
+        }
+
+        let result = sqlx::query!(
+            "UPDATE posts SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .context(format!("恢复文章 (id: {}) 失败", id))?;
+
+        if result.rows_affected() == 0 {
+            anyhow::bail!("回收站中未找到文章 (id: {})", id);
+        }
+
+        tracing::info!("文章 {} 已从回收站恢复", id);
+        Ok(())
+    }
+
+    // 彻底删除超过保留天数的文章
+    // post_categories 和 post_tags 设置了 ON DELETE CASCADE，关联记录会自动删除
+    async fn purge_deleted(&self, retention_days: i32) -> Result<u64> {
+        let result = sqlx::query!(
+            "DELETE FROM posts WHERE deleted_at <= NOW() - make_interval(days => $1)",
+            retention_days
+        )
+        .execute(&self.pool)
+        .await
+        .context("清理回收站中的过期文章失败")?;
+
+        Ok(result.rows_affected())
+    }
+
+    // 用正文中的维基链接整体替换文章的出链
+    async fn replace_links(&self, source_post_id: Uuid, target_slugs: &[String]) -> Result<()> {
+        let mut txn = self.pool.begin().await.context("开启事务失败")?;
+
+        sqlx::query!(
+            "DELETE FROM post_links WHERE source_post_id = $1",
+            source_post_id
+        )
+        .execute(&mut *txn)
+        .await
+        .context(format!("清除文章 {} 的链接失败", source_post_id))?;
+
+        if !target_slugs.is_empty() {
+            sqlx::query!(
+                r#"
+                INSERT INTO post_links (source_post_id, target_slug)
+                SELECT $1, UNNEST($2::VARCHAR[])
+                ON CONFLICT DO NOTHING
+                "#,
+                source_post_id,
+                target_slugs
+            )
+            .execute(&mut *txn)
+            .await
+            .context(format!("保存文章 {} 的链接失败", source_post_id))?;
+        }
+
+        txn.commit().await.context("提交事务失败")?;
+        Ok(())
+    }
+
+    // 获取链接到指定 slug 的已发布文章（不含文章自身）
+    async fn list_backlinks(&self, target_slug: &str, post_id: Uuid) -> Result<Vec<PostLinkDto>> {
+        sqlx::query_as!(
+            PostLinkDto,
+            r#"
+            SELECT p.id, p.title, p.slug, p.published_at
+            FROM post_links l
+            JOIN posts p ON p.id = l.source_post_id
+            WHERE l.target_slug = $1
+              AND p.id <> $2
+              AND p.published_at IS NOT NULL
+              AND p.deleted_at IS NULL
+              AND COALESCE(p.is_banned, FALSE) = FALSE
+            ORDER BY p.published_at DESC
+            "#,
+            target_slug,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context(format!("获取链接到 {} 的文章失败", target_slug))
+    }
+
+    async fn get_wiki_link_targets(&self, slugs: &[String]) -> Result<HashMap<String, bool>> {
+        if slugs.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let rows = sqlx::query!(
+            r#"
+            SELECT slug, (published_at IS NOT NULL AND NOT COALESCE(is_banned, FALSE)) AS "linkable!"
+            FROM posts
+            WHERE slug = ANY($1) AND deleted_at IS NULL
+            "#,
+            slugs
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("解析维基链接目标失败")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.slug, row.linkable))
+            .collect())
+    }
+
+    async fn bulk_apply(
+        &self,
+        post_ids: &[Uuid],
+        action: &BulkPostAction,
+        actor_id: Uuid,
+        require_approved: bool,
+    ) -> Result<()> {
+        let now = Utc::now();
+        let mut txn = self.pool.begin().await.context("开启批量操作事务失败")?;
+
+        for post_id in post_ids {
+            // 每个动作都会更新 updated_at，使持有旧版本的编辑请求返回 412
+            let result = match action {
+                BulkPostAction::Publish => sqlx::query!(
+                    "UPDATE posts SET published_at = $1, updated_at = $1 WHERE id = $2 AND deleted_at IS NULL",
+                    now,
+                    post_id
+                )
+                .execute(&mut *txn)
+                .await,
+                BulkPostAction::Unpublish => sqlx::query!(
+                    "UPDATE posts SET published_at = NULL, updated_at = $1 WHERE id = $2 AND deleted_at IS NULL",
+                    now,
+                    post_id
+                )
+                .execute(&mut *txn)
+                .await,
+                BulkPostAction::Delete => sqlx::query!(
+                    "UPDATE posts SET deleted_at = $1 WHERE id = $2 AND deleted_at IS NULL",
+                    now,
+                    post_id
+                )
+                .execute(&mut *txn)
+                .await,
+                BulkPostAction::Ban { .. } => sqlx::query!(
+                    "UPDATE posts SET is_banned = true WHERE id = $1 AND deleted_at IS NULL AND published_at IS NOT NULL AND COALESCE(is_banned, false) = false",
+                    post_id
+                )
+                .execute(&mut *txn)
+                .await,
+                BulkPostAction::AddTags { .. }
+                | BulkPostAction::RemoveTags { .. }
+                | BulkPostAction::SetCategories { .. } => sqlx::query!(
+                    "UPDATE posts SET updated_at = $1 WHERE id = $2 AND deleted_at IS NULL",
+                    now,
+                    post_id
+                )
+                .execute(&mut *txn)
+                .await,
+            }
+            .context(format!("批量操作文章 (id: {}) 失败", post_id))?;
+
+            if result.rows_affected() == 0 {
+                anyhow::bail!("批量操作时未找到文章 (id: {}) 或文章状态已改变", post_id);
+            }
+
+            match action {
+                BulkPostAction::Publish => {
+                    if require_approved {
+                        Self::ensure_review_approved(&mut txn, *post_id).await?;
+                    }
+                    Self::set_review_status(&mut txn, *post_id, "published", actor_id).await?;
+                }
+                BulkPostAction::Unpublish => {
+                    Self::set_review_status(&mut txn, *post_id, "draft", actor_id).await?;
+                }
+                BulkPostAction::Delete => {}
+                BulkPostAction::Ban { reason } => {
+                    sqlx::query!(
+                        "INSERT INTO post_bans (post_id, banned_by, reason) VALUES ($1, $2, $3)",
+                        post_id,
+                        actor_id,
+                        reason.as_deref()
+                    )
+                    .execute(&mut *txn)
+                    .await
+                    .context(format!("记录封禁信息失败，post_id: {}", post_id))?;
+                }
+                BulkPostAction::AddTags { tag_ids } => {
+                    Self::manage_post_tags(&mut txn, *post_id, &Some(tag_ids.clone()), false)
+                        .await?;
+                }
+                BulkPostAction::RemoveTags { tag_ids } => {
+                    sqlx::query!(
+                        "DELETE FROM post_tags WHERE post_id = $1 AND tag_id = ANY($2)",
+                        post_id,
+                        tag_ids
+                    )
+                    .execute(&mut *txn)
+                    .await
+                    .context(format!("移除文章 {} 的标签失败", post_id))?;
+                }
+                BulkPostAction::SetCategories { category_ids } => {
+                    Self::manage_post_categories(
+                        &mut txn,
+                        *post_id,
+                        &Some(category_ids.clone()),
+                        true,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        txn.commit().await.context("提交批量操作事务失败")?;
+        tracing::info!("用户 {} 批量操作了 {} 篇文章", actor_id, post_ids.len());
+        Ok(())
+    }
 }
@@ -0,0 +1,117 @@
+use crate::models::PostAutosave;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// 文章自动保存的数据库操作
+#[async_trait]
+pub trait PostAutosaveRepository: Send + Sync {
+    // 写入用户对文章的自动保存（已存在则覆盖）
+    async fn upsert(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        title: &str,
+        content: &str,
+    ) -> Result<PostAutosave>;
+    // 获取用户对文章的自动保存，只返回保留期内的记录
+    async fn get(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        retention_days: i32,
+    ) -> Result<Option<PostAutosave>>;
+    // 删除用户对文章的自动保存，返回是否删除了记录
+    async fn delete(&self, post_id: Uuid, user_id: Uuid) -> Result<bool>;
+    // 清理超过保留期的自动保存，返回清理的数量
+    async fn purge_older_than(&self, retention_days: i32) -> Result<u64>;
+}
+
+#[derive(Clone)]
+pub struct PostgresPostAutosaveRepository {
+    pool: PgPool,
+}
+
+impl PostgresPostAutosaveRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PostAutosaveRepository for PostgresPostAutosaveRepository {
+    async fn upsert(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        title: &str,
+        content: &str,
+    ) -> Result<PostAutosave> {
+        sqlx::query_as!(
+            PostAutosave,
+            r#"
+            INSERT INTO post_autosaves (post_id, user_id, title, content)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (post_id, user_id) DO UPDATE
+                SET title = EXCLUDED.title, content = EXCLUDED.content
+            RETURNING post_id, user_id, title, content, created_at, updated_at
+            "#,
+            post_id,
+            user_id,
+            title,
+            content
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("写入自动保存失败")
+    }
+
+    async fn get(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        retention_days: i32,
+    ) -> Result<Option<PostAutosave>> {
+        sqlx::query_as!(
+            PostAutosave,
+            r#"
+            SELECT post_id, user_id, title, content, created_at, updated_at
+            FROM post_autosaves
+            WHERE post_id = $1 AND user_id = $2
+              AND updated_at > NOW() - make_interval(days => $3)
+            "#,
+            post_id,
+            user_id,
+            retention_days
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("查询自动保存失败")
+    }
+
+    async fn delete(&self, post_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query!(
+            "DELETE FROM post_autosaves WHERE post_id = $1 AND user_id = $2",
+            post_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .context("删除自动保存失败")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn purge_older_than(&self, retention_days: i32) -> Result<u64> {
+        let result = sqlx::query!(
+            "DELETE FROM post_autosaves WHERE updated_at <= NOW() - make_interval(days => $1)",
+            retention_days
+        )
+        .execute(&self.pool)
+        .await
+        .context("清理过期自动保存失败")?;
+
+        Ok(result.rows_affected())
+    }
+}
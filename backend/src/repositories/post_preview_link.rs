@@ -0,0 +1,153 @@
+use crate::models::PostPreviewLink;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// 草稿预览链接的数据库操作
+#[async_trait]
+pub trait PostPreviewLinkRepository: Send + Sync {
+    // 创建预览链接
+    async fn create(
+        &self,
+        post_id: Uuid,
+        created_by: Uuid,
+        label: Option<&str>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<PostPreviewLink>;
+    // 根据 ID 获取预览链接（包括已撤销和已过期的）
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<PostPreviewLink>>;
+    // 列出文章仍然有效（未撤销、未过期）的预览链接
+    async fn list_active_for_post(&self, post_id: Uuid) -> Result<Vec<PostPreviewLink>>;
+    // 撤销预览链接，返回是否撤销了一条有效链接
+    async fn revoke(&self, post_id: Uuid, id: Uuid) -> Result<bool>;
+    // 记录一次链接使用：更新使用次数，并写入草稿访问日志
+    async fn record_use(&self, link: &PostPreviewLink) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct PostgresPostPreviewLinkRepository {
+    pool: PgPool,
+}
+
+impl PostgresPostPreviewLinkRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PostPreviewLinkRepository for PostgresPostPreviewLinkRepository {
+    async fn create(
+        &self,
+        post_id: Uuid,
+        created_by: Uuid,
+        label: Option<&str>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<PostPreviewLink> {
+        sqlx::query_as!(
+            PostPreviewLink,
+            r#"
+            INSERT INTO post_preview_links (post_id, created_by, label, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, post_id, created_by, label, expires_at, revoked_at,
+                      use_count, last_used_at, created_at
+            "#,
+            post_id,
+            created_by,
+            label,
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("创建预览链接失败")
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<PostPreviewLink>> {
+        sqlx::query_as!(
+            PostPreviewLink,
+            r#"
+            SELECT id, post_id, created_by, label, expires_at, revoked_at,
+                   use_count, last_used_at, created_at
+            FROM post_preview_links
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("获取预览链接 {} 失败", id))
+    }
+
+    async fn list_active_for_post(&self, post_id: Uuid) -> Result<Vec<PostPreviewLink>> {
+        sqlx::query_as!(
+            PostPreviewLink,
+            r#"
+            SELECT id, post_id, created_by, label, expires_at, revoked_at,
+                   use_count, last_used_at, created_at
+            FROM post_preview_links
+            WHERE post_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+            ORDER BY created_at DESC
+            "#,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context(format!("获取文章 {} 的预览链接失败", post_id))
+    }
+
+    async fn revoke(&self, post_id: Uuid, id: Uuid) -> Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE post_preview_links SET revoked_at = NOW()
+            WHERE id = $1 AND post_id = $2 AND revoked_at IS NULL
+            "#,
+            id,
+            post_id
+        )
+        .execute(&self.pool)
+        .await
+        .context(format!("撤销预览链接 {} 失败", id))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_use(&self, link: &PostPreviewLink) -> Result<()> {
+        let mut tx = self.pool.begin().await.context("开启事务失败")?;
+
+        sqlx::query!(
+            r#"
+            UPDATE post_preview_links
+            SET use_count = use_count + 1, last_used_at = NOW()
+            WHERE id = $1
+            "#,
+            link.id
+        )
+        .execute(&mut *tx)
+        .await
+        .context("更新预览链接使用次数失败")?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO draft_access_logs (post_id, accessed_by, access_type, access_reason, preview_link_id)
+            VALUES ($1, NULL, 'preview_link', $2, $3)
+            "#,
+            link.post_id,
+            link.label,
+            link.id
+        )
+        .execute(&mut *tx)
+        .await
+        .context("记录草稿访问日志失败")?;
+
+        tx.commit().await.context("提交事务失败")?;
+
+        tracing::info!(
+            "记录草稿访问日志：预览链接 {} 访问了草稿 {}",
+            link.id,
+            link.post_id
+        );
+        Ok(())
+    }
+}
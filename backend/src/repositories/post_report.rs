@@ -0,0 +1,242 @@
+use crate::dtos::report::{PostReportDto, ReportCategoryCount, ReportedPostDto};
+use crate::models::{PostReport, ReportCategory};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// 读者举报的数据库操作
+#[async_trait]
+pub trait PostReportRepository: Send + Sync {
+    // 创建举报；同一举报人对同一篇文章已有待处理的举报时返回 None。
+    // 频率限制在同一事务内检查：举报人最近一小时已提交 max_reports_per_hour 条及以上时拒绝
+    async fn create(
+        &self,
+        post_id: Uuid,
+        reporter_id: Option<Uuid>,
+        reporter_key: &str,
+        category: ReportCategory,
+        details: Option<&str>,
+        max_reports_per_hour: i64,
+    ) -> Result<Option<PostReport>>;
+    // 举报队列：按文章聚合待处理的举报，举报多的排在前面
+    async fn list_reported_posts(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<ReportedPostDto>, i64)>;
+    // 获取文章所有待处理的举报
+    async fn list_open_for_post(&self, post_id: Uuid) -> Result<Vec<PostReportDto>>;
+    // 关闭文章所有待处理的举报，返回关闭的数量
+    async fn resolve_open_for_post(
+        &self,
+        post_id: Uuid,
+        moderator_id: Uuid,
+        status: &str,
+    ) -> Result<u64>;
+}
+
+#[derive(Clone)]
+pub struct PostgresPostReportRepository {
+    pool: PgPool,
+}
+
+impl PostgresPostReportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PostReportRepository for PostgresPostReportRepository {
+    async fn create(
+        &self,
+        post_id: Uuid,
+        reporter_id: Option<Uuid>,
+        reporter_key: &str,
+        category: ReportCategory,
+        details: Option<&str>,
+        max_reports_per_hour: i64,
+    ) -> Result<Option<PostReport>> {
+        let mut txn = self.pool.begin().await.context("开启举报事务失败")?;
+
+        // 按举报人加事务级咨询锁，同一举报人的并发请求在这里排队，
+        // 避免多个请求同时通过计数检查后一起插入而绕过频率限制
+        sqlx::query!("SELECT pg_advisory_xact_lock(hashtext($1))", reporter_key)
+            .execute(&mut *txn)
+            .await
+            .context("获取举报频率限制锁失败")?;
+
+        let recent = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM post_reports
+            WHERE reporter_key = $1 AND created_at > NOW() - INTERVAL '1 hour'
+            "#,
+            reporter_key
+        )
+        .fetch_one(&mut *txn)
+        .await
+        .context("统计举报频率失败")?;
+        if recent >= max_reports_per_hour {
+            anyhow::bail!("举报过于频繁，请稍后再试");
+        }
+
+        let report = sqlx::query_as!(
+            PostReport,
+            r#"
+            INSERT INTO post_reports (post_id, reporter_id, reporter_key, category, details)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (post_id, reporter_key) WHERE status = 'open' DO NOTHING
+            RETURNING id, post_id, reporter_id, reporter_key, category, details, status,
+                      resolved_by, resolved_at, created_at
+            "#,
+            post_id,
+            reporter_id,
+            reporter_key,
+            category.as_str(),
+            details
+        )
+        .fetch_optional(&mut *txn)
+        .await
+        .context("创建举报失败")?;
+
+        txn.commit().await.context("提交举报事务失败")?;
+        Ok(report)
+    }
+
+    async fn list_reported_posts(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<ReportedPostDto>, i64)> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                p.id, p.title, p.slug, p.author_id, u.username as "author_username?",
+                p.is_banned,
+                COUNT(r.id) as "report_count!",
+                MIN(r.created_at) as "first_reported_at!",
+                MAX(r.created_at) as "last_reported_at!"
+            FROM post_reports r
+            JOIN posts p ON p.id = r.post_id
+            LEFT JOIN users u ON u.id = p.author_id
+            WHERE r.status = 'open' AND p.deleted_at IS NULL
+            GROUP BY p.id, u.username
+            ORDER BY COUNT(r.id) DESC, MAX(r.created_at) DESC
+            LIMIT $1 OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取举报队列失败")?;
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(DISTINCT r.post_id) as "count!"
+            FROM post_reports r
+            JOIN posts p ON p.id = r.post_id
+            WHERE r.status = 'open' AND p.deleted_at IS NULL
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("获取举报队列总数失败")?;
+
+        // 当前页文章按分类统计的举报数量
+        let post_ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let category_rows = sqlx::query!(
+            r#"
+            SELECT post_id, category, COUNT(*) as "count!"
+            FROM post_reports
+            WHERE status = 'open' AND post_id = ANY($1)
+            GROUP BY post_id, category
+            ORDER BY COUNT(*) DESC
+            "#,
+            &post_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取举报分类统计失败")?;
+
+        let posts = rows
+            .into_iter()
+            .map(|row| ReportedPostDto {
+                post_id: row.id,
+                title: row.title,
+                slug: row.slug,
+                author_id: row.author_id,
+                author_username: row.author_username,
+                is_banned: row.is_banned,
+                report_count: row.report_count,
+                categories: category_rows
+                    .iter()
+                    .filter(|c| c.post_id == row.id)
+                    .map(|c| ReportCategoryCount {
+                        category: ReportCategory::from_db(&c.category),
+                        count: c.count,
+                    })
+                    .collect(),
+                first_reported_at: row.first_reported_at,
+                last_reported_at: row.last_reported_at,
+            })
+            .collect();
+
+        Ok((posts, total))
+    }
+
+    async fn list_open_for_post(&self, post_id: Uuid) -> Result<Vec<PostReportDto>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                r.id, r.post_id, r.category, r.details, r.reporter_id,
+                u.username as "reporter_username?", r.created_at
+            FROM post_reports r
+            LEFT JOIN users u ON u.id = r.reporter_id
+            WHERE r.post_id = $1 AND r.status = 'open'
+            ORDER BY r.created_at DESC
+            "#,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取文章的举报列表失败")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PostReportDto {
+                id: row.id,
+                post_id: row.post_id,
+                category: ReportCategory::from_db(&row.category),
+                details: row.details,
+                reporter_id: row.reporter_id,
+                reporter_username: row.reporter_username,
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+
+    async fn resolve_open_for_post(
+        &self,
+        post_id: Uuid,
+        moderator_id: Uuid,
+        status: &str,
+    ) -> Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE post_reports
+            SET status = $3, resolved_by = $2, resolved_at = NOW()
+            WHERE post_id = $1 AND status = 'open'
+            "#,
+            post_id,
+            moderator_id,
+            status
+        )
+        .execute(&self.pool)
+        .await
+        .context("关闭举报失败")?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,129 @@
+use crate::dtos::post_template::{CreatePostTemplatePayload, UpdatePostTemplatePayload};
+use crate::models::PostTemplate;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+// 文章模板的数据库操作
+#[async_trait]
+pub trait PostTemplateRepository: Send + Sync {
+    // 列出用户可用的模板：自己的模板和全局模板
+    async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<PostTemplate>>;
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<PostTemplate>>;
+    // 创建模板，owner_id 为空时创建全局模板
+    async fn create(
+        &self,
+        owner_id: Option<Uuid>,
+        payload: &CreatePostTemplatePayload,
+    ) -> Result<PostTemplate>;
+    async fn update(&self, id: Uuid, payload: &UpdatePostTemplatePayload) -> Result<PostTemplate>;
+    async fn delete(&self, id: Uuid) -> Result<bool>;
+}
+
+#[derive(Clone)]
+pub struct PostgresPostTemplateRepository {
+    pool: PgPool,
+}
+
+impl PostgresPostTemplateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PostTemplateRepository for PostgresPostTemplateRepository {
+    async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<PostTemplate>> {
+        sqlx::query_as!(
+            PostTemplate,
+            r#"
+            SELECT id, owner_id, name, title_pattern, content, category_ids, tag_ids,
+                   created_at, updated_at
+            FROM post_templates
+            WHERE owner_id = $1 OR owner_id IS NULL
+            ORDER BY owner_id IS NULL, name
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取文章模板列表失败")
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<PostTemplate>> {
+        sqlx::query_as!(
+            PostTemplate,
+            r#"
+            SELECT id, owner_id, name, title_pattern, content, category_ids, tag_ids,
+                   created_at, updated_at
+            FROM post_templates
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("获取文章模板失败")
+    }
+
+    async fn create(
+        &self,
+        owner_id: Option<Uuid>,
+        payload: &CreatePostTemplatePayload,
+    ) -> Result<PostTemplate> {
+        sqlx::query_as!(
+            PostTemplate,
+            r#"
+            INSERT INTO post_templates (owner_id, name, title_pattern, content, category_ids, tag_ids)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, owner_id, name, title_pattern, content, category_ids, tag_ids,
+                      created_at, updated_at
+            "#,
+            owner_id,
+            payload.name.trim(),
+            payload.title_pattern,
+            payload.content,
+            &payload.category_ids,
+            &payload.tag_ids
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("创建文章模板失败")
+    }
+
+    async fn update(&self, id: Uuid, payload: &UpdatePostTemplatePayload) -> Result<PostTemplate> {
+        sqlx::query_as!(
+            PostTemplate,
+            r#"
+            UPDATE post_templates
+            SET name = COALESCE($2, name),
+                title_pattern = COALESCE($3, title_pattern),
+                content = COALESCE($4, content),
+                category_ids = COALESCE($5, category_ids),
+                tag_ids = COALESCE($6, tag_ids),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, owner_id, name, title_pattern, content, category_ids, tag_ids,
+                      created_at, updated_at
+            "#,
+            id,
+            payload.name.as_deref().map(str::trim),
+            payload.title_pattern,
+            payload.content,
+            payload.category_ids.as_deref(),
+            payload.tag_ids.as_deref()
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("更新文章模板失败")
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query!("DELETE FROM post_templates WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .context("删除文章模板失败")?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,37 @@
+use crate::utils::similarity::{SimilarityCluster, cluster_similar_names, normalize_name};
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// 找出相似的名称并聚类，标签和分类仓库共用
+///
+/// 三元组相似度由 pg_trgm 在规范化后的名称上计算，`%` 运算符先按
+/// `pg_trgm.similarity_threshold`（默认 0.3）过滤掉明显不相似的组合
+pub async fn find_similar_name_clusters(
+    pool: &PgPool,
+    names: &[(Uuid, String)],
+) -> Result<Vec<SimilarityCluster>> {
+    let ids: Vec<Uuid> = names.iter().map(|(id, _)| *id).collect();
+    let keys: Vec<String> = names.iter().map(|(_, name)| normalize_name(name)).collect();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT a.id AS "a_id!", b.id AS "b_id!", similarity(a.key, b.key) AS "score!"
+        FROM unnest($1::uuid[], $2::text[]) AS a(id, key)
+        JOIN unnest($1::uuid[], $2::text[]) AS b(id, key)
+            ON a.id < b.id AND a.key <> '' AND b.key <> '' AND a.key % b.key
+        "#,
+        &ids,
+        &keys
+    )
+    .fetch_all(pool)
+    .await
+    .context("计算名称的三元组相似度失败")?;
+
+    let trigram_pairs: Vec<(Uuid, Uuid, f64)> = rows
+        .into_iter()
+        .map(|row| (row.a_id, row.b_id, f64::from(row.score)))
+        .collect();
+
+    Ok(cluster_similar_names(names, &trigram_pairs))
+}
@@ -1,7 +1,11 @@
-use crate::models::Tag;
+use crate::models::{Tag, TagAlias, TrashedTag};
+use crate::repositories::similarity::find_similar_name_clusters;
+use crate::utils::VersionError;
 use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// 标签使用统计
@@ -15,7 +19,8 @@ pub struct TagUsageStats {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SimilarTagGroup {
     pub tags: Vec<Tag>,
-    pub similarity_reason: String, // 例如："名称相似"、"同义词"等
+    pub similarity_reason: String, // 例如："名称拼写相近"
+    pub confidence: f64,           // 0 到 1，越高越可能是重复标签
 }
 
 // 定义 TagRepository trait,用于Tag的数据库操作
@@ -54,11 +59,13 @@ pub trait TagRepository: Send + Sync {
     /// * `id` - 需要更新的标签的 UUID
     /// * `name_opt` - 可选的新标签名称。
     /// * `new_slug_opt` - 可选的新标签 slug。
+    /// * `expected_version` - 客户端读取时的 updated_at，为 None 时不校验版本。
     async fn update(
         &self,
         id: Uuid,
         name_opt: Option<&str>,
         new_slug_opt: Option<&str>,
+        expected_version: Option<DateTime<Utc>>,
     ) -> Result<Tag>;
 
     /// 根据 UUID 删除标签
@@ -92,11 +99,14 @@ pub trait TagRepository: Send + Sync {
     async fn batch_delete(&self, tag_ids: &[Uuid]) -> Result<usize>;
 
     /// 增强版批量删除：支持孤儿文章处理
+    ///
+    /// `suggested_tags` 是为失去全部标签的文章推荐的新标签（文章 ID -> 标签 ID）
     async fn batch_delete_enhanced(
         &self,
         tag_ids: &[Uuid],
         handle_orphaned: bool,
-    ) -> Result<(usize, usize, usize)>;
+        suggested_tags: &HashMap<Uuid, Vec<Uuid>>,
+    ) -> Result<(usize, usize, usize, usize)>;
 
     /// 获取标签使用统计
     async fn get_usage_stats(&self) -> Result<Vec<TagUsageStats>>;
@@ -107,6 +117,37 @@ pub trait TagRepository: Send + Sync {
     /// 获取单个标签的文章关联信息
     async fn get_tag_post_info(&self, tag_id: Uuid) -> Result<(usize, Vec<String>)>;
 
+    // === 回收站 ===
+
+    /// 获取回收站中的标签
+    async fn list_deleted(&self) -> Result<Vec<TrashedTag>>;
+
+    /// 从回收站恢复标签
+    async fn restore(&self, id: Uuid) -> Result<()>;
+
+    /// 彻底删除在回收站中超过保留天数的标签，返回删除的数量
+    async fn purge_deleted(&self, retention_days: i32) -> Result<u64>;
+
+    // === 别名 ===
+
+    /// 通过别名的 slug 查找规范标签（不包括回收站中的标签）
+    async fn get_by_alias(&self, slug: &str) -> Result<Option<Tag>>;
+
+    /// 按 slug 查找别名，不论它指向的标签是否在回收站中
+    async fn find_alias(&self, slug: &str) -> Result<Option<TagAlias>>;
+
+    /// 获取标签的全部别名
+    async fn list_aliases(&self, tag_id: Uuid) -> Result<Vec<TagAlias>>;
+
+    /// 获取所有标签的别名
+    async fn list_all_aliases(&self) -> Result<Vec<TagAlias>>;
+
+    /// 为标签添加别名
+    async fn create_alias(&self, tag_id: Uuid, name: &str, slug: &str) -> Result<TagAlias>;
+
+    /// 删除标签的某个别名
+    async fn delete_alias(&self, tag_id: Uuid, alias_id: Uuid) -> Result<()>;
+
     // 以后可能添加的方法
     // async fn name_exists(&self, name: &str) -> Result<bool>; // 检查名称是否存在
     // async fn slug_exists(&self, slug: &str) -> Result<bool>; // 检查slug是否存在
@@ -156,7 +197,7 @@ impl TagRepository for PostgresTagRepository {
             r#"
             select id,name,slug,created_at,updated_at
             from tags
-            where id = $1
+            where id = $1 and deleted_at is null
             "#,
             id
         )
@@ -173,7 +214,7 @@ impl TagRepository for PostgresTagRepository {
             r#"
             select id,name,slug,created_at,updated_at
             from tags
-            where slug = $1
+            where slug = $1 and deleted_at is null
             "#,
             slug
         )
@@ -190,6 +231,7 @@ impl TagRepository for PostgresTagRepository {
             r#"
             select id,name,slug,created_at,updated_at
             from tags
+            where deleted_at is null
             order by name ASC  -- 按名称升序排序
             "#
         )
@@ -205,6 +247,7 @@ impl TagRepository for PostgresTagRepository {
         id: Uuid,
         name_opt: Option<&str>,     // 可选的新名称
         new_slug_opt: Option<&str>, // 可选的新 slug
+        expected_version: Option<DateTime<Utc>>,
     ) -> Result<Tag> {
         // 1. 先获取当前标签数据，以便只更新提供的字段
         let mut current_tag = self
@@ -220,31 +263,43 @@ impl TagRepository for PostgresTagRepository {
         if let Some(new_slug) = new_slug_opt {
             current_tag.slug = new_slug.to_string();
         }
-        // 3. 执行SQL
+        // 3. 执行SQL，版本不一致时不更新任何行
         let updated_tag = sqlx::query_as!(
             Tag,
             r#"
             update tags
             set name = $1,slug = $2 -- updated_at 会自动更新
-            where id = $3
+            where id = $3 and deleted_at is null and ($4::timestamptz is null or updated_at = $4)
             returning id,name,slug,created_at,updated_at
             "#,
             current_tag.name,
             current_tag.slug,
-            id
+            id,
+            expected_version
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
         .context(format!("数据库层面更新标签 (ID: {}) 失败", id))?;
 
-        Ok(updated_tag)
+        match updated_tag {
+            Some(tag) => Ok(tag),
+            None => {
+                // 未更新任何行：标签在此期间已被其他请求修改（或删除）
+                let latest = self
+                    .get_by_id(id)
+                    .await?
+                    .ok_or_else(|| anyhow!("更新目标标签 (ID: {}) 未找到", id))?;
+                Err(VersionError::stale(latest.updated_at).into())
+            }
+        }
     }
 
     async fn delete(&self, id: Uuid) -> Result<()> {
+        // 软删除：标签移入回收站，文章关联保留以便恢复
         let result = sqlx::query!(
             r#"
-            delete from tags
-            where id = $1
+            update tags set deleted_at = NOW()
+            where id = $1 and deleted_at is null
             "#,
             id
         )
@@ -293,7 +348,10 @@ impl TagRepository for PostgresTagRepository {
                 .context(format!("删除标签 {} 的关联失败", source_id))?;
         }
 
-        // 2. 删除源标签
+        // 2. 源标签的名称成为目标标签的别名
+        record_merge_aliases(&mut txn, target_tag_id, source_tag_ids).await?;
+
+        // 3. 删除源标签
         for &source_id in source_tag_ids {
             sqlx::query!("DELETE FROM tags WHERE id = $1", source_id)
                 .execute(&mut *txn)
@@ -394,9 +452,24 @@ impl TagRepository for PostgresTagRepository {
             }
         }
 
-        // 3. 更新目标标签名称（如果提供）
+        // 3. 源标签的名称成为目标标签的别名
+        record_merge_aliases(&mut txn, target_tag_id, source_tag_ids).await?;
+
+        // 4. 更新目标标签名称（如果提供），旧名称同样保留为别名
         if let Some(new_name) = new_target_name {
             let new_slug = slug::slugify(new_name);
+            sqlx::query!(
+                r#"
+                INSERT INTO tag_aliases (tag_id, name, slug)
+                SELECT id, name, slug FROM tags WHERE id = $1 AND slug <> $2
+                ON CONFLICT (slug) DO UPDATE SET tag_id = EXCLUDED.tag_id
+                "#,
+                target_tag_id,
+                new_slug
+            )
+            .execute(&mut *txn)
+            .await
+            .context("保存目标标签旧名称为别名失败")?;
             sqlx::query!(
                 "UPDATE tags SET name = $1, slug = $2 WHERE id = $3",
                 new_name,
@@ -407,13 +480,25 @@ impl TagRepository for PostgresTagRepository {
             .await?;
         }
 
-        // 4. 删除源标签
+        // 5. 删除源标签
         for &source_id in source_tag_ids {
             sqlx::query!("DELETE FROM tags WHERE id = $1", source_id)
                 .execute(&mut *txn)
                 .await?;
         }
 
+        // 6. 与目标标签当前 slug 相同的别名已经没有意义
+        sqlx::query!(
+            r#"
+            DELETE FROM tag_aliases
+            WHERE tag_id = $1 AND slug = (SELECT slug FROM tags WHERE id = $1)
+            "#,
+            target_tag_id
+        )
+        .execute(&mut *txn)
+        .await
+        .context("清理目标标签的多余别名失败")?;
+
         txn.commit().await.context("提交增强标签合并事务失败")?;
 
         // 返回 (受影响的文章数, 合并的标签数, 移除的重复关联数)
@@ -479,10 +564,14 @@ impl TagRepository for PostgresTagRepository {
 
         let mut deleted_count = 0;
         for &tag_id in tag_ids {
-            let result = sqlx::query!("DELETE FROM tags WHERE id = $1", tag_id)
-                .execute(&self.pool)
-                .await
-                .context(format!("批量删除：删除标签 {} 失败", tag_id))?;
+            // 软删除：标签移入回收站
+            let result = sqlx::query!(
+                "UPDATE tags SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL",
+                tag_id
+            )
+            .execute(&self.pool)
+            .await
+            .context(format!("批量删除：删除标签 {} 失败", tag_id))?;
 
             deleted_count += result.rows_affected() as usize;
         }
@@ -494,9 +583,10 @@ impl TagRepository for PostgresTagRepository {
         &self,
         tag_ids: &[Uuid],
         handle_orphaned: bool,
-    ) -> Result<(usize, usize, usize)> {
+        suggested_tags: &HashMap<Uuid, Vec<Uuid>>,
+    ) -> Result<(usize, usize, usize, usize)> {
         if tag_ids.is_empty() {
-            return Ok((0, 0, 0));
+            return Ok((0, 0, 0, 0));
         }
 
         let mut txn = self
@@ -526,7 +616,12 @@ impl TagRepository for PostgresTagRepository {
             let orphaned_count = sqlx::query!(
                 r#"
                 SELECT COUNT(*) as count FROM posts p
-                WHERE NOT EXISTS (SELECT 1 FROM post_tags pt WHERE pt.post_id = p.id)
+                WHERE p.deleted_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1 FROM post_tags pt
+                    JOIN tags t ON t.id = pt.tag_id
+                    WHERE pt.post_id = p.id AND t.deleted_at IS NULL
+                )
                 "#
             )
             .fetch_one(&mut *txn)
@@ -536,19 +631,49 @@ impl TagRepository for PostgresTagRepository {
             0
         };
 
-        // 4. 删除标签
+        // 4. 为孤儿文章添加推荐的标签
+        let mut auto_tagged_posts = 0;
+        if handle_orphaned {
+            for (post_id, suggested_tag_ids) in suggested_tags {
+                let result = sqlx::query!(
+                    r#"
+                    INSERT INTO post_tags (post_id, tag_id)
+                    SELECT $1, UNNEST($2::uuid[])
+                    ON CONFLICT DO NOTHING
+                    "#,
+                    post_id,
+                    suggested_tag_ids
+                )
+                .execute(&mut *txn)
+                .await
+                .context(format!("为孤儿文章 {} 添加推荐标签失败", post_id))?;
+                if result.rows_affected() > 0 {
+                    auto_tagged_posts += 1;
+                }
+            }
+        }
+
+        // 5. 删除标签（软删除，移入回收站）
         let mut deleted_count = 0;
         for &tag_id in tag_ids {
-            let result = sqlx::query!("DELETE FROM tags WHERE id = $1", tag_id)
-                .execute(&mut *txn)
-                .await?;
+            let result = sqlx::query!(
+                "UPDATE tags SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL",
+                tag_id
+            )
+            .execute(&mut *txn)
+            .await?;
             deleted_count += result.rows_affected() as usize;
         }
 
         txn.commit().await.context("提交增强批量删除事务失败")?;
 
-        // 返回 (删除的标签数, 受影响的文章数, 孤儿文章数)
-        Ok((deleted_count, affected_posts as usize, orphaned_posts))
+        // 返回 (删除的标签数, 受影响的文章数, 孤儿文章数, 添加了推荐标签的文章数)
+        Ok((
+            deleted_count,
+            affected_posts as usize,
+            orphaned_posts,
+            auto_tagged_posts,
+        ))
     }
 
     async fn get_usage_stats(&self) -> Result<Vec<TagUsageStats>> {
@@ -559,6 +684,8 @@ impl TagRepository for PostgresTagRepository {
                 COALESCE(COUNT(pt.post_id), 0) as post_count
             FROM tags t
             LEFT JOIN post_tags pt ON t.id = pt.tag_id
+                AND EXISTS (SELECT 1 FROM posts p WHERE p.id = pt.post_id AND p.deleted_at IS NULL)
+            WHERE t.deleted_at IS NULL
             GROUP BY t.id, t.name, t.slug, t.created_at, t.updated_at
             ORDER BY post_count DESC, t.name ASC
             "#
@@ -586,25 +713,23 @@ impl TagRepository for PostgresTagRepository {
     }
 
     async fn find_similar_tags(&self) -> Result<Vec<SimilarTagGroup>> {
-        // 这是一个简化的实现，查找名称相似的标签
         let tags = self.list().await?;
-        let mut similar_groups = Vec::new();
-
-        // 查找名称长度相似且编辑距离小的标签
-        for i in 0..tags.len() {
-            for j in (i + 1)..tags.len() {
-                let tag1 = &tags[i];
-                let tag2 = &tags[j];
-
-                // 简单的相似性检查：名称长度相近且包含相似字符
-                if is_similar(&tag1.name, &tag2.name) {
-                    similar_groups.push(SimilarTagGroup {
-                        tags: vec![tag1.clone(), tag2.clone()],
-                        similarity_reason: "名称相似".to_string(),
-                    });
-                }
-            }
-        }
+        let names: Vec<(Uuid, String)> = tags.iter().map(|t| (t.id, t.name.clone())).collect();
+        let clusters = find_similar_name_clusters(&self.pool, &names).await?;
+
+        let tags_by_id: HashMap<Uuid, Tag> = tags.into_iter().map(|t| (t.id, t)).collect();
+        let similar_groups = clusters
+            .into_iter()
+            .map(|cluster| SimilarTagGroup {
+                tags: cluster
+                    .ids
+                    .iter()
+                    .filter_map(|id| tags_by_id.get(id).cloned())
+                    .collect(),
+                similarity_reason: cluster.reason,
+                confidence: cluster.confidence,
+            })
+            .collect();
 
         Ok(similar_groups)
     }
@@ -612,7 +737,11 @@ impl TagRepository for PostgresTagRepository {
     async fn get_tag_post_info(&self, tag_id: Uuid) -> Result<(usize, Vec<String>)> {
         // 获取文章数量
         let post_count = sqlx::query!(
-            "SELECT COUNT(*) as count FROM post_tags WHERE tag_id = $1",
+            r#"
+            SELECT COUNT(*) as count FROM post_tags pt
+            JOIN posts p ON p.id = pt.post_id
+            WHERE pt.tag_id = $1 AND p.deleted_at IS NULL
+            "#,
             tag_id
         )
         .fetch_one(&self.pool)
@@ -624,7 +753,7 @@ impl TagRepository for PostgresTagRepository {
             SELECT p.title 
             FROM posts p 
             JOIN post_tags pt ON p.id = pt.post_id 
-            WHERE pt.tag_id = $1 
+            WHERE pt.tag_id = $1 AND p.deleted_at IS NULL
             ORDER BY p.created_at DESC 
             LIMIT 5
             "#,
@@ -637,28 +766,203 @@ impl TagRepository for PostgresTagRepository {
 
         Ok((post_count.count.unwrap_or(0) as usize, titles))
     }
-}
 
-// 辅助函数：简单的相似性检查
-fn is_similar(name1: &str, name2: &str) -> bool {
-    let name1_lower = name1.to_lowercase();
-    let name2_lower = name2.to_lowercase();
+    // === 回收站功能实现 ===
+
+    async fn list_deleted(&self) -> Result<Vec<TrashedTag>> {
+        let items = sqlx::query_as!(
+            TrashedTag,
+            r#"
+            SELECT id, name, slug, deleted_at as "deleted_at!"
+            FROM tags
+            WHERE deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取回收站中的标签失败")?;
 
-    // 检查是否一个包含另一个，或者长度相近且有共同子串
-    if name1_lower.contains(&name2_lower) || name2_lower.contains(&name1_lower) {
-        return true;
+        Ok(items)
     }
 
-    // 检查是否有共同的长子串（简化版本）
-    let min_len = name1_lower.len().min(name2_lower.len());
-    if min_len >= 3 {
-        for i in 0..=(name1_lower.len() - 3) {
-            let substr = &name1_lower[i..i + 3];
-            if name2_lower.contains(substr) {
-                return true;
-            }
+    async fn restore(&self, id: Uuid) -> Result<()> {
+        // 名称和 slug 只在未删除的标签之间唯一，删除后可能已被新建的标签占用
+        let conflict = sqlx::query_scalar!(
+            r#"
+            SELECT live.name
+            FROM tags trashed
+            JOIN tags live ON live.name = trashed.name OR live.slug = trashed.slug
+            WHERE trashed.id = $1 AND trashed.deleted_at IS NOT NULL
+              AND live.deleted_at IS NULL AND live.id <> trashed.id
+            LIMIT 1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("检查标签 (ID: {}) 的名称冲突失败", id))?;
+        if let Some(name) = conflict {
+            return Err(anyhow!(
+                "标签 '{}' 已存在，请先重命名或合并它再从回收站恢复",
+                name
+            ));
         }
+
+        let result = sqlx::query!(
+            "UPDATE tags SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .context(format!("恢复标签 (ID: {}) 失败", id))?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow!("回收站中未找到标签 (ID: {})", id));
+        }
+
+        Ok(())
+    }
+
+    async fn purge_deleted(&self, retention_days: i32) -> Result<u64> {
+        // 文章关联通过 ON DELETE CASCADE 自动删除
+        let result = sqlx::query!(
+            "DELETE FROM tags WHERE deleted_at <= NOW() - make_interval(days => $1)",
+            retention_days
+        )
+        .execute(&self.pool)
+        .await
+        .context("清理回收站中的过期标签失败")?;
+
+        Ok(result.rows_affected())
+    }
+
+    // === 别名 ===
+
+    async fn get_by_alias(&self, slug: &str) -> Result<Option<Tag>> {
+        let tag = sqlx::query_as!(
+            Tag,
+            r#"
+            select t.id, t.name, t.slug, t.created_at, t.updated_at
+            from tag_aliases a
+            join tags t on t.id = a.tag_id
+            where a.slug = $1 and t.deleted_at is null
+            "#,
+            slug
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("通过别名 ('{}') 查询标签失败", slug))?;
+
+        Ok(tag)
     }
 
-    false
+    async fn find_alias(&self, slug: &str) -> Result<Option<TagAlias>> {
+        let alias = sqlx::query_as!(
+            TagAlias,
+            "select id, tag_id, name, slug, created_at from tag_aliases where slug = $1",
+            slug
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("查询别名 '{}' 失败", slug))?;
+
+        Ok(alias)
+    }
+
+    async fn list_aliases(&self, tag_id: Uuid) -> Result<Vec<TagAlias>> {
+        let aliases = sqlx::query_as!(
+            TagAlias,
+            r#"
+            select id, tag_id, name, slug, created_at
+            from tag_aliases
+            where tag_id = $1
+            order by name ASC
+            "#,
+            tag_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context(format!("查询标签 {} 的别名失败", tag_id))?;
+
+        Ok(aliases)
+    }
+
+    async fn list_all_aliases(&self) -> Result<Vec<TagAlias>> {
+        let aliases = sqlx::query_as!(
+            TagAlias,
+            "select id, tag_id, name, slug, created_at from tag_aliases order by name ASC"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("查询标签别名失败")?;
+
+        Ok(aliases)
+    }
+
+    async fn create_alias(&self, tag_id: Uuid, name: &str, slug: &str) -> Result<TagAlias> {
+        let alias = sqlx::query_as!(
+            TagAlias,
+            r#"
+            insert into tag_aliases (tag_id, name, slug)
+            values ($1, $2, $3)
+            returning id, tag_id, name, slug, created_at
+            "#,
+            tag_id,
+            name,
+            slug
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context(format!("为标签 {} 添加别名 '{}' 失败", tag_id, name))?;
+
+        Ok(alias)
+    }
+
+    async fn delete_alias(&self, tag_id: Uuid, alias_id: Uuid) -> Result<()> {
+        let result = sqlx::query!(
+            "delete from tag_aliases where id = $1 and tag_id = $2",
+            alias_id,
+            tag_id
+        )
+        .execute(&self.pool)
+        .await
+        .context(format!("删除别名 {} 失败", alias_id))?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow!("未找到标签 {} 的别名 {}", tag_id, alias_id));
+        }
+        Ok(())
+    }
+}
+
+// 合并时把源标签的名称记为目标标签的别名，并把源标签原有的别名转移到目标标签
+async fn record_merge_aliases(
+    txn: &mut Transaction<'_, Postgres>,
+    target_tag_id: Uuid,
+    source_tag_ids: &[Uuid],
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE tag_aliases SET tag_id = $1 WHERE tag_id = ANY($2)",
+        target_tag_id,
+        source_tag_ids
+    )
+    .execute(&mut **txn)
+    .await
+    .context("转移源标签的别名失败")?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO tag_aliases (tag_id, name, slug)
+        SELECT $1, name, slug FROM tags WHERE id = ANY($2)
+        ON CONFLICT (slug) DO UPDATE SET tag_id = EXCLUDED.tag_id, name = EXCLUDED.name
+        "#,
+        target_tag_id,
+        source_tag_ids
+    )
+    .execute(&mut **txn)
+    .await
+    .context("保存源标签名称为别名失败")?;
+
+    Ok(())
 }
@@ -1,10 +1,11 @@
 use crate::dtos::admin::UserRegistrationPayload;
 use crate::dtos::user::UpdateProfilePayload;
-use crate::models::{Permission, Role, User, UserPublic};
+use crate::models::{Permission, Role, SocialLink, User, UserAvatar, UserProfile, UserPublic};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 // 定义 UserRepository trait，抽象用户数据的数据库操作
@@ -68,6 +69,24 @@ pub trait UserRepository: Send + Sync {
     // 删除用户
     async fn delete(&self, user_id: Uuid) -> Result<()>;
 
+    // 获取用户资料，没有填写过资料的用户返回空资料
+    async fn find_profile(&self, user_id: Uuid) -> Result<UserProfile>;
+    // 批量获取用户资料，没有填写过资料的用户不在结果中
+    async fn find_profiles(&self, user_ids: &[Uuid]) -> Result<HashMap<Uuid, UserProfile>>;
+    // 保存用户资料，社交链接整体替换
+    async fn save_profile(&self, user_id: Uuid, profile: &UserProfile) -> Result<()>;
+    // 保存上传的头像，返回保存时间
+    async fn save_avatar(
+        &self,
+        user_id: Uuid,
+        content_type: &str,
+        data: &[u8],
+    ) -> Result<DateTime<Utc>>;
+    // 获取上传的头像
+    async fn find_avatar(&self, user_id: Uuid) -> Result<Option<UserAvatar>>;
+    // 删除上传的头像
+    async fn delete_avatar(&self, user_id: Uuid) -> Result<()>;
+
     // 更新密码
     async fn update_password(&self, user_id: Uuid, new_hashed_password: &str) -> Result<()>;
     // 为用户删除所有的 refresh token
@@ -485,6 +504,168 @@ impl UserRepository for PostgresUserRepository {
         Ok(())
     }
 
+    async fn find_profile(&self, user_id: Uuid) -> Result<UserProfile> {
+        let mut profiles = self.find_profiles(&[user_id]).await?;
+        Ok(profiles.remove(&user_id).unwrap_or_default())
+    }
+
+    async fn find_profiles(&self, user_ids: &[Uuid]) -> Result<HashMap<Uuid, UserProfile>> {
+        if user_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_id, display_name, bio, bio_html, avatar_url, website
+            FROM user_profiles
+            WHERE user_id = ANY($1)
+            "#,
+            user_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取用户资料失败")?;
+
+        let mut profiles: HashMap<Uuid, UserProfile> = rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.user_id,
+                    UserProfile {
+                        display_name: row.display_name,
+                        bio: row.bio,
+                        bio_html: row.bio_html,
+                        avatar_url: row.avatar_url,
+                        website: row.website,
+                        social_links: Vec::new(),
+                    },
+                )
+            })
+            .collect();
+
+        let links = sqlx::query!(
+            r#"
+            SELECT user_id, platform, url
+            FROM user_social_links
+            WHERE user_id = ANY($1)
+            ORDER BY user_id, position, platform
+            "#,
+            user_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("获取用户社交链接失败")?;
+
+        for link in links {
+            profiles
+                .entry(link.user_id)
+                .or_default()
+                .social_links
+                .push(SocialLink {
+                    platform: link.platform,
+                    url: link.url,
+                });
+        }
+
+        Ok(profiles)
+    }
+
+    async fn save_profile(&self, user_id: Uuid, profile: &UserProfile) -> Result<()> {
+        let mut txn = self.pool.begin().await.context("开启事务失败")?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO user_profiles (user_id, display_name, bio, bio_html, avatar_url, website)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id) DO UPDATE
+                SET display_name = EXCLUDED.display_name,
+                    bio = EXCLUDED.bio,
+                    bio_html = EXCLUDED.bio_html,
+                    avatar_url = EXCLUDED.avatar_url,
+                    website = EXCLUDED.website,
+                    updated_at = NOW()
+            "#,
+            user_id,
+            profile.display_name,
+            profile.bio,
+            profile.bio_html,
+            profile.avatar_url,
+            profile.website
+        )
+        .execute(&mut *txn)
+        .await
+        .context(format!("保存用户 {} 的资料失败", user_id))?;
+
+        sqlx::query!("DELETE FROM user_social_links WHERE user_id = $1", user_id)
+            .execute(&mut *txn)
+            .await
+            .context(format!("清除用户 {} 的社交链接失败", user_id))?;
+
+        for (position, link) in profile.social_links.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                INSERT INTO user_social_links (user_id, platform, url, position)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                user_id,
+                link.platform,
+                link.url,
+                position as i32
+            )
+            .execute(&mut *txn)
+            .await
+            .context(format!("保存用户 {} 的社交链接失败", user_id))?;
+        }
+
+        txn.commit().await.context("提交事务失败")?;
+        Ok(())
+    }
+
+    async fn save_avatar(
+        &self,
+        user_id: Uuid,
+        content_type: &str,
+        data: &[u8],
+    ) -> Result<DateTime<Utc>> {
+        let updated_at = sqlx::query_scalar!(
+            r#"
+            INSERT INTO user_avatars (user_id, content_type, data)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id) DO UPDATE
+                SET content_type = EXCLUDED.content_type,
+                    data = EXCLUDED.data,
+                    updated_at = NOW()
+            RETURNING updated_at
+            "#,
+            user_id,
+            content_type,
+            data
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context(format!("保存用户 {} 的头像失败", user_id))?;
+        Ok(updated_at)
+    }
+
+    async fn find_avatar(&self, user_id: Uuid) -> Result<Option<UserAvatar>> {
+        sqlx::query_as!(
+            UserAvatar,
+            "SELECT content_type, data, updated_at FROM user_avatars WHERE user_id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context(format!("获取用户 {} 的头像失败", user_id))
+    }
+
+    async fn delete_avatar(&self, user_id: Uuid) -> Result<()> {
+        sqlx::query!("DELETE FROM user_avatars WHERE user_id = $1", user_id)
+            .execute(&self.pool)
+            .await
+            .context(format!("删除用户 {} 的头像失败", user_id))?;
+        Ok(())
+    }
+
     async fn update_password(&self, user_id: Uuid, new_hashed_password: &str) -> Result<()> {
         sqlx::query!(
             "UPDATE users SET hashed_password = $1, updated_at = NOW() WHERE id = $2",
@@ -597,7 +778,7 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn get_total_post_count(&self) -> Result<i64> {
-        let result = sqlx::query!("SELECT COUNT(*) as count FROM posts")
+        let result = sqlx::query!("SELECT COUNT(*) as count FROM posts WHERE deleted_at IS NULL")
             .fetch_one(&self.pool)
             .await
             .context("获取文章总数失败")?;
@@ -607,7 +788,7 @@ impl UserRepository for PostgresUserRepository {
 
     async fn get_published_post_count(&self) -> Result<i64> {
         let result =
-            sqlx::query!("SELECT COUNT(*) as count FROM posts WHERE published_at IS NOT NULL")
+            sqlx::query!("SELECT COUNT(*) as count FROM posts WHERE published_at IS NOT NULL AND deleted_at IS NULL")
                 .fetch_one(&self.pool)
                 .await
                 .context("获取已发布文章数失败")?;
@@ -616,25 +797,28 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn get_draft_post_count(&self) -> Result<i64> {
-        let result = sqlx::query!("SELECT COUNT(*) as count FROM posts WHERE published_at IS NULL")
-            .fetch_one(&self.pool)
-            .await
-            .context("获取草稿文章数失败")?;
+        let result = sqlx::query!(
+            "SELECT COUNT(*) as count FROM posts WHERE published_at IS NULL AND deleted_at IS NULL"
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("获取草稿文章数失败")?;
 
         Ok(result.count.unwrap_or(0))
     }
 
     async fn get_category_count(&self) -> Result<i64> {
-        let result = sqlx::query!("SELECT COUNT(*) as count FROM categories")
-            .fetch_one(&self.pool)
-            .await
-            .context("获取分类数失败")?;
+        let result =
+            sqlx::query!("SELECT COUNT(*) as count FROM categories WHERE deleted_at IS NULL")
+                .fetch_one(&self.pool)
+                .await
+                .context("获取分类数失败")?;
 
         Ok(result.count.unwrap_or(0))
     }
 
     async fn get_tag_count(&self) -> Result<i64> {
-        let result = sqlx::query!("SELECT COUNT(*) as count FROM tags")
+        let result = sqlx::query!("SELECT COUNT(*) as count FROM tags WHERE deleted_at IS NULL")
             .fetch_one(&self.pool)
             .await
             .context("获取标签数失败")?;
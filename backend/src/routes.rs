@@ -12,20 +12,45 @@ use crate::handlers::auth::{
 };
 use crate::handlers::category::{
     create_category_handler, delete_category_handler, get_category_handler,
-    list_categories_handler, update_category_handler,
+    get_category_tree_handler, list_categories_handler, update_category_handler,
+};
+use crate::handlers::draft_comment::{
+    create_draft_comment_handler, list_draft_comments_handler, resolve_draft_comment_handler,
+    unresolve_draft_comment_handler,
 };
 use crate::handlers::post::{
-    ban_post_handler, create_post_handler, delete_post_handler, get_post_handler,
-    get_published_post_handler, list_posts_handler, list_published_posts_handler,
-    publish_post_handler, share_draft_handler, unban_post_handler, unpublish_post_handler,
+    appeal_ban_handler, assign_reviewer_handler, autosave_post_handler, ban_post_handler,
+    bulk_posts_handler, clone_post_handler, create_post_handler, create_preview_link_handler,
+    delete_post_handler, discard_autosave_handler, get_author_profile_handler,
+    get_autosave_handler, get_post_handler, get_post_review_handler, get_preview_post_handler,
+    get_published_post_handler, list_author_posts_handler, list_ban_appeals_handler,
+    list_category_posts_handler, list_pending_reviews_handler, list_posts_handler,
+    list_preview_links_handler, list_published_posts_handler, list_shared_with_me_handler,
+    publish_post_handler, resolve_ban_appeal_handler, review_post_handler,
+    revoke_preview_link_handler, set_post_authors_handler, share_draft_handler,
+    submit_review_handler, suggest_taxonomy_handler, unban_post_handler, unpublish_post_handler,
     update_post_handler,
 };
+use crate::handlers::post_template::{
+    create_post_template_handler, delete_post_template_handler, get_post_template_handler,
+    list_post_templates_handler, update_post_template_handler,
+};
+use crate::handlers::report::{
+    ban_reported_post_handler, dismiss_post_reports_handler, list_post_reports_handler,
+    list_reported_posts_handler, report_post_handler,
+};
 use crate::handlers::tag::{
-    create_tag_handler, delete_tag_handler, get_tag_handler, list_tags_handler, update_tag_handler,
+    create_tag_alias_handler, create_tag_handler, delete_tag_alias_handler, delete_tag_handler,
+    get_tag_handler, list_tag_aliases_handler, list_tags_handler, update_tag_handler,
+};
+use crate::handlers::trash::{
+    list_trash_handler, restore_category_handler, restore_post_handler, restore_tag_handler,
 };
 use crate::handlers::user::{
-    change_my_password_handler, delete_my_account_handler, get_my_profile_handler,
-    get_my_stats_handler, update_my_profile_handler,
+    change_my_password_handler, delete_my_account_handler, delete_my_avatar_handler,
+    get_my_notification_preferences_handler, get_my_profile_handler, get_my_stats_handler,
+    get_user_avatar_handler, update_my_notification_preferences_handler,
+    update_my_profile_handler, upload_my_avatar_handler,
 };
 use crate::handlers::{
     AppState,
@@ -59,9 +84,19 @@ pub fn create_router(app_state: AppState) -> Router {
             get(get_my_profile_handler).put(update_my_profile_handler),
         )
         .route("/me", delete(delete_my_account_handler))
+        .route(
+            "/me/avatar",
+            put(upload_my_avatar_handler).delete(delete_my_avatar_handler),
+        )
         .route("/me/password", put(change_my_password_handler))
         .route("/me/permissions", get(list_user_permissions_handler))
         .route("/me/stats", get(get_my_stats_handler))
+        .route("/me/shared-drafts", get(list_shared_with_me_handler))
+        .route(
+            "/me/notification-preferences",
+            get(get_my_notification_preferences_handler)
+                .put(update_my_notification_preferences_handler),
+        )
         // --- Admin 相关的路由 ---
         .route("/admin/stats/dashboard", get(get_dashboard_stats_handler))
         .route("/admin/stats/users", get(get_user_stats_handler))
@@ -115,10 +150,18 @@ pub fn create_router(app_state: AppState) -> Router {
         // -- 博客公开接口（无需认证）--
         .route("/blog/posts", get(list_published_posts_handler))
         .route("/blog/posts/{identifier}", get(get_published_post_handler))
+        .route("/blog/authors/{username}", get(get_author_profile_handler))
+        .route("/blog/categories/{slug}/posts", get(list_category_posts_handler))
+        .route("/users/{id}/avatar", get(get_user_avatar_handler))
+        .route("/blog/authors/{username}/posts", get(list_author_posts_handler))
+        .route("/blog/preview/{token}", get(get_preview_post_handler))
+        .route("/blog/posts/{id}/report", post(report_post_handler))
         // -- Post 管理接口（需要认证）--
         // POST /posts -> 创建帖子
         // GET  /posts -> 获取帖子列表
         .route("/posts", post(create_post_handler).get(list_posts_handler))
+        // 批量操作文章
+        .route("/posts/bulk", post(bulk_posts_handler))
         // 路由: /posts/{id} (复数，使用花括号捕获 id)
         // GET    /posts/{id} -> 获取单个帖子
         // PUT    /posts/{id} -> 更新单个帖子
@@ -132,11 +175,80 @@ pub fn create_router(app_state: AppState) -> Router {
         // 文章发布和撤回路由
         .route("/posts/{id}/publish", put(publish_post_handler))
         .route("/posts/{id}/unpublish", put(unpublish_post_handler))
+        // 复制文章为新草稿
+        .route("/posts/{id}/clone", post(clone_post_handler))
+        // 文章作者（主作者和合著者）
+        .route("/posts/{id}/authors", put(set_post_authors_handler))
+        // 文章模板路由（个人模板和全局模板）
+        .route(
+            "/post-templates",
+            get(list_post_templates_handler).post(create_post_template_handler),
+        )
+        .route(
+            "/post-templates/{id}",
+            get(get_post_template_handler)
+                .put(update_post_template_handler)
+                .delete(delete_post_template_handler),
+        )
         // 草稿分享路由
         .route("/posts/{id}/share", put(share_draft_handler))
+        // 草稿预览链接路由（给没有账号的外部审阅者）
+        .route(
+            "/posts/{id}/preview-links",
+            get(list_preview_links_handler).post(create_preview_link_handler),
+        )
+        .route(
+            "/posts/{id}/preview-links/{link_id}",
+            delete(revoke_preview_link_handler),
+        )
         // 文章封禁和解封路由
         .route("/posts/{id}/ban", put(ban_post_handler))
         .route("/posts/{id}/unban", put(unban_post_handler))
+        // 封禁申诉：作者提交申诉，版主在申诉队列中维持封禁或解封
+        .route("/posts/{id}/ban/appeal", post(appeal_ban_handler))
+        .route("/ban-appeals", get(list_ban_appeals_handler))
+        .route("/ban-appeals/{id}/decision", put(resolve_ban_appeal_handler))
+        // 读者举报队列：按文章聚合，版主驳回举报或直接封禁
+        .route("/reports", get(list_reported_posts_handler))
+        .route("/reports/posts/{post_id}", get(list_post_reports_handler))
+        .route(
+            "/reports/posts/{post_id}/dismiss",
+            put(dismiss_post_reports_handler),
+        )
+        .route("/reports/posts/{post_id}/ban", put(ban_reported_post_handler))
+        // 编辑审核工作流路由
+        .route("/posts/{id}/review", get(get_post_review_handler))
+        .route("/posts/{id}/review/submit", put(submit_review_handler))
+        .route("/posts/{id}/review/assign", put(assign_reviewer_handler))
+        .route("/posts/{id}/review/decision", put(review_post_handler))
+        .route("/reviews/pending", get(list_pending_reviews_handler))
+        // 自动保存路由（每个用户对每篇文章一个槽位）
+        .route(
+            "/posts/{id}/autosave",
+            get(get_autosave_handler)
+                .put(autosave_post_handler)
+                .delete(discard_autosave_handler),
+        )
+        // 编辑器中获取标签和分类推荐
+        .route("/posts/{id}/suggest-taxonomy", post(suggest_taxonomy_handler))
+        // 回收站路由（按删除/管理权限过滤）
+        .route("/trash", get(list_trash_handler))
+        .route("/trash/posts/{id}/restore", put(restore_post_handler))
+        .route("/trash/categories/{id}/restore", put(restore_category_handler))
+        .route("/trash/tags/{id}/restore", put(restore_tag_handler))
+        // 草稿评审意见（行内评论）路由
+        .route(
+            "/posts/{id}/comments",
+            get(list_draft_comments_handler).post(create_draft_comment_handler),
+        )
+        .route(
+            "/posts/{id}/comments/{comment_id}/resolve",
+            put(resolve_draft_comment_handler),
+        )
+        .route(
+            "/posts/{id}/comments/{comment_id}/unresolve",
+            put(unresolve_draft_comment_handler),
+        )
         // --- Category 相关的路由 ---
         // GET /categories -> 获取列表,
         // POST /categories -> 创建分类
@@ -144,6 +256,8 @@ pub fn create_router(app_state: AppState) -> Router {
             "/categories",
             get(list_categories_handler).post(create_category_handler),
         )
+        // GET /categories/tree -> 获取分类树
+        .route("/categories/tree", get(get_category_tree_handler))
         // GET /categories/{id_or_slug} -> 获取单个分类详情
         // PUT /categories/{id} -> 更新,
         // DELETE /categories/{id} -> 删除
@@ -188,6 +302,16 @@ pub fn create_router(app_state: AppState) -> Router {
                 .put(update_tag_handler)
                 .delete(delete_tag_handler),
         )
+        // GET /tags/{id}/aliases -> 获取标签的别名
+        // POST /tags/{id}/aliases -> 为标签添加别名
+        .route(
+            "/tags/{identifier}/aliases",
+            get(list_tag_aliases_handler).post(create_tag_alias_handler),
+        )
+        .route(
+            "/tags/{identifier}/aliases/{alias_id}",
+            delete(delete_tag_alias_handler),
+        )
         // --- 标签管理路由（管理员专用）---
         .route("/admin/tags/merge", post(merge_tags_handler))
         .route("/admin/tags/batch-delete", post(batch_delete_tags_handler))
@@ -211,7 +335,9 @@ pub fn create_router(app_state: AppState) -> Router {
                     "content-type".parse().unwrap(),
                     "authorization".parse().unwrap(),
                     "accept".parse().unwrap(),
+                    "if-match".parse().unwrap(), // 乐观并发控制
                 ])
+                .expose_headers(["etag".parse().unwrap()])
                 .allow_credentials(true)
         )
         .with_state(app_state) // 将共享状态注入路由
@@ -0,0 +1,104 @@
+use crate::config::AutosaveConfig;
+use crate::dtos::post::AutosavePayload;
+use crate::models::{Post, PostAutosave};
+use crate::repositories::{PostAutosaveRepository, PostRepository};
+use anyhow::{Context, Result, anyhow};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// 文章自动保存服务
+// 自动保存与文章本身分开存储，只有保存它的用户能读取，显式保存文章后被删除
+#[derive(Clone)]
+pub struct AutosaveService {
+    autosave_repo: Arc<dyn PostAutosaveRepository>,
+    post_repo: Arc<dyn PostRepository>,
+    retention_days: i32,
+}
+
+impl AutosaveService {
+    pub fn new(
+        autosave_repo: Arc<dyn PostAutosaveRepository>,
+        post_repo: Arc<dyn PostRepository>,
+        config: &AutosaveConfig,
+    ) -> Self {
+        Self {
+            autosave_repo,
+            post_repo,
+            retention_days: config.retention_days,
+        }
+    }
+
+    // 写入自动保存
+    // can_edit_any: 用户是否拥有 post:edit_any 权限，否则只能自动保存自己的文章或通过 editor 分享可编辑的草稿
+    pub async fn save(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_edit_any: bool,
+        payload: AutosavePayload,
+    ) -> Result<PostAutosave> {
+        let post = self
+            .post_repo
+            .get_by_id(post_id)
+            .await
+            .context("获取文章信息失败")?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", post_id))?;
+
+        if !can_edit_any
+            && !self.post_repo.is_author(post.id, user_id).await?
+            && !self.can_edit_shared_draft(&post, user_id).await?
+        {
+            return Err(anyhow!("您只能自动保存自己的文章"));
+        }
+
+        self.autosave_repo
+            .upsert(post_id, user_id, &payload.title, &payload.content)
+            .await
+            .context(format!("自动保存文章 {} 失败", post_id))
+    }
+
+    // 用户是否通过有效的 editor 分享可以编辑这篇草稿，已发布的文章不再按分享授权
+    async fn can_edit_shared_draft(&self, post: &Post, user_id: Uuid) -> Result<bool> {
+        if post.published_at.is_some() {
+            return Ok(false);
+        }
+        let role = self
+            .post_repo
+            .get_draft_share_role(post.id, user_id)
+            .await
+            .context("获取草稿分享角色失败")?;
+        Ok(role.is_some_and(|r| r.can_edit()))
+    }
+
+    // 获取当前用户的自动保存（其他用户的自动保存永远不可见）
+    pub async fn get(&self, post_id: Uuid, user_id: Uuid) -> Result<PostAutosave> {
+        self.autosave_repo
+            .get(post_id, user_id, self.retention_days)
+            .await?
+            .ok_or_else(|| anyhow!("未找到文章 {} 的自动保存", post_id))
+    }
+
+    // 丢弃当前用户的自动保存（显式保存文章后调用）
+    pub async fn discard(&self, post_id: Uuid, user_id: Uuid) -> Result<()> {
+        if self.autosave_repo.delete(post_id, user_id).await? {
+            tracing::debug!("已删除用户 {} 对文章 {} 的自动保存", user_id, post_id);
+        }
+        Ok(())
+    }
+
+    // 清理超过保留期的自动保存，由后台任务定期调用
+    pub async fn purge_expired(&self) -> Result<u64> {
+        let purged = self
+            .autosave_repo
+            .purge_older_than(self.retention_days)
+            .await?;
+        if purged > 0 {
+            tracing::info!(
+                "已清理 {} 条超过 {} 天的自动保存",
+                purged,
+                self.retention_days
+            );
+        }
+        Ok(purged)
+    }
+}
@@ -1,8 +1,13 @@
-use crate::dtos::category::{CreateCategoryPayload, UpdateCategoryPayload};
+use crate::dtos::category::{
+    CategoryTreeNode, ChildCategoriesHandling, CreateCategoryPayload, UpdateCategoryPayload,
+};
 use crate::models::Category;
 use crate::repositories::CategoryRepository;
+use crate::utils::VersionError;
 use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
 use slug::slugify;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -29,6 +34,10 @@ impl CategoryService {
         // 2. 生成 slug
         let slug = slugify(name);
 
+        if let Some(parent_id) = payload.parent_id {
+            self.validate_parent(None, parent_id).await?;
+        }
+
         // 3. 检查名称或者slug是否已经存在
         //    这取决于你的业务规则。如果 name 和 slug 在数据库层面有 UNIQUE 约束，
         //    那么直接调用 repo.create 并在错误时处理数据库错误也是一种方式。
@@ -47,7 +56,7 @@ impl CategoryService {
         // 4. 调用仓库创建分类
         let category = self
             .repo
-            .create(name, &slug)
+            .create(name, &slug, payload.parent_id)
             .await
             .context(format!("Service 未能创建分类 '{}'", name))?;
         // 如果依赖数据库 UNIQUE 约束，这里需要处理可能的数据库错误
@@ -81,11 +90,97 @@ impl CategoryService {
         self.repo.list().await.context("Service 未能获取分类列表")
     }
 
+    /// 获取分类树，父分类在回收站中的分类作为顶级分类展示
+    pub async fn get_category_tree(&self) -> Result<Vec<CategoryTreeNode>> {
+        let categories = self.repo.list().await.context("Service 未能获取分类列表")?;
+        let ids: HashSet<Uuid> = categories.iter().map(|c| c.id).collect();
+
+        // 按父分类分组，列表已按名称排序，分组后子分类仍按名称排列
+        let mut children_by_parent: HashMap<Option<Uuid>, Vec<Category>> = HashMap::new();
+        for category in categories {
+            let parent_id = category.parent_id.filter(|id| ids.contains(id));
+            children_by_parent
+                .entry(parent_id)
+                .or_default()
+                .push(category);
+        }
+
+        fn build(
+            parent_id: Option<Uuid>,
+            children_by_parent: &mut HashMap<Option<Uuid>, Vec<Category>>,
+        ) -> Vec<CategoryTreeNode> {
+            children_by_parent
+                .remove(&parent_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|category| {
+                    let children = build(Some(category.id), children_by_parent);
+                    CategoryTreeNode { category, children }
+                })
+                .collect()
+        }
+
+        Ok(build(None, &mut children_by_parent))
+    }
+
+    // 校验父分类：必须存在，且不能是分类自身或其后代，避免形成循环
+    async fn validate_parent(&self, category_id: Option<Uuid>, parent_id: Uuid) -> Result<()> {
+        if self.repo.get_by_id(parent_id).await?.is_none() {
+            return Err(anyhow!("无效的父分类：ID 为 {} 的分类不存在", parent_id));
+        }
+        if let Some(id) = category_id {
+            let descendant_ids = self.repo.get_descendant_ids(id).await?;
+            if descendant_ids.contains(&parent_id) {
+                return Err(anyhow!("无效的父分类：不能把分类移到自身或其子分类下"));
+            }
+        }
+        Ok(())
+    }
+
+    // 子分类处理策略为 Refuse 时，被移除的分类不能还有保留下来的子分类
+    async fn check_child_categories(
+        &self,
+        removed_ids: &[Uuid],
+        handling: ChildCategoriesHandling,
+    ) -> Result<()> {
+        if handling != ChildCategoriesHandling::Refuse {
+            return Ok(());
+        }
+        let children = self.repo.list_children(removed_ids).await?;
+        if let Some(child) = children.iter().find(|c| !removed_ids.contains(&c.id)) {
+            return Err(anyhow!(
+                "无效的操作：分类 '{}' 的父分类将被移除，请先移动子分类，或选择重新指定父分类",
+                child.name
+            ));
+        }
+        Ok(())
+    }
+
+    // 合并时目标分类不能是源分类的后代，否则源分类的子分类移到目标分类下会形成循环
+    async fn check_merge_target(
+        &self,
+        target_category: &Category,
+        source_categories: &[Category],
+    ) -> Result<()> {
+        for source in source_categories {
+            let descendant_ids = self.repo.get_descendant_ids(source.id).await?;
+            if descendant_ids.contains(&target_category.id) {
+                return Err(anyhow!(
+                    "无效的合并：目标分类 '{}' 是源分类 '{}' 的子分类",
+                    target_category.name,
+                    source.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// 跟新分类
     pub async fn update_category(
         &self,
         id: Uuid,
         payload: UpdateCategoryPayload,
+        expected_version: Option<DateTime<Utc>>,
     ) -> Result<Category> {
         let mut new_name_opt: Option<String> = None;
         let mut new_slug_opt: Option<String> = None;
@@ -113,29 +208,42 @@ impl CategoryService {
             //      }
             // }
         }
-        // 2. 如果没有任何要更新的字段（这里只考虑 name），可以直接返回当前分类或错误
-        if new_name_opt.is_none() {
-            // 如果 UpdateCategoryPayload 允许更新其他字段，这里逻辑会更复杂
-            // 目前它只有 name，所以如果 name 是 None，意味着没有有效更新
+        if let Some(Some(parent_id)) = payload.parent_id {
+            self.validate_parent(Some(id), parent_id).await?;
+        }
+
+        // 2. 如果没有任何要更新的字段，可以直接返回当前分类或错误
+        if new_name_opt.is_none() && payload.parent_id.is_none() {
             // 可以选择返回错误，或者获取并返回未修改的分类
-            return self
+            let current = self
                 .get_category_by_id(id)
                 .await
-                .context("更新时未提供有效字段，尝试获取当前分类失败");
+                .context("更新时未提供有效字段，尝试获取当前分类失败")?;
+            // 即使没有修改，也要告知客户端其持有的版本已过期
+            if expected_version.is_some_and(|v| v != current.updated_at) {
+                return Err(VersionError::stale(current.updated_at).into());
+            }
+            return Ok(current);
             // return Err(anyhow!("没有提供要更新的分类信息"));
         }
         // 3. 调用仓库更新分类
         // 将 Option<String> 转换为 Option<&str> 给 repository
         let category = self
             .repo
-            .update(id, new_name_opt.as_deref(), new_slug_opt.as_deref())
+            .update(
+                id,
+                new_name_opt.as_deref(),
+                new_slug_opt.as_deref(),
+                payload.parent_id,
+                expected_version,
+            )
             .await
             .context(format!("Service 未能更新分类 (ID: {})", id))?;
 
         Ok(category)
     }
 
-    /// 删除分类
+    /// 删除分类，子分类移到被删除分类的父分类下
     pub async fn delete_category(&self, id: Uuid) -> Result<()> {
         // 在删除前，可以添加业务逻辑检查，例如：
         // - 分类下是否还有文章？如果有关联文章，是禁止删除、将文章设为无分类，还是级联删除？
@@ -155,6 +263,7 @@ impl CategoryService {
         &self,
         target_category_id: Uuid,
         source_category_ids: &[Uuid],
+        child_categories: ChildCategoriesHandling,
     ) -> Result<Category> {
         // 1. 验证目标分类存在
         let target_category = self
@@ -163,14 +272,21 @@ impl CategoryService {
             .context("合并操作：目标分类不存在")?;
 
         // 2. 验证源分类都存在且不包含目标分类
+        let mut source_categories = Vec::new();
         for &source_id in source_category_ids {
             if source_id == target_category_id {
                 return Err(anyhow!("不能将分类合并到自身"));
             }
-            self.get_category_by_id(source_id)
+            let source_category = self
+                .get_category_by_id(source_id)
                 .await
                 .context(format!("合并操作：源分类 {} 不存在", source_id))?;
+            source_categories.push(source_category);
         }
+        self.check_merge_target(&target_category, &source_categories)
+            .await?;
+        self.check_child_categories(source_category_ids, child_categories)
+            .await?;
 
         // 3. 调用仓库进行合并操作
         self.repo
@@ -187,6 +303,7 @@ impl CategoryService {
         target_category_id: Uuid,
         source_category_ids: &[Uuid],
         new_target_name: Option<&str>,
+        child_categories: ChildCategoriesHandling,
     ) -> Result<crate::dtos::category::MergeCategoriesResponse> {
         // 1. 验证目标分类存在
         let target_category = self
@@ -206,6 +323,10 @@ impl CategoryService {
                 .context(format!("合并操作：源分类 {} 不存在", source_id))?;
             source_categories.push(source_category);
         }
+        self.check_merge_target(&target_category, &source_categories)
+            .await?;
+        self.check_child_categories(source_category_ids, child_categories)
+            .await?;
 
         // 3. 执行增强合并操作
         let (merged_count, affected_posts, duplicates_removed) = self
@@ -305,6 +426,24 @@ impl CategoryService {
             }
         }
 
+        let children = self.repo.list_children(source_category_ids).await?;
+        let moved_children = children
+            .iter()
+            .filter(|c| !source_category_ids.contains(&c.id) && c.id != target_category_id)
+            .count();
+        if moved_children > 0 {
+            potential_issues.push(format!(
+                "ℹ️ 源分类共有 {} 个子分类，合并后将移到目标分类下",
+                moved_children
+            ));
+        }
+        if let Err(e) = self
+            .check_merge_target(&target_category, &source_categories)
+            .await
+        {
+            potential_issues.push(format!("⚠️ {}", e));
+        }
+
         if duplicates > 0 {
             potential_issues.push(format!(
                 "ℹ️ 有 {} 篇文章同时使用了源分类和目标分类，将自动去重",
@@ -327,13 +466,19 @@ impl CategoryService {
     }
 
     /// 批量删除分类
-    pub async fn batch_delete_categories(&self, category_ids: &[Uuid]) -> Result<usize> {
+    pub async fn batch_delete_categories(
+        &self,
+        category_ids: &[Uuid],
+        child_categories: ChildCategoriesHandling,
+    ) -> Result<usize> {
         // 验证所有分类都存在
         for &category_id in category_ids {
             self.get_category_by_id(category_id)
                 .await
                 .context(format!("批量删除：分类 {} 不存在", category_id))?;
         }
+        self.check_child_categories(category_ids, child_categories)
+            .await?;
 
         self.repo
             .batch_delete(category_ids)
@@ -342,10 +487,14 @@ impl CategoryService {
     }
 
     /// 增强版批量删除分类（支持孤儿文章处理）
+    ///
+    /// `suggested_categories` 为孤儿文章推荐的分类，没有推荐的文章归入"未分类"
     pub async fn batch_delete_categories_enhanced(
         &self,
         category_ids: &[Uuid],
         handle_orphaned: bool,
+        child_categories: ChildCategoriesHandling,
+        suggested_categories: &HashMap<Uuid, Vec<Uuid>>,
     ) -> Result<crate::dtos::category::BatchDeleteCategoriesResponse> {
         // 验证所有分类都存在
         for &category_id in category_ids {
@@ -353,14 +502,24 @@ impl CategoryService {
                 .await
                 .context(format!("批量删除：分类 {} 不存在", category_id))?;
         }
+        self.check_child_categories(category_ids, child_categories)
+            .await?;
 
-        let (deleted_count, affected_post_count, orphaned_post_count) = self
-            .repo
-            .batch_delete_enhanced(category_ids, handle_orphaned)
-            .await
-            .context("增强版批量删除分类操作失败")?;
+        let (deleted_count, affected_post_count, orphaned_post_count, auto_assigned_post_count) =
+            self.repo
+                .batch_delete_enhanced(category_ids, handle_orphaned, suggested_categories)
+                .await
+                .context("增强版批量删除分类操作失败")?;
 
-        let operation_summary = if handle_orphaned && orphaned_post_count > 0 {
+        let operation_summary = if handle_orphaned && auto_assigned_post_count > 0 {
+            format!(
+                "成功删除 {} 个分类，影响了 {} 篇文章，其中 {} 篇孤儿文章已按推荐归类，{} 篇归类到'未分类'",
+                deleted_count,
+                affected_post_count,
+                auto_assigned_post_count,
+                orphaned_post_count - auto_assigned_post_count
+            )
+        } else if handle_orphaned && orphaned_post_count > 0 {
             format!(
                 "成功删除 {} 个分类，影响了 {} 篇文章，其中 {} 篇孤儿文章已自动归类到'未分类'",
                 deleted_count, affected_post_count, orphaned_post_count
@@ -376,6 +535,7 @@ impl CategoryService {
             deleted_count,
             affected_post_count,
             orphaned_post_count,
+            auto_assigned_post_count,
             operation_summary,
         })
     }
@@ -0,0 +1,289 @@
+use crate::dtos::draft_comment::{CommentAnchor, CreateDraftCommentPayload, DraftCommentDto};
+use crate::models::Post;
+use crate::repositories::{DraftCommentRepository, PostRepository};
+use anyhow::{Context, Result, anyhow};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// 草稿评审意见服务
+#[derive(Clone)]
+pub struct DraftCommentService {
+    comment_repo: Arc<dyn DraftCommentRepository>,
+    post_repo: Arc<dyn PostRepository>,
+}
+
+impl DraftCommentService {
+    pub fn new(
+        comment_repo: Arc<dyn DraftCommentRepository>,
+        post_repo: Arc<dyn PostRepository>,
+    ) -> Self {
+        Self {
+            comment_repo,
+            post_repo,
+        }
+    }
+
+    // 辅助函数：检查用户能否访问草稿的评审意见，并记录草稿访问日志
+    // can_access_shared: 用户是否拥有 post:draft:access_shared 权限
+    // require_comment: 是否为写操作（添加、解决评论），需要 commenter 或 editor 分享角色
+    async fn ensure_comment_access(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_access_shared: bool,
+        require_comment: bool,
+        access_type: &str,
+        reason: Option<&str>,
+    ) -> Result<Post> {
+        let post = self
+            .post_repo
+            .get_by_id(post_id)
+            .await
+            .context("获取文章信息失败")?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", post_id))?;
+
+        let is_author = self.post_repo.is_author(post_id, user_id).await?;
+        if !is_author {
+            // 已发布文章的评审意见属于内部讨论，只有作者本人可以查看
+            if post.published_at.is_some() {
+                return Err(anyhow!("只能访问草稿的评审意见"));
+            }
+            if !can_access_shared
+                || !self
+                    .post_repo
+                    .can_access_draft(post_id, user_id)
+                    .await
+                    .context("检查草稿访问权限失败")?
+            {
+                return Err(anyhow!("您没有权限访问该草稿的评审意见"));
+            }
+            if require_comment {
+                let role = self
+                    .post_repo
+                    .get_draft_share_role(post_id, user_id)
+                    .await
+                    .context("获取草稿分享角色失败")?;
+                // 没有单独分享、通过公开草稿访问时沿用原来的规则，可以评论
+                let can_comment = match role {
+                    Some(role) => role.can_comment(),
+                    None => post.is_draft_public == Some(true),
+                };
+                if !can_comment {
+                    return Err(anyhow!("您的分享角色没有添加评审意见的权限"));
+                }
+            }
+        }
+
+        self.post_repo
+            .log_draft_access(post_id, user_id, access_type, reason)
+            .await?;
+
+        Ok(post)
+    }
+
+    // 获取草稿的评审意见，按讨论串组织
+    pub async fn list_comments(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_access_shared: bool,
+    ) -> Result<Vec<DraftCommentDto>> {
+        self.ensure_comment_access(
+            post_id,
+            user_id,
+            can_access_shared,
+            false,
+            "comment_view",
+            None,
+        )
+        .await?;
+
+        let comments = self
+            .comment_repo
+            .list_for_post(post_id)
+            .await
+            .context("获取草稿评审意见失败")?;
+
+        // 评论按创建时间排序，顶层评论一定先于它的回复出现
+        let mut threads: Vec<DraftCommentDto> = Vec::new();
+        for comment in comments {
+            match comment.parent_id {
+                None => threads.push(comment.into()),
+                Some(parent_id) => {
+                    if let Some(thread) = threads.iter_mut().find(|t| t.id == parent_id) {
+                        thread.replies.push(comment.into());
+                    }
+                }
+            }
+        }
+
+        Ok(threads)
+    }
+
+    // 添加评审意见或回复
+    pub async fn create_comment(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_access_shared: bool,
+        payload: CreateDraftCommentPayload,
+    ) -> Result<DraftCommentDto> {
+        let post = self
+            .ensure_comment_access(
+                post_id,
+                user_id,
+                can_access_shared,
+                true,
+                "comment_create",
+                None,
+            )
+            .await?;
+
+        if post.published_at.is_some() {
+            return Err(anyhow!("只能在草稿上添加评审意见"));
+        }
+
+        let body = payload.body.trim();
+        if body.is_empty() {
+            return Err(anyhow!("无效的评论：内容不能为空"));
+        }
+
+        let comment = match payload.parent_id {
+            Some(parent_id) => {
+                if payload.anchor.is_some() {
+                    return Err(anyhow!("无效的评论：回复不能设置锚点"));
+                }
+                let parent = self
+                    .comment_repo
+                    .get_by_id(parent_id)
+                    .await?
+                    .filter(|c| c.post_id == post_id)
+                    .ok_or_else(|| anyhow!("未找到 ID 为 {} 的评论", parent_id))?;
+                // 回复统一挂到顶层评论下，保持单层讨论串
+                let thread_id = parent.parent_id.unwrap_or(parent.id);
+
+                self.comment_repo
+                    .create(post_id, user_id, Some(thread_id), None, None, body)
+                    .await
+                    .context("回复评审意见失败")?
+            }
+            None => {
+                let anchor = payload
+                    .anchor
+                    .ok_or_else(|| anyhow!("无效的评论：顶层评论必须锚定到文本范围或标题"))?;
+                let quoted_text = Self::resolve_anchor(&post.content, &anchor)?;
+
+                self.comment_repo
+                    .create(
+                        post_id,
+                        user_id,
+                        None,
+                        Some(&anchor),
+                        Some(&quoted_text),
+                        body,
+                    )
+                    .await
+                    .context("添加评审意见失败")?
+            }
+        };
+
+        tracing::info!(
+            "用户 {} 在草稿 {} 上添加了评论 {}",
+            user_id,
+            post_id,
+            comment.id
+        );
+        Ok(comment.into())
+    }
+
+    // 解决 / 重新打开评审意见（作用于整个讨论串）
+    pub async fn set_comment_resolved(
+        &self,
+        post_id: Uuid,
+        comment_id: Uuid,
+        user_id: Uuid,
+        can_access_shared: bool,
+        resolved: bool,
+    ) -> Result<DraftCommentDto> {
+        let access_type = if resolved {
+            "comment_resolve"
+        } else {
+            "comment_unresolve"
+        };
+        let comment_ref = comment_id.to_string();
+        let post = self
+            .ensure_comment_access(
+                post_id,
+                user_id,
+                can_access_shared,
+                true,
+                access_type,
+                Some(&comment_ref),
+            )
+            .await?;
+
+        let comment = self
+            .comment_repo
+            .get_by_id(comment_id)
+            .await?
+            .filter(|c| c.post_id == post_id)
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的评论", comment_id))?;
+
+        if comment.parent_id.is_some() {
+            return Err(anyhow!("只能解决顶层评论，回复跟随所属讨论串"));
+        }
+        if comment.author_id != user_id && !self.post_repo.is_author(post.id, user_id).await? {
+            return Err(anyhow!("只能由文章作者或评论作者解决评审意见"));
+        }
+
+        let updated = self
+            .comment_repo
+            .set_resolved(comment_id, resolved.then_some(user_id))
+            .await
+            .context("更新评审意见状态失败")?;
+
+        let mut dto: DraftCommentDto = updated.into();
+        dto.replies = self
+            .comment_repo
+            .list_for_post(post_id)
+            .await?
+            .into_iter()
+            .filter(|c| c.parent_id == Some(comment_id))
+            .map(Into::into)
+            .collect();
+        Ok(dto)
+    }
+
+    // 校验锚点是否落在草稿内容上，返回被引用的原文片段
+    fn resolve_anchor(content: &str, anchor: &CommentAnchor) -> Result<String> {
+        match anchor {
+            CommentAnchor::Range { start, end } => {
+                let char_count = content.chars().count();
+                if *start < 0 || start >= end || *end as usize > char_count {
+                    return Err(anyhow!(
+                        "无效的锚点：文本范围 [{}, {}) 超出草稿内容（共 {} 个字符）",
+                        start,
+                        end,
+                        char_count
+                    ));
+                }
+                Ok(content
+                    .chars()
+                    .skip(*start as usize)
+                    .take((*end - *start) as usize)
+                    .collect())
+            }
+            CommentAnchor::Heading { text } => {
+                let wanted = text.trim();
+                content
+                    .lines()
+                    .map(str::trim_start)
+                    .filter(|line| line.starts_with('#'))
+                    .map(|line| line.trim_start_matches('#').trim())
+                    .find(|heading| *heading == wanted && !wanted.is_empty())
+                    .map(str::to_string)
+                    .ok_or_else(|| anyhow!("无效的锚点：草稿中未找到标题 '{}'", wanted))
+            }
+        }
+    }
+}
@@ -1,15 +1,31 @@
 pub mod admin;
 pub mod auth;
+pub mod autosave;
 pub mod category;
+pub mod draft_comment;
+pub mod notification;
 pub mod post;
+pub mod post_template;
+pub mod preview_link;
+pub mod report;
 pub mod tag;
+pub mod taxonomy_suggestion;
+pub mod trash;
 pub mod user;
 pub mod email;
 
 pub use admin::AdminService;
 pub use auth::AuthService;
+pub use autosave::AutosaveService;
 pub use category::CategoryService;
+pub use draft_comment::DraftCommentService;
 pub use email::EmailService;
+pub use notification::NotificationService;
 pub use post::PostService;
+pub use post_template::PostTemplateService;
+pub use preview_link::PreviewLinkService;
+pub use report::ReportService;
 pub use tag::TagService;
+pub use taxonomy_suggestion::TaxonomySuggestionService;
+pub use trash::TrashService;
 pub use user::UserService;
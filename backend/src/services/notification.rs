@@ -0,0 +1,240 @@
+use crate::config::EmailConfig;
+use crate::dtos::post::DraftShareEntry;
+use crate::dtos::user::UpdateNotificationPreferencesPayload;
+use crate::models::{DraftShareRole, NotificationPreferences};
+use crate::repositories::{NotificationPreferenceRepository, UserRepository};
+use crate::services::EmailService;
+use anyhow::{Context, Result, anyhow};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// 通知服务
+// 负责按用户的通知偏好发送邮件，邮件在后台任务中发送，失败只记录日志，不影响业务请求
+#[derive(Clone)]
+pub struct NotificationService {
+    preference_repo: Arc<dyn NotificationPreferenceRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    email_service: Arc<EmailService>,
+    admin_base_url: String,
+}
+
+// 分享草稿通知所需的信息
+pub struct DraftSharedNotice {
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub shared_by: Uuid,
+    pub message: Option<String>,
+    pub recipients: Vec<DraftShareEntry>,
+}
+
+impl NotificationService {
+    pub fn new(
+        preference_repo: Arc<dyn NotificationPreferenceRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        email_service: Arc<EmailService>,
+        config: &EmailConfig,
+    ) -> Self {
+        Self {
+            preference_repo,
+            user_repo,
+            email_service,
+            admin_base_url: config.admin_base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    // 获取当前用户的通知偏好
+    pub async fn get_preferences(&self, user_id: Uuid) -> Result<NotificationPreferences> {
+        self.preference_repo
+            .get(user_id)
+            .await?
+            .ok_or_else(|| anyhow!("无法找到当前用户的信息"))
+    }
+
+    // 更新当前用户的通知偏好
+    pub async fn update_preferences(
+        &self,
+        user_id: Uuid,
+        payload: UpdateNotificationPreferencesPayload,
+    ) -> Result<NotificationPreferences> {
+        self.preference_repo
+            .upsert(user_id, payload.draft_shared)
+            .await
+            .context("更新通知偏好失败")
+    }
+
+    // 通知新的分享接收人，立即返回，邮件在后台任务中逐个发送
+    pub fn notify_draft_shared(&self, notice: DraftSharedNotice) {
+        if notice.recipients.is_empty() {
+            return;
+        }
+        let service = self.clone();
+        tokio::spawn(async move {
+            let sharer = match service.user_repo.find_by_id(notice.shared_by).await {
+                Ok(Some(user)) => user.username,
+                Ok(None) => "一位作者".to_string(),
+                Err(e) => {
+                    tracing::warn!("发送草稿分享通知失败，无法获取分享人信息: {:?}", e);
+                    return;
+                }
+            };
+            for recipient in &notice.recipients {
+                if let Err(e) = service
+                    .send_draft_shared_email(&notice, &sharer, recipient)
+                    .await
+                {
+                    tracing::warn!(
+                        "草稿 {} 的分享通知发送给用户 {} 失败: {:?}",
+                        notice.post_id,
+                        recipient.user_id,
+                        e
+                    );
+                }
+            }
+        });
+    }
+
+    // 通知作者文章被封禁，这类通知与账号安全相关，不受通知偏好影响
+    pub fn notify_post_banned(
+        &self,
+        author_id: Uuid,
+        post_id: Uuid,
+        post_title: &str,
+        reason: Option<&str>,
+    ) {
+        let link = format!("{}/posts/{}/edit", self.admin_base_url, post_id);
+        let reason = reason
+            .map(|r| format!("<p>封禁原因：{}</p>", escape_html(r)))
+            .unwrap_or_default();
+        let subject = format!("您的文章《{}》已被封禁", post_title);
+        let body = format!(
+            "<p>您的文章《{}》因违反社区规范已被管理员封禁，读者暂时无法访问。</p>{}<p>如果您认为这是误判，可以<a href=\"{}\">打开文章</a>提交申诉。</p>",
+            escape_html(post_title),
+            reason,
+            link
+        );
+        self.send_in_background(author_id, subject, body);
+    }
+
+    // 通知作者封禁申诉的处理结果
+    pub fn notify_ban_appeal_resolved(
+        &self,
+        author_id: Uuid,
+        post_title: &str,
+        unbanned: bool,
+        note: Option<&str>,
+    ) {
+        let note = note
+            .map(|n| format!("<p>处理说明：{}</p>", escape_html(n)))
+            .unwrap_or_default();
+        let (subject, result) = if unbanned {
+            (
+                format!("您的文章《{}》已解除封禁", post_title),
+                "申诉已通过，文章已解除封禁并恢复访问。",
+            )
+        } else {
+            (
+                format!("您的文章《{}》的封禁申诉未通过", post_title),
+                "经复核，管理员决定维持封禁。",
+            )
+        };
+        let body = format!(
+            "<p>您对文章《{}》提交的封禁申诉已处理。</p><p>{}</p>{}",
+            escape_html(post_title),
+            result,
+            note
+        );
+        self.send_in_background(author_id, subject, body);
+    }
+
+    // 在后台任务中给用户发送邮件，失败只记录日志
+    fn send_in_background(&self, user_id: Uuid, subject: String, body: String) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let result = match service.user_repo.find_by_id(user_id).await {
+                Ok(Some(user)) => {
+                    let body = format!("<p>您好, {},</p>{}", escape_html(&user.username), body);
+                    service
+                        .email_service
+                        .send_email(&user.email, &subject, &body)
+                        .await
+                }
+                Ok(None) => Ok(()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                tracing::warn!("发送通知邮件给用户 {} 失败: {:?}", user_id, e);
+            }
+        });
+    }
+
+    async fn send_draft_shared_email(
+        &self,
+        notice: &DraftSharedNotice,
+        sharer: &str,
+        recipient: &DraftShareEntry,
+    ) -> Result<()> {
+        let preferences = self.preference_repo.get(recipient.user_id).await?;
+        if !preferences.is_some_and(|p| p.draft_shared) {
+            tracing::debug!("用户 {} 关闭了草稿分享通知，跳过", recipient.user_id);
+            return Ok(());
+        }
+        let Some(user) = self.user_repo.find_by_id(recipient.user_id).await? else {
+            return Ok(());
+        };
+
+        let link = format!("{}/posts/{}/edit", self.admin_base_url, notice.post_id);
+        let permission = match recipient.role {
+            DraftShareRole::Viewer => "查看",
+            DraftShareRole::Commenter => "查看并评审",
+            DraftShareRole::Editor => "查看并编辑",
+        };
+        let message = notice
+            .message
+            .as_deref()
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(|m| format!("<blockquote>{}</blockquote>", escape_html(m)))
+            .unwrap_or_default();
+        let expiry = recipient
+            .expires_at
+            .map(|t| {
+                format!(
+                    "<p>此分享将于 {} 失效。</p>",
+                    t.format("%Y-%m-%d %H:%M UTC")
+                )
+            })
+            .unwrap_or_default();
+
+        let email_subject = format!("{} 与您分享了草稿《{}》", sharer, notice.post_title);
+        let email_body = format!(
+            "<p>您好, {},</p><p>{} 与您分享了草稿《{}》，您可以{}这篇草稿。</p>{}<p><a href=\"{}\">打开草稿</a></p>{}<p>如果不想再收到此类邮件，可以在通知偏好中关闭草稿分享通知。</p>",
+            escape_html(&user.username),
+            escape_html(sharer),
+            escape_html(&notice.post_title),
+            permission,
+            message,
+            link,
+            expiry
+        );
+
+        self.email_service
+            .send_email(&user.email, &email_subject, &email_body)
+            .await
+    }
+}
+
+// 转义插入邮件 HTML 的用户输入
+fn escape_html(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
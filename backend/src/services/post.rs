@@ -1,16 +1,39 @@
+use crate::config::MarkdownConfig;
 use crate::dtos::post::{
-    CategoryDto, CreatePostPayload, PostDetailDto, ShareDraftPayload, TagDto, UpdatePostPayload,
-    UserBasicDto,
+    BanAppealDecision, BanAppealDto, BanPostPayload, BrokenWikiLinkDto, BulkPostAction,
+    BulkPostItemResult, BulkPostPayload, BulkPostResponse, CategoryDto, CreateBanAppealPayload,
+    CreatePostPayload, DraftShareEntry, PostBanDto, PostDetailDto, PostReviewDto,
+    ResolveBanAppealPayload, ReviewDecision, ReviewPostPayload, SetPostAuthorsPayload,
+    ShareDraftPayload, SharedDraftDto, SubmitReviewPayload, TagDto, UpdatePostPayload,
+    UserBasicDto, WikiLinkIssue,
 };
+use crate::dtos::user::AuthorProfileDto;
 use crate::dtos::{PaginatedResponse, Pagination};
-use crate::models::Post;
+use crate::models::{BanAppealStatus, DraftShareRole, Post, ReviewStatus, User};
 use crate::repositories::{CategoryRepository, PostRepository, TagRepository, UserRepository};
-use crate::utils::markdown_to_html_safe;
+use crate::services::NotificationService;
+use crate::services::notification::DraftSharedNotice;
+use crate::utils::MarkdownRenderer;
 use anyhow::{Context, Ok, Result, anyhow};
+use chrono::{DateTime, Utc};
 use slug::slugify;
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
 
+// 封禁原因、申诉理由和处理说明的最大长度（字符数）
+const MAX_BAN_TEXT_LENGTH: usize = 2000;
+// 一次批量操作最多处理的文章数
+const MAX_BULK_POSTS: usize = 100;
+// 一篇文章最多的作者数（包括主作者）
+const MAX_POST_AUTHORS: usize = 20;
+
+// 渲染后的文章正文
+struct RenderedContent {
+    html: String,
+    broken_wiki_links: Vec<BrokenWikiLinkDto>, // 无法解析的维基链接
+}
+
 // Post服务结构体，持有仓库的引用（使用Arc<dyn Trait>支持多态和共享）
 #[derive(Clone)]
 pub struct PostService {
@@ -18,6 +41,8 @@ pub struct PostService {
     category_repo: Arc<dyn CategoryRepository>,
     tag_repo: Arc<dyn TagRepository>,
     user_repo: Arc<dyn UserRepository>,
+    notification_service: Arc<NotificationService>,
+    markdown: Arc<MarkdownRenderer>,
 }
 
 impl PostService {
@@ -26,12 +51,16 @@ impl PostService {
         category_repo: Arc<dyn CategoryRepository>,
         tag_repo: Arc<dyn TagRepository>,
         user_repo: Arc<dyn UserRepository>,
+        notification_service: Arc<NotificationService>,
+        markdown_config: &MarkdownConfig,
     ) -> Self {
         Self {
             repo,
             category_repo,
             tag_repo,
             user_repo,
+            notification_service,
+            markdown: Arc::new(MarkdownRenderer::new(markdown_config)),
         }
     }
 
@@ -39,10 +68,12 @@ impl PostService {
     async fn get_author_info(&self, author_id: Option<Uuid>) -> Result<Option<UserBasicDto>> {
         if let Some(id) = author_id {
             if let Some(user) = self.user_repo.find_by_id(id).await? {
+                let profile = self.user_repo.find_profile(user.id).await?;
                 Ok(Some(UserBasicDto {
                     id: user.id,
                     username: user.username,
-                    email: user.email,
+                    email: Some(user.email),
+                    profile,
                 }))
             } else {
                 Ok(None)
@@ -52,23 +83,72 @@ impl PostService {
         }
     }
 
+    // 辅助方法：获取文章的作者列表（主作者在前）
+    async fn get_authors(&self, post_id: Uuid) -> Result<Vec<UserBasicDto>> {
+        let mut authors = self
+            .repo
+            .list_authors(post_id)
+            .await
+            .context(format!("获取文章 {} 的作者失败", post_id))?;
+
+        // 补充作者的署名资料
+        let author_ids: Vec<Uuid> = authors.iter().map(|author| author.id).collect();
+        let mut profiles = self.user_repo.find_profiles(&author_ids).await?;
+        for author in &mut authors {
+            author.profile = profiles.remove(&author.id).unwrap_or_default();
+        }
+        Ok(authors)
+    }
+
+    // 辅助方法：获取公开展示的作者列表，不包含邮箱
+    async fn get_public_authors(&self, post_id: Uuid) -> Result<Vec<UserBasicDto>> {
+        let mut authors = self.get_authors(post_id).await?;
+        for author in &mut authors {
+            author.email = None;
+        }
+        Ok(authors)
+    }
+
+    // 辅助方法：为草稿加载当前有效的分享接收人
+    // 文章查询不再附带接收人，只在可能返回草稿的路径上按需批量查询，已发布文章不产生查询
+    async fn load_draft_shares(&self, posts: &mut [Post]) -> Result<()> {
+        let draft_ids: Vec<Uuid> = posts
+            .iter()
+            .filter(|post| post.published_at.is_none())
+            .map(|post| post.id)
+            .collect();
+        if draft_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut shares = self.repo.get_draft_share_user_ids(&draft_ids).await?;
+        for post in posts.iter_mut().filter(|post| post.published_at.is_none()) {
+            post.draft_shared_with = shares.remove(&post.id);
+        }
+        Ok(())
+    }
+
     // 辅助函数：创建PostDetailDto，统一处理草稿分享字段
     fn create_post_detail_dto(
         post: &Post,
         categories: Vec<CategoryDto>,
         tags: Vec<TagDto>,
-        rendered_html: String,
+        rendered: RenderedContent,
         accessing_user_id: Option<Uuid>,
-        author: Option<UserBasicDto>,
+        authors: Vec<UserBasicDto>,
         can_read_any: bool, // 是否有管理员权限
     ) -> PostDetailDto {
+        // 合著者和主作者一样可以查看和编辑文章，删除和发布仍由主作者负责
+        let is_co_author = accessing_user_id
+            .is_some_and(|user_id| authors.iter().any(|author| author.id == user_id));
+
         // 判断是否在访问他人的草稿
         let is_accessing_others_draft = if post.published_at.is_some() {
             // 已发布文章，不是草稿
             None
         } else if let Some(user_id) = accessing_user_id {
-            // 草稿状态，检查是否为作者本人
-            Some(post.author_id != Some(user_id))
+            // 草稿状态，检查是否为作者本人（包括合著者）
+            Some(post.author_id != Some(user_id) && !is_co_author)
         } else {
             // 未提供用户ID，无法判断
             None
@@ -97,7 +177,7 @@ impl PostService {
         // 设置操作权限
         let is_banned = post.is_banned.unwrap_or(false);
 
-        let can_view_detail = if is_own_post {
+        let can_view_detail = if is_own_post || is_co_author {
             true // 自己的文章总是可以查看详情（包括被封禁的）
         } else if is_published && !is_banned {
             true // 已发布且未被封禁的文章所有人都可以查看详情
@@ -111,11 +191,10 @@ impl PostService {
             false // 其他情况不允许查看详情
         };
 
-        let can_edit = if is_own_post {
+        let can_edit = if is_own_post || is_co_author {
             true // 可以编辑自己的文章
-        } else if can_access_draft && !can_read_any {
-            true // 普通用户可以编辑分享给自己的草稿，但管理员不能
         } else {
+            // 分享接收人能否编辑取决于分享角色（editor），由 apply_share_role 补充
             false // 管理员不能编辑他人的文章（包括草稿和已发布文章）
         };
 
@@ -132,13 +211,13 @@ impl PostService {
         };
 
         // 设置封禁相关权限
-        let can_ban = if is_published && can_read_any && !is_own_post {
+        let can_ban = if is_published && can_read_any && !is_own_post && !is_co_author {
             true // 管理员可以封禁他人的已发布文章
         } else {
             false
         };
 
-        let can_unban = if is_published && can_read_any && !is_own_post {
+        let can_unban = if is_published && can_read_any && !is_own_post && !is_co_author {
             true // 管理员可以解封他人的已发布文章
         } else {
             false
@@ -149,9 +228,13 @@ impl PostService {
             slug: post.slug.clone(),
             title: post.title.clone(),
             content_markdown: post.content.clone(),
-            content_html: rendered_html,
+            content_html: rendered.html,
             author_id: post.author_id, // 保留以兼容现有代码
-            author,                    // 新增作者详细信息
+            author: authors
+                .iter()
+                .find(|author| Some(author.id) == post.author_id)
+                .cloned(),
+            authors,
             created_at: post.created_at,
             updated_at: post.updated_at,
             published_at: post.published_at,
@@ -173,6 +256,16 @@ impl PostService {
                 None
             },
             is_accessing_others_draft,
+            share_role: None,
+            ban: None,
+            // 无法解析的维基链接只提示给能编辑文章的用户
+            broken_wiki_links: if can_edit && !rendered.broken_wiki_links.is_empty() {
+                Some(rendered.broken_wiki_links)
+            } else {
+                None
+            },
+            backlinks: None,
+            render_profile: post.render_profile.clone(),
             // 封禁状态
             is_banned: post.is_banned,
             // 新增的权限字段
@@ -185,6 +278,100 @@ impl PostService {
         }
     }
 
+    // 辅助函数：为他人的草稿补充当前用户的分享角色，editor 角色可以编辑
+    async fn apply_share_role(&self, dto: &mut PostDetailDto, user_id: Uuid) -> Result<()> {
+        if dto.published_at.is_none() && !dto.authors.iter().any(|author| author.id == user_id) {
+            let role = self
+                .repo
+                .get_draft_share_role(dto.id, user_id)
+                .await
+                .context("获取草稿分享角色失败")?;
+            dto.can_edit = role.is_some_and(|r| r.can_edit());
+            dto.share_role = role;
+        }
+        Ok(())
+    }
+
+    // 渲染文章正文，维基链接按 slug 一次性解析，只有已发布且未被封禁的目标才渲染为链接
+    // report_broken_links: 是否收集无法解析的链接，只有单篇查看和编辑时需要，列表中不展示
+    async fn render_content(
+        &self,
+        post: &Post,
+        report_broken_links: bool,
+    ) -> Result<RenderedContent> {
+        let profile = post.render_profile.as_deref();
+        let slugs = self.markdown.wiki_link_slugs(&post.content, profile);
+        let targets = self.repo.get_wiki_link_targets(&slugs).await?;
+
+        let mut resolved_slugs = HashSet::new();
+        let mut broken_wiki_links = Vec::new();
+        for slug in slugs {
+            let issue = match targets.get(&slug) {
+                Some(true) => {
+                    resolved_slugs.insert(slug);
+                    continue;
+                }
+                Some(false) => WikiLinkIssue::Unpublished,
+                None => WikiLinkIssue::Missing,
+            };
+            if report_broken_links {
+                broken_wiki_links.push(BrokenWikiLinkDto { slug, issue });
+            }
+        }
+
+        Ok(RenderedContent {
+            html: self
+                .markdown
+                .render_with_wiki_links(&post.content, profile, &resolved_slugs),
+            broken_wiki_links,
+        })
+    }
+
+    // 补充链接到本文的已发布文章，只在详情接口中查询
+    async fn apply_backlinks(&self, dto: &mut PostDetailDto) -> Result<()> {
+        let backlinks = self.repo.list_backlinks(&dto.slug, dto.id).await?;
+        dto.backlinks = Some(backlinks);
+        Ok(())
+    }
+
+    // 补充生效中的封禁记录，只对作者（包括合著者，他们同样可以申诉）和有管理权限的用户展示
+    async fn apply_ban_info(
+        &self,
+        dto: &mut PostDetailDto,
+        user_id: Uuid,
+        can_read_any: bool,
+    ) -> Result<()> {
+        let is_author = dto.authors.iter().any(|author| author.id == user_id);
+        if dto.is_banned == Some(true) && (is_author || can_read_any) {
+            dto.ban = self.repo.get_active_ban(dto.id).await?;
+        }
+        Ok(())
+    }
+
+    // 检查用户能否以指定的渲染方案保存文章。
+    // 未指定方案时，已有文章按其当前方案检查（修改受限方案文章的正文同样需要权限），新文章按默认方案检查
+    pub async fn ensure_can_use_render_profile(
+        &self,
+        post_id: Option<Uuid>,
+        render_profile: Option<&str>,
+        roles: &[String],
+        permissions: &[String],
+    ) -> Result<()> {
+        let current_profile = match (render_profile, post_id) {
+            (None, Some(id)) => self
+                .repo
+                .get_by_id(id)
+                .await?
+                .and_then(|post| post.render_profile),
+            _ => None,
+        };
+        self.markdown.check_profile(
+            render_profile.or(current_profile.as_deref()),
+            roles,
+            permissions,
+        )
+    }
+
     // 辅助函数：验证分类 IDs 是否都有效
     async fn validate_category_ids(&self, category_ids: &Option<Vec<Uuid>>) -> Result<()> {
         if let Some(ids) = category_ids {
@@ -231,13 +418,111 @@ impl PostService {
 
         // 根据文章 title 生成 slug
         let slug = slugify(&payload.title);
+        self.insert_post(author_id, &payload, &slug).await
+    }
+
+    // 复制文章为当前用户的新草稿：复制正文、分类、标签和渲染方案，slug 自动避开已有文章。
+    // 可以复制自己的文章、已发布的文章，以及和查看详情一样可以访问的草稿（分享给自己或公开的草稿）；
+    // 管理员可以在列表中看到私有草稿但不能查看详情，同样不能复制。用户不能使用原文的渲染方案时改用默认方案
+    // can_access_shared: 用户是否拥有 post:draft:access_shared 权限
+    pub async fn clone_post(
+        &self,
+        id: Uuid,
+        author_id: Uuid,
+        can_access_shared: bool,
+        roles: &[String],
+        permissions: &[String],
+    ) -> Result<PostDetailDto> {
+        let source = self
+            .repo
+            .get_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", id))?;
+        let can_read = if self.repo.is_author(id, author_id).await? {
+            true
+        } else if source.published_at.is_some() {
+            !source.is_banned.unwrap_or(false)
+        } else {
+            can_access_shared && self.repo.can_access_draft(id, author_id).await?
+        };
+        if !can_read {
+            return Err(anyhow!(
+                "您只能复制自己的文章、已发布的文章或分享给您的草稿"
+            ));
+        }
+        let render_profile = source.render_profile.filter(|profile| {
+            self.markdown
+                .check_profile(Some(profile), roles, permissions)
+                .is_ok()
+        });
+        let category_ids = self
+            .repo
+            .get_categories_for_post(id)
+            .await?
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        let tag_ids = self
+            .repo
+            .get_tags_for_post(id)
+            .await?
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+
+        let payload = CreatePostPayload {
+            title: format!("{} (副本)", source.title),
+            content: source.content,
+            category_ids: Some(category_ids),
+            tag_ids: Some(tag_ids),
+            draft_shared_with: None,
+            is_draft_public: None,
+            render_profile,
+            template_id: None,
+        };
+        let slug = self
+            .available_slug(&format!("{}-copy", source.slug))
+            .await?;
+        let post = self.insert_post(author_id, &payload, &slug).await?;
+        tracing::info!("用户 {} 将文章 {} 复制为 {}", author_id, id, post.id);
+        Ok(post)
+    }
+
+    // 在 base 后追加序号，直到找到未被占用的 slug
+    async fn available_slug(&self, base: &str) -> Result<String> {
+        let mut slug = base.to_string();
+        let mut suffix = 2;
+        while self.repo.slug_exists(&slug).await? {
+            slug = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        Ok(slug)
+    }
 
+    // 写入新文章及其关联和维基链接，返回作者视角的 PostDetailDto
+    async fn insert_post(
+        &self,
+        author_id: Uuid,
+        payload: &CreatePostPayload,
+        slug: &str,
+    ) -> Result<PostDetailDto> {
         // 调用仓库，repo.create 返回基本的 Post 对象，它已经处理了关联表的写入
-        let created_post_basic = self
+        let mut created_post_basic = self
             .repo
-            .create(author_id, &payload, &slug)
+            .create(author_id, payload, slug)
             .await
             .context("Service未能创建帖子基本信息及关联")?;
+        self.load_draft_shares(std::slice::from_mut(&mut created_post_basic))
+            .await?;
+        self.repo
+            .replace_links(
+                created_post_basic.id,
+                &self.markdown.wiki_link_slugs(
+                    &created_post_basic.content,
+                    created_post_basic.render_profile.as_deref(),
+                ),
+            )
+            .await?;
 
         // 创建成功后，获取完整的 PostDetailDto
         let categories = self
@@ -258,25 +543,26 @@ impl PostService {
             ))?;
 
         // markdown转换
-        let rendered_html = markdown_to_html_safe(&created_post_basic.content);
+        let rendered = self.render_content(&created_post_basic, true).await?;
 
         // 获取作者信息
-        let author = self.get_author_info(created_post_basic.author_id).await?;
+        let authors = self.get_authors(created_post_basic.id).await?;
 
         let post_detail_dto = Self::create_post_detail_dto(
             &created_post_basic,
             categories,
             tags,
-            rendered_html,
+            rendered,
             Some(author_id), // 创建者查看自己的文章
-            author,
+            authors,
             false, // 创建者查看自己的文章，不需要管理员权限
         );
         Ok(post_detail_dto)
     }
 
-    // 获取单个帖子详情，返回 PostDetailDto
-    pub async fn get_post_by_id(&self, id: Uuid) -> Result<PostDetailDto> {
+    // 通过预览链接获取文章详情（公开只读）
+    // 持有预览链接的人是匿名访问者：不加载分享接收人，也不返回草稿分享设置和无法解析的维基链接
+    pub async fn get_preview_post(&self, id: Uuid) -> Result<PostDetailDto> {
         let post = self
             .repo
             .get_by_id(id)
@@ -286,31 +572,26 @@ impl PostService {
 
         // --- Markdown 转换 ---
         // 从 post.content(原始Markdown)渲染出 HTML
-        let rendered_html = markdown_to_html_safe(&post.content);
+        let rendered = self.render_content(&post, false).await?;
 
         let categories = self
             .repo
             .get_categories_for_post(post.id)
             .await
-            .context(format!("获取新创建帖子 {} 的分类失败", post.id))?;
+            .context(format!("获取帖子 {} 的分类失败", post.id))?;
         let tags = self
             .repo
             .get_tags_for_post(post.id)
             .await
-            .context(format!("获取新创建帖子 {} 的标签失败", post.id))?;
+            .context(format!("获取帖子 {} 的标签失败", post.id))?;
 
-        // 获取作者信息
-        let author = self.get_author_info(post.author_id).await?;
+        // 获取作者信息（不包含邮箱）
+        let authors = self.get_public_authors(post.id).await?;
 
-        let post_detail_dto = Self::create_post_detail_dto(
-            &post,
-            categories,
-            tags,
-            rendered_html,
-            None, // 这里需要调用方传入用户ID来确定是否访问他人草稿
-            author,
-            false, // 单个文章查看，默认非管理员权限
-        );
+        let mut post_detail_dto =
+            Self::create_post_detail_dto(&post, categories, tags, rendered, None, authors, false);
+        post_detail_dto.draft_shared_with = None;
+        post_detail_dto.is_draft_public = None;
         Ok(post_detail_dto)
     }
 
@@ -321,16 +602,18 @@ impl PostService {
         user_id: Uuid,
         can_read_any: bool,
     ) -> Result<PostDetailDto> {
-        let post = self
+        let mut post = self
             .repo
             .get_by_id(id)
             .await
             .context(format!("Service未能通过id: ({})获取帖子基本信息", id))?
             .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", id))?;
+        self.load_draft_shares(std::slice::from_mut(&mut post))
+            .await?;
 
         // --- Markdown 转换 ---
         // 从 post.content(原始Markdown)渲染出 HTML
-        let rendered_html = markdown_to_html_safe(&post.content);
+        let rendered = self.render_content(&post, true).await?;
 
         let categories = self
             .repo
@@ -344,32 +627,38 @@ impl PostService {
             .context(format!("获取新创建帖子 {} 的标签失败", post.id))?;
 
         // 获取作者信息
-        let author = self.get_author_info(post.author_id).await?;
+        let authors = self.get_authors(post.id).await?;
 
-        let post_detail_dto = Self::create_post_detail_dto(
+        let mut post_detail_dto = Self::create_post_detail_dto(
             &post,
             categories,
             tags,
-            rendered_html,
+            rendered,
             Some(user_id), // 传入用户ID用于权限判断
-            author,
+            authors,
             can_read_any, // 传入用户的管理员权限
         );
+        self.apply_share_role(&mut post_detail_dto, user_id).await?;
+        self.apply_ban_info(&mut post_detail_dto, user_id, can_read_any)
+            .await?;
+        self.apply_backlinks(&mut post_detail_dto).await?;
         Ok(post_detail_dto)
     }
 
     // 获取单个帖子详情（通过slug），返回 PostDetailDto
     pub async fn get_post_by_slug(&self, slug: &str) -> Result<PostDetailDto> {
-        let post = self
+        let mut post = self
             .repo
             .get_by_slug(slug)
             .await
             .context(format!("Service未能通过 slug ({}) 获取帖子基本信息", slug))?
             .ok_or_else(|| anyhow!("未找到 slug 为 '{}' 的帖子", slug))?;
+        self.load_draft_shares(std::slice::from_mut(&mut post))
+            .await?;
 
         // --- Markdown 转换 ---
         // 从 post.content(原始Markdown)渲染出 HTML
-        let rendered_html = markdown_to_html_safe(&post.content);
+        let rendered = self.render_content(&post, true).await?;
 
         let categories = self
             .repo
@@ -383,17 +672,10 @@ impl PostService {
             .context(format!("获取新创建帖子 {} 的标签失败", post.id))?;
 
         // 获取作者信息
-        let author = self.get_author_info(post.author_id).await?;
+        let authors = self.get_authors(post.id).await?;
 
-        let post_detail_dto = Self::create_post_detail_dto(
-            &post,
-            categories,
-            tags,
-            rendered_html,
-            None,
-            author,
-            false,
-        );
+        let post_detail_dto =
+            Self::create_post_detail_dto(&post, categories, tags, rendered, None, authors, false);
         Ok(post_detail_dto)
     }
 
@@ -404,16 +686,18 @@ impl PostService {
         user_id: Uuid,
         can_read_any: bool,
     ) -> Result<PostDetailDto> {
-        let post = self
+        let mut post = self
             .repo
             .get_by_slug(slug)
             .await
             .context(format!("Service未能通过 slug ({}) 获取帖子基本信息", slug))?
             .ok_or_else(|| anyhow!("未找到 slug 为 '{}' 的帖子", slug))?;
+        self.load_draft_shares(std::slice::from_mut(&mut post))
+            .await?;
 
         // --- Markdown 转换 ---
         // 从 post.content(原始Markdown)渲染出 HTML
-        let rendered_html = markdown_to_html_safe(&post.content);
+        let rendered = self.render_content(&post, true).await?;
 
         let categories = self
             .repo
@@ -427,17 +711,21 @@ impl PostService {
             .context(format!("获取新创建帖子 {} 的标签失败", post.id))?;
 
         // 获取作者信息
-        let author = self.get_author_info(post.author_id).await?;
+        let authors = self.get_authors(post.id).await?;
 
-        let post_detail_dto = Self::create_post_detail_dto(
+        let mut post_detail_dto = Self::create_post_detail_dto(
             &post,
             categories,
             tags,
-            rendered_html,
+            rendered,
             Some(user_id), // 传入用户ID用于权限判断
-            author,
+            authors,
             can_read_any, // 传入用户的管理员权限
         );
+        self.apply_share_role(&mut post_detail_dto, user_id).await?;
+        self.apply_ban_info(&mut post_detail_dto, user_id, can_read_any)
+            .await?;
+        self.apply_backlinks(&mut post_detail_dto).await?;
         Ok(post_detail_dto)
     }
 
@@ -453,12 +741,14 @@ impl PostService {
         let page_size = pagination.page_size();
 
         // 1. 获取基本的帖子列表
-        let (posts, total_items) = self
+        let (mut posts, total_items) = self
             .repo
             .list(limit, offset)
             .await
             .context("Service 未能获取分页的帖子列表(基本信息)")?;
 
+        self.load_draft_shares(&mut posts).await?;
+
         // 2. 为每个帖子获取其关联的分类和标签 (N+1 查询问题警告)
         let mut post_details_list = Vec::with_capacity(posts.len());
 
@@ -475,19 +765,13 @@ impl PostService {
                 .context(format!("获取新创建帖子 {} 的标签失败", post.id))?;
 
             // markdown转换
-            let rendered_html = markdown_to_html_safe(&post.content);
+            let rendered = self.render_content(&post, false).await?;
 
             // 获取作者信息
-            let author = self.get_author_info(post.author_id).await?;
+            let authors = self.get_authors(post.id).await?;
 
             let post_detail_dto = Self::create_post_detail_dto(
-                &post,
-                categories,
-                tags,
-                rendered_html,
-                None,
-                author,
-                false,
+                &post, categories, tags, rendered, None, authors, false,
             ); // list_posts方法，默认非管理员权限
             post_details_list.push(post_detail_dto);
         }
@@ -512,7 +796,7 @@ impl PostService {
         let page_size = pagination.page_size();
 
         // 根据权限决定查询范围
-        let (posts, total_items) = if can_read_any {
+        let (mut posts, total_items) = if can_read_any {
             // 管理员权限：查看所有文章（包括私有草稿）
             self.repo
                 .list(limit, offset)
@@ -526,6 +810,8 @@ impl PostService {
                 .context("获取用户可访问的文章列表失败")?
         };
 
+        self.load_draft_shares(&mut posts).await?;
+
         // 2. 为每个帖子获取其关联的分类和标签 (N+1 查询问题警告)
         let mut post_details_list = Vec::with_capacity(posts.len());
 
@@ -542,19 +828,21 @@ impl PostService {
                 .context(format!("获取帖子 {} 的标签失败", post.id))?;
 
             // markdown转换
-            let rendered_html = markdown_to_html_safe(&post.content);
+            let rendered = self.render_content(&post, false).await?;
 
             // 获取作者信息
-            let author = self.get_author_info(post.author_id).await?;
-            let post_detail_dto = Self::create_post_detail_dto(
+            let authors = self.get_authors(post.id).await?;
+            let mut post_detail_dto = Self::create_post_detail_dto(
                 &post,
                 categories,
                 tags,
-                rendered_html,
+                rendered,
                 Some(user_id),
-                author,
+                authors,
                 can_read_any, // 传递用户的权限信息
             );
+            self.apply_ban_info(&mut post_detail_dto, user_id, can_read_any)
+                .await?;
             post_details_list.push(post_detail_dto);
         }
 
@@ -581,7 +869,96 @@ impl PostService {
             .await
             .context("Service 未能获取已发布文章列表")?;
 
-        // 为每个帖子获取其关联的分类和标签
+        let post_details_list = self.build_public_post_details(posts).await?;
+        let response = PaginatedResponse::new(post_details_list, total_items, page, page_size);
+        Ok(response)
+    }
+
+    // 获取作者公开资料（博客展示界面专用）
+    // 没有已发布文章的用户不公开，避免通过用户名探测注册用户
+    pub async fn get_author_profile(&self, username: &str) -> Result<AuthorProfileDto> {
+        let user = self.find_public_author(username).await?;
+        let published_post_count = self.repo.count_published_by_author(user.id).await?;
+        if published_post_count == 0 {
+            return Err(anyhow!("未找到作者 {}", username));
+        }
+        let profile = self.user_repo.find_profile(user.id).await?;
+        Ok(AuthorProfileDto {
+            id: user.id,
+            username: user.username,
+            joined_at: user.created_at,
+            published_post_count,
+            profile,
+        })
+    }
+
+    // 获取作者已发布的文章列表（包括合写的文章，博客展示界面专用）
+    pub async fn list_published_posts_by_author(
+        &self,
+        username: &str,
+        pagination: Pagination,
+    ) -> Result<PaginatedResponse<PostDetailDto>> {
+        let user = self.find_public_author(username).await?;
+        let (posts, total_items) = self
+            .repo
+            .list_published_by_author(user.id, pagination.limit(), pagination.offset())
+            .await
+            .context(format!("Service 未能获取作者 {} 的已发布文章", username))?;
+        if total_items == 0 {
+            return Err(anyhow!("未找到作者 {}", username));
+        }
+
+        let post_details_list = self.build_public_post_details(posts).await?;
+        Ok(PaginatedResponse::new(
+            post_details_list,
+            total_items,
+            pagination.page(),
+            pagination.page_size(),
+        ))
+    }
+
+    // 获取分类下已发布的文章列表（博客展示界面专用），include_descendants 时包括所有后代分类
+    pub async fn list_published_posts_by_category(
+        &self,
+        slug: &str,
+        include_descendants: bool,
+        pagination: Pagination,
+    ) -> Result<PaginatedResponse<PostDetailDto>> {
+        let category = self
+            .category_repo
+            .get_by_slug(slug)
+            .await?
+            .ok_or_else(|| anyhow!("未找到 Slug 为 '{}' 的分类", slug))?;
+        let category_ids = if include_descendants {
+            self.category_repo.get_descendant_ids(category.id).await?
+        } else {
+            vec![category.id]
+        };
+
+        let (posts, total_items) = self
+            .repo
+            .list_published_by_categories(&category_ids, pagination.limit(), pagination.offset())
+            .await
+            .context(format!("Service 未能获取分类 {} 的已发布文章", slug))?;
+
+        let post_details_list = self.build_public_post_details(posts).await?;
+        Ok(PaginatedResponse::new(
+            post_details_list,
+            total_items,
+            pagination.page(),
+            pagination.page_size(),
+        ))
+    }
+
+    async fn find_public_author(&self, username: &str) -> Result<User> {
+        self.user_repo
+            .find_by_username(username)
+            .await?
+            .ok_or_else(|| anyhow!("未找到作者 {}", username))
+    }
+
+    // 辅助函数：为公开接口组装文章详情，作者信息不包含邮箱
+    async fn build_public_post_details(&self, posts: Vec<Post>) -> Result<Vec<PostDetailDto>> {
         let mut post_details_list = Vec::with_capacity(posts.len());
 
         for post in posts {
@@ -597,25 +974,17 @@ impl PostService {
                 .context(format!("获取文章 {} 的标签失败", post.id))?;
 
             // markdown转换
-            let rendered_html = markdown_to_html_safe(&post.content);
+            let rendered = self.render_content(&post, false).await?;
 
             // 获取作者信息
-            let author = self.get_author_info(post.author_id).await?;
+            let authors = self.get_public_authors(post.id).await?;
 
             let post_detail_dto = Self::create_post_detail_dto(
-                &post,
-                categories,
-                tags,
-                rendered_html,
-                None,
-                author,
-                false,
+                &post, categories, tags, rendered, None, authors, false,
             ); // 获取已发布文章，公开接口不需要管理员权限
             post_details_list.push(post_detail_dto);
         }
-
-        let response = PaginatedResponse::new(post_details_list, total_items, page, page_size);
-        Ok(response)
+        Ok(post_details_list)
     }
 
     // 根据ID获取已发布文章（博客展示界面专用）
@@ -628,7 +997,7 @@ impl PostService {
             .ok_or_else(|| anyhow!("未找到 ID 为 {} 的已发布文章", id))?;
 
         // Markdown 转换
-        let rendered_html = markdown_to_html_safe(&post.content);
+        let rendered = self.render_content(&post, false).await?;
 
         let categories = self
             .repo
@@ -642,17 +1011,11 @@ impl PostService {
             .context(format!("获取已发布文章 {} 的标签失败", post.id))?;
 
         // 获取作者信息
-        let author = self.get_author_info(post.author_id).await?;
+        let authors = self.get_public_authors(post.id).await?;
 
-        let post_detail_dto = Self::create_post_detail_dto(
-            &post,
-            categories,
-            tags,
-            rendered_html,
-            None,
-            author,
-            false,
-        );
+        let mut post_detail_dto =
+            Self::create_post_detail_dto(&post, categories, tags, rendered, None, authors, false);
+        self.apply_backlinks(&mut post_detail_dto).await?;
         Ok(post_detail_dto)
     }
 
@@ -669,7 +1032,7 @@ impl PostService {
             .ok_or_else(|| anyhow!("未找到 slug 为 '{}' 的已发布文章", slug))?;
 
         // Markdown 转换
-        let rendered_html = markdown_to_html_safe(&post.content);
+        let rendered = self.render_content(&post, false).await?;
 
         let categories = self
             .repo
@@ -683,22 +1046,23 @@ impl PostService {
             .context(format!("获取已发布文章 {} 的标签失败", post.id))?;
 
         // 获取作者信息
-        let author = self.get_author_info(post.author_id).await?;
+        let authors = self.get_public_authors(post.id).await?;
 
-        let post_detail_dto = Self::create_post_detail_dto(
-            &post,
-            categories,
-            tags,
-            rendered_html,
-            None,
-            author,
-            false,
-        );
+        let mut post_detail_dto =
+            Self::create_post_detail_dto(&post, categories, tags, rendered, None, authors, false);
+        self.apply_backlinks(&mut post_detail_dto).await?;
         Ok(post_detail_dto)
     }
 
     // 更新帖子，并返回包含完整关联信息的 PostDetailDto
-    pub async fn update_post(&self, id: Uuid, payload: UpdatePostPayload) -> Result<PostDetailDto> {
+    // expected_version: 客户端 If-Match 中的版本（乐观并发控制），None 表示不校验
+    pub async fn update_post(
+        &self,
+        id: Uuid,
+        payload: UpdatePostPayload,
+        expected_version: Option<DateTime<Utc>>,
+        user_id: Uuid,
+    ) -> Result<PostDetailDto> {
         // 验证 category_ids 和 tag_ids (如果提供了)
         if payload.category_ids.is_some() {
             self.validate_category_ids(&payload.category_ids)
@@ -722,17 +1086,36 @@ impl PostService {
         };
 
         // repo.update 返回基本的 Post 对象，它已经处理了关联表的更新
-        let post = self
+        let mut post = self
             .repo
-            .update(id, &payload, maybe_new_slug.as_deref())
+            .update(
+                id,
+                &payload,
+                maybe_new_slug.as_deref(),
+                expected_version,
+                user_id,
+            )
             .await
             .context(format!(
                 "Service 未能更新帖子 (id: {}) 的基本信息和关联",
                 id
             ))?;
+        self.load_draft_shares(std::slice::from_mut(&mut post))
+            .await?;
+        if payload.content.is_some() || payload.render_profile.is_some() {
+            self.repo
+                .replace_links(
+                    post.id,
+                    &self
+                        .markdown
+                        .wiki_link_slugs(&post.content, post.render_profile.as_deref()),
+                )
+                .await?;
+        }
 
         // markdown转换
-        let rendered_html = markdown_to_html_safe(&post.content);
+        let rendered = self.render_content(&post, true).await?;
+        let broken_wiki_links = rendered.broken_wiki_links.clone();
 
         // 更新成功后，获取完整的 PostDetailDto
         let categories = self
@@ -747,17 +1130,14 @@ impl PostService {
             .context(format!("获取更新后帖子 {} 的标签失败", post.id))?;
 
         // 获取作者信息
-        let author = self.get_author_info(post.author_id).await?;
+        let authors = self.get_authors(post.id).await?;
 
-        let post_detail_dto = Self::create_post_detail_dto(
-            &post,
-            categories,
-            tags,
-            rendered_html,
-            None,
-            author,
-            false,
-        ); // update_post方法，权限在上层检查
+        let mut post_detail_dto =
+            Self::create_post_detail_dto(&post, categories, tags, rendered, None, authors, false); // update_post方法，权限在上层检查
+        // 调用方已检查编辑权限，保存后直接提示无法解析的维基链接
+        if !broken_wiki_links.is_empty() {
+            post_detail_dto.broken_wiki_links = Some(broken_wiki_links);
+        }
         Ok(post_detail_dto)
     }
 
@@ -776,23 +1156,327 @@ impl PostService {
             .context("Service层获取作者ID失败")
     }
 
+    // 用户是否是文章的作者（主作者或合著者）
+    pub async fn is_post_author(&self, post_id: Uuid, user_id: Uuid) -> Result<bool> {
+        self.repo
+            .is_author(post_id, user_id)
+            .await
+            .context("Service层检查文章作者失败")
+    }
+
+    // 设置文章的作者列表，第一个为主作者。
+    // 主作者可以增删和调整合著者；更换主作者需要管理任意文章的权限
+    pub async fn set_post_authors(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_manage_any: bool,
+        payload: SetPostAuthorsPayload,
+    ) -> Result<Vec<UserBasicDto>> {
+        let post = self
+            .repo
+            .get_by_id(post_id)
+            .await?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", post_id))?;
+
+        let author_ids = payload.author_ids;
+        let Some(primary_id) = author_ids.first() else {
+            return Err(anyhow!("无效的作者列表：至少需要一位作者"));
+        };
+        if !can_manage_any {
+            if post.author_id != Some(user_id) {
+                return Err(anyhow!("没有管理文章作者的权限：只有主作者可以管理合著者"));
+            }
+            if *primary_id != user_id {
+                return Err(anyhow!("没有更换主作者的权限"));
+            }
+        }
+        if author_ids.len() > MAX_POST_AUTHORS {
+            return Err(anyhow!(
+                "无效的作者列表：一篇文章最多 {} 位作者",
+                MAX_POST_AUTHORS
+            ));
+        }
+        let mut seen = HashSet::new();
+        for id in &author_ids {
+            if !seen.insert(*id) {
+                return Err(anyhow!("无效的作者列表：用户 {} 重复", id));
+            }
+            if self.user_repo.find_by_id(*id).await?.is_none() {
+                return Err(anyhow!("无效的作者列表：用户 {} 不存在", id));
+            }
+        }
+
+        self.repo.set_authors(post_id, &author_ids).await?;
+        tracing::info!("用户 {} 更新了文章 {} 的作者", user_id, post_id);
+        self.get_authors(post_id).await
+    }
+
     // 发布文章
-    pub async fn publish_post(&self, id: Uuid) -> Result<()> {
+    // requires_review 为 true 时（必须经过审核的角色），只能发布已通过审核的文章
+    pub async fn publish_post(&self, id: Uuid, user_id: Uuid, requires_review: bool) -> Result<()> {
+        // 审核状态的检查、发布与审核状态流转在仓储层的同一事务中完成
         self.repo
-            .publish(id)
+            .publish(id, user_id, requires_review)
             .await
-            .context(format!("Service层发布文章 (id: {}) 失败", id))
+            .context(format!("Service层发布文章 (id: {}) 失败", id))?;
+        Ok(())
     }
 
-    // 撤回文章
-    pub async fn unpublish_post(&self, id: Uuid) -> Result<()> {
+    // 撤回文章，撤回后审核状态回到草稿，再次发布需要重新审核
+    pub async fn unpublish_post(&self, id: Uuid, user_id: Uuid) -> Result<()> {
         self.repo
-            .unpublish(id)
+            .unpublish(id, user_id)
+            .await
+            .context(format!("Service层撤回文章 (id: {}) 失败", id))?;
+        Ok(())
+    }
+
+    // 批量操作文章：先逐篇做与单篇接口相同的权限和前置条件检查，
+    // 再在一个事务中对通过检查的文章执行动作，事务失败时这些文章全部回滚
+    pub async fn bulk_update_posts(
+        &self,
+        user_id: Uuid,
+        permissions: &[String],
+        payload: BulkPostPayload,
+    ) -> Result<BulkPostResponse> {
+        let mut seen = HashSet::new();
+        let post_ids: Vec<Uuid> = payload
+            .post_ids
+            .into_iter()
+            .filter(|id| seen.insert(*id))
+            .collect();
+        if post_ids.is_empty() {
+            return Err(anyhow!("无效的批量操作：文章列表不能为空"));
+        }
+        if post_ids.len() > MAX_BULK_POSTS {
+            return Err(anyhow!(
+                "无效的批量操作：一次最多操作 {} 篇文章",
+                MAX_BULK_POSTS
+            ));
+        }
+
+        let mut action = payload.action;
+        match &mut action {
+            BulkPostAction::AddTags { tag_ids } | BulkPostAction::RemoveTags { tag_ids } => {
+                if tag_ids.is_empty() {
+                    return Err(anyhow!("无效的批量操作：标签列表不能为空"));
+                }
+                self.validate_tag_ids(&Some(tag_ids.clone())).await?;
+            }
+            BulkPostAction::SetCategories { category_ids } => {
+                self.validate_category_ids(&Some(category_ids.clone()))
+                    .await?;
+            }
+            BulkPostAction::Ban { reason } => {
+                *reason = reason
+                    .take()
+                    .map(|r| r.trim().to_string())
+                    .filter(|r| !r.is_empty());
+                if reason
+                    .as_ref()
+                    .is_some_and(|r| r.chars().count() > MAX_BAN_TEXT_LENGTH)
+                {
+                    return Err(anyhow!(
+                        "无效的封禁原因：不能超过 {} 个字符",
+                        MAX_BAN_TEXT_LENGTH
+                    ));
+                }
+            }
+            BulkPostAction::Publish | BulkPostAction::Unpublish | BulkPostAction::Delete => {}
+        }
+
+        let mut results = Vec::with_capacity(post_ids.len());
+        let mut allowed = Vec::new();
+        for id in post_ids {
+            let error = match self
+                .check_bulk_item(id, user_id, permissions, &action)
+                .await
+            {
+                Result::Ok(post) => {
+                    allowed.push(post);
+                    None
+                }
+                Err(e) => {
+                    tracing::warn!("用户 {} 批量操作文章 {} 被拒绝: {}", user_id, id, e);
+                    Some(e.to_string())
+                }
+            };
+            results.push(BulkPostItemResult {
+                post_id: id,
+                success: error.is_none(),
+                error,
+            });
+        }
+
+        if !allowed.is_empty() {
+            let ids: Vec<Uuid> = allowed.iter().map(|post| post.id).collect();
+            // 与 check_bulk_item 的规则一致，仓储层会在事务中再次确认审核已通过
+            let requires_review = permissions.iter().any(|p| p == "post:submit_review")
+                && !permissions.iter().any(|p| p == "post:review");
+            if let Err(e) = self
+                .repo
+                .bulk_apply(&ids, &action, user_id, requires_review)
+                .await
+            {
+                tracing::error!("批量操作事务失败，已全部回滚: {:?}", e);
+                let message = format!("批量操作已回滚：{}", e);
+                for result in results.iter_mut().filter(|r| r.success) {
+                    result.success = false;
+                    result.error = Some(message.clone());
+                }
+                allowed.clear();
+            }
+        }
+
+        // 事务提交后再通知作者
+        if let BulkPostAction::Ban { reason } = &action {
+            for post in &allowed {
+                if let Some(author_id) = post.author_id {
+                    self.notification_service.notify_post_banned(
+                        author_id,
+                        post.id,
+                        &post.title,
+                        reason.as_deref(),
+                    );
+                }
+            }
+        }
+
+        let succeeded = results.iter().filter(|r| r.success).count();
+        Ok(BulkPostResponse {
+            succeeded,
+            failed: results.len() - succeeded,
+            results,
+        })
+    }
+
+    // 批量操作的单篇检查，规则与对应的单篇接口一致，通过后返回文章
+    async fn check_bulk_item(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        permissions: &[String],
+        action: &BulkPostAction,
+    ) -> Result<Post> {
+        let has = |permission: &str| permissions.iter().any(|p| p == permission);
+        let post = self
+            .repo
+            .get_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的文章", id))?;
+        // 发布、撤回和删除与单篇接口一致，只有主作者可以操作；修改标签和分类的权限见 check_bulk_edit
+        let is_primary_author = post.author_id == Some(user_id);
+
+        match action {
+            BulkPostAction::Publish => {
+                if !has("post:publish:any") {
+                    Self::ensure_own(
+                        has("post:publish:own"),
+                        is_primary_author,
+                        "您只能发布自己的文章",
+                    )?;
+                }
+                let requires_review = has("post:submit_review") && !has("post:review");
+                let status = self.current_review_status(id).await?;
+                if requires_review && status != ReviewStatus::Approved {
+                    return Err(anyhow!(
+                        "只能发布已通过审核的文章，当前审核状态为 {}",
+                        status.as_str()
+                    ));
+                }
+            }
+            BulkPostAction::Unpublish => {
+                if !has("post:unpublish:any") {
+                    Self::ensure_own(
+                        has("post:unpublish:own"),
+                        is_primary_author,
+                        "您只能撤回自己的文章",
+                    )?;
+                }
+            }
+            BulkPostAction::Delete => {
+                if !has("post:delete_any") {
+                    Self::ensure_own(
+                        has("post:delete_own"),
+                        is_primary_author,
+                        "您只能删除自己的帖子",
+                    )?;
+                }
+            }
+            BulkPostAction::AddTags { tag_ids } | BulkPostAction::RemoveTags { tag_ids } => {
+                let payload = UpdatePostPayload {
+                    tag_ids: Some(tag_ids.clone()),
+                    ..Default::default()
+                };
+                self.check_bulk_edit(&post, user_id, permissions, &payload)
+                    .await?;
+            }
+            BulkPostAction::SetCategories { category_ids } => {
+                let payload = UpdatePostPayload {
+                    category_ids: Some(category_ids.clone()),
+                    ..Default::default()
+                };
+                self.check_bulk_edit(&post, user_id, permissions, &payload)
+                    .await?;
+            }
+            BulkPostAction::Ban { .. } => {
+                if !has("post:ban") {
+                    return Err(anyhow!("您没有权限封禁文章"));
+                }
+                if post.published_at.is_none() {
+                    return Err(anyhow!("只能封禁已发布的文章"));
+                }
+                if post.is_banned.unwrap_or(false) {
+                    return Err(anyhow!("文章已被封禁"));
+                }
+            }
+        }
+        Ok(post)
+    }
+
+    // 没有 any 权限时，需要 own 权限并且是文章作者
+    fn ensure_own(has_own_permission: bool, is_author: bool, denied: &str) -> Result<()> {
+        if !has_own_permission {
+            return Err(anyhow!("权限不足"));
+        }
+        if !is_author {
+            return Err(anyhow!("{}", denied));
+        }
+        Ok(())
+    }
+
+    // 修改分类和标签的权限与编辑接口一致：post:edit_any、作者（包括合著者）的 post:edit_own，或草稿的 editor 分享
+    async fn check_bulk_edit(
+        &self,
+        post: &Post,
+        user_id: Uuid,
+        permissions: &[String],
+        payload: &UpdatePostPayload,
+    ) -> Result<()> {
+        let has = |permission: &str| permissions.iter().any(|p| p == permission);
+        if has("post:edit_any") {
+            return Ok(());
+        }
+        let is_author = self.repo.is_author(post.id, user_id).await?;
+        let required = if is_author {
+            "post:edit_own"
+        } else {
+            "post:draft:access_shared"
+        };
+        if !has(required) {
+            return Err(anyhow!("权限不足"));
+        }
+        if is_author {
+            return Ok(());
+        }
+        self.ensure_can_edit_shared_draft(post.id, user_id, payload)
             .await
-            .context(format!("Service层撤回文章 (id: {}) 失败", id))
     }
 
     // 草稿分享功能
+    // 提交的分享列表整体替换现有设置；shared_with 中的用户按 commenter 角色分享，
+    // shares 中为同一用户指定的角色和有效期优先
     pub async fn share_draft(
         &self,
         post_id: Uuid,
@@ -817,30 +1501,139 @@ impl PostService {
             return Err(anyhow!("您只能分享自己的草稿"));
         }
 
-        // 4. 验证要分享给的用户是否存在
-        for &shared_user_id in &payload.shared_with {
-            if self.user_repo.find_by_id(shared_user_id).await?.is_none() {
-                return Err(anyhow!("要分享的用户 {} 不存在", shared_user_id));
+        // 4. 合并两种分享方式，同一用户只保留一条
+        let mut shares: Vec<DraftShareEntry> = Vec::new();
+        let legacy = payload.shared_with.iter().map(|&id| DraftShareEntry {
+            user_id: id,
+            role: DraftShareRole::Commenter,
+            expires_at: None,
+        });
+        for entry in legacy.chain(payload.shares.iter().cloned()) {
+            shares.retain(|s| s.user_id != entry.user_id);
+            shares.push(entry);
+        }
+
+        // 5. 验证要分享给的用户是否存在，有效期是否合理
+        let now = Utc::now();
+        for share in &shares {
+            if Some(share.user_id) == post.author_id {
+                return Err(anyhow!("无效的分享：不能分享给作者本人"));
+            }
+            if share.expires_at.is_some_and(|expires_at| expires_at <= now) {
+                return Err(anyhow!(
+                    "无效的分享：用户 {} 的有效期必须晚于当前时间",
+                    share.user_id
+                ));
+            }
+            if self.user_repo.find_by_id(share.user_id).await?.is_none() {
+                return Err(anyhow!("要分享的用户 {} 不存在", share.user_id));
             }
         }
 
-        // 5. 调用仓库层执行分享
+        // 替换前的接收人，用于只通知本次新增的接收人
+        let existing = self
+            .repo
+            .get_draft_share_user_ids(&[post_id])
+            .await?
+            .remove(&post_id)
+            .unwrap_or_default();
+
+        // 6. 调用仓库层执行分享
         self.repo
-            .share_draft(post_id, &payload)
+            .share_draft(post_id, user_id, &shares, payload.is_public)
             .await
             .context("分享草稿失败")?;
 
+        // 7. 只通知本次新增的接收人，已有分享只调整角色或有效期时不重复发邮件
+        let recipients: Vec<DraftShareEntry> = shares
+            .iter()
+            .filter(|s| !existing.contains(&s.user_id))
+            .cloned()
+            .collect();
+        self.notification_service
+            .notify_draft_shared(DraftSharedNotice {
+                post_id,
+                post_title: post.title.clone(),
+                shared_by: user_id,
+                message: payload.message,
+                recipients,
+            });
+
         tracing::info!(
             "用户 {} 成功分享草稿 {} 给 {} 个用户，公开设置: {}",
             user_id,
             post_id,
-            payload.shared_with.len(),
+            shares.len(),
             payload.is_public
         );
 
         Ok(())
     }
 
+    // 获取用户对草稿的有效分享角色，已发布的文章不再按分享授权
+    pub async fn get_draft_share_role(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<DraftShareRole>> {
+        let Some(post) = self.repo.get_by_id(post_id).await? else {
+            return Ok(None);
+        };
+        if post.published_at.is_some() {
+            return Ok(None);
+        }
+        self.repo
+            .get_draft_share_role(post_id, user_id)
+            .await
+            .context("获取草稿分享角色失败")
+    }
+
+    // 检查用户能否通过 editor 分享编辑他人的草稿，通过后记录草稿访问日志
+    // 共享编辑者只能修改内容，不能发布、撤回或更改分享设置
+    pub async fn ensure_can_edit_shared_draft(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        payload: &UpdatePostPayload,
+    ) -> Result<()> {
+        let role = self.get_draft_share_role(post_id, user_id).await?;
+        if !role.is_some_and(|r| r.can_edit()) {
+            return Err(anyhow!("您只能编辑自己的帖子"));
+        }
+
+        if payload.published_at.is_some()
+            || payload.unpublish
+            || payload.draft_shared_with.is_some()
+            || payload.is_draft_public.is_some()
+        {
+            return Err(anyhow!("共享编辑者只能修改草稿的标题、内容、分类和标签"));
+        }
+
+        self.repo
+            .log_draft_access(post_id, user_id, "edit", Some("editor 分享"))
+            .await
+    }
+
+    // 获取分享给当前用户的草稿列表
+    pub async fn list_shared_with_me(
+        &self,
+        user_id: Uuid,
+        pagination: Pagination,
+    ) -> Result<PaginatedResponse<SharedDraftDto>> {
+        let (items, total_items) = self
+            .repo
+            .list_shared_with_user(user_id, pagination.limit(), pagination.offset())
+            .await
+            .context("获取分享给我的草稿失败")?;
+
+        Ok(PaginatedResponse::new(
+            items,
+            total_items,
+            pagination.page(),
+            pagination.page_size(),
+        ))
+    }
+
     // 检查用户是否可以访问草稿
     pub async fn can_access_draft(&self, post_id: Uuid, user_id: Uuid) -> Result<bool> {
         self.repo
@@ -860,12 +1653,14 @@ impl PostService {
         let page = pagination.page();
         let page_size = pagination.page_size();
 
-        let (posts, total_items) = self
+        let (mut posts, total_items) = self
             .repo
             .list_accessible_drafts(user_id, limit, offset)
             .await
             .context("获取用户可访问的草稿列表失败")?;
 
+        self.load_draft_shares(&mut posts).await?;
+
         let mut post_details_list = Vec::with_capacity(posts.len());
 
         for post in posts {
@@ -880,16 +1675,16 @@ impl PostService {
                 .await
                 .context(format!("获取草稿 {} 的标签失败", post.id))?;
 
-            let rendered_html = markdown_to_html_safe(&post.content);
-            let author = self.get_author_info(post.author_id).await?;
+            let rendered = self.render_content(&post, false).await?;
+            let authors = self.get_authors(post.id).await?;
 
             let post_detail_dto = Self::create_post_detail_dto(
                 &post,
                 categories,
                 tags,
-                rendered_html,
+                rendered,
                 Some(user_id),
-                author,
+                authors,
                 false, // list_accessible_drafts，已经通过权限过滤，不需要管理员权限
             );
             post_details_list.push(post_detail_dto);
@@ -900,7 +1695,13 @@ impl PostService {
     }
 
     // 封禁文章（仅管理员）
-    pub async fn ban_post(&self, post_id: Uuid, user_id: Uuid, can_ban: bool) -> Result<()> {
+    pub async fn ban_post(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_ban: bool,
+        payload: BanPostPayload,
+    ) -> Result<PostBanDto> {
         if !can_ban {
             return Err(anyhow!("您没有权限封禁文章"));
         }
@@ -923,11 +1724,36 @@ impl PostService {
             return Err(anyhow!("文章已被封禁"));
         }
 
-        // 4. 执行封禁
-        self.repo.ban_post(post_id).await.context("封禁文章失败")?;
+        // 4. 校验封禁原因
+        let reason = payload
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty());
+        if reason.is_some_and(|r| r.chars().count() > MAX_BAN_TEXT_LENGTH) {
+            return Err(anyhow!(
+                "无效的封禁原因：不能超过 {} 个字符",
+                MAX_BAN_TEXT_LENGTH
+            ));
+        }
+
+        // 5. 执行封禁并记录封禁信息
+        self.repo
+            .ban_post(post_id, user_id, reason)
+            .await
+            .context("封禁文章失败")?;
+
+        // 6. 邮件通知作者（后台发送）
+        if let Some(author_id) = post.author_id {
+            self.notification_service
+                .notify_post_banned(author_id, post_id, &post.title, reason);
+        }
 
         tracing::info!("管理员 {} 封禁了文章 {}", user_id, post_id);
-        Ok(())
+        self.repo
+            .get_active_ban(post_id)
+            .await?
+            .ok_or_else(|| anyhow!("未找到文章 {} 的封禁记录", post_id))
     }
 
     // 解封文章（仅管理员）
@@ -951,7 +1777,7 @@ impl PostService {
 
         // 3. 执行解封
         self.repo
-            .unban_post(post_id)
+            .unban_post(post_id, user_id)
             .await
             .context("解封文章失败")?;
 
@@ -959,6 +1785,150 @@ impl PostService {
         Ok(())
     }
 
+    // 作者（包括合著者）对生效中的封禁提交申诉，每次封禁只能申诉一次
+    pub async fn appeal_ban(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        payload: CreateBanAppealPayload,
+    ) -> Result<BanAppealDto> {
+        let post = self
+            .repo
+            .get_by_id(post_id)
+            .await
+            .context("获取文章信息失败")?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的文章", post_id))?;
+
+        if !self.repo.is_author(post.id, user_id).await? {
+            return Err(anyhow!("您只能对自己的文章提交申诉"));
+        }
+
+        let ban = self
+            .repo
+            .get_active_ban(post_id)
+            .await?
+            .filter(|_| post.is_banned.unwrap_or(false))
+            .ok_or_else(|| anyhow!("无效的申诉：文章当前未被封禁"))?;
+        if ban.appeal_status.is_some() {
+            return Err(anyhow!("本次封禁的申诉已存在，不能重复提交"));
+        }
+
+        let message = payload.message.trim();
+        if message.is_empty() {
+            return Err(anyhow!("无效的申诉：申诉理由不能为空"));
+        }
+        if message.chars().count() > MAX_BAN_TEXT_LENGTH {
+            return Err(anyhow!(
+                "无效的申诉：申诉理由不能超过 {} 个字符",
+                MAX_BAN_TEXT_LENGTH
+            ));
+        }
+
+        let appeal = self
+            .repo
+            .create_ban_appeal(ban.id, post_id, user_id, message)
+            .await?;
+
+        tracing::info!("作者 {} 对文章 {} 的封禁提交了申诉", user_id, post_id);
+        self.repo
+            .get_ban_appeal(appeal.id)
+            .await?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的申诉", appeal.id))
+    }
+
+    // 版主申诉队列，默认只列出待处理的申诉
+    pub async fn list_ban_appeals(
+        &self,
+        can_ban: bool,
+        status: Option<BanAppealStatus>,
+        pagination: Pagination,
+    ) -> Result<PaginatedResponse<BanAppealDto>> {
+        if !can_ban {
+            return Err(anyhow!("您没有权限处理封禁申诉"));
+        }
+        let (appeals, total) = self
+            .repo
+            .list_ban_appeals(
+                status.unwrap_or(BanAppealStatus::Pending),
+                pagination.limit(),
+                pagination.offset(),
+            )
+            .await?;
+        Ok(PaginatedResponse::new(
+            appeals,
+            total,
+            pagination.page(),
+            pagination.page_size(),
+        ))
+    }
+
+    // 版主处理申诉：维持封禁或解封文章，处理结果通过邮件告知作者
+    pub async fn resolve_ban_appeal(
+        &self,
+        appeal_id: Uuid,
+        user_id: Uuid,
+        can_ban: bool,
+        payload: ResolveBanAppealPayload,
+    ) -> Result<BanAppealDto> {
+        if !can_ban {
+            return Err(anyhow!("您没有权限处理封禁申诉"));
+        }
+
+        let appeal = self
+            .repo
+            .get_ban_appeal(appeal_id)
+            .await?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的申诉", appeal_id))?;
+        if appeal.status != BanAppealStatus::Pending {
+            return Err(anyhow!("无效的操作：该申诉已处理"));
+        }
+
+        let note = payload
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty());
+        if note.is_some_and(|n| n.chars().count() > MAX_BAN_TEXT_LENGTH) {
+            return Err(anyhow!(
+                "无效的处理说明：不能超过 {} 个字符",
+                MAX_BAN_TEXT_LENGTH
+            ));
+        }
+
+        let (status, lift_ban) = match payload.decision {
+            BanAppealDecision::Uphold => (BanAppealStatus::Upheld, false),
+            BanAppealDecision::Unban => (BanAppealStatus::Unbanned, true),
+        };
+        let resolved = self
+            .repo
+            .resolve_ban_appeal(appeal_id, user_id, status, note, lift_ban)
+            .await
+            .context("处理封禁申诉失败")?;
+        if !resolved {
+            return Err(anyhow!("无效的操作：该申诉已处理"));
+        }
+
+        if let Some(author_id) = appeal.appellant_id {
+            self.notification_service.notify_ban_appeal_resolved(
+                author_id,
+                &appeal.post_title,
+                lift_ban,
+                note,
+            );
+        }
+
+        tracing::info!(
+            "管理员 {} 处理了文章 {} 的封禁申诉，结果: {}",
+            user_id,
+            appeal.post_id,
+            status.as_str()
+        );
+        self.repo
+            .get_ban_appeal(appeal_id)
+            .await?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的申诉", appeal_id))
+    }
+
     // 检查文章是否被封禁
     pub async fn is_post_banned(&self, post_id: Uuid) -> Result<bool> {
         self.repo
@@ -966,4 +1936,298 @@ impl PostService {
             .await
             .context("检查文章封禁状态失败")
     }
+
+    // ================================
+    // 编辑审核工作流
+    // ================================
+
+    // 获取文章当前审核状态，没有审核记录时视为草稿
+    async fn current_review_status(&self, post_id: Uuid) -> Result<ReviewStatus> {
+        let review = self
+            .repo
+            .get_review(post_id)
+            .await
+            .context("获取文章审核状态失败")?;
+        Ok(review
+            .map(|r| ReviewStatus::from_db(&r.status))
+            .unwrap_or(ReviewStatus::Draft))
+    }
+
+    // 辅助函数：验证被指派的审核人存在且拥有审核权限，且不是文章作者（包括合著者）
+    async fn validate_reviewer(&self, post_id: Uuid, reviewer_id: Uuid) -> Result<()> {
+        if self.repo.is_author(post_id, reviewer_id).await? {
+            return Err(anyhow!("无效的审核人：不能指派作者本人审核"));
+        }
+        if self.user_repo.find_by_id(reviewer_id).await?.is_none() {
+            return Err(anyhow!("无效的审核人：用户 {} 不存在", reviewer_id));
+        }
+        let permissions = self.user_repo.get_user_permissions(reviewer_id).await?;
+        if !permissions.iter().any(|p| p.name == "post:review") {
+            return Err(anyhow!("无效的审核人：用户 {} 不能审核文章", reviewer_id));
+        }
+        Ok(())
+    }
+
+    // 提交文章审核（draft / changes_requested -> in_review）
+    pub async fn submit_for_review(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        payload: SubmitReviewPayload,
+    ) -> Result<PostReviewDto> {
+        let post = self
+            .repo
+            .get_by_id(post_id)
+            .await
+            .context("获取文章信息失败")?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", post_id))?;
+
+        // 合著者和主作者一样可以提交审核
+        if !self.repo.is_author(post.id, user_id).await? {
+            return Err(anyhow!("您只能提交自己的文章进行审核"));
+        }
+        if post.published_at.is_some() {
+            return Err(anyhow!("只能提交未发布的文章进行审核"));
+        }
+
+        let status = self.current_review_status(post_id).await?;
+        if !matches!(status, ReviewStatus::Draft | ReviewStatus::ChangesRequested) {
+            return Err(anyhow!(
+                "只能提交草稿或需修改的文章进行审核，当前审核状态为 {}",
+                status.as_str()
+            ));
+        }
+
+        if let Some(reviewer_id) = payload.reviewer_id {
+            self.validate_reviewer(post_id, reviewer_id).await?;
+        }
+
+        self.repo
+            .transition_review(
+                post_id,
+                status.as_str(),
+                ReviewStatus::InReview.as_str(),
+                user_id,
+                payload.reviewer_id,
+                payload.comment.as_deref(),
+            )
+            .await
+            .context("提交文章审核失败")?;
+
+        tracing::info!("用户 {} 提交了文章 {} 的审核", user_id, post_id);
+        self.build_review_dto(post_id).await
+    }
+
+    // 指派审核人（仅审核人角色）
+    pub async fn assign_reviewer(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        reviewer_id: Uuid,
+    ) -> Result<PostReviewDto> {
+        let post = self
+            .repo
+            .get_by_id(post_id)
+            .await
+            .context("获取文章信息失败")?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", post_id))?;
+
+        if post.published_at.is_some() {
+            return Err(anyhow!("只能为未发布的文章指派审核人"));
+        }
+
+        self.validate_reviewer(post_id, reviewer_id).await?;
+
+        self.repo
+            .assign_reviewer(post_id, reviewer_id)
+            .await
+            .context("指派审核人失败")?;
+
+        tracing::info!(
+            "用户 {} 将文章 {} 指派给审核人 {}",
+            user_id,
+            post_id,
+            reviewer_id
+        );
+        self.build_review_dto(post_id).await
+    }
+
+    // 审核文章（in_review -> approved / changes_requested）
+    pub async fn review_post(
+        &self,
+        post_id: Uuid,
+        reviewer_id: Uuid,
+        payload: ReviewPostPayload,
+    ) -> Result<PostReviewDto> {
+        let post = self
+            .repo
+            .get_by_id(post_id)
+            .await
+            .context("获取文章信息失败")?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", post_id))?;
+
+        if self.repo.is_author(post.id, reviewer_id).await? {
+            return Err(anyhow!("您只能审核他人的文章"));
+        }
+
+        let review = self
+            .repo
+            .get_review(post_id)
+            .await
+            .context("获取文章审核状态失败")?;
+        let status = review
+            .as_ref()
+            .map(|r| ReviewStatus::from_db(&r.status))
+            .unwrap_or(ReviewStatus::Draft);
+        if status != ReviewStatus::InReview {
+            return Err(anyhow!(
+                "只能审核处于审核中的文章，当前审核状态为 {}",
+                status.as_str()
+            ));
+        }
+
+        // 已指派审核人时，只有该审核人可以给出结论
+        if let Some(assigned) = review.and_then(|r| r.reviewer_id)
+            && assigned != reviewer_id
+        {
+            return Err(anyhow!("该文章只能由指定的审核人审核"));
+        }
+
+        let comment = payload
+            .comment
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty());
+        let next_status = match payload.decision {
+            ReviewDecision::Approve => ReviewStatus::Approved,
+            ReviewDecision::RequestChanges => {
+                if comment.is_none() {
+                    return Err(anyhow!("无效的审核意见：要求修改时必须说明修改原因"));
+                }
+                ReviewStatus::ChangesRequested
+            }
+        };
+
+        self.repo
+            .transition_review(
+                post_id,
+                status.as_str(),
+                next_status.as_str(),
+                reviewer_id,
+                Some(reviewer_id),
+                comment,
+            )
+            .await
+            .context("提交审核结论失败")?;
+
+        // 审核人查看了他人的草稿，记录到草稿访问日志
+        self.repo
+            .log_draft_access(post_id, reviewer_id, "review", Some(next_status.as_str()))
+            .await?;
+
+        tracing::info!(
+            "审核人 {} 将文章 {} 的审核状态设为 {}",
+            reviewer_id,
+            post_id,
+            next_status.as_str()
+        );
+        self.build_review_dto(post_id).await
+    }
+
+    // 获取文章的审核详情（作者本人或审核人可见）
+    pub async fn get_post_review(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_review: bool,
+    ) -> Result<PostReviewDto> {
+        // 合著者和主作者一样可以查看审核记录
+        if !can_review
+            && !self
+                .repo
+                .is_author(post_id, user_id)
+                .await
+                .context("获取文章作者失败")?
+        {
+            return Err(anyhow!("您只能查看自己文章的审核记录"));
+        }
+
+        self.build_review_dto(post_id).await
+    }
+
+    // 获取当前审核人的待审核文章列表
+    pub async fn list_pending_reviews(
+        &self,
+        reviewer_id: Uuid,
+        pagination: Pagination,
+    ) -> Result<PaginatedResponse<PostDetailDto>> {
+        let (mut posts, total_items) = self
+            .repo
+            .list_pending_reviews(reviewer_id, pagination.limit(), pagination.offset())
+            .await
+            .context("获取待审核文章列表失败")?;
+
+        self.load_draft_shares(&mut posts).await?;
+
+        let mut post_details_list = Vec::with_capacity(posts.len());
+        for post in posts {
+            let categories = self
+                .repo
+                .get_categories_for_post(post.id)
+                .await
+                .context(format!("获取待审核文章 {} 的分类失败", post.id))?;
+            let tags = self
+                .repo
+                .get_tags_for_post(post.id)
+                .await
+                .context(format!("获取待审核文章 {} 的标签失败", post.id))?;
+
+            let rendered = self.render_content(&post, false).await?;
+            let authors = self.get_authors(post.id).await?;
+
+            post_details_list.push(Self::create_post_detail_dto(
+                &post,
+                categories,
+                tags,
+                rendered,
+                Some(reviewer_id),
+                authors,
+                false, // 审核人只读，不授予管理员操作权限
+            ));
+        }
+
+        Ok(PaginatedResponse::new(
+            post_details_list,
+            total_items,
+            pagination.page(),
+            pagination.page_size(),
+        ))
+    }
+
+    // 辅助函数：组装审核详情DTO
+    async fn build_review_dto(&self, post_id: Uuid) -> Result<PostReviewDto> {
+        let review = self
+            .repo
+            .get_review(post_id)
+            .await
+            .context("获取文章审核状态失败")?;
+        let transitions = self
+            .repo
+            .get_review_transitions(post_id)
+            .await
+            .context("获取文章审核流转记录失败")?;
+
+        let (status, reviewer_id) = match review {
+            Some(r) => (ReviewStatus::from_db(&r.status), r.reviewer_id),
+            None => (ReviewStatus::Draft, None),
+        };
+        let reviewer = self.get_author_info(reviewer_id).await?;
+
+        Ok(PostReviewDto {
+            post_id,
+            status,
+            reviewer,
+            transitions,
+        })
+    }
 }
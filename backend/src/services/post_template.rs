@@ -0,0 +1,211 @@
+use crate::dtos::post::CreatePostPayload;
+use crate::dtos::post_template::{
+    CreatePostTemplatePayload, PostTemplateDto, UpdatePostTemplatePayload,
+};
+use crate::models::PostTemplate;
+use crate::repositories::{CategoryRepository, PostTemplateRepository, TagRepository};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Datelike, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// 模板名称的最大长度（字符数），与数据库字段一致
+const MAX_NAME_LENGTH: usize = 100;
+// 标题模式的最大长度（字符数），与数据库字段一致
+const MAX_TITLE_PATTERN_LENGTH: usize = 255;
+
+// 文章模板服务
+// 作者可以管理自己的模板；全局模板对所有作者可见，只有拥有 post:template:manage_global 权限的用户可以管理
+#[derive(Clone)]
+pub struct PostTemplateService {
+    template_repo: Arc<dyn PostTemplateRepository>,
+    category_repo: Arc<dyn CategoryRepository>,
+    tag_repo: Arc<dyn TagRepository>,
+}
+
+impl PostTemplateService {
+    pub fn new(
+        template_repo: Arc<dyn PostTemplateRepository>,
+        category_repo: Arc<dyn CategoryRepository>,
+        tag_repo: Arc<dyn TagRepository>,
+    ) -> Self {
+        Self {
+            template_repo,
+            category_repo,
+            tag_repo,
+        }
+    }
+
+    // 列出用户可用的模板：自己的模板在前，全局模板在后
+    pub async fn list_templates(&self, user_id: Uuid) -> Result<Vec<PostTemplateDto>> {
+        let templates = self.template_repo.list_for_user(user_id).await?;
+        Ok(templates.into_iter().map(PostTemplateDto::from).collect())
+    }
+
+    // 获取单个模板，他人的个人模板视为不存在
+    pub async fn get_template(&self, id: Uuid, user_id: Uuid) -> Result<PostTemplateDto> {
+        Ok(self.get_visible(id, user_id).await?.into())
+    }
+
+    pub async fn create_template(
+        &self,
+        user_id: Uuid,
+        can_manage_global: bool,
+        payload: CreatePostTemplatePayload,
+    ) -> Result<PostTemplateDto> {
+        if payload.global && !can_manage_global {
+            return Err(anyhow!("没有管理全局模板的权限"));
+        }
+        Self::validate_name(&payload.name)?;
+        Self::validate_title_pattern(&payload.title_pattern)?;
+        self.validate_category_ids(&payload.category_ids).await?;
+        self.validate_tag_ids(&payload.tag_ids).await?;
+
+        let owner_id = if payload.global { None } else { Some(user_id) };
+        let template = self.template_repo.create(owner_id, &payload).await?;
+        tracing::info!("用户 {} 创建了文章模板 {}", user_id, template.id);
+        Ok(template.into())
+    }
+
+    pub async fn update_template(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        can_manage_global: bool,
+        payload: UpdatePostTemplatePayload,
+    ) -> Result<PostTemplateDto> {
+        let template = self.get_visible(id, user_id).await?;
+        Self::ensure_can_manage(&template, can_manage_global)?;
+        if let Some(name) = &payload.name {
+            Self::validate_name(name)?;
+        }
+        if let Some(title_pattern) = &payload.title_pattern {
+            Self::validate_title_pattern(title_pattern)?;
+        }
+        if let Some(category_ids) = &payload.category_ids {
+            self.validate_category_ids(category_ids).await?;
+        }
+        if let Some(tag_ids) = &payload.tag_ids {
+            self.validate_tag_ids(tag_ids).await?;
+        }
+
+        Ok(self.template_repo.update(id, &payload).await?.into())
+    }
+
+    pub async fn delete_template(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        can_manage_global: bool,
+    ) -> Result<()> {
+        let template = self.get_visible(id, user_id).await?;
+        Self::ensure_can_manage(&template, can_manage_global)?;
+        self.template_repo.delete(id).await?;
+        tracing::info!("用户 {} 删除了文章模板 {}", user_id, id);
+        Ok(())
+    }
+
+    // 用模板补全新建文章的内容：标题和正文为空时使用模板，未提供分类和标签时使用模板的默认值。
+    // 模板保存后被删除的分类和标签会被忽略
+    pub async fn apply_template(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        mut payload: CreatePostPayload,
+    ) -> Result<CreatePostPayload> {
+        let template = self.get_visible(id, user_id).await?;
+
+        if payload.title.trim().is_empty() {
+            payload.title = render_title_pattern(&template.title_pattern, Utc::now());
+        }
+        if payload.content.trim().is_empty() {
+            payload.content = template.content;
+        }
+        if payload.category_ids.is_none() {
+            let mut category_ids = Vec::new();
+            for category_id in template.category_ids {
+                if self.category_repo.get_by_id(category_id).await?.is_some() {
+                    category_ids.push(category_id);
+                }
+            }
+            payload.category_ids = Some(category_ids);
+        }
+        if payload.tag_ids.is_none() {
+            let mut tag_ids = Vec::new();
+            for tag_id in template.tag_ids {
+                if self.tag_repo.get_by_id(tag_id).await?.is_some() {
+                    tag_ids.push(tag_id);
+                }
+            }
+            payload.tag_ids = Some(tag_ids);
+        }
+        Ok(payload)
+    }
+
+    // 获取用户可见的模板：自己的模板或全局模板
+    async fn get_visible(&self, id: Uuid, user_id: Uuid) -> Result<PostTemplate> {
+        self.template_repo
+            .get_by_id(id)
+            .await?
+            .filter(|t| t.owner_id.is_none() || t.owner_id == Some(user_id))
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的文章模板", id))
+    }
+
+    fn ensure_can_manage(template: &PostTemplate, can_manage_global: bool) -> Result<()> {
+        if template.owner_id.is_none() && !can_manage_global {
+            return Err(anyhow!("没有管理全局模板的权限"));
+        }
+        Ok(())
+    }
+
+    fn validate_name(name: &str) -> Result<()> {
+        let length = name.trim().chars().count();
+        if length == 0 {
+            return Err(anyhow!("模板名称无效：不能为空"));
+        }
+        if length > MAX_NAME_LENGTH {
+            return Err(anyhow!("模板名称无效：不能超过 {} 个字符", MAX_NAME_LENGTH));
+        }
+        Ok(())
+    }
+
+    fn validate_title_pattern(title_pattern: &str) -> Result<()> {
+        if title_pattern.chars().count() > MAX_TITLE_PATTERN_LENGTH {
+            return Err(anyhow!(
+                "标题模式无效：不能超过 {} 个字符",
+                MAX_TITLE_PATTERN_LENGTH
+            ));
+        }
+        Ok(())
+    }
+
+    async fn validate_category_ids(&self, ids: &[Uuid]) -> Result<()> {
+        for id in ids {
+            if self.category_repo.get_by_id(*id).await?.is_none() {
+                return Err(anyhow!("无效的分类 ID：{}", id));
+            }
+        }
+        Ok(())
+    }
+
+    async fn validate_tag_ids(&self, ids: &[Uuid]) -> Result<()> {
+        for id in ids {
+            if self.tag_repo.get_by_id(*id).await?.is_none() {
+                return Err(anyhow!("无效的标签 ID：{}", id));
+            }
+        }
+        Ok(())
+    }
+}
+
+// 替换标题模式中的日期占位符：{date}（2025-08-04）、{year}、{month}、{day}，
+// 以及 ISO 周数 {week}，其余内容原样保留
+fn render_title_pattern(pattern: &str, now: DateTime<Utc>) -> String {
+    let iso_week = now.iso_week();
+    pattern
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{year}", &now.year().to_string())
+        .replace("{month}", &format!("{:02}", now.month()))
+        .replace("{day}", &format!("{:02}", now.day()))
+        .replace("{week}", &format!("{:02}", iso_week.week()))
+}
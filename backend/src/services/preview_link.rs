@@ -0,0 +1,203 @@
+use crate::config::AuthConfig;
+use crate::dtos::post::{CreatePreviewLinkPayload, PreviewLinkDto};
+use crate::models::PostPreviewLink;
+use crate::repositories::{PostPreviewLinkRepository, PostRepository};
+use anyhow::{Context, Result, anyhow};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// 预览链接默认有效期与最长有效期（小时）
+const DEFAULT_EXPIRY_HOURS: i64 = 72;
+const MAX_EXPIRY_HOURS: i64 = 24 * 30;
+
+// 派生签名密钥时使用的上下文，保证与其他用途的密钥互不相同
+const SIGNING_KEY_CONTEXT: &str = "zincbloom 2025-07 draft preview link signing key";
+
+// 草稿预览链接服务
+// 令牌格式为 "{链接ID}.{签名}"，签名覆盖链接 ID、文章 ID 和过期时间，
+// 数据库中的链接记录负责撤销和使用统计
+#[derive(Clone)]
+pub struct PreviewLinkService {
+    preview_link_repo: Arc<dyn PostPreviewLinkRepository>,
+    post_repo: Arc<dyn PostRepository>,
+    signing_key: [u8; 32],
+}
+
+impl PreviewLinkService {
+    pub fn new(
+        preview_link_repo: Arc<dyn PostPreviewLinkRepository>,
+        post_repo: Arc<dyn PostRepository>,
+        auth_config: &AuthConfig,
+    ) -> Self {
+        Self {
+            preview_link_repo,
+            post_repo,
+            signing_key: blake3::derive_key(SIGNING_KEY_CONTEXT, auth_config.jwt_secret.as_bytes()),
+        }
+    }
+
+    // 计算链接的签名
+    fn sign(&self, link: &PostPreviewLink) -> blake3::Hash {
+        let message = format!(
+            "{}:{}:{}",
+            link.id,
+            link.post_id,
+            link.expires_at.timestamp_micros()
+        );
+        blake3::keyed_hash(&self.signing_key, message.as_bytes())
+    }
+
+    fn to_dto(&self, link: PostPreviewLink) -> PreviewLinkDto {
+        let token = format!("{}.{}", link.id.simple(), self.sign(&link).to_hex());
+        PreviewLinkDto {
+            id: link.id,
+            post_id: link.post_id,
+            label: link.label,
+            token,
+            expires_at: link.expires_at,
+            use_count: link.use_count,
+            last_used_at: link.last_used_at,
+            created_at: link.created_at,
+        }
+    }
+
+    // 检查用户能否管理文章的预览链接：作者本人，或拥有 post:edit_any 权限
+    async fn ensure_can_manage(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_edit_any: bool,
+    ) -> Result<()> {
+        let post = self
+            .post_repo
+            .get_by_id(post_id)
+            .await
+            .context("获取文章信息失败")?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", post_id))?;
+
+        if !can_edit_any && !self.post_repo.is_author(post.id, user_id).await? {
+            return Err(anyhow!("您只能管理自己文章的预览链接"));
+        }
+        Ok(())
+    }
+
+    // 为草稿创建预览链接
+    pub async fn create_link(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_edit_any: bool,
+        payload: CreatePreviewLinkPayload,
+    ) -> Result<PreviewLinkDto> {
+        self.ensure_can_manage(post_id, user_id, can_edit_any)
+            .await?;
+
+        if !self.post_repo.is_draft(post_id).await? {
+            return Err(anyhow!("无效的操作：文章已发布，无需预览链接"));
+        }
+
+        let hours = payload.expires_in_hours.unwrap_or(DEFAULT_EXPIRY_HOURS);
+        if !(1..=MAX_EXPIRY_HOURS).contains(&hours) {
+            return Err(anyhow!(
+                "无效的有效期：必须在 1 到 {} 小时之间",
+                MAX_EXPIRY_HOURS
+            ));
+        }
+
+        let label = payload
+            .label
+            .as_deref()
+            .map(str::trim)
+            .filter(|label| !label.is_empty());
+        let expires_at = Utc::now() + Duration::hours(hours);
+
+        let link = self
+            .preview_link_repo
+            .create(post_id, user_id, label, expires_at)
+            .await?;
+        tracing::info!(
+            "用户 {} 为草稿 {} 创建了预览链接 {}，有效期至 {}",
+            user_id,
+            post_id,
+            link.id,
+            expires_at
+        );
+        Ok(self.to_dto(link))
+    }
+
+    // 列出文章仍然有效的预览链接
+    pub async fn list_links(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_edit_any: bool,
+    ) -> Result<Vec<PreviewLinkDto>> {
+        self.ensure_can_manage(post_id, user_id, can_edit_any)
+            .await?;
+
+        let links = self.preview_link_repo.list_active_for_post(post_id).await?;
+        Ok(links.into_iter().map(|link| self.to_dto(link)).collect())
+    }
+
+    // 撤销预览链接
+    pub async fn revoke_link(
+        &self,
+        post_id: Uuid,
+        link_id: Uuid,
+        user_id: Uuid,
+        can_edit_any: bool,
+    ) -> Result<()> {
+        self.ensure_can_manage(post_id, user_id, can_edit_any)
+            .await?;
+
+        if !self.preview_link_repo.revoke(post_id, link_id).await? {
+            return Err(anyhow!("未找到有效的预览链接 (id: {})", link_id));
+        }
+        tracing::info!(
+            "用户 {} 撤销了草稿 {} 的预览链接 {}",
+            user_id,
+            post_id,
+            link_id
+        );
+        Ok(())
+    }
+
+    // 校验预览令牌并记录访问，返回可预览的文章 ID
+    // 令牌格式错误、签名不符、已撤销、已过期或文章已不存在时统一返回"未找到"，不泄露具体原因
+    pub async fn resolve(&self, token: &str) -> Result<Uuid> {
+        let not_found = || anyhow!("未找到有效的预览链接");
+
+        let (id, signature) = token.split_once('.').ok_or_else(not_found)?;
+        let id = Uuid::parse_str(id).map_err(|_| not_found())?;
+        let signature = blake3::Hash::from_hex(signature).map_err(|_| not_found())?;
+
+        let link = self
+            .preview_link_repo
+            .get_by_id(id)
+            .await?
+            .ok_or_else(not_found)?;
+
+        // blake3::Hash 的比较是常数时间的
+        if self.sign(&link) != signature {
+            tracing::warn!("预览链接 {} 的签名校验失败", id);
+            return Err(not_found());
+        }
+        if link.revoked_at.is_some() || link.expires_at <= Utc::now() {
+            return Err(not_found());
+        }
+
+        // 文章可能已被删除或封禁
+        let post = self
+            .post_repo
+            .get_by_id(link.post_id)
+            .await?
+            .ok_or_else(not_found)?;
+        if post.is_banned == Some(true) {
+            return Err(not_found());
+        }
+
+        self.preview_link_repo.record_use(&link).await?;
+        Ok(link.post_id)
+    }
+}
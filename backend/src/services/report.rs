@@ -0,0 +1,208 @@
+use crate::config::{AuthConfig, ReportConfig};
+use crate::dtos::post::BanPostPayload;
+use crate::dtos::report::{
+    CreateReportPayload, PostReportDto, ReportReceiptDto, ReportResolutionDto, ReportedPostDto,
+};
+use crate::dtos::{PaginatedResponse, Pagination};
+use crate::models::ReportCategory;
+use crate::repositories::{PostReportRepository, PostRepository};
+use crate::services::PostService;
+use anyhow::{Context, Result, anyhow};
+use std::net::IpAddr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+// 举报补充说明的最大长度（字符数）
+const MAX_DETAILS_LENGTH: usize = 1000;
+
+// 派生游客标识密钥时使用的上下文，保证与其他用途的密钥互不相同
+const REPORTER_KEY_CONTEXT: &str = "zincbloom 2025-07 guest reporter key";
+
+// 读者举报服务
+// 读者（包括游客）举报已发布的文章，版主在按文章聚合的举报队列中驳回举报或封禁文章
+#[derive(Clone)]
+pub struct ReportService {
+    report_repo: Arc<dyn PostReportRepository>,
+    post_repo: Arc<dyn PostRepository>,
+    post_service: Arc<PostService>,
+    max_reports_per_hour: i64,
+    trust_forwarded_for: bool,
+    reporter_key: [u8; 32],
+}
+
+impl ReportService {
+    pub fn new(
+        report_repo: Arc<dyn PostReportRepository>,
+        post_repo: Arc<dyn PostRepository>,
+        post_service: Arc<PostService>,
+        config: &ReportConfig,
+        auth_config: &AuthConfig,
+    ) -> Self {
+        Self {
+            report_repo,
+            post_repo,
+            post_service,
+            max_reports_per_hour: config.max_reports_per_hour,
+            trust_forwarded_for: config.trust_forwarded_for,
+            reporter_key: blake3::derive_key(
+                REPORTER_KEY_CONTEXT,
+                auth_config.jwt_secret.as_bytes(),
+            ),
+        }
+    }
+
+    // 计算举报人标识：登录用户按用户 ID，游客按客户端 IP 的带密钥哈希（不保存原始 IP）
+    // forwarded_for 为 X-Forwarded-For 请求头，只有配置为信任时才使用
+    fn reporter_key(
+        &self,
+        user_id: Option<Uuid>,
+        forwarded_for: Option<&str>,
+        peer_ip: Option<IpAddr>,
+    ) -> String {
+        if let Some(user_id) = user_id {
+            return format!("user:{}", user_id);
+        }
+        let forwarded_ip = forwarded_for
+            .filter(|_| self.trust_forwarded_for)
+            .and_then(|value| value.split(',').next())
+            .and_then(|ip| ip.trim().parse::<IpAddr>().ok());
+        match forwarded_ip.or(peer_ip) {
+            Some(ip) => format!(
+                "guest:{}",
+                blake3::keyed_hash(&self.reporter_key, ip.to_string().as_bytes()).to_hex()
+            ),
+            // 无法获取客户端地址时所有游客共用一个频率限制
+            None => "guest:unknown".to_string(),
+        }
+    }
+
+    // 举报文章，只能举报已发布且未被封禁的文章
+    pub async fn report_post(
+        &self,
+        post_id: Uuid,
+        user_id: Option<Uuid>,
+        forwarded_for: Option<&str>,
+        peer_ip: Option<IpAddr>,
+        payload: CreateReportPayload,
+    ) -> Result<ReportReceiptDto> {
+        let post = self
+            .post_repo
+            .get_by_id(post_id)
+            .await
+            .context("获取文章信息失败")?
+            .filter(|p| p.published_at.is_some() && !p.is_banned.unwrap_or(false))
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的文章", post_id))?;
+
+        let details = payload
+            .details
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty());
+        if details.is_some_and(|d| d.chars().count() > MAX_DETAILS_LENGTH) {
+            return Err(anyhow!(
+                "无效的举报说明：不能超过 {} 个字符",
+                MAX_DETAILS_LENGTH
+            ));
+        }
+
+        // 频率限制与写入在仓储层的同一事务中完成
+        let reporter_key = self.reporter_key(user_id, forwarded_for, peer_ip);
+        let report = self
+            .report_repo
+            .create(
+                post.id,
+                user_id,
+                &reporter_key,
+                payload.category,
+                details,
+                self.max_reports_per_hour,
+            )
+            .await?
+            .ok_or_else(|| anyhow!("您对这篇文章的举报已存在，正在等待处理"))?;
+
+        tracing::info!(
+            "文章 {} 收到举报，分类: {}",
+            post.id,
+            payload.category.as_str()
+        );
+        Ok(ReportReceiptDto {
+            id: report.id,
+            post_id: report.post_id,
+            category: ReportCategory::from_db(&report.category),
+            created_at: report.created_at,
+        })
+    }
+
+    // 举报队列：按文章聚合待处理的举报
+    pub async fn list_reported_posts(
+        &self,
+        pagination: Pagination,
+    ) -> Result<PaginatedResponse<ReportedPostDto>> {
+        let (posts, total) = self
+            .report_repo
+            .list_reported_posts(pagination.limit(), pagination.offset())
+            .await?;
+        Ok(PaginatedResponse::new(
+            posts,
+            total,
+            pagination.page(),
+            pagination.page_size(),
+        ))
+    }
+
+    // 获取文章所有待处理的举报
+    pub async fn list_reports_for_post(&self, post_id: Uuid) -> Result<Vec<PostReportDto>> {
+        self.report_repo.list_open_for_post(post_id).await
+    }
+
+    // 驳回文章所有待处理的举报
+    pub async fn dismiss_reports(
+        &self,
+        post_id: Uuid,
+        moderator_id: Uuid,
+    ) -> Result<ReportResolutionDto> {
+        let resolved_reports = self
+            .report_repo
+            .resolve_open_for_post(post_id, moderator_id, "dismissed")
+            .await?;
+        if resolved_reports == 0 {
+            return Err(anyhow!("未找到文章 {} 待处理的举报", post_id));
+        }
+
+        tracing::info!(
+            "管理员 {} 驳回了文章 {} 的 {} 条举报",
+            moderator_id,
+            post_id,
+            resolved_reports
+        );
+        Ok(ReportResolutionDto {
+            post_id,
+            resolved_reports,
+            ban: None,
+        })
+    }
+
+    // 从举报队列直接封禁文章，走与封禁接口相同的流程，成功后关闭该文章的举报
+    pub async fn ban_reported_post(
+        &self,
+        post_id: Uuid,
+        moderator_id: Uuid,
+        can_ban: bool,
+        payload: BanPostPayload,
+    ) -> Result<ReportResolutionDto> {
+        let ban = self
+            .post_service
+            .ban_post(post_id, moderator_id, can_ban, payload)
+            .await?;
+        let resolved_reports = self
+            .report_repo
+            .resolve_open_for_post(post_id, moderator_id, "actioned")
+            .await?;
+
+        Ok(ReportResolutionDto {
+            post_id,
+            resolved_reports,
+            ban: Some(ban),
+        })
+    }
+}
@@ -1,8 +1,11 @@
-use crate::dtos::tag::{CreateTagPayload, UpdateTagPayload};
-use crate::models::Tag;
+use crate::dtos::tag::{CreateTagAliasPayload, CreateTagPayload, UpdateTagPayload};
+use crate::models::{Tag, TagAlias};
 use crate::repositories::TagRepository;
+use crate::utils::VersionError;
 use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
 use slug::slugify;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -18,15 +21,21 @@ impl TagService {
     }
 
     /// 创建一个新的标签
-    pub async fn create_tag(&self, payload: CreateTagPayload) -> Result<Tag> {
+    ///
+    /// 名称对应的标签或别名已经存在时直接返回规范标签，第二个返回值表示是否新建了标签
+    pub async fn create_tag(&self, payload: CreateTagPayload) -> Result<(Tag, bool)> {
         // 1. 验证输入（例如：名称不能为空）
         let name = payload.name.trim();
         if name.is_empty() {
             return Err(anyhow!("标签名称不能为空"));
         }
 
-        // 2. 生成 slug
+        // 2. 生成 slug，"Rust"、"rust" 这类写法或已登记的别名都解析到已有的规范标签
         let slug = slugify(name);
+        if let Some(existing) = self.resolve_slug(&slug).await? {
+            tracing::info!("标签 '{}' 解析为已有标签 '{}'", name, existing.name);
+            return Ok((existing, false));
+        }
 
         // 3. （可选）检查名称或 slug 是否已存在。
         //    如果你的数据库表对 name 和 slug 字段设置了 UNIQUE 约束，
@@ -51,7 +60,7 @@ impl TagService {
         // 如果依赖数据库 UNIQUE 约束，这里需要处理可能的数据库错误。
         // ApiError 层会尝试将特定的数据库唯一约束错误转换为用户友好的 anyhow 错误。
 
-        Ok(tag)
+        Ok((tag, true))
     }
 
     /// 根据 ID 获取标签
@@ -67,24 +76,41 @@ impl TagService {
         tag_option.ok_or_else(|| anyhow!("未找到 ID 为 {} 的标签", id))
     }
 
-    /// 根据 Slug 获取标签
+    /// 根据 Slug 获取标签，别名的 slug 会解析到规范标签
     pub async fn get_tag_by_slug(&self, slug: &str) -> Result<Tag> {
         let tag_option = self
-            .repo
-            .get_by_slug(slug)
+            .resolve_slug(slug)
             .await
             .context(format!("Service 未能通过 slug ('{}') 获取标签", slug))?;
 
         tag_option.ok_or_else(|| anyhow!("未找到 Slug 为 '{}'", slug))
     }
 
+    /// 根据名称查找标签，名称不区分大小写和标点写法，别名会解析到规范标签
+    pub async fn find_tag_by_name(&self, name: &str) -> Result<Option<Tag>> {
+        self.resolve_slug(&slugify(name.trim())).await
+    }
+
+    // 先按标签自身的 slug 查找，找不到再按别名查找
+    async fn resolve_slug(&self, slug: &str) -> Result<Option<Tag>> {
+        if let Some(tag) = self.repo.get_by_slug(slug).await? {
+            return Ok(Some(tag));
+        }
+        self.repo.get_by_alias(slug).await
+    }
+
     /// 获取所有标签列表
     pub async fn list_tags(&self) -> Result<Vec<Tag>> {
         self.repo.list().await.context("Service 未能获取标签列表")
     }
 
     /// 更新标签
-    pub async fn update_tag(&self, id: Uuid, payload: UpdateTagPayload) -> Result<Tag> {
+    pub async fn update_tag(
+        &self,
+        id: Uuid,
+        payload: UpdateTagPayload,
+        expected_version: Option<DateTime<Utc>>,
+    ) -> Result<Tag> {
         let mut new_name_opt: Option<String> = None;
         let mut new_slug_opt: Option<String> = None;
 
@@ -114,19 +140,49 @@ impl TagService {
 
         // 2. 如果没有任何要更新的字段（这里只考虑 name），可以直接返回当前标签或错误
         if new_name_opt.is_none() {
-            return self
+            let current = self
                 .get_tag_by_id(id)
                 .await
-                .context("更新时未提供有效字段，尝试获取当前标签失败");
+                .context("更新时未提供有效字段，尝试获取当前标签失败")?;
+            // 即使没有修改，也要告知客户端其持有的版本已过期
+            if expected_version.is_some_and(|v| v != current.updated_at) {
+                return Err(VersionError::stale(current.updated_at).into());
+            }
+            return Ok(current);
         }
 
-        // 3. 调用仓库更新标签
+        // 3. 新名称不能是其他标签的别名；如果是自己的别名，改名后该别名就多余了
+        let existing_alias = match new_slug_opt.as_deref() {
+            Some(ns) => self.repo.find_alias(ns).await?,
+            None => None,
+        };
+        let mut redundant_alias = None;
+        if let Some(alias) = existing_alias {
+            if alias.tag_id != id {
+                return Err(anyhow!(
+                    "名称 '{}' 已存在：它是另一个标签的别名",
+                    alias.name
+                ));
+            }
+            redundant_alias = Some(alias.id);
+        }
+
+        // 4. 调用仓库更新标签
         let tag = self
             .repo
-            .update(id, new_name_opt.as_deref(), new_slug_opt.as_deref())
+            .update(
+                id,
+                new_name_opt.as_deref(),
+                new_slug_opt.as_deref(),
+                expected_version,
+            )
             .await
             .context(format!("Service 未能更新标签 (ID: {})", id))?;
 
+        if let Some(alias_id) = redundant_alias {
+            self.repo.delete_alias(id, alias_id).await?;
+        }
+
         Ok(tag)
     }
 
@@ -141,6 +197,59 @@ impl TagService {
             .context(format!("Service 未能删除标签 (ID: {})", id))
     }
 
+    // === 别名 ===
+
+    /// 获取标签的全部别名
+    pub async fn list_tag_aliases(&self, tag_id: Uuid) -> Result<Vec<TagAlias>> {
+        self.get_tag_by_id(tag_id).await?;
+        self.repo
+            .list_aliases(tag_id)
+            .await
+            .context(format!("Service 未能获取标签 {} 的别名", tag_id))
+    }
+
+    /// 为标签添加别名，之后按这个名称创建或查找标签都会得到该标签
+    pub async fn add_tag_alias(
+        &self,
+        tag_id: Uuid,
+        payload: CreateTagAliasPayload,
+    ) -> Result<TagAlias> {
+        let name = payload.name.trim();
+        let slug = slugify(name);
+        if slug.is_empty() {
+            return Err(anyhow!("无效的别名：名称不能为空"));
+        }
+
+        let tag = self.get_tag_by_id(tag_id).await?;
+        if slug == tag.slug {
+            return Err(anyhow!(
+                "无效的别名：'{}' 与标签 '{}' 的名称相同",
+                name,
+                tag.name
+            ));
+        }
+        if let Some(other) = self.repo.get_by_slug(&slug).await? {
+            return Err(anyhow!(
+                "标签 '{}' 已存在，请使用合并功能将它并入 '{}'",
+                other.name,
+                tag.name
+            ));
+        }
+        if let Some(alias) = self.repo.find_alias(&slug).await? {
+            return Err(anyhow!("别名 '{}' 已存在", alias.name));
+        }
+
+        self.repo
+            .create_alias(tag_id, name, &slug)
+            .await
+            .context(format!("Service 未能为标签 {} 添加别名 '{}'", tag_id, name))
+    }
+
+    /// 删除标签的某个别名
+    pub async fn remove_tag_alias(&self, tag_id: Uuid, alias_id: Uuid) -> Result<()> {
+        self.repo.delete_alias(tag_id, alias_id).await
+    }
+
     // === 管理员专用功能 ===
 
     /// 合并标签：将多个标签合并为一个目标标签
@@ -277,6 +386,13 @@ impl TagService {
 
         if source_tags.is_empty() {
             potential_issues.push("没有找到有效的源标签".to_string());
+        } else {
+            let source_names: Vec<_> = source_tags.iter().map(|t| t.name.clone()).collect();
+            potential_issues.push(format!(
+                "合并后以下名称将成为 '{}' 的别名：{}",
+                target_tag.name,
+                source_names.join(", ")
+            ));
         }
 
         let high_usage_tags: Vec<_> = posts_by_tag
@@ -313,6 +429,38 @@ impl TagService {
         Ok(deleted_count)
     }
 
+    /// 增强版批量删除标签：为失去全部标签的文章添加推荐的标签
+    pub async fn batch_delete_tags_enhanced(
+        &self,
+        tag_ids: &[Uuid],
+        suggested_tags: &HashMap<Uuid, Vec<Uuid>>,
+    ) -> Result<crate::dtos::tag::BatchDeleteTagsResponse> {
+        for &tag_id in tag_ids {
+            self.get_tag_by_id(tag_id)
+                .await
+                .context(format!("批量删除：标签 {} 不存在", tag_id))?;
+        }
+
+        let (deleted_count, affected_post_count, orphaned_post_count, auto_assigned_post_count) =
+            self.repo
+                .batch_delete_enhanced(tag_ids, true, suggested_tags)
+                .await
+                .context("增强版批量删除标签失败")?;
+
+        let operation_summary = format!(
+            "成功删除 {} 个标签，影响了 {} 篇文章，其中 {} 篇文章失去了全部标签，已为 {} 篇添加推荐的标签",
+            deleted_count, affected_post_count, orphaned_post_count, auto_assigned_post_count
+        );
+
+        Ok(crate::dtos::tag::BatchDeleteTagsResponse {
+            deleted_count,
+            affected_post_count,
+            orphaned_post_count,
+            auto_assigned_post_count,
+            operation_summary,
+        })
+    }
+
     /// 获取标签使用统计（包含多少篇文章）
     pub async fn get_tag_usage_stats(&self) -> Result<Vec<TagUsageStats>> {
         self.repo
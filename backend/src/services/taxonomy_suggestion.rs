@@ -0,0 +1,274 @@
+use crate::dtos::post::{
+    CategorySuggestionDto, SuggestTaxonomyPayload, TagSuggestionDto, TaxonomySuggestionsResponse,
+};
+use crate::models::{Category, Tag};
+use crate::repositories::post::PostTaxonomyDocument;
+use crate::repositories::{CategoryRepository, PostRepository, TagRepository};
+use crate::utils::suggestion::{SuggestionModel, TaxonomyLabel};
+use anyhow::{Context, Result, anyhow};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+// 标签和分类推荐服务
+// 训练好的模型按训练数据的版本号缓存：文章、标签、分类或别名发生变化时数据库中的版本号递增，
+// 下一次推荐会重新训练，因此刚刚打上的标签、新建的别名仍然立即生效
+
+/// 编辑器中最多展示的推荐数
+const MAX_EDITOR_SUGGESTIONS: usize = 5;
+/// 孤儿文章最多自动添加的标签数
+const MAX_ORPHAN_TAGS: usize = 3;
+/// 自动应用推荐时要求的最低分数，低于它的推荐只在编辑器中展示
+const MIN_AUTO_APPLY_SCORE: f64 = 0.2;
+
+// 某个版本的训练数据训练出的模型，以及生成推荐结果时需要的标签和分类
+struct TrainedModels {
+    version: i64,
+    tag_model: SuggestionModel,
+    category_model: SuggestionModel,
+    tags_by_id: HashMap<Uuid, Tag>,
+    categories_by_id: HashMap<Uuid, Category>,
+}
+
+#[derive(Clone)]
+pub struct TaxonomySuggestionService {
+    post_repo: Arc<dyn PostRepository>,
+    tag_repo: Arc<dyn TagRepository>,
+    category_repo: Arc<dyn CategoryRepository>,
+    // 用 tokio 的 Mutex：版本号变化后只有一个请求重新训练，其他请求等待它的结果
+    models: Arc<Mutex<Option<Arc<TrainedModels>>>>,
+}
+
+impl TaxonomySuggestionService {
+    pub fn new(
+        post_repo: Arc<dyn PostRepository>,
+        tag_repo: Arc<dyn TagRepository>,
+        category_repo: Arc<dyn CategoryRepository>,
+    ) -> Self {
+        Self {
+            post_repo,
+            tag_repo,
+            category_repo,
+            models: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    // 编辑器中为文章推荐标签和分类
+    // can_edit_any: 用户是否拥有 post:edit_any 权限，否则只能为自己的文章获取推荐
+    pub async fn suggest_for_post(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        can_edit_any: bool,
+        payload: SuggestTaxonomyPayload,
+    ) -> Result<TaxonomySuggestionsResponse> {
+        let post = self
+            .post_repo
+            .get_by_id(post_id)
+            .await
+            .context("获取文章信息失败")?
+            .ok_or_else(|| anyhow!("未找到 ID 为 {} 的帖子", post_id))?;
+
+        if !can_edit_any && !self.post_repo.is_author(post.id, user_id).await? {
+            return Err(anyhow!("您只能为自己的文章获取标签和分类推荐"));
+        }
+
+        // 编辑器提交的内容优先，未提交的部分使用已保存的内容
+        let title = payload.title.unwrap_or(post.title);
+        let content = payload.content.unwrap_or(post.content);
+
+        // 文章已有的标签和分类不再推荐（模型中包含这篇文章，它们的质心总是与文章相似）
+        let current_tag_ids: Vec<Uuid> = self
+            .post_repo
+            .get_tags_for_post(post_id)
+            .await?
+            .into_iter()
+            .map(|tag| tag.id)
+            .collect();
+        let current_category_ids: Vec<Uuid> = self
+            .post_repo
+            .get_categories_for_post(post_id)
+            .await?
+            .into_iter()
+            .map(|category| category.id)
+            .collect();
+
+        let models = self.models().await?;
+        let tag_suggestions = models
+            .tag_model
+            .suggest(&title, &content, &current_tag_ids, MAX_EDITOR_SUGGESTIONS)
+            .into_iter()
+            .filter_map(|suggestion| {
+                let tag = models.tags_by_id.get(&suggestion.id)?.clone();
+                Some(TagSuggestionDto {
+                    tag: tag.into(),
+                    score: suggestion.score,
+                    reasons: suggestion.reasons,
+                })
+            })
+            .collect();
+
+        let category_suggestions = models
+            .category_model
+            .suggest(
+                &title,
+                &content,
+                &current_category_ids,
+                MAX_EDITOR_SUGGESTIONS,
+            )
+            .into_iter()
+            .filter_map(|suggestion| {
+                let category = models.categories_by_id.get(&suggestion.id)?.clone();
+                Some(CategorySuggestionDto {
+                    category: category.into(),
+                    score: suggestion.score,
+                    reasons: suggestion.reasons,
+                })
+            })
+            .collect();
+
+        Ok(TaxonomySuggestionsResponse {
+            tags: tag_suggestions,
+            categories: category_suggestions,
+        })
+    }
+
+    /// 删除这些标签后会失去全部标签的文章，以及为它们推荐的标签（文章 ID -> 标签 ID）
+    pub async fn suggest_tags_for_orphans(
+        &self,
+        removed_tag_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<Uuid>>> {
+        let documents = self.post_repo.list_taxonomy_documents().await?;
+        let models = self.models().await?;
+
+        Ok(suggest_for_orphans(
+            &documents,
+            &models.tag_model,
+            removed_tag_ids,
+            |document| &document.tag_ids,
+            MAX_ORPHAN_TAGS,
+        ))
+    }
+
+    /// 删除这些分类后会失去全部分类的文章，以及为它们推荐的分类（每篇文章至多一个）
+    pub async fn suggest_categories_for_orphans(
+        &self,
+        removed_category_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<Uuid>>> {
+        let documents = self.post_repo.list_taxonomy_documents().await?;
+        let models = self.models().await?;
+
+        Ok(suggest_for_orphans(
+            &documents,
+            &models.category_model,
+            removed_category_ids,
+            |document| &document.category_ids,
+            1,
+        ))
+    }
+
+    // 返回与当前训练数据版本一致的模型，版本号变化后重新训练
+    async fn models(&self) -> Result<Arc<TrainedModels>> {
+        let mut cached = self.models.lock().await;
+        let version = self.post_repo.taxonomy_version().await?;
+        if let Some(models) = cached.as_ref().filter(|models| models.version == version) {
+            return Ok(models.clone());
+        }
+
+        // 先读版本号再读数据：训练期间数据又发生变化时，缓存的版本号偏旧，下次会再训练一次
+        let documents = self.post_repo.list_taxonomy_documents().await?;
+        let tags = self.tag_repo.list().await?;
+        let categories = self.category_repo.list().await?;
+        let models = Arc::new(TrainedModels {
+            version,
+            tag_model: train(&documents, self.tag_labels(&tags).await?, |document| {
+                &document.tag_ids
+            }),
+            category_model: train(&documents, category_labels(&categories), |document| {
+                &document.category_ids
+            }),
+            tags_by_id: tags.into_iter().map(|tag| (tag.id, tag)).collect(),
+            categories_by_id: categories
+                .into_iter()
+                .map(|category| (category.id, category))
+                .collect(),
+        });
+        tracing::debug!("推荐模型已按版本 {} 重新训练", version);
+
+        *cached = Some(models.clone());
+        Ok(models)
+    }
+
+    // 标签的名称和别名都参与关键词匹配
+    async fn tag_labels(&self, tags: &[Tag]) -> Result<Vec<TaxonomyLabel>> {
+        let mut aliases: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for alias in self.tag_repo.list_all_aliases().await? {
+            aliases.entry(alias.tag_id).or_default().push(alias.name);
+        }
+
+        Ok(tags
+            .iter()
+            .map(|tag| {
+                let mut names = vec![tag.name.clone()];
+                names.extend(aliases.remove(&tag.id).unwrap_or_default());
+                TaxonomyLabel { id: tag.id, names }
+            })
+            .collect())
+    }
+}
+
+fn category_labels(categories: &[Category]) -> Vec<TaxonomyLabel> {
+    categories
+        .iter()
+        .map(|category| TaxonomyLabel {
+            id: category.id,
+            names: vec![category.name.clone()],
+        })
+        .collect()
+}
+
+// labels_of 取出文章上与模型对应的标签或分类
+fn train(
+    documents: &[PostTaxonomyDocument],
+    labels: Vec<TaxonomyLabel>,
+    labels_of: fn(&PostTaxonomyDocument) -> &[Uuid],
+) -> SuggestionModel {
+    let texts: Vec<String> = documents
+        .iter()
+        .map(|document| format!("{}\n{}", document.title, document.content))
+        .collect();
+    SuggestionModel::train(
+        texts
+            .iter()
+            .zip(documents)
+            .map(|(text, document)| (text.as_str(), labels_of(document))),
+        labels,
+    )
+}
+
+// 只为原本有标签（分类）且全部被移除的文章推荐，不会推荐被移除的标签（分类），分数过低的推荐不会自动应用
+fn suggest_for_orphans(
+    documents: &[PostTaxonomyDocument],
+    model: &SuggestionModel,
+    removed_ids: &[Uuid],
+    labels_of: fn(&PostTaxonomyDocument) -> &[Uuid],
+    limit: usize,
+) -> HashMap<Uuid, Vec<Uuid>> {
+    documents
+        .iter()
+        .filter(|document| {
+            let labels = labels_of(document);
+            !labels.is_empty() && labels.iter().all(|id| removed_ids.contains(id))
+        })
+        .filter_map(|document| {
+            let suggested: Vec<Uuid> = model
+                .suggest(&document.title, &document.content, removed_ids, limit)
+                .into_iter()
+                .filter(|suggestion| suggestion.score >= MIN_AUTO_APPLY_SCORE)
+                .map(|suggestion| suggestion.id)
+                .collect();
+            (!suggested.is_empty()).then_some((document.id, suggested))
+        })
+        .collect()
+}
@@ -0,0 +1,129 @@
+use crate::config::TrashConfig;
+use crate::dtos::trash::TrashDto;
+use crate::repositories::{CategoryRepository, PostRepository, TagRepository};
+use anyhow::{Context, Result, anyhow};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// 回收站服务
+// 文章、分类、标签删除后进入回收站，保留期内可以恢复，过期后由后台任务彻底删除
+#[derive(Clone)]
+pub struct TrashService {
+    post_repo: Arc<dyn PostRepository>,
+    category_repo: Arc<dyn CategoryRepository>,
+    tag_repo: Arc<dyn TagRepository>,
+    retention_days: i32,
+}
+
+impl TrashService {
+    pub fn new(
+        post_repo: Arc<dyn PostRepository>,
+        category_repo: Arc<dyn CategoryRepository>,
+        tag_repo: Arc<dyn TagRepository>,
+        config: &TrashConfig,
+    ) -> Self {
+        Self {
+            post_repo,
+            category_repo,
+            tag_repo,
+            retention_days: config.retention_days,
+        }
+    }
+
+    // 列出当前用户可见的回收站内容
+    // 文章：拥有 post:delete_any 可看到全部，只有 post:delete_own 时只能看到自己的文章
+    // 分类、标签：分别需要 category:manage / tag:manage 权限
+    pub async fn list_trash(
+        &self,
+        user_id: Uuid,
+        can_delete_any_post: bool,
+        can_delete_own_post: bool,
+        can_manage_categories: bool,
+        can_manage_tags: bool,
+    ) -> Result<TrashDto> {
+        let posts = if can_delete_any_post {
+            self.post_repo.list_deleted(None).await?
+        } else if can_delete_own_post {
+            self.post_repo.list_deleted(Some(user_id)).await?
+        } else {
+            Vec::new()
+        };
+
+        let categories = if can_manage_categories {
+            self.category_repo.list_deleted().await?
+        } else {
+            Vec::new()
+        };
+
+        let tags = if can_manage_tags {
+            self.tag_repo.list_deleted().await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(TrashDto {
+            posts,
+            categories,
+            tags,
+            retention_days: self.retention_days,
+        })
+    }
+
+    // 从回收站恢复文章
+    // can_delete_any: 用户是否拥有 post:delete_any 权限，否则只能恢复自己的文章
+    pub async fn restore_post(&self, id: Uuid, user_id: Uuid, can_delete_any: bool) -> Result<()> {
+        let post = self
+            .post_repo
+            .get_deleted_by_id(id)
+            .await
+            .context("获取回收站文章失败")?
+            .ok_or_else(|| anyhow!("回收站中未找到文章 (id: {})", id))?;
+
+        if !can_delete_any && post.author_id != Some(user_id) {
+            return Err(anyhow!("您只能恢复自己的文章"));
+        }
+
+        self.post_repo
+            .restore(id)
+            .await
+            .context(format!("恢复文章 {} 失败", id))?;
+        tracing::info!("用户 {} 从回收站恢复了文章 {}", user_id, id);
+        Ok(())
+    }
+
+    // 从回收站恢复分类
+    pub async fn restore_category(&self, id: Uuid) -> Result<()> {
+        self.category_repo.restore(id).await?;
+        tracing::info!("已从回收站恢复分类 {}", id);
+        Ok(())
+    }
+
+    // 从回收站恢复标签
+    pub async fn restore_tag(&self, id: Uuid) -> Result<()> {
+        self.tag_repo.restore(id).await?;
+        tracing::info!("已从回收站恢复标签 {}", id);
+        Ok(())
+    }
+
+    // 彻底删除超过保留期的条目，由后台任务定期调用
+    pub async fn purge_expired(&self) -> Result<u64> {
+        let posts = self.post_repo.purge_deleted(self.retention_days).await?;
+        let categories = self
+            .category_repo
+            .purge_deleted(self.retention_days)
+            .await?;
+        let tags = self.tag_repo.purge_deleted(self.retention_days).await?;
+
+        let purged = posts + categories + tags;
+        if purged > 0 {
+            tracing::info!(
+                "已彻底删除回收站中超过 {} 天的条目：文章 {} 篇，分类 {} 个，标签 {} 个",
+                self.retention_days,
+                posts,
+                categories,
+                tags
+            );
+        }
+        Ok(purged)
+    }
+}
@@ -1,14 +1,34 @@
 use crate::dtos::user::{
-    ChangePasswordPayload, CreateUserPayload, ResetUserPasswordPayload, UpdateUserPayload,
-    UserStatsResponse,
+    ChangePasswordPayload, CreateUserPayload, MyProfileResponse, ResetUserPasswordPayload,
+    UpdateUserPayload, UserStatsResponse,
+};
+use crate::models::{SocialLink, UserAvatar, UserProfile};
+use crate::utils::{
+    hash_password, markdown_to_html_safe, validate_password_strength, verify_password,
 };
-use crate::utils::{hash_password, validate_password_strength, verify_password};
 use crate::{dtos::user::UpdateProfilePayload, models::UserPublic, repositories::UserRepository};
 use anyhow::{Result, anyhow};
 use sqlx;
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
 
+// 显示名称的最大长度（字符数）
+const MAX_DISPLAY_NAME_LENGTH: usize = 50;
+// 简介的最大长度（字符数）
+const MAX_BIO_LENGTH: usize = 2000;
+// 头像、个人网站和社交链接地址的最大长度
+const MAX_PROFILE_URL_LENGTH: usize = 500;
+// 最多的社交链接数
+const MAX_SOCIAL_LINKS: usize = 10;
+// 支持的社交平台
+const SOCIAL_PLATFORMS: [&str; 12] = [
+    "github", "gitlab", "twitter", "mastodon", "linkedin", "weibo", "zhihu", "bilibili", "youtube",
+    "telegram", "juejin", "rss",
+];
+// 上传头像的最大字节数
+pub const MAX_AVATAR_BYTES: usize = 1024 * 1024;
+
 #[derive(Clone)]
 pub struct UserService {
     user_repo: Arc<dyn UserRepository>,
@@ -19,8 +39,8 @@ impl UserService {
         Self { user_repo }
     }
 
-    // 获取当前用户的公开信息
-    pub async fn get_my_profile(&self, user_id: Uuid) -> Result<UserPublic> {
+    // 获取当前用户的公开信息和个人资料
+    pub async fn get_my_profile(&self, user_id: Uuid) -> Result<MyProfileResponse> {
         let user = self
             .user_repo
             .find_by_id(user_id)
@@ -28,13 +48,17 @@ impl UserService {
             .ok_or_else(|| anyhow!("无法找到当前用户的信息"))?;
 
         let roles = self.user_repo.get_user_roles(user.id).await?;
-
-        Ok(UserPublic {
-            id: user.id,
-            username: user.username,
-            email: user.email,
-            created_at: user.created_at,
-            roles: roles.into_iter().map(|r| r.name).collect(),
+        let profile = self.user_repo.find_profile(user.id).await?;
+
+        Ok(MyProfileResponse {
+            user: UserPublic {
+                id: user.id,
+                username: user.username,
+                email: user.email,
+                created_at: user.created_at,
+                roles: roles.into_iter().map(|r| r.name).collect(),
+            },
+            profile,
         })
     }
 
@@ -43,7 +67,7 @@ impl UserService {
         &self,
         user_id: Uuid,
         payload: UpdateProfilePayload,
-    ) -> Result<UserPublic> {
+    ) -> Result<MyProfileResponse> {
         // 业务逻辑检查：如果要更新用户名或邮箱，检查新的值是否已被其他用户占用
         if let Some(new_username) = &payload.username {
             if let Some(existing_user) = self.user_repo.find_by_username(new_username).await? {
@@ -60,16 +84,211 @@ impl UserService {
             }
         }
 
-        let updated_user = self.user_repo.update(user_id, &payload).await?;
-        let roles = self.user_repo.get_user_roles(updated_user.id).await?;
+        // 先校验资料字段，避免账户信息已更新而资料校验失败
+        let current_profile = self.user_repo.find_profile(user_id).await?;
+        let profile = Self::apply_profile_changes(current_profile.clone(), &payload)?;
 
-        Ok(UserPublic {
-            id: updated_user.id,
-            username: updated_user.username,
-            email: updated_user.email,
-            created_at: updated_user.created_at,
-            roles: roles.into_iter().map(|r| r.name).collect(),
-        })
+        self.user_repo.update(user_id, &payload).await?;
+
+        // 头像换成外部地址或被清除时，删除已上传的头像
+        if profile.avatar_url != current_profile.avatar_url
+            && current_profile
+                .avatar_url
+                .as_deref()
+                .is_some_and(|url| Self::is_uploaded_avatar_url(user_id, url))
+        {
+            self.user_repo.delete_avatar(user_id).await?;
+        }
+        self.user_repo.save_profile(user_id, &profile).await?;
+
+        self.get_my_profile(user_id).await
+    }
+
+    // 校验并合并资料字段的修改，空字符串表示清除该字段
+    fn apply_profile_changes(
+        mut profile: UserProfile,
+        payload: &UpdateProfilePayload,
+    ) -> Result<UserProfile> {
+        if let Some(display_name) = &payload.display_name {
+            let display_name = display_name.trim();
+            if display_name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+                return Err(anyhow!(
+                    "无效的显示名称：不能超过 {} 个字符",
+                    MAX_DISPLAY_NAME_LENGTH
+                ));
+            }
+            if display_name.chars().any(char::is_control) {
+                return Err(anyhow!("无效的显示名称：不能包含控制字符"));
+            }
+            profile.display_name = Some(display_name.to_string()).filter(|name| !name.is_empty());
+        }
+
+        if let Some(bio) = &payload.bio {
+            let bio = bio.trim();
+            if bio.chars().count() > MAX_BIO_LENGTH {
+                return Err(anyhow!("无效的简介：不能超过 {} 个字符", MAX_BIO_LENGTH));
+            }
+            if bio.is_empty() {
+                profile.bio = None;
+                profile.bio_html = None;
+            } else {
+                profile.bio_html = Some(markdown_to_html_safe(bio));
+                profile.bio = Some(bio.to_string());
+            }
+        }
+
+        if let Some(avatar_url) = &payload.avatar_url {
+            let avatar_url = avatar_url.trim();
+            // 原样提交当前的头像地址（包括上传头像的地址）时保持不变
+            if profile.avatar_url.as_deref() != Some(avatar_url) {
+                if !avatar_url.is_empty() {
+                    Self::validate_profile_url("头像地址", avatar_url)?;
+                }
+                profile.avatar_url = Some(avatar_url.to_string()).filter(|url| !url.is_empty());
+            }
+        }
+
+        if let Some(website) = &payload.website {
+            let website = website.trim();
+            if !website.is_empty() {
+                Self::validate_profile_url("个人网站", website)?;
+            }
+            profile.website = Some(website.to_string()).filter(|url| !url.is_empty());
+        }
+
+        if let Some(social_links) = &payload.social_links {
+            if social_links.len() > MAX_SOCIAL_LINKS {
+                return Err(anyhow!("无效的社交链接：最多 {} 个", MAX_SOCIAL_LINKS));
+            }
+            let mut platforms = HashSet::new();
+            let mut links = Vec::with_capacity(social_links.len());
+            for link in social_links {
+                let platform = link.platform.trim().to_lowercase();
+                if !SOCIAL_PLATFORMS.contains(&platform.as_str()) {
+                    return Err(anyhow!(
+                        "无效的社交平台 '{}'，支持的平台：{}",
+                        link.platform,
+                        SOCIAL_PLATFORMS.join(", ")
+                    ));
+                }
+                if !platforms.insert(platform.clone()) {
+                    return Err(anyhow!("无效的社交链接：平台 {} 重复", platform));
+                }
+                let url = link.url.trim();
+                Self::validate_profile_url(&format!("{} 链接", platform), url)?;
+                links.push(SocialLink {
+                    platform,
+                    url: url.to_string(),
+                });
+            }
+            profile.social_links = links;
+        }
+
+        Ok(profile)
+    }
+
+    // 资料中的地址只允许 http 和 https，避免 javascript: 等协议在前端被当作链接执行
+    fn validate_profile_url(field: &str, url: &str) -> Result<()> {
+        if url.len() > MAX_PROFILE_URL_LENGTH {
+            return Err(anyhow!(
+                "无效的{}：不能超过 {} 个字符",
+                field,
+                MAX_PROFILE_URL_LENGTH
+            ));
+        }
+        if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(anyhow!("无效的{}：不能包含空白字符", field));
+        }
+        let lower = url.to_ascii_lowercase();
+        let rest = lower
+            .strip_prefix("https://")
+            .or_else(|| lower.strip_prefix("http://"))
+            .ok_or_else(|| anyhow!("无效的{}：只支持 http 或 https 地址", field))?;
+        let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+        if host.is_empty() || host.contains('@') {
+            return Err(anyhow!("无效的{}：缺少主机名", field));
+        }
+        Ok(())
+    }
+
+    // 上传头像的访问路径，资料中的地址还会带上保存时间，以便浏览器在更换头像后重新获取
+    fn uploaded_avatar_path(user_id: Uuid) -> String {
+        format!("/users/{}/avatar", user_id)
+    }
+
+    fn is_uploaded_avatar_url(user_id: Uuid, url: &str) -> bool {
+        url.split('?').next() == Some(Self::uploaded_avatar_path(user_id).as_str())
+    }
+
+    // 上传头像，按文件内容识别图片格式，不信任客户端提供的类型
+    pub async fn upload_avatar(&self, user_id: Uuid, data: &[u8]) -> Result<MyProfileResponse> {
+        if data.is_empty() {
+            return Err(anyhow!("无效的头像：文件为空"));
+        }
+        if data.len() > MAX_AVATAR_BYTES {
+            return Err(anyhow!(
+                "无效的头像：文件不能超过 {} KB",
+                MAX_AVATAR_BYTES / 1024
+            ));
+        }
+        let content_type = Self::detect_image_type(data)
+            .ok_or_else(|| anyhow!("无效的头像格式：只支持 PNG、JPEG、GIF 和 WebP 图片"))?;
+
+        let saved_at = self
+            .user_repo
+            .save_avatar(user_id, content_type, data)
+            .await?;
+
+        let mut profile = self.user_repo.find_profile(user_id).await?;
+        profile.avatar_url = Some(format!(
+            "{}?v={}",
+            Self::uploaded_avatar_path(user_id),
+            saved_at.timestamp()
+        ));
+        self.user_repo.save_profile(user_id, &profile).await?;
+
+        tracing::info!("用户 {} 上传了新头像（{} 字节）", user_id, data.len());
+        self.get_my_profile(user_id).await
+    }
+
+    // 删除上传的头像；资料中使用的是上传头像时一并清除
+    pub async fn delete_avatar(&self, user_id: Uuid) -> Result<MyProfileResponse> {
+        self.user_repo.delete_avatar(user_id).await?;
+
+        let mut profile = self.user_repo.find_profile(user_id).await?;
+        if profile
+            .avatar_url
+            .as_deref()
+            .is_some_and(|url| Self::is_uploaded_avatar_url(user_id, url))
+        {
+            profile.avatar_url = None;
+            self.user_repo.save_profile(user_id, &profile).await?;
+        }
+
+        self.get_my_profile(user_id).await
+    }
+
+    // 获取用户上传的头像
+    pub async fn get_avatar(&self, user_id: Uuid) -> Result<UserAvatar> {
+        self.user_repo
+            .find_avatar(user_id)
+            .await?
+            .ok_or_else(|| anyhow!("未找到用户 {} 的头像", user_id))
+    }
+
+    // 根据文件头识别图片格式
+    fn detect_image_type(data: &[u8]) -> Option<&'static str> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some("image/png")
+        } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some("image/jpeg")
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            Some("image/gif")
+        } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+            Some("image/webp")
+        } else {
+            None
+        }
     }
 
     // 删除当前用户的账户
@@ -219,6 +438,7 @@ impl UserService {
         let update_payload = UpdateProfilePayload {
             username: payload.username.clone(),
             email: payload.email.clone(),
+            ..Default::default()
         };
 
         // 更新基本信息
@@ -322,31 +542,35 @@ impl UserService {
     pub async fn get_user_stats(&self, user_id: Uuid) -> Result<UserStatsResponse> {
         let pool = self.user_repo.get_pool();
 
-        // 获取用户自己的文章统计
+        // 获取用户自己的文章统计（包括合写的文章）
         let user_posts_result = sqlx::query!(
             "SELECT 
                 COUNT(*) as total_posts,
                 COUNT(CASE WHEN published_at IS NOT NULL THEN 1 END) as published_posts,
-                COUNT(CASE WHEN published_at IS NULL THEN 1 END) as draft_posts
-             FROM posts WHERE author_id = $1",
+                COUNT(CASE WHEN published_at IS NULL THEN 1 END) as draft_posts,
+                COUNT(CASE WHEN author_id IS DISTINCT FROM $1 THEN 1 END) as co_authored_posts
+             FROM posts WHERE is_post_author(id, $1) AND deleted_at IS NULL",
             user_id
         )
         .fetch_one(pool)
         .await?;
 
         // 获取全局分类和标签数量
-        let categories_result = sqlx::query!("SELECT COUNT(*) as count FROM categories")
-            .fetch_one(pool)
-            .await?;
+        let categories_result =
+            sqlx::query!("SELECT COUNT(*) as count FROM categories WHERE deleted_at IS NULL")
+                .fetch_one(pool)
+                .await?;
 
-        let tags_result = sqlx::query!("SELECT COUNT(*) as count FROM tags")
-            .fetch_one(pool)
-            .await?;
+        let tags_result =
+            sqlx::query!("SELECT COUNT(*) as count FROM tags WHERE deleted_at IS NULL")
+                .fetch_one(pool)
+                .await?;
 
         Ok(UserStatsResponse {
             total_posts: user_posts_result.total_posts.unwrap_or(0),
             published_posts: user_posts_result.published_posts.unwrap_or(0),
             draft_posts: user_posts_result.draft_posts.unwrap_or(0),
+            co_authored_posts: user_posts_result.co_authored_posts.unwrap_or(0),
             total_categories: categories_result.count.unwrap_or(0),
             total_tags: tags_result.count.unwrap_or(0),
         })
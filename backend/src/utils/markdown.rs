@@ -1,4 +1,15 @@
-use comrak::{markdown_to_html, ComrakOptions};
+use crate::config::{MarkdownConfig, RenderProfileConfig};
+use crate::utils::sanitize::sanitize_html;
+use crate::utils::shortcode::ShortcodeRegistry;
+use anyhow::{anyhow, Result};
+use comrak::adapters::SyntaxHighlighterAdapter;
+use comrak::nodes::{AstNode, NodeValue};
+use comrak::plugins::syntect::{SyntectAdapter, SyntectAdapterBuilder};
+use comrak::{
+    format_html_with_plugins, markdown_to_html, parse_document, Arena, ComrakOptions, Plugins,
+};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
 
 /// 返回一个配置好安全选项和扩展的 ComrakOptions 实例。
 fn get_comrak_options() -> ComrakOptions<'static> {
@@ -10,6 +21,7 @@ fn get_comrak_options() -> ComrakOptions<'static> {
     options.extension.tasklist = true; // 允许任务列表
     options.extension.footnotes = true; // 允许脚注
     options.extension.description_lists = true; // 允许描述列表
+    options.extension.math_dollars = true; // 允许 $...$ 和 $$...$$ 数学公式
     // 未来可以在这里统一管理所有 Markdown 相关的配置
     options
 }
@@ -20,13 +32,424 @@ fn get_comrak_options() -> ComrakOptions<'static> {
 /// 并启用了一些常用的 GFM (GitHub Flavored Markdown) 扩展。
 pub fn markdown_to_html_safe(markdown_input: &str) -> String {
     let options = get_comrak_options();
-    markdown_to_html(markdown_input, &options)
+    tag_math_and_diagrams(markdown_to_html(markdown_input, &options))
+}
+
+// comrak 输出公式和图表时使用的标签，以及替换后供前端挂载 KaTeX / mermaid 的类名
+const MATH_AND_DIAGRAM_TAGS: [(&str, &str); 4] = [
+    (
+        "<span data-math-style=\"inline\">",
+        "<span class=\"math math-inline\">",
+    ),
+    (
+        "<span data-math-style=\"display\">",
+        "<span class=\"math math-display\">",
+    ),
+    (
+        "<code class=\"language-math\" data-math-style=\"display\">",
+        "<code class=\"language-math math math-display\">",
+    ),
+    (
+        "<code class=\"language-mermaid\">",
+        "<code class=\"language-mermaid mermaid\">",
+    ),
+];
+
+// 代码块语言为 mermaid 时按图表处理，不做高亮也不按行包裹
+const MERMAID_LANGUAGE: &str = "mermaid";
+
+// syntect 输出的高亮类名统一加上的前缀，前端生成主题 CSS 时使用同样的前缀
+const HIGHLIGHT_CLASS_PREFIX: &str = "hl-";
+
+/// 给公式（`$...$`、`$$...$$`、```` ```math ````）和 mermaid 图表加上类名，供前端渲染。
+///
+/// 公式和图表的内容已由 comrak 转义；原始 HTML 被禁用或已经过白名单清理，
+/// 替换只会增加类名，不会引入新的标签或属性。
+fn tag_math_and_diagrams(mut html: String) -> String {
+    for (from, to) in MATH_AND_DIAGRAM_TAGS {
+        if html.contains(from) {
+            html = html.replace(from, to);
+        }
+    }
+    html
+}
+
+/// 根据渲染方案生成 ComrakOptions。
+///
+/// 只有开启 `sanitized_html` 的方案才允许原始 HTML，渲染结果之后必须经过 [`sanitize_html`] 清理。
+fn profile_options(profile: &RenderProfileConfig) -> ComrakOptions<'static> {
+    let mut options = ComrakOptions::default();
+
+    options.render.unsafe_ = profile.sanitized_html;
+    options.render.hardbreaks = profile.hard_breaks;
+    options.parse.smart = profile.smart_punctuation;
+    options.extension.strikethrough = profile.strikethrough;
+    options.extension.table = profile.table;
+    options.extension.tasklist = profile.tasklist;
+    options.extension.footnotes = profile.footnotes;
+    options.extension.description_lists = profile.description_lists;
+    options.extension.math_dollars = profile.math;
+    options.extension.wikilinks_title_after_pipe = profile.wikilinks;
+    options.extension.autolink = profile.autolink;
+    options
+}
+
+/// 使用允许原始 HTML 的渲染方案所需的权限
+pub const RAW_HTML_PERMISSION: &str = "post:raw_html";
+
+// 一个渲染方案的 comrak 选项和使用限制
+struct RenderProfile {
+    options: ComrakOptions<'static>,
+    sanitized_html: bool,
+    allowed_roles: Vec<String>,
+}
+
+/// 带服务端代码高亮、嵌入短代码和渲染方案的 Markdown 渲染器。
+///
+/// 每篇文章按自己的渲染方案渲染，未指定方案时使用配置中的默认方案。
+/// 加载语法定义的开销较大，渲染器应在启动时创建一次并共享使用。
+pub struct MarkdownRenderer {
+    profiles: HashMap<String, RenderProfile>,
+    default_profile: String,
+    highlighter: CodeHighlighter,
+    shortcodes: ShortcodeRegistry,
+}
+
+impl MarkdownRenderer {
+    pub fn new(config: &MarkdownConfig) -> Self {
+        let mut profiles: HashMap<String, RenderProfile> = config
+            .profiles
+            .iter()
+            .map(|profile| {
+                (
+                    profile.name.clone(),
+                    RenderProfile {
+                        options: profile_options(profile),
+                        sanitized_html: profile.sanitized_html,
+                        allowed_roles: profile.allowed_roles.clone(),
+                    },
+                )
+            })
+            .collect();
+        if !profiles.contains_key(&config.default_profile) {
+            tracing::error!(
+                "默认渲染方案 {} 未定义，已使用内置的安全配置",
+                config.default_profile
+            );
+            // 维基链接只在渲染器中开启，由渲染器决定链接指向哪里
+            let mut options = get_comrak_options();
+            options.extension.wikilinks_title_after_pipe = true;
+            profiles.insert(
+                config.default_profile.clone(),
+                RenderProfile {
+                    options,
+                    sanitized_html: false,
+                    allowed_roles: Vec::new(),
+                },
+            );
+        }
+
+        Self {
+            profiles,
+            default_profile: config.default_profile.clone(),
+            highlighter: CodeHighlighter::new(config),
+            shortcodes: ShortcodeRegistry::new(&config.shortcodes),
+        }
+    }
+
+    // 查找渲染方案；未指定或方案已从配置中移除时使用默认方案
+    fn profile(&self, name: Option<&str>) -> &RenderProfile {
+        name.and_then(|name| self.profiles.get(name))
+            .unwrap_or_else(|| &self.profiles[&self.default_profile])
+    }
+
+    /// 检查拥有 `roles` 和 `permissions` 的用户能否使用渲染方案，`None` 表示默认方案。
+    /// 允许原始 HTML 的方案还要求 [`RAW_HTML_PERMISSION`] 权限
+    pub fn check_profile(
+        &self,
+        name: Option<&str>,
+        roles: &[String],
+        permissions: &[String],
+    ) -> Result<()> {
+        let name = name.unwrap_or(&self.default_profile);
+        let profile = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| anyhow!("无效的渲染方案: {}", name))?;
+        if profile.sanitized_html && !permissions.iter().any(|p| p == RAW_HTML_PERMISSION) {
+            return Err(anyhow!(
+                "没有使用渲染方案 {} 的权限：需要 {} 权限",
+                name,
+                RAW_HTML_PERMISSION
+            ));
+        }
+        if !profile.allowed_roles.is_empty()
+            && !profile
+                .allowed_roles
+                .iter()
+                .any(|role| roles.contains(role))
+        {
+            return Err(anyhow!("没有使用渲染方案 {} 的权限", name));
+        }
+        Ok(())
+    }
+
+    /// 用默认方案将 Markdown 字符串转换为 HTML 字符串，高亮白名单语言的代码块并展开短代码。
+    /// 维基链接全部按普通文本输出。
+    pub fn render(&self, markdown_input: &str) -> String {
+        self.render_with_wiki_links(markdown_input, None, &HashSet::new())
+    }
+
+    /// 用指定的渲染方案渲染，`resolved_slugs` 中的维基链接渲染为指向文章的链接，
+    /// 其余维基链接（目标不存在或未发布）去掉链接，只保留文本。
+    pub fn render_with_wiki_links(
+        &self,
+        markdown_input: &str,
+        profile: Option<&str>,
+        resolved_slugs: &HashSet<String>,
+    ) -> String {
+        let profile = self.profile(profile);
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown_input, &profile.options);
+
+        for node in wiki_link_nodes(root) {
+            let slug = match &node.data.borrow().value {
+                NodeValue::WikiLink(link) => link.url.trim().to_string(),
+                _ => continue,
+            };
+            if resolved_slugs.contains(&slug) {
+                if let NodeValue::WikiLink(link) = &mut node.data.borrow_mut().value {
+                    link.url = format!("{}{}", WIKI_LINK_BASE_PATH, slug);
+                }
+            } else {
+                // 把链接文本移到链接节点之前，再移除链接节点
+                let children: Vec<_> = node.children().collect();
+                for child in children {
+                    node.insert_before(child);
+                }
+                node.detach();
+            }
+        }
+
+        let mut plugins = Plugins::default();
+        plugins.render.codefence_syntax_highlighter = Some(&self.highlighter);
+        let mut html = Vec::new();
+        format_html_with_plugins(root, &profile.options, &mut html, &plugins)
+            .expect("写入内存缓冲区不会失败");
+        let mut html = String::from_utf8_lossy(&html).into_owned();
+        // 短代码模板是可信的，必须在清理之后展开
+        if profile.sanitized_html {
+            html = sanitize_html(&html);
+        }
+        let html = tag_math_and_diagrams(html);
+        self.shortcodes.expand(&html)
+    }
+
+    /// 提取 Markdown 中维基链接（`[[slug]]`、`[[slug|text]]`）的目标 slug，按出现顺序去重。
+    /// 代码块和行内代码中的内容不会被识别为链接，渲染方案未开启维基链接时返回空列表。
+    pub fn wiki_link_slugs(&self, markdown_input: &str, profile: Option<&str>) -> Vec<String> {
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown_input, &self.profile(profile).options);
+
+        let mut slugs: Vec<String> = Vec::new();
+        for node in wiki_link_nodes(root) {
+            if let NodeValue::WikiLink(link) = &node.data.borrow().value {
+                let slug = link.url.trim();
+                if !slug.is_empty() && !slugs.iter().any(|s| s == slug) {
+                    slugs.push(slug.to_string());
+                }
+            }
+        }
+        slugs
+    }
+}
+
+// 维基链接指向的博客前台文章地址
+const WIKI_LINK_BASE_PATH: &str = "/posts/";
+
+fn wiki_link_nodes<'a>(root: &'a AstNode<'a>) -> Vec<&'a AstNode<'a>> {
+    root.descendants()
+        .filter(|node| matches!(node.data.borrow().value, NodeValue::WikiLink(_)))
+        .collect()
+}
+
+/// 代码块高亮适配器。
+///
+/// 白名单中的语言交给 syntect 输出带 `hl-` 前缀 CSS 类名的 `<span>`（配色由前端主题决定），
+/// 其余语言和未标注语言的代码块按普通代码块转义输出。
+struct CodeHighlighter {
+    syntect: SyntectAdapter,
+    languages: HashSet<String>,
+    line_numbers: bool,
+}
+
+impl CodeHighlighter {
+    fn new(config: &MarkdownConfig) -> Self {
+        Self {
+            syntect: SyntectAdapterBuilder::new().css().build(),
+            languages: config
+                .highlight_languages
+                .iter()
+                .map(|lang| lang.trim().to_lowercase())
+                .collect(),
+            line_numbers: config.line_numbers,
+        }
+    }
+
+    fn is_highlighted(&self, lang: Option<&str>) -> bool {
+        lang.is_some_and(|l| l != MERMAID_LANGUAGE && self.languages.contains(&l.to_lowercase()))
+    }
+
+    fn wraps_lines(&self, lang: Option<&str>) -> bool {
+        self.line_numbers && lang != Some(MERMAID_LANGUAGE)
+    }
+}
+
+impl SyntaxHighlighterAdapter for CodeHighlighter {
+    fn write_highlighted(
+        &self,
+        output: &mut dyn Write,
+        lang: Option<&str>,
+        code: &str,
+    ) -> io::Result<()> {
+        let mut html = Vec::with_capacity(code.len());
+        if self.is_highlighted(lang) {
+            self.syntect.write_highlighted(&mut html, lang, code)?;
+        } else {
+            comrak::html::escape(&mut html, code.as_bytes())?;
+        }
+        let mut html = String::from_utf8(html).map_err(io::Error::other)?;
+        if self.is_highlighted(lang) {
+            html = prefix_highlight_classes(&html);
+        }
+
+        if self.wraps_lines(lang) {
+            output.write_all(wrap_lines(&html).as_bytes())
+        } else {
+            output.write_all(html.as_bytes())
+        }
+    }
+
+    fn write_pre_tag(
+        &self,
+        output: &mut dyn Write,
+        attributes: HashMap<String, String>,
+    ) -> io::Result<()> {
+        comrak::html::write_opening_tag(output, "pre", attributes)
+    }
+
+    fn write_code_tag(
+        &self,
+        output: &mut dyn Write,
+        mut attributes: HashMap<String, String>,
+    ) -> io::Result<()> {
+        // comrak 把语言写在 class="language-xxx" 中，据此给高亮的代码块加上类名
+        let lang = attributes
+            .get("class")
+            .and_then(|class| class.strip_prefix("language-"))
+            .map(str::to_string);
+        let mut classes: Vec<String> = attributes.remove("class").into_iter().collect();
+        if self.is_highlighted(lang.as_deref()) {
+            classes.push("highlight".to_string());
+        }
+        if self.wraps_lines(lang.as_deref()) {
+            classes.push("line-numbers".to_string());
+        }
+        if !classes.is_empty() {
+            attributes.insert("class".to_string(), classes.join(" "));
+        }
+        comrak::html::write_opening_tag(output, "code", attributes)
+    }
+}
+
+/// 给 syntect 输出的类名（如 `source rust`）加上 `hl-` 前缀，
+/// 避免与站点自身的类名冲突，允许原始 HTML 的渲染方案也据此区分高亮和文章中手写的类名。
+/// 文本中的引号已转义，`<span class="` 只会出现在标签中。
+fn prefix_highlight_classes(html: &str) -> String {
+    const SPAN_CLASS: &str = "<span class=\"";
+    let mut output = String::with_capacity(html.len() + html.len() / 4);
+    let mut rest = html;
+    while let Some(start) = rest.find(SPAN_CLASS) {
+        let (before, after) = rest.split_at(start + SPAN_CLASS.len());
+        output.push_str(before);
+        let end = after.find('"').unwrap_or(after.len());
+        let classes: Vec<String> = after[..end]
+            .split_ascii_whitespace()
+            .map(|class| format!("{}{}", HIGHLIGHT_CLASS_PREFIX, class))
+            .collect();
+        output.push_str(&classes.join(" "));
+        rest = &after[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// 把代码块 HTML 的每一行包裹在 `<span class="line">` 中，前端可用 CSS 计数器显示行号。
+///
+/// 高亮输出的 `<span>` 可能跨行，因此在行尾关闭仍未闭合的标签，并在下一行开头重新打开，
+/// 保证每一行的标签都是完整嵌套的。输入中的文本均已转义，`<` 只会出现在标签中。
+fn wrap_lines(html: &str) -> String {
+    let mut output = String::with_capacity(html.len() * 2);
+    let mut open_tags: Vec<&str> = Vec::new();
+
+    for piece in html.split_inclusive('\n') {
+        let (line, newline) = match piece.strip_suffix('\n') {
+            Some(line) => (line, "\n"),
+            None => (piece, ""),
+        };
+
+        let tags_before = open_tags.clone();
+        let mut has_text = false;
+        let mut rest = line;
+        while !rest.is_empty() {
+            match rest.find('<') {
+                Some(0) => {
+                    let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+                    let tag = &rest[..end];
+                    if tag.starts_with("</") {
+                        open_tags.pop();
+                    } else {
+                        open_tags.push(tag);
+                    }
+                    rest = &rest[end..];
+                }
+                Some(start) => {
+                    has_text = true;
+                    rest = &rest[start..];
+                }
+                None => {
+                    has_text = true;
+                    rest = "";
+                }
+            }
+        }
+
+        // 最后一行之后只剩下收尾的闭合标签，它们已在上一行行尾关闭
+        if newline.is_empty() && !has_text {
+            continue;
+        }
+
+        output.push_str("<span class=\"line\">");
+        for tag in &tags_before {
+            output.push_str(tag);
+        }
+        output.push_str(line);
+        for _ in &open_tags {
+            output.push_str("</span>");
+        }
+        output.push_str("</span>");
+        output.push_str(newline);
+    }
+    output
 }
 
 // 使用 #[cfg(test)] 属性，这段代码只会在执行 `cargo test` 时被编译
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{
+        RenderProfileConfig, ShortcodeConfig, default_render_profiles, default_shortcodes,
+    };
     // 导入父模块的所有内容，即 markdown_to_html_safe 函数
 
     #[test]
@@ -117,10 +540,463 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_wiki_links_not_rendered_outside_renderer() {
+        // 个人简介等内容不经过维基链接解析，[[slug]] 原样输出为文本
+        let result = markdown_to_html_safe("See [[rust-intro]] and [[x|text]].");
+        assert_eq!(result, "<p>See [[rust-intro]] and [[x|text]].</p>\n");
+    }
+
     #[test]
     fn test_empty_string_input() {
         let markdown = "";
         let expected_html = "";
         assert_eq!(markdown_to_html_safe(markdown), expected_html);
     }
+
+    fn renderer(languages: &[&str], line_numbers: bool) -> MarkdownRenderer {
+        MarkdownRenderer::new(&MarkdownConfig {
+            highlight_languages: languages.iter().map(|l| l.to_string()).collect(),
+            line_numbers,
+            shortcodes: default_shortcodes(),
+            default_profile: "standard".to_string(),
+            profiles: default_render_profiles(),
+        })
+    }
+
+    #[test]
+    fn test_code_highlighting_uses_css_classes() {
+        let markdown = "```rust\nfn main() {\n    let x = 1;\n}\n```";
+        let result = renderer(&["rust"], false).render(markdown);
+
+        assert!(result.starts_with("<pre><code class=\"language-rust highlight\">"));
+        // 使用 CSS 类名而不是内联样式，配色交给前端主题
+        assert!(result.contains("<span class=\""));
+        assert!(!result.contains("style="));
+        assert_eq!(
+            result.matches("<span").count(),
+            result.matches("</span>").count()
+        );
+
+        // 语言名不区分大小写
+        let upper = renderer(&["RUST"], false).render(markdown);
+        assert!(upper.contains("highlight"));
+    }
+
+    #[test]
+    fn test_code_highlighting_falls_back_to_plain() {
+        let renderer = renderer(&["rust"], false);
+        let inputs = vec![
+            "```python\nprint('hi')\n```",     // 不在白名单中
+            "```no-such-lang\nsome code\n```", // 未知语言
+            "```\nno language\n```",           // 未标注语言
+            "    indented code\n",             // 缩进代码块
+            "# Hello\n\nThis is **bold** text.",
+        ];
+
+        for input in inputs {
+            assert_eq!(renderer.render(input), markdown_to_html_safe(input));
+        }
+    }
+
+    #[test]
+    fn test_code_highlighting_line_numbers() {
+        let markdown = "```rust\n/* 跨行\n注释 */\nlet x = 1;\n```";
+        let result = renderer(&["rust"], true).render(markdown);
+
+        assert!(result.contains("class=\"language-rust highlight line-numbers\""));
+        assert_eq!(result.matches("<span class=\"line\">").count(), 3);
+        // 跨行的高亮标签在每行内都完整闭合
+        for line in result.lines().filter(|l| l.contains("class=\"line\"")) {
+            assert_eq!(
+                line.matches("<span").count(),
+                line.matches("</span>").count()
+            );
+        }
+
+        // 未高亮的代码块同样按行包裹
+        let plain = renderer(&["rust"], true).render("```text\na < b\nb > c\n```");
+        assert_eq!(
+            plain,
+            "<pre><code class=\"language-text line-numbers\"><span class=\"line\">a &lt; b</span>\n<span class=\"line\">b &gt; c</span>\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn test_security_code_highlighting_escapes_content() {
+        let renderer = renderer(&["rust", "html"], true);
+        let dangerous_inputs = vec![
+            "```rust\n</code></pre><script>alert('xss')</script>\n```",
+            "```html\n<img src=x onerror=alert('xss')>\n```",
+            "```unknown\n<iframe src='javascript:alert(1)'></iframe>\n```",
+            "```\"><script>alert(1)</script>\ncode\n```",
+            "```rust\nok\n```\n\n<script>alert('xss')</script>",
+        ];
+
+        for input in dangerous_inputs {
+            let result = renderer.render(input);
+
+            assert!(
+                !result.contains("<script"),
+                "Found script tag in: {}",
+                result
+            );
+            assert!(!result.contains("<img"), "Found img tag in: {}", result);
+            assert!(
+                !result.contains("<iframe"),
+                "Found iframe tag in: {}",
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_math_rendering() {
+        let inline = markdown_to_html_safe("Euler: $e^{i\\pi} + 1 = 0$");
+        assert_eq!(
+            inline,
+            "<p>Euler: <span class=\"math math-inline\">e^{i\\pi} + 1 = 0</span></p>\n"
+        );
+
+        let display = markdown_to_html_safe("$$\\sum_{i=1}^n i$$");
+        assert!(display.contains("<span class=\"math math-display\">\\sum_{i=1}^n i</span>"));
+
+        let fenced = renderer(&["rust"], true).render("```math\na < b\n```");
+        assert_eq!(
+            fenced,
+            "<pre><code class=\"language-math math math-display\">a &lt; b\n</code></pre>\n"
+        );
+
+        // 金额等普通美元符号不会被识别为公式
+        let prices = markdown_to_html_safe("It costs $5 and $10.");
+        assert_eq!(prices, "<p>It costs $5 and $10.</p>\n");
+    }
+
+    #[test]
+    fn test_mermaid_rendering() {
+        let markdown = "```mermaid\ngraph TD;\n  A-->B;\n```";
+        let expected =
+            "<pre><code class=\"language-mermaid mermaid\">graph TD;\n  A--&gt;B;\n</code></pre>\n";
+
+        assert_eq!(markdown_to_html_safe(markdown), expected);
+        // 即使配置了高亮和行号，图表内容也保持原样
+        assert_eq!(renderer(&["mermaid"], true).render(markdown), expected);
+    }
+
+    #[test]
+    fn test_security_math_and_diagrams_escape_content() {
+        let renderer = renderer(&["rust"], true);
+        let dangerous_inputs = vec![
+            "$<script>alert('xss')</script>$",
+            "$$</span><img src=x onerror=alert(1)>$$",
+            "```math\n</code></pre><script>alert(1)</script>\n```",
+            "```mermaid\ngraph TD; A[\"<img src=x onerror=alert(1)>\"]\n```",
+            "<span data-math-style=\"inline\">x</span><script>alert(1)</script>",
+        ];
+
+        for input in dangerous_inputs {
+            for result in [markdown_to_html_safe(input), renderer.render(input)] {
+                assert!(
+                    !result.contains("<script"),
+                    "Found script tag in: {}",
+                    result
+                );
+                assert!(!result.contains("<img"), "Found img tag in: {}", result);
+            }
+        }
+
+        // 原始 HTML 仍然被禁用，即使它模仿公式的标签
+        let result = markdown_to_html_safe("<span data-math-style=\"inline\">x</span>");
+        assert!(!result.contains("class=\"math"));
+        assert!(result.contains("<!-- raw HTML omitted -->"));
+    }
+
+    #[test]
+    fn test_shortcode_embeds() {
+        let renderer = renderer(&["rust"], false);
+
+        let youtube = renderer.render("Intro\n\n{{< youtube dQw4w9WgXcQ >}}\n\nOutro");
+        assert!(
+            youtube.contains("<iframe src=\"https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ\"")
+        );
+        assert!(youtube.starts_with("<p>Intro</p>\n<div class=\"embed embed-youtube\">"));
+        assert!(youtube.ends_with("</div>\n<p>Outro</p>\n"));
+
+        let gist = renderer.render("{{<gist octocat/aa5a315d61ae9438b18d>}}");
+        assert!(gist.contains("data-gist=\"octocat/aa5a315d61ae9438b18d\""));
+
+        let tweet = renderer.render("{{< tweet 1234567890 >}}");
+        assert!(tweet.contains("<blockquote class=\"embed embed-tweet twitter-tweet\">"));
+
+        // 代码块和行内的短代码不展开
+        let code = renderer.render("```\n{{< youtube dQw4w9WgXcQ >}}\n```");
+        assert!(!code.contains("<iframe"));
+        let inline = renderer.render("See {{< youtube dQw4w9WgXcQ >}} here");
+        assert!(!inline.contains("<iframe"));
+
+        // 注册表可以配置，未注册的短代码原样保留
+        let custom = MarkdownRenderer::new(&MarkdownConfig {
+            highlight_languages: vec![],
+            line_numbers: false,
+            shortcodes: vec![ShortcodeConfig {
+                name: "bilibili".to_string(),
+                id_pattern: "BV[0-9A-Za-z]{10}".to_string(),
+                template:
+                    "<iframe src=\"https://player.bilibili.com/player.html?bvid={id}\"></iframe>"
+                        .to_string(),
+            }],
+            default_profile: "standard".to_string(),
+            profiles: default_render_profiles(),
+        });
+        assert!(
+            custom
+                .render("{{< bilibili BV1xx411c7mD >}}")
+                .contains("bvid=BV1xx411c7mD")
+        );
+        assert_eq!(
+            custom.render("{{< youtube dQw4w9WgXcQ >}}"),
+            "<p>{{&lt; youtube dQw4w9WgXcQ &gt;}}</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_security_shortcodes_reject_smuggled_urls() {
+        let renderer = renderer(&["rust"], false);
+        let dangerous_inputs = vec![
+            "{{< youtube javascript:alert(1) >}}",
+            "{{< youtube https://evil.example/x >}}",
+            "{{< youtube dQw4w9WgXcQ\" onload=\"alert(1) >}}",
+            "{{< youtube dQw4w9WgXcQ&quot;&gt;&lt;script&gt; >}}",
+            "{{< youtube dQw4w9WgXcQ/../../evil >}}",
+            "{{< youtube dQw4w9WgXcQ?autoplay=1&src=//evil >}}",
+            "{{< vimeo 123 onload=alert(1) >}}",
+            "{{< gist octocat/aa5a315d61ae9438b18d\"><script>alert(1)</script> >}}",
+            "{{< gist ../../evil.example/aa5a315d61ae9438b18d >}}",
+            "{{< tweet 123#<img src=x onerror=alert(1)> >}}",
+            "{{< iframe https://evil.example >}}",
+            "{{< script alert(1) >}}",
+            "<p>{{< youtube dQw4w9WgXcQ >}}</p><script>alert(1)</script>",
+        ];
+
+        for input in dangerous_inputs {
+            let result = renderer.render(input);
+
+            assert!(!result.contains("<iframe"), "Found iframe in: {}", result);
+            assert!(
+                !result.contains("<script"),
+                "Found script tag in: {}",
+                result
+            );
+            assert!(!result.contains("<img"), "Found img tag in: {}", result);
+            assert!(
+                !result.contains("evil.example\""),
+                "Found URL in: {}",
+                result
+            );
+        }
+
+        // id_pattern 自动要求完整匹配，配置时遗漏锚点也不会放过额外内容
+        let loose = MarkdownRenderer::new(&MarkdownConfig {
+            highlight_languages: vec![],
+            line_numbers: false,
+            shortcodes: vec![ShortcodeConfig {
+                name: "video".to_string(),
+                id_pattern: "[0-9]+".to_string(),
+                template: "<iframe src=\"https://video.example/{id}\"></iframe>".to_string(),
+            }],
+            default_profile: "standard".to_string(),
+            profiles: default_render_profiles(),
+        });
+        assert!(
+            !loose
+                .render("{{< video 123/../evil >}}")
+                .contains("<iframe")
+        );
+        assert!(loose.render("{{< video 123 >}}").contains("<iframe"));
+    }
+
+    #[test]
+    fn test_wiki_links() {
+        let markdown = "See [[rust-intro]], [[rust-intro|the intro]] and [[draft-post|a draft]].\n\n`[[in-code]]`\n\n```\n[[in-block]]\n```";
+        let renderer = renderer(&["rust"], false);
+        assert_eq!(
+            renderer.wiki_link_slugs(markdown, None),
+            vec!["rust-intro", "draft-post"]
+        );
+
+        let resolved = HashSet::from(["rust-intro".to_string()]);
+        let result = renderer.render_with_wiki_links(markdown, None, &resolved);
+        assert!(result.starts_with(
+            "<p>See <a href=\"/posts/rust-intro\" data-wikilink=\"true\">rust-intro</a>, <a href=\"/posts/rust-intro\" data-wikilink=\"true\">the intro</a> and a draft.</p>"
+        ));
+        assert!(result.contains("<code>[[in-code]]</code>"));
+        assert!(result.contains("[[in-block]]"));
+
+        // 未解析时所有维基链接都只保留文本
+        let plain = renderer.render("[[rust-intro|the intro]] & more");
+        assert_eq!(plain, "<p>the intro &amp; more</p>\n");
+    }
+
+    #[test]
+    fn test_security_wiki_links() {
+        let renderer = renderer(&["rust"], false);
+        let dangerous_inputs = vec![
+            "[[javascript:alert(1)|click]]",
+            "[[\"><script>alert(1)</script>]]",
+            "[[//evil.example|click]]",
+            "[[x|<img src=x onerror=alert(1)>]]",
+        ];
+
+        for input in dangerous_inputs {
+            // 即使被误判为已解析，链接也只会指向 /posts/ 下的地址
+            let resolved: HashSet<String> =
+                renderer.wiki_link_slugs(input, None).into_iter().collect();
+            for result in [
+                renderer.render(input),
+                renderer.render_with_wiki_links(input, None, &resolved),
+            ] {
+                assert!(
+                    !result.contains("<script"),
+                    "Found script tag in: {}",
+                    result
+                );
+                assert!(!result.contains("<img"), "Found img tag in: {}", result);
+                assert!(
+                    !result.contains("href=\"javascript:"),
+                    "Found javascript URL in: {}",
+                    result
+                );
+                assert!(
+                    !result.contains("href=\"//"),
+                    "Found external URL in: {}",
+                    result
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_profiles() {
+        let renderer = renderer(&["rust"], false);
+        let markdown = "He said \"hi\" -- wait...\nsee https://example.com";
+
+        assert_eq!(
+            renderer.render_with_wiki_links(markdown, Some("standard"), &HashSet::new()),
+            "<p>He said &quot;hi&quot; -- wait...\nsee https://example.com</p>\n"
+        );
+        // casual 方案开启智能标点、硬换行和自动链接
+        assert_eq!(
+            renderer.render_with_wiki_links(markdown, Some("casual"), &HashSet::new()),
+            "<p>He said “hi” – wait…<br />\nsee <a href=\"https://example.com\">https://example.com</a></p>\n"
+        );
+        // 未指定或已不存在的方案使用默认方案
+        assert_eq!(
+            renderer.render(markdown),
+            renderer.render_with_wiki_links(markdown, Some("removed"), &HashSet::new())
+        );
+
+        // 关闭的扩展按普通文本渲染
+        let minimal = MarkdownRenderer::new(&MarkdownConfig {
+            highlight_languages: vec![],
+            line_numbers: false,
+            shortcodes: vec![],
+            default_profile: "minimal".to_string(),
+            profiles: vec![RenderProfileConfig {
+                name: "minimal".to_string(),
+                ..Default::default()
+            }],
+        });
+        assert_eq!(
+            minimal.render("~~gone~~ $x$ [[post]]"),
+            "<p>~~gone~~ $x$ [[post]]</p>\n"
+        );
+        assert!(minimal.wiki_link_slugs("[[post]]", None).is_empty());
+        assert!(!minimal.render("| a |\n|---|\n| b |").contains("<table>"));
+        assert_eq!(
+            renderer.render("~~gone~~ $x$"),
+            "<p><del>gone</del> <span class=\"math math-inline\">x</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_profile_raw_html_elements() {
+        let renderer = renderer(&["rust"], false);
+        let markdown = "<details open>\n<summary>Shortcuts</summary>\n\nPress <kbd>Ctrl</kbd>+<kbd>C</kbd>, H<sub>2</sub>O, x<sup>2</sup>\n\n</details>\n\n<table>\n<tr><th scope=\"col\" colspan=\"2\" style=\"color:red\">Span</th></tr>\n<tr><td rowspan=\"2\" onmouseover=\"x()\">A</td><td>B</td></tr>\n</table>";
+        let result = renderer.render_with_wiki_links(markdown, Some("trusted"), &HashSet::new());
+        assert_eq!(
+            result,
+            "<details open=\"\">\n<summary>Shortcuts</summary>\n<p>Press <kbd>Ctrl</kbd>+<kbd>C</kbd>, H<sub>2</sub>O, x<sup>2</sup></p>\n</details>\n<table>\n<tr><th scope=\"col\" colspan=\"2\">Span</th></tr>\n<tr><td rowspan=\"2\">A</td><td>B</td></tr>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_profile_sanitized_html() {
+        let renderer = renderer(&["rust"], false);
+        let markdown = "Press <strong onclick=\"x()\">Enter</strong> <marquee>now</marquee>\n\n<form action=\"/x\">\n\n*note*\n\n</form>\n\n<script>alert(1)</script>";
+        let result = renderer.render_with_wiki_links(markdown, Some("trusted"), &HashSet::new());
+        assert_eq!(
+            result,
+            "<p>Press <strong>Enter</strong> now</p>\n\n<p><em>note</em></p>\n\n\n"
+        );
+
+        // 其他方案仍然禁止原始 HTML
+        let standard = renderer.render(markdown);
+        assert!(!standard.contains("<strong"));
+        assert!(standard.contains("<!-- raw HTML omitted -->"));
+
+        // 清理之后才展开短代码，短代码模板中的 iframe 不受影响
+        let embed = renderer.render_with_wiki_links(
+            "{{< youtube dQw4w9WgXcQ >}}\n\n<iframe src=\"https://evil.example\"></iframe>",
+            Some("trusted"),
+            &HashSet::new(),
+        );
+        assert_eq!(embed.matches("<iframe").count(), 1);
+        assert!(embed.contains("youtube-nocookie.com"));
+    }
+
+    #[test]
+    fn test_render_profile_permissions() {
+        let mut config = MarkdownConfig {
+            highlight_languages: vec![],
+            line_numbers: false,
+            shortcodes: vec![],
+            default_profile: "standard".to_string(),
+            profiles: default_render_profiles(),
+        };
+        config.profiles.push(RenderProfileConfig {
+            name: "staff".to_string(),
+            allowed_roles: vec!["editor".to_string()],
+            ..Default::default()
+        });
+        let renderer = MarkdownRenderer::new(&config);
+        let strings = |items: &[&str]| items.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        let user = strings(&["user"]);
+        let editor = strings(&["editor"]);
+        let raw_html = strings(&[RAW_HTML_PERMISSION]);
+
+        assert!(renderer.check_profile(None, &user, &[]).is_ok());
+        assert!(renderer.check_profile(Some("casual"), &user, &[]).is_ok());
+
+        // 允许 HTML 的方案看权限，不看角色
+        assert!(
+            renderer
+                .check_profile(Some("trusted"), &user, &raw_html)
+                .is_ok()
+        );
+        let denied = renderer.check_profile(Some("trusted"), &editor, &[]);
+        assert!(
+            denied
+                .unwrap_err()
+                .to_string()
+                .contains(RAW_HTML_PERMISSION)
+        );
+
+        // 限定角色的方案
+        assert!(renderer.check_profile(Some("staff"), &editor, &[]).is_ok());
+        let denied = renderer.check_profile(Some("staff"), &user, &raw_html);
+        assert!(denied.unwrap_err().to_string().contains("权限"));
+
+        let unknown = renderer.check_profile(Some("removed"), &editor, &raw_html);
+        assert!(unknown.unwrap_err().to_string().contains("无效"));
+    }
 }
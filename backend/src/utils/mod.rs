@@ -1,5 +1,12 @@
 pub mod markdown;
 pub mod password;
+pub mod sanitize;
+pub mod shortcode;
+pub mod similarity;
+pub mod suggestion;
+pub mod version;
 
-pub use markdown::markdown_to_html_safe;
+pub use markdown::{MarkdownRenderer, markdown_to_html_safe};
 pub use password::{hash_password, validate_password_strength, verify_password};
+pub use sanitize::sanitize_html;
+pub use version::{VersionError, etag_header, expected_version};
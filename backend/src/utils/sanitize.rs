@@ -0,0 +1,804 @@
+/// 按白名单清理 HTML，用于允许原始 HTML 的渲染方案。
+///
+/// 只保留白名单中的标签和属性，其余标签去掉但保留其中的文本；`<script>`、`<style>`
+/// 等标签连同内容一起移除，注释和 `<!DOCTYPE>` 等声明直接丢弃。链接地址只允许
+/// http、https、mailto 和相对地址。保留的标签按解析结果重新输出，属性值重新转义；
+/// 多余的闭合标签被丢弃，未闭合的标签在末尾补齐，文章内容不会影响页面的其余部分。
+///
+/// `id` 统一加上 `user-content-` 前缀（页内锚点链接随之改写），避免覆盖页面上的元素或
+/// 全局变量（DOM clobbering）；`class` 只保留渲染器自身使用的类名，文章不能借用站点界面的样式。
+pub fn sanitize_html(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut open_tags: Vec<&'static str> = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            continue;
+        }
+
+        let Some((tag, after)) = parse_tag(rest) else {
+            // 不构成标签的 `<` 按文本输出
+            output.push_str("&lt;");
+            rest = &rest[1..];
+            continue;
+        };
+        rest = after;
+
+        if !tag.closing && DROPPED_WITH_CONTENT.contains(&tag.name.as_str()) {
+            rest = skip_element_content(rest, &tag.name);
+            continue;
+        }
+        let Some(name) = ALLOWED_TAGS.iter().copied().find(|t| *t == tag.name) else {
+            continue;
+        };
+
+        if tag.closing {
+            if let Some(index) = open_tags.iter().rposition(|t| *t == name) {
+                for open in open_tags.drain(index..).rev() {
+                    push_closing_tag(&mut output, open);
+                }
+            }
+            continue;
+        }
+
+        let Some(attributes) = allowed_attributes(name, &tag.attributes) else {
+            continue;
+        };
+        output.push('<');
+        output.push_str(name);
+        for (attr, value) in attributes {
+            output.push(' ');
+            output.push_str(attr);
+            output.push_str("=\"");
+            push_escaped(&mut output, &value);
+            output.push('"');
+        }
+        if VOID_TAGS.contains(&name) {
+            output.push_str(" />");
+        } else {
+            output.push('>');
+            open_tags.push(name);
+        }
+    }
+    output.push_str(rest);
+
+    for open in open_tags.into_iter().rev() {
+        push_closing_tag(&mut output, open);
+    }
+    output
+}
+
+// 允许保留的标签：comrak 渲染 Markdown 时会输出的标签，
+// 以及 Markdown 无法表达的折叠块、按键、上下标和复杂表格
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "samp",
+    "section",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+    "var",
+];
+
+// 没有闭合标签的元素
+const VOID_TAGS: &[&str] = &["br", "col", "hr", "img", "input"];
+
+// 连同内容一起移除的标签：脚本、样式、嵌入内容，以及内容按特殊规则解析的元素
+const DROPPED_WITH_CONTENT: &[&str] = &[
+    "script",
+    "style",
+    "iframe",
+    "object",
+    "applet",
+    "noscript",
+    "noembed",
+    "noframes",
+    "frameset",
+    "template",
+    "textarea",
+    "title",
+    "xmp",
+    "plaintext",
+    "select",
+    "svg",
+    "math",
+];
+
+// 所有保留的标签都允许的属性，其中 id 和 class 的值还要经过改写或过滤
+const GLOBAL_ATTRIBUTES: &[&str] = &["class", "id", "title", "lang", "dir", "aria-label"];
+
+// 文章中的 id 和页内锚点统一加上的前缀
+const USER_CONTENT_ID_PREFIX: &str = "user-content-";
+
+// 允许的类名前缀：代码块语言和语法高亮
+const ALLOWED_CLASS_PREFIXES: &[&str] = &["language-", "hl-"];
+
+// 允许的完整类名：代码块、行号和脚注
+const ALLOWED_CLASSES: &[&str] = &[
+    "highlight",
+    "line-numbers",
+    "line",
+    "footnotes",
+    "footnote-ref",
+    "footnote-backref",
+];
+
+// 各标签额外允许的属性，其中 href、src 和 cite 还要检查链接地址
+const TAG_ATTRIBUTES: &[(&str, &[&str])] = &[
+    (
+        "a",
+        &[
+            "href",
+            "data-wikilink",
+            "data-footnote-ref",
+            "data-footnote-backref",
+            "data-footnote-backref-idx",
+        ],
+    ),
+    ("img", &["src", "alt", "width", "height"]),
+    ("input", &["type", "checked", "disabled"]),
+    ("ol", &["start", "reversed"]),
+    ("details", &["open"]),
+    ("blockquote", &["cite"]),
+    ("q", &["cite"]),
+    ("del", &["cite", "datetime"]),
+    ("ins", &["cite", "datetime"]),
+    ("th", &["align", "colspan", "rowspan", "scope"]),
+    ("td", &["align", "colspan", "rowspan"]),
+    ("col", &["span"]),
+    ("colgroup", &["span"]),
+    ("span", &["data-math-style"]),
+    ("code", &["data-math-style"]),
+    ("section", &["data-footnotes"]),
+];
+
+// 链接地址允许的协议，不带协议的相对地址总是允许
+const ALLOWED_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+// 过滤标签的属性；返回 None 表示整个标签都不保留
+fn allowed_attributes<'a>(
+    tag: &str,
+    attributes: &'a [(String, String)],
+) -> Option<Vec<(&'a str, String)>> {
+    let tag_attributes = TAG_ATTRIBUTES
+        .iter()
+        .find(|(name, _)| *name == tag)
+        .map_or(&[][..], |(_, attrs)| *attrs);
+
+    let mut allowed: Vec<(&str, String)> = Vec::new();
+    for (attr, value) in attributes {
+        let (attr, value) = (attr.as_str(), value.as_str());
+        // 重复的属性以第一个为准，与浏览器一致
+        if allowed.iter().any(|(a, _)| *a == attr)
+            || !(GLOBAL_ATTRIBUTES.contains(&attr) || tag_attributes.contains(&attr))
+        {
+            continue;
+        }
+        if matches!(attr, "href" | "src" | "cite") && !is_safe_url(value) {
+            continue;
+        }
+        let value = match attr {
+            "id" => prefix_user_content_id(value),
+            "href" => match value.strip_prefix('#') {
+                Some(fragment) => format!("#{}", prefix_user_content_id(fragment)),
+                None => value.to_string(),
+            },
+            "class" => allowed_classes(value),
+            _ => value.to_string(),
+        };
+        if matches!(attr, "id" | "class") && value.is_empty() {
+            continue;
+        }
+        allowed.push((attr, value));
+    }
+
+    // 只允许任务列表使用的复选框，不允许文本框等表单控件
+    if tag == "input"
+        && !allowed
+            .iter()
+            .any(|(a, v)| *a == "type" && v.eq_ignore_ascii_case("checkbox"))
+    {
+        return None;
+    }
+    Some(allowed)
+}
+
+// 给 id 加上前缀，已有前缀的保持不变，清理结果再次清理时不会重复添加
+fn prefix_user_content_id(id: &str) -> String {
+    let id = id.trim();
+    if id.is_empty() || id.starts_with(USER_CONTENT_ID_PREFIX) {
+        id.to_string()
+    } else {
+        format!("{}{}", USER_CONTENT_ID_PREFIX, id)
+    }
+}
+
+// 只保留白名单中的类名
+fn allowed_classes(classes: &str) -> String {
+    classes
+        .split_ascii_whitespace()
+        .filter(|class| {
+            ALLOWED_CLASSES.contains(class)
+                || ALLOWED_CLASS_PREFIXES
+                    .iter()
+                    .any(|prefix| class.len() > prefix.len() && class.starts_with(prefix))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// 检查链接地址的协议。浏览器会忽略地址中的空白和控制字符，检查前先去掉它们
+fn is_safe_url(url: &str) -> bool {
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect();
+    match url.find([':', '/', '?', '#']) {
+        Some(index) if url[index..].starts_with(':') => {
+            let scheme = url[..index].to_ascii_lowercase();
+            ALLOWED_URL_SCHEMES.contains(&scheme.as_str())
+        }
+        _ => true,
+    }
+}
+
+struct Tag {
+    name: String,
+    closing: bool,
+    attributes: Vec<(String, String)>,
+}
+
+// 从 `<` 开始解析一个标签，返回标签和标签之后的剩余内容。
+// 标签名和属性名转为小写，属性值已解码字符引用；不是完整标签时返回 None
+fn parse_tag(input: &str) -> Option<(Tag, &str)> {
+    let bytes = input.as_bytes();
+    let mut i = 1;
+    let closing = bytes.get(i) == Some(&b'/');
+    if closing {
+        i += 1;
+    }
+    if !bytes.get(i)?.is_ascii_alphabetic() {
+        return None;
+    }
+
+    let name_start = i;
+    while i < bytes.len() && !is_tag_delimiter(bytes[i]) {
+        i += 1;
+    }
+    let name = input[name_start..i].to_ascii_lowercase();
+
+    let mut attributes = Vec::new();
+    loop {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+            i += 1;
+        }
+        match bytes.get(i)? {
+            b'>' => break,
+            _ => {
+                // 属性名可以以 `=` 开头，其余位置遇到 `=` 结束
+                let attr_start = i;
+                i += 1;
+                while i < bytes.len() && !is_tag_delimiter(bytes[i]) && bytes[i] != b'=' {
+                    i += 1;
+                }
+                let attr = input[attr_start..i].to_ascii_lowercase();
+
+                let mut j = i;
+                while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+                    j += 1;
+                }
+                let mut value = String::new();
+                if bytes.get(j) == Some(&b'=') {
+                    j += 1;
+                    while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+                        j += 1;
+                    }
+                    let raw = match bytes.get(j)? {
+                        quote @ (b'"' | b'\'') => {
+                            let end = j + 1 + input[j + 1..].find(*quote as char)?;
+                            let raw = &input[j + 1..end];
+                            j = end + 1;
+                            raw
+                        }
+                        _ => {
+                            let start = j;
+                            while j < bytes.len()
+                                && !bytes[j].is_ascii_whitespace()
+                                && bytes[j] != b'>'
+                            {
+                                j += 1;
+                            }
+                            &input[start..j]
+                        }
+                    };
+                    value = decode_character_references(raw);
+                    i = j;
+                }
+                attributes.push((attr, value));
+            }
+        }
+    }
+
+    let tag = Tag {
+        name,
+        closing,
+        attributes,
+    };
+    Some((tag, &input[i + 1..]))
+}
+
+fn is_tag_delimiter(byte: u8) -> bool {
+    byte.is_ascii_whitespace() || byte == b'/' || byte == b'>'
+}
+
+// 跳过需要连同内容一起移除的元素，返回其闭合标签之后的内容；没有闭合标签时移除剩余的全部内容
+fn skip_element_content<'a>(input: &'a str, name: &str) -> &'a str {
+    let closing = format!("</{}", name);
+    let lowercase = input.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(found) = lowercase[from..].find(&closing) {
+        let end = from + found + closing.len();
+        // 确认是完整的标签名，`</scripts>` 不是 `</script>`
+        if lowercase[end..].starts_with(|c: char| c == '>' || c == '/' || c.is_ascii_whitespace()) {
+            return input[end..].find('>').map_or("", |i| &input[end + i + 1..]);
+        }
+        from = end;
+    }
+    ""
+}
+
+// 解码属性值中的字符引用。保留的属性值会重新转义输出，浏览器看到的值与这里检查的值一致
+fn decode_character_references(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match decode_reference(rest) {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+// 解码 `&` 开头的一个字符引用，返回字符和引用的长度
+fn decode_reference(input: &str) -> Option<(char, usize)> {
+    let body = &input[1..];
+    if let Some(number) = body.strip_prefix('#') {
+        let (digits, radix, prefix_len) = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16, 2),
+            None => (number, 10, 1),
+        };
+        let digits_len = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        if digits_len == 0 {
+            return None;
+        }
+        let code = u32::from_str_radix(&digits[..digits_len], radix).unwrap_or(u32::MAX);
+        let c = char::from_u32(code)
+            .filter(|c| *c != '\0')
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        let semicolon = usize::from(digits[digits_len..].starts_with(';'));
+        return Some((c, 1 + prefix_len + digits_len + semicolon));
+    }
+
+    let name_len = body
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(body.len());
+    if !body[name_len..].starts_with(';') {
+        return None;
+    }
+    let c = match &body[..name_len] {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "colon" => ':',
+        "sol" => '/',
+        "tab" => '\t',
+        "newline" => '\n',
+        "nbsp" => '\u{a0}',
+        _ => return None,
+    };
+    Some((c, name_len + 2))
+}
+
+fn push_escaped(output: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '"' => output.push_str("&quot;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+fn push_closing_tag(output: &mut String, name: &str) {
+    output.push_str("</");
+    output.push_str(name);
+    output.push('>');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_keeps_allowed_markup() {
+        let html = "<p>Hello <strong title=\"x\">world</strong><br />\n<a href=\"https://example.com/a?b=1&amp;c=2\" title=\"t\">link</a></p>\n";
+        assert_eq!(sanitize_html(html), html);
+
+        let task = "<li><input type=\"checkbox\" checked=\"\" disabled=\"\" /> done</li>";
+        assert_eq!(sanitize_html(task), task);
+    }
+
+    #[test]
+    fn test_sanitize_keeps_raw_html_elements() {
+        let html = "<details open=\"\"><summary>More</summary><p><kbd>Ctrl</kbd> H<sub>2</sub>O x<sup>2</sup> <abbr title=\"HyperText\">HTML</abbr></p></details>";
+        assert_eq!(sanitize_html(html), html);
+
+        let table = "<table><caption>Plan</caption><colgroup><col span=\"2\" /></colgroup><tr><th scope=\"row\" rowspan=\"2\">A</th><td colspan=\"2\" align=\"center\">B</td></tr></table>";
+        assert_eq!(sanitize_html(table), table);
+
+        // 属性名大小写和引号统一，属性值重新转义
+        assert_eq!(
+            sanitize_html("<DETAILS OPEN><SUMMARY title='a \"b\" &lt;c&gt;'>x</SUMMARY></DETAILS>"),
+            "<details open=\"\"><summary title=\"a &quot;b&quot; &lt;c&gt;\">x</summary></details>"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_removes_unknown_tags_and_attributes() {
+        assert_eq!(
+            sanitize_html(
+                "<center style=\"color:red\"><p onclick=\"x()\" id=\"a\">text</p></center>"
+            ),
+            "<p id=\"user-content-a\">text</p>"
+        );
+        assert_eq!(sanitize_html("a <form>b</form> c"), "a b c");
+        assert_eq!(sanitize_html("<input type=\"text\" value=\"x\" />"), "");
+    }
+
+    #[test]
+    fn test_sanitize_balances_tags() {
+        assert_eq!(sanitize_html("</p></div>text"), "text");
+        assert_eq!(sanitize_html("<p><em>text"), "<p><em>text</em></p>");
+        assert_eq!(
+            sanitize_html("<ul><li>one</ul>after"),
+            "<ul><li>one</li></ul>after"
+        );
+        assert_eq!(sanitize_html("a < b > c"), "a &lt; b > c");
+    }
+
+    // 以下 XSS 回归用例覆盖常见的绕过手法，新发现的绕过方式应补充到这里
+    const DANGEROUS_INPUTS: &[&str] = &[
+        // 脚本标签的各种写法
+        "<script>alert(1)</script>",
+        "<SCRIPT SRC=//evil.example/x.js></SCRIPT>",
+        "<script/src=//evil.example/x.js></script>",
+        "<script>alert(1)</script >after",
+        "<script>alert('</scripts>')</script>after",
+        "<scr<script>ipt>alert(1)</scr</script>ipt>",
+        "<script>alert(1)",
+        // 事件处理器
+        "<img src=x onerror=alert(1)>",
+        "<img src=x onerror=\"alert(1)\">",
+        "<p onmouseover='alert(1)'>x</p>",
+        "<div/onclick=alert(1)>x</div>",
+        "<details open ontoggle=alert(1)>",
+        "<a href=\"https://ok.example\"onclick=alert(1)>x</a>",
+        "<body onload=alert(1)>",
+        "<kbd ONFOCUS=alert(1) autofocus tabindex=1>x</kbd>",
+        // 危险的链接协议及其变形
+        "<a href=\"javascript:alert(1)\">x</a>",
+        "<a href=javascript:alert(1)>x</a>",
+        "<a href=\"JaVaScRiPt:alert(1)\">x</a>",
+        "<a href=\"  javascript:alert(1)\">x</a>",
+        "<a href=\"java\tscript:alert(1)\">x</a>",
+        "<a href=\"java\nscript:alert(1)\">x</a>",
+        "<a href=\"java\0script:alert(1)\">x</a>",
+        "<a href=\"&#106;avascript:alert(1)\">x</a>",
+        "<a href=\"&#x6A;avascript:alert(1)\">x</a>",
+        "<a href=\"&#0000106&#0000097vascript:alert(1)\">x</a>",
+        "<a href=\"javascript&colon;alert(1)\">x</a>",
+        "<a href=\"javascript&#58;alert(1)\">x</a>",
+        "<a href=\"jav&#x09;ascript:alert(1)\">x</a>",
+        "<a href=\"vbscript:msgbox(1)\">x</a>",
+        "<a href=\"data:text/html;base64,PHNjcmlwdD5hbGVydCgxKTwvc2NyaXB0Pg==\">x</a>",
+        "<img src=\"data:image/svg+xml;base64,PHN2Zz4=\">",
+        "<blockquote cite=\"javascript:alert(1)\">x</blockquote>",
+        // 危险的元素和属性
+        "<iframe src=\"https://evil.example\"></iframe>",
+        "<iframe srcdoc=\"&lt;script&gt;alert(1)&lt;/script&gt;\"></iframe>",
+        "<object data=\"javascript:alert(1)\"></object>",
+        "<embed src=\"javascript:alert(1)\">",
+        "<svg><script>alert(1)</script></svg>",
+        "<svg onload=alert(1)>",
+        "<math><mtext><table><mglyph><style><img src=x onerror=alert(1)>",
+        "<style>@import 'https://evil.example/x.css';</style>",
+        "<p style=\"background:url(javascript:alert(1))\">x</p>",
+        "<link rel=stylesheet href=\"https://evil.example/x.css\">",
+        "<meta http-equiv=\"refresh\" content=\"0;url=javascript:alert(1)\">",
+        "<base href=\"https://evil.example/\">",
+        "<form action=\"https://evil.example\"><button formaction=\"javascript:alert(1)\">x</button></form>",
+        "<input type=\"image\" src=\"x\" onerror=\"alert(1)\">",
+        "<input type=\"checkbox\" onfocus=\"alert(1)\" autofocus>",
+        "<img srcset=\"javascript:alert(1)\">",
+        "<a href=\"https://ok.example\" target=\"_blank\">x</a>",
+        // 解析差异和标签截断
+        "<!--><script>alert(1)</script>-->",
+        "<!-- --!><script>alert(1)</script> -->",
+        "<![CDATA[<script>alert(1)</script>]]>",
+        "<?xml version=\"1.0\"?><script>alert(1)</script>",
+        "<noscript><p title=\"</noscript><img src=x onerror=alert(1)>\"></noscript>",
+        "<textarea><script>alert(1)</script></textarea>",
+        "<title><img src=x onerror=alert(1)></title>",
+        "<template><script>alert(1)</script></template>",
+        "<xmp><img src=x onerror=alert(1)></xmp>",
+        "<p title=\"a\"onclick=\"alert(1)\">x</p>",
+        "<p title='\"><script>alert(1)</script>'>x</p>",
+        "<p title=\"&quot;&gt;&lt;script&gt;alert(1)&lt;/script&gt;\">x</p>",
+        "<img src=\"x\" alt=\"\" / onerror=alert(1)>",
+        "<img src=\"x",
+        "<a href=\"javascript:alert(1)",
+        "<<script>alert(1)//<</script>",
+        "</p><script>alert(1)</script><p>",
+        "<a\u{0c}href=\"javascript:alert(1)\">x</a>",
+        // DOM clobbering 和冒用站点样式
+        "<a id=\"config\" href=\"https://evil.example\">x</a>",
+        "<img id=\"location\" src=\"x\">",
+        "<a id=\"defaultView\"></a><a id=\"defaultView\" title=\"x\"></a>",
+        "<p id=\" \">x</p>",
+        "<div class=\"modal admin-banner\">x</div>",
+        "<span class=\"btn btn-primary\">Log in</span>",
+        "<p class=\"language-\">x</p>",
+    ];
+
+    // 重新解析清理结果：所有标签和属性都必须在白名单中，链接地址必须安全
+    fn assert_safe(input: &str, output: &str) {
+        let mut rest = output;
+        while let Some(start) = rest.find('<') {
+            rest = &rest[start..];
+            let (tag, after) = parse_tag(rest)
+                .unwrap_or_else(|| panic!("Unparsable tag in {:?} -> {:?}", input, output));
+            assert!(
+                ALLOWED_TAGS.contains(&tag.name.as_str()),
+                "Found tag <{}> in {:?} -> {:?}",
+                tag.name,
+                input,
+                output
+            );
+            for (attr, value) in &tag.attributes {
+                assert!(
+                    !attr.starts_with("on")
+                        && !matches!(attr.as_str(), "style" | "srcset" | "srcdoc"),
+                    "Found attribute {} in {:?} -> {:?}",
+                    attr,
+                    input,
+                    output
+                );
+                if attr == "id" {
+                    assert!(
+                        value.starts_with(USER_CONTENT_ID_PREFIX),
+                        "Found unprefixed id {:?} in {:?} -> {:?}",
+                        value,
+                        input,
+                        output
+                    );
+                }
+                if attr == "class" {
+                    assert_eq!(
+                        &allowed_classes(value),
+                        value,
+                        "Found disallowed class in {:?} -> {:?}",
+                        input,
+                        output
+                    );
+                }
+                if matches!(attr.as_str(), "href" | "src" | "cite") {
+                    assert!(
+                        is_safe_url(value),
+                        "Found unsafe URL {:?} in {:?} -> {:?}",
+                        value,
+                        input,
+                        output
+                    );
+                }
+            }
+            rest = after;
+        }
+        assert!(
+            !output.to_lowercase().contains("<script"),
+            "Found script in: {}",
+            output
+        );
+    }
+
+    #[test]
+    fn test_security_sanitizer_blocks_xss_vectors() {
+        for input in DANGEROUS_INPUTS {
+            let output = sanitize_html(input);
+            assert_safe(input, &output);
+            // 清理结果再次清理保持不变，存储或二次渲染不会产生新的标签
+            assert_eq!(sanitize_html(&output), output, "Not stable for: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_security_sanitizer_drops_scripts_with_content() {
+        assert_eq!(
+            sanitize_html("a<script>alert(1)</script>b<style>*{}</style>c"),
+            "abc"
+        );
+        assert_eq!(
+            sanitize_html("<script>alert('</scripts>')</script>after"),
+            "after"
+        );
+        assert_eq!(sanitize_html("<script>alert(1)"), "");
+        assert_eq!(
+            sanitize_html("<svg><script>alert(1)</script></svg>ok"),
+            "ok"
+        );
+        assert_eq!(sanitize_html("<!-- <script>x</script> -->ok"), "ok");
+        assert_eq!(
+            sanitize_html(
+                "<noscript><p title=\"</noscript><img src=x onerror=alert(1)>\"></noscript>"
+            ),
+            "<img src=\"x\" />\">"
+        );
+    }
+
+    #[test]
+    fn test_security_sanitizer_rejects_unsafe_urls() {
+        for input in DANGEROUS_INPUTS
+            .iter()
+            .filter(|i| i.contains("script:") || i.contains("data:"))
+        {
+            let output = sanitize_html(input);
+            assert!(!output.contains("<a href"), "Found href in: {}", output);
+            assert!(
+                !output.contains("src=\"data"),
+                "Found data URL in: {}",
+                output
+            );
+            assert!(!output.contains("cite="), "Found cite in: {}", output);
+        }
+
+        // 安全的地址保留，字符引用解码后重新转义
+        for url in [
+            "https://example.com/a?b=1&amp;c=2",
+            "http://example.com",
+            "mailto:someone@example.com",
+            "/posts/rust-intro",
+            "#user-content-fn-1",
+            "?page=2",
+            "relative/path:with-colon",
+            "//cdn.example.com/x.png",
+        ] {
+            let html = format!("<a href=\"{}\">x</a>", url);
+            assert_eq!(sanitize_html(&html), html);
+        }
+        assert_eq!(
+            sanitize_html("<a href=\"&#104;ttps://example.com\">x</a>"),
+            "<a href=\"https://example.com\">x</a>"
+        );
+    }
+
+    #[test]
+    fn test_security_sanitizer_strips_event_handlers() {
+        assert_eq!(
+            sanitize_html("<details open ontoggle=alert(1)><summary>x</summary></details>"),
+            "<details open=\"\"><summary>x</summary></details>"
+        );
+        assert_eq!(
+            sanitize_html("<a href=\"https://ok.example\"onclick=alert(1)>x</a>"),
+            "<a href=\"https://ok.example\">x</a>"
+        );
+        assert_eq!(
+            sanitize_html("<p title=\"a\"onclick=\"alert(1)\">x</p>"),
+            "<p title=\"a\">x</p>"
+        );
+        assert_eq!(
+            sanitize_html("<img src=\"x\" alt=\"\" / onerror=alert(1)>"),
+            "<img src=\"x\" alt=\"\" />"
+        );
+    }
+
+    #[test]
+    fn test_security_sanitizer_rewrites_ids_and_classes() {
+        // id 加上前缀，页内锚点随之改写，脚注链接仍然有效
+        assert_eq!(
+            sanitize_html(
+                "<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup><li id=\"fn-1\"><a href=\"#fnref-1\" class=\"footnote-backref\">↩</a></li>"
+            ),
+            "<sup class=\"footnote-ref\"><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\">1</a></sup><li id=\"user-content-fn-1\"><a href=\"#user-content-fnref-1\" class=\"footnote-backref\">↩</a></li>"
+        );
+        assert_eq!(
+            sanitize_html("<a id=\"config\">x</a><p id=\"  \">y</p>"),
+            "<a id=\"user-content-config\">x</a><p>y</p>"
+        );
+
+        // 只保留渲染器使用的类名，全部被过滤时去掉整个属性
+        assert_eq!(
+            sanitize_html(
+                "<code class=\"language-rust highlight modal\"><span class=\"hl-source hl-rust\">x</span></code>"
+            ),
+            "<code class=\"language-rust highlight\"><span class=\"hl-source hl-rust\">x</span></code>"
+        );
+        assert_eq!(
+            sanitize_html("<div class=\"modal admin-banner\">x</div>"),
+            "<div>x</div>"
+        );
+    }
+}
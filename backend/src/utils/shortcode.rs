@@ -0,0 +1,75 @@
+use crate::config::ShortcodeConfig;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+/// 嵌入短代码注册表。
+///
+/// 短代码写法为单独成段的 `{{< youtube dQw4w9WgXcQ >}}`，在 Markdown 渲染完成之后展开：
+/// 此时短代码已被 comrak 转义并包在 `<p>` 中，代码块中的短代码不在 `<p>` 里，不会被展开。
+/// 只有注册过的短代码、且参数完整匹配其 `id_pattern` 时才替换为模板，否则原样保留为文本。
+pub struct ShortcodeRegistry {
+    shortcode_pattern: Regex,
+    shortcodes: HashMap<String, Shortcode>,
+}
+
+struct Shortcode {
+    id_pattern: Regex,
+    template: String,
+}
+
+impl ShortcodeRegistry {
+    pub fn new(configs: &[ShortcodeConfig]) -> Self {
+        let mut shortcodes = HashMap::new();
+        for config in configs {
+            // 参数必须完整匹配，避免 id_pattern 遗漏 ^ $ 时放过额外的内容
+            match Regex::new(&format!("^(?:{})$", config.id_pattern)) {
+                Ok(id_pattern) => {
+                    shortcodes.insert(
+                        config.name.to_lowercase(),
+                        Shortcode {
+                            id_pattern,
+                            template: config.template.clone(),
+                        },
+                    );
+                }
+                Err(e) => {
+                    tracing::error!("短代码 {} 的 id_pattern 无效，已忽略: {}", config.name, e);
+                }
+            }
+        }
+
+        Self {
+            shortcode_pattern: Regex::new(
+                r"(?m)^<p>\{\{&lt; *([A-Za-z][A-Za-z0-9_-]*) +([^\s<>&]+) *&gt;\}\}</p>$",
+            )
+            .expect("短代码的正则表达式是固定的，一定合法"),
+            shortcodes,
+        }
+    }
+
+    /// 展开渲染结果中的短代码
+    pub fn expand(&self, html: &str) -> String {
+        if self.shortcodes.is_empty() || !html.contains("{{&lt;") {
+            return html.to_string();
+        }
+
+        self.shortcode_pattern
+            .replace_all(html, |caps: &Captures| {
+                let name = caps[1].to_lowercase();
+                let id = &caps[2];
+                match self.shortcodes.get(&name) {
+                    Some(shortcode) if shortcode.id_pattern.is_match(id) => {
+                        // 参数已通过校验，仍然转义后再放进模板
+                        let mut escaped = Vec::with_capacity(id.len());
+                        comrak::html::escape(&mut escaped, id.as_bytes())
+                            .expect("写入内存缓冲区不会失败");
+                        shortcode
+                            .template
+                            .replace("{id}", &String::from_utf8_lossy(&escaped))
+                    }
+                    _ => caps[0].to_string(),
+                }
+            })
+            .into_owned()
+    }
+}
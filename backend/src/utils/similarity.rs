@@ -0,0 +1,211 @@
+use slug::slugify;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// 相似名称检测，标签和分类共用
+// 名称先规范化（小写、去掉标点和空白、中文转为拼音），再综合编辑距离和三元组相似度打分，
+// 最后把相似的名称聚成组，而不是逐对列出
+
+/// 两个名称被视为相似所需的最低置信度
+pub const SIMILARITY_THRESHOLD: f64 = 0.6;
+
+const NORMALIZED_MATCH_REASON: &str = "名称仅大小写、标点或拼音写法不同";
+const FUZZY_MATCH_REASON: &str = "名称拼写相近";
+
+/// 一组相似的名称
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarityCluster {
+    pub ids: Vec<Uuid>,
+    /// 0 到 1 之间，取组内把成员连在一起的最弱一对相似关系的得分
+    pub confidence: f64,
+    pub reason: String,
+}
+
+/// 规范化名称："Rust-Lang"、"rust lang"、"RustLang" 都得到 "rustlang"，"编程" 得到 "biancheng"
+pub fn normalize_name(name: &str) -> String {
+    slugify(name).replace('-', "")
+}
+
+/// 基于编辑距离的相似度：1 - 编辑距离 / 较长名称的字符数
+pub fn edit_similarity(a: &str, b: &str) -> f64 {
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein_distance(a, b) as f64 / max_len as f64
+}
+
+/// 把名称聚成相似组
+///
+/// `trigram_pairs` 是数据库用 pg_trgm 在规范化名称上找出的候选对及其三元组相似度。
+/// 规范化后完全相同的名称不依赖候选对，直接归为一组，置信度为 1
+pub fn cluster_similar_names(
+    names: &[(Uuid, String)],
+    trigram_pairs: &[(Uuid, Uuid, f64)],
+) -> Vec<SimilarityCluster> {
+    let keys: HashMap<Uuid, String> = names
+        .iter()
+        .map(|(id, name)| (*id, normalize_name(name)))
+        .filter(|(_, key)| !key.is_empty())
+        .collect();
+
+    // 1. 打分：(名称 A, 名称 B, 置信度, 是否规范化后相同)
+    let mut edges: Vec<(Uuid, Uuid, f64, bool)> = Vec::new();
+    let mut first_by_key: HashMap<&str, Uuid> = HashMap::new();
+    for (id, _) in names {
+        let Some(key) = keys.get(id) else { continue };
+        match first_by_key.get(key.as_str()) {
+            Some(&first) => edges.push((first, *id, 1.0, true)),
+            None => {
+                first_by_key.insert(key, *id);
+            }
+        }
+    }
+    for &(a, b, trigram) in trigram_pairs {
+        let (Some(key_a), Some(key_b)) = (keys.get(&a), keys.get(&b)) else {
+            continue;
+        };
+        if key_a == key_b {
+            continue; // 已在上面按规范化名称归组
+        }
+        let score = (edit_similarity(key_a, key_b) + trigram) / 2.0;
+        if score >= SIMILARITY_THRESHOLD {
+            edges.push((a, b, score, false));
+        }
+    }
+
+    // 2. 按得分从高到低合并（单链接聚类），组的置信度取合并时用到的最弱一条边
+    edges.sort_by(|x, y| y.2.total_cmp(&x.2));
+    let index: HashMap<Uuid, usize> = names
+        .iter()
+        .enumerate()
+        .map(|(i, (id, _))| (*id, i))
+        .collect();
+    let mut parent: Vec<usize> = (0..names.len()).collect();
+    let mut confidence = vec![1.0; names.len()];
+    let mut matched_by = vec![(false, false); names.len()]; // (规范化相同, 拼写相近)
+    for (a, b, score, normalized) in edges {
+        let root_a = find_root(&mut parent, index[&a]);
+        let root_b = find_root(&mut parent, index[&b]);
+        if root_a == root_b {
+            continue;
+        }
+        parent[root_b] = root_a;
+        confidence[root_a] = f64::min(confidence[root_a], confidence[root_b]).min(score);
+        matched_by[root_a].0 |= matched_by[root_b].0 || normalized;
+        matched_by[root_a].1 |= matched_by[root_b].1 || !normalized;
+    }
+
+    // 3. 收集成员数不少于 2 的组，成员保持输入顺序
+    let mut members: HashMap<usize, Vec<Uuid>> = HashMap::new();
+    for (i, (id, _)) in names.iter().enumerate() {
+        let root = find_root(&mut parent, i);
+        members.entry(root).or_default().push(*id);
+    }
+    let mut clusters: Vec<SimilarityCluster> = members
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(root, ids)| {
+            let (normalized, fuzzy) = matched_by[root];
+            let reasons: Vec<&str> = [
+                (normalized, NORMALIZED_MATCH_REASON),
+                (fuzzy, FUZZY_MATCH_REASON),
+            ]
+            .into_iter()
+            .filter_map(|(matched, reason)| matched.then_some(reason))
+            .collect();
+            SimilarityCluster {
+                ids,
+                confidence: confidence[root],
+                reason: reasons.join("；"),
+            }
+        })
+        .collect();
+
+    clusters.sort_by(|x, y| {
+        y.confidence
+            .total_cmp(&x.confidence)
+            .then(y.ids.len().cmp(&x.ids.len()))
+    });
+    clusters
+}
+
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+// 按字符计算编辑距离，只保留上一行
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let cost = usize::from(a_char != *b_char);
+            current[j + 1] = (previous[j + 1] + 1)
+                .min(current[j] + 1)
+                .min(previous[j] + cost);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name_ignores_case_punctuation_and_script() {
+        assert_eq!(normalize_name("Rust-Lang"), "rustlang");
+        assert_eq!(normalize_name("  rust lang! "), "rustlang");
+        assert_eq!(normalize_name("编程"), "biancheng");
+        assert_eq!(normalize_name("Bian Cheng"), "biancheng");
+    }
+
+    #[test]
+    fn test_edit_similarity_counts_characters() {
+        assert_eq!(edit_similarity("rust", "rust"), 1.0);
+        assert_eq!(edit_similarity("javascript", "javscript"), 0.9);
+        // 多字节字符按字符而不是字节计算，也不会在切片时 panic
+        assert_eq!(edit_similarity("数据库", "数据"), 1.0 - 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_cluster_similar_names() {
+        let names: Vec<(Uuid, String)> = [
+            "rustlang",
+            "Rust Lang",
+            "RUST-LANG!",
+            "javascript",
+            "javscript",
+            "Go",
+            "Java",
+        ]
+        .iter()
+        .map(|name| (Uuid::new_v4(), name.to_string()))
+        .collect();
+        let id = |i: usize| names[i].0;
+        // 假设数据库返回的三元组候选对
+        let trigram_pairs = vec![(id(3), id(4), 0.62), (id(3), id(6), 0.36)];
+
+        let clusters = cluster_similar_names(&names, &trigram_pairs);
+        assert_eq!(clusters.len(), 2);
+
+        assert_eq!(clusters[0].ids, vec![id(0), id(1), id(2)]);
+        assert_eq!(clusters[0].confidence, 1.0);
+        assert_eq!(clusters[0].reason, NORMALIZED_MATCH_REASON);
+
+        // javascript 和 Java 得分低于阈值，不会被归为一组
+        assert_eq!(clusters[1].ids, vec![id(3), id(4)]);
+        assert!((clusters[1].confidence - 0.76).abs() < 1e-9);
+        assert_eq!(clusters[1].reason, FUZZY_MATCH_REASON);
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+// 标签和分类推荐
+// 用已有文章训练 TF-IDF 模型：每个标签（或分类）的特征是使用它的文章向量的质心，
+// 待推荐文章与质心的余弦相似度，再加上标题和正文中是否直接提到了标签名称，得到推荐分数
+//
+// 中文没有空格分词，连续的汉字按二元组切分；英文和数字按单词切分
+
+/// 推荐分数中 TF-IDF 相似度所占的权重，其余为关键词匹配
+const TFIDF_WEIGHT: f64 = 0.6;
+/// 名称出现在标题中时的关键词得分
+const TITLE_MATCH_SCORE: f64 = 1.0;
+/// 名称只出现在正文中时的关键词得分
+const CONTENT_MATCH_SCORE: f64 = 0.6;
+/// 低于该分数的推荐直接丢弃
+pub const MIN_SUGGESTION_SCORE: f64 = 0.1;
+/// 余弦相似度高于该值时才在推荐理由中说明内容相似
+const SIMILAR_CONTENT_THRESHOLD: f64 = 0.05;
+
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "the", "and", "for", "are", "but", "not", "you", "all", "can", "was", "this", "that", "with",
+    "from", "have", "has", "will", "your", "into", "its", "our", "use", "using", "how", "what",
+    "when", "which", "than", "then", "there", "their", "they", "them", "were", "been", "also",
+    "more", "some", "such", "only", "other", "about", "would", "should", "could", "http", "https",
+    "www", "com",
+];
+
+const CHINESE_STOPWORDS: &[&str] = &[
+    "我们", "你们", "他们", "一个", "这个", "那个", "这些", "那些", "可以", "没有", "就是", "不是",
+    "什么", "如果", "因为", "所以", "但是", "然后", "还是", "已经", "需要", "进行", "通过", "以及",
+    "或者", "其中", "自己", "这样", "这里", "时候",
+];
+
+/// 可推荐的标签或分类，`names` 包括名称和别名
+#[derive(Debug, Clone)]
+pub struct TaxonomyLabel {
+    pub id: Uuid,
+    pub names: Vec<String>,
+}
+
+/// 一条推荐结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub id: Uuid,
+    /// 0 到 1 之间，越高越相关
+    pub score: f64,
+    pub reasons: Vec<String>,
+}
+
+type TermVector = HashMap<String, f64>;
+
+/// 训练好的推荐模型
+pub struct SuggestionModel {
+    idf: HashMap<String, f64>,
+    centroids: HashMap<Uuid, TermVector>,
+    labels: Vec<TaxonomyLabel>,
+}
+
+impl SuggestionModel {
+    /// 用 (文章文本, 文章已有的标签或分类) 训练模型
+    ///
+    /// 只为 `labels` 中的标签计算质心，文章上的其他标签（例如即将删除的标签）会被忽略
+    pub fn train<'a, I>(documents: I, labels: Vec<TaxonomyLabel>) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, &'a [Uuid])>,
+    {
+        let documents: Vec<(Vec<String>, &[Uuid])> = documents
+            .into_iter()
+            .map(|(text, doc_labels)| (tokenize(text), doc_labels))
+            .collect();
+
+        // 1. 逆文档频率（平滑处理，避免只出现在所有文章中的词得到 0 或负值）
+        let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+        for (tokens, _) in &documents {
+            let unique: HashSet<&str> = tokens.iter().map(String::as_str).collect();
+            for token in unique {
+                *document_frequency.entry(token).or_default() += 1;
+            }
+        }
+        let total = documents.len() as f64;
+        let idf: HashMap<String, f64> = document_frequency
+            .into_iter()
+            .map(|(token, df)| {
+                let weight = ((1.0 + total) / (1.0 + df as f64)).ln() + 1.0;
+                (token.to_string(), weight)
+            })
+            .collect();
+
+        // 2. 每个标签的质心：使用它的文章向量之和，再归一化
+        let wanted: HashSet<Uuid> = labels.iter().map(|label| label.id).collect();
+        let mut centroids: HashMap<Uuid, TermVector> = HashMap::new();
+        for (tokens, doc_labels) in &documents {
+            let vector = tfidf_vector(tokens, &idf);
+            for label in doc_labels.iter().filter(|id| wanted.contains(id)) {
+                let centroid = centroids.entry(*label).or_default();
+                for (term, weight) in &vector {
+                    *centroid.entry(term.clone()).or_default() += weight;
+                }
+            }
+        }
+        for centroid in centroids.values_mut() {
+            normalize(centroid);
+        }
+
+        Self {
+            idf,
+            centroids,
+            labels,
+        }
+    }
+
+    /// 为一篇文章推荐标签或分类，跳过 `exclude` 中的标签，按分数从高到低返回至多 `limit` 条
+    pub fn suggest(
+        &self,
+        title: &str,
+        content: &str,
+        exclude: &[Uuid],
+        limit: usize,
+    ) -> Vec<Suggestion> {
+        let tokens = tokenize(&format!("{}\n{}", title, content));
+        let vector = tfidf_vector(&tokens, &self.idf);
+        let title_lower = title.to_lowercase();
+        let content_lower = content.to_lowercase();
+
+        let mut suggestions: Vec<Suggestion> = self
+            .labels
+            .iter()
+            .filter(|label| !exclude.contains(&label.id))
+            .filter_map(|label| {
+                let mut reasons = Vec::new();
+
+                let similarity = self
+                    .centroids
+                    .get(&label.id)
+                    .map(|centroid| cosine(&vector, centroid))
+                    .unwrap_or(0.0);
+                if similarity >= SIMILAR_CONTENT_THRESHOLD {
+                    reasons.push("与已使用它的文章内容相似".to_string());
+                }
+
+                let mut keyword = 0.0;
+                for name in &label.names {
+                    if mentions(&title_lower, name) {
+                        keyword = TITLE_MATCH_SCORE;
+                        reasons.push(format!("标题中提到了 '{}'", name));
+                        break;
+                    }
+                    if keyword == 0.0 && mentions(&content_lower, name) {
+                        keyword = CONTENT_MATCH_SCORE;
+                        reasons.push(format!("正文中提到了 '{}'", name));
+                    }
+                }
+
+                let score = TFIDF_WEIGHT * similarity + (1.0 - TFIDF_WEIGHT) * keyword;
+                (score >= MIN_SUGGESTION_SCORE).then_some(Suggestion {
+                    id: label.id,
+                    score,
+                    reasons,
+                })
+            })
+            .collect();
+
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+        suggestions.truncate(limit);
+        suggestions
+    }
+}
+
+/// 把文本切分为词：英文和数字按单词，连续的汉字按二元组，统一小写并去掉常见停用词
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut han: Vec<char> = Vec::new();
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if is_han(c) {
+            flush_word(&mut word, &mut tokens);
+            han.push(c);
+        } else if c.is_alphanumeric() {
+            flush_han(&mut han, &mut tokens);
+            word.push(c);
+        } else {
+            flush_word(&mut word, &mut tokens);
+            flush_han(&mut han, &mut tokens);
+        }
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_han(&mut han, &mut tokens);
+
+    tokens
+}
+
+fn flush_word(word: &mut String, tokens: &mut Vec<String>) {
+    if word.chars().count() >= 2 && !ENGLISH_STOPWORDS.contains(&word.as_str()) {
+        tokens.push(word.clone());
+    }
+    word.clear();
+}
+
+fn flush_han(han: &mut Vec<char>, tokens: &mut Vec<String>) {
+    if han.len() == 1 {
+        tokens.push(han[0].to_string());
+    }
+    for pair in han.windows(2) {
+        let bigram: String = pair.iter().collect();
+        if !CHINESE_STOPWORDS.contains(&bigram.as_str()) {
+            tokens.push(bigram);
+        }
+    }
+    han.clear();
+}
+
+fn is_han(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '\u{F900}'..='\u{FAFF}')
+}
+
+// 名称是否出现在文本中（文本已转为小写）；英文名称要求前后不是字母或数字，避免 "go" 匹配到 "google"
+fn mentions(text: &str, name: &str) -> bool {
+    let name = name.trim().to_lowercase();
+    if name.chars().count() < 2 {
+        return false;
+    }
+    text.match_indices(&name).any(|(start, matched)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + matched.len()..].chars().next();
+        let is_boundary = |c: Option<char>| c.is_none_or(|c| !c.is_alphanumeric() || is_han(c));
+        name.starts_with(is_han) || (is_boundary(before) && is_boundary(after))
+    })
+}
+
+fn tfidf_vector(tokens: &[String], idf: &HashMap<String, f64>) -> TermVector {
+    let mut vector = TermVector::new();
+    for token in tokens {
+        // 语料中没有出现过的词无法与任何标签比较，直接忽略
+        if let Some(weight) = idf.get(token) {
+            *vector.entry(token.clone()).or_default() += weight;
+        }
+    }
+    normalize(&mut vector);
+    vector
+}
+
+fn normalize(vector: &mut TermVector) {
+    let norm = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        for weight in vector.values_mut() {
+            *weight /= norm;
+        }
+    }
+}
+
+// 两个向量都已归一化，点积即余弦相似度
+fn cosine(a: &TermVector, b: &TermVector) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    small
+        .iter()
+        .filter_map(|(term, weight)| large.get(term).map(|other| weight * other))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_mixed_text() {
+        assert_eq!(
+            tokenize("Rust 异步编程: the Tokio runtime"),
+            vec!["rust", "异步", "步编", "编程", "tokio", "runtime"]
+        );
+        // 停用词和单个字母被忽略，单独的汉字保留
+        assert_eq!(tokenize("我们 a 书 and 可以"), vec!["书"]);
+    }
+
+    #[test]
+    fn test_mentions_respects_word_boundaries() {
+        assert!(mentions("learning go in 2025", "Go"));
+        assert!(!mentions("search with google", "Go"));
+        assert!(mentions("深入理解数据库索引", "数据库"));
+    }
+
+    #[test]
+    fn test_suggest_combines_tfidf_and_keywords() {
+        let rust = Uuid::new_v4();
+        let cooking = Uuid::new_v4();
+        let python = Uuid::new_v4();
+        let rust_labels = [rust];
+        let cooking_labels = [cooking];
+        let documents = vec![
+            (
+                "tokio async runtime futures borrow checker",
+                &rust_labels[..],
+            ),
+            ("cargo crates borrow checker lifetimes", &rust_labels[..]),
+            ("红烧肉 食谱 火候 调味", &cooking_labels[..]),
+        ];
+        let labels = vec![
+            TaxonomyLabel {
+                id: rust,
+                names: vec!["Rust".to_string(), "rust-lang".to_string()],
+            },
+            TaxonomyLabel {
+                id: cooking,
+                names: vec!["美食".to_string()],
+            },
+            TaxonomyLabel {
+                id: python,
+                names: vec!["Python".to_string()],
+            },
+        ];
+        let model = SuggestionModel::train(documents, labels);
+
+        // 内容与 Rust 文章相似，但没有提到名称
+        let suggestions = model.suggest("异步入门", "borrow checker and lifetimes", &[], 5);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].id, rust);
+        assert_eq!(suggestions[0].reasons, vec!["与已使用它的文章内容相似"]);
+
+        // 从未使用过的 Python 标签可以通过标题中的关键词推荐
+        let suggestions = model.suggest("Python vs rust-lang", "", &[], 5);
+        let ids: Vec<Uuid> = suggestions.iter().map(|s| s.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&rust) && ids.contains(&python));
+
+        // 排除的标签不会出现在结果中
+        let suggestions = model.suggest("Python vs rust-lang", "", &[python], 5);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].id, rust);
+    }
+}
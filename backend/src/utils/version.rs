@@ -0,0 +1,82 @@
+use anyhow::Result;
+use axum::http::{HeaderMap, HeaderValue, header};
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+// 乐观并发控制
+// 资源（文章、分类、标签）以 updated_at 的微秒时间戳作为版本号，
+// GET 时通过 ETag 返回，PUT 时客户端必须通过 If-Match 带回读取时的版本
+
+/// 资源的版本号（updated_at 的微秒时间戳）
+pub fn resource_version(updated_at: DateTime<Utc>) -> String {
+    updated_at.timestamp_micros().to_string()
+}
+
+/// 资源版本对应的 ETag 响应头
+pub fn etag_header(updated_at: DateTime<Utc>) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", resource_version(updated_at)))
+        .expect("版本号只包含数字，一定是合法的响应头")
+}
+
+/// 版本校验失败的错误，由 ApiError 转换为 428 / 412 响应
+#[derive(Debug)]
+pub enum VersionError {
+    /// 更新请求缺少 If-Match 请求头
+    Missing,
+    /// If-Match 中的版本与服务器当前版本不一致
+    Stale { current_version: String },
+}
+
+impl VersionError {
+    pub fn stale(updated_at: DateTime<Utc>) -> Self {
+        VersionError::Stale {
+            current_version: resource_version(updated_at),
+        }
+    }
+}
+
+impl fmt::Display for VersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionError::Missing => {
+                write!(
+                    f,
+                    "更新请求必须携带 If-Match 请求头（取自 GET 返回的 ETag）"
+                )
+            }
+            VersionError::Stale { current_version } => {
+                write!(
+                    f,
+                    "资源已被其他人修改，服务器当前版本为 {}",
+                    current_version
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for VersionError {}
+
+/// 从 If-Match 请求头解析客户端期望的版本
+/// 返回 None 表示 `If-Match: *`，即不校验版本
+pub fn expected_version(headers: &HeaderMap) -> Result<Option<DateTime<Utc>>> {
+    let raw = headers
+        .get(header::IF_MATCH)
+        .ok_or(VersionError::Missing)?
+        .to_str()
+        .map_err(|_| anyhow::anyhow!("无效的 If-Match 请求头"))?
+        .trim();
+
+    if raw == "*" {
+        return Ok(None);
+    }
+
+    // 兼容弱校验前缀 W/ 和引号
+    let version = raw.trim_start_matches("W/").trim_matches('"');
+    version
+        .parse::<i64>()
+        .ok()
+        .and_then(DateTime::from_timestamp_micros)
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!("无效的 If-Match 请求头：'{}' 不是有效的版本号", raw))
+}
@@ -6,10 +6,16 @@ use axum::{
 };
 use backend::services::auth::LoginTokens;
 use backend::services::{
-    AdminService, AuthService, CategoryService, EmailService, PostService, TagService, UserService,
+    AdminService, AuthService, AutosaveService, CategoryService, DraftCommentService, EmailService,
+    NotificationService, PostService, PostTemplateService, PreviewLinkService, ReportService,
+    TagService, TaxonomySuggestionService, TrashService, UserService,
 };
 use backend::{
-    config::{AppConfig, AuthConfig, DatabaseConfig, DraftPolicy, EmailConfig, ServerConfig},
+    config::{
+        AppConfig, AuthConfig, AutosaveConfig, DatabaseConfig, DraftPolicy, EmailConfig,
+        MarkdownConfig, ReportConfig, ServerConfig, TrashConfig, default_render_profiles,
+        default_shortcodes,
+    },
     dtos::auth::{LoginResponsePayload, RefreshTokenPayload},
     handlers::AppState,
     routes::create_router,
@@ -60,12 +66,32 @@ async fn setup_test_app(pool: PgPool) -> Router {
             smtp_user: "".to_string(),
             smtp_pass: "".to_string(),
             from_address: "test@example.com".to_string(),
+            admin_base_url: "http://localhost:5173".to_string(),
         },
         draft_policy: DraftPolicy {
             mode: "private".to_string(),
             admin_access_all_drafts: false,
             audit_draft_access: true,
         },
+        autosave: AutosaveConfig {
+            retention_days: 30,
+            purge_interval_minutes: 60,
+        },
+        trash: TrashConfig {
+            retention_days: 30,
+            purge_interval_minutes: 60,
+        },
+        reports: ReportConfig {
+            max_reports_per_hour: 3,
+            trust_forwarded_for: true,
+        },
+        markdown: MarkdownConfig {
+            highlight_languages: vec!["rust".to_string(), "python".to_string()],
+            line_numbers: false,
+            shortcodes: default_shortcodes(),
+            default_profile: "standard".to_string(),
+            profiles: default_render_profiles(),
+        },
     };
 
     let user_repo = Arc::new(backend::repositories::PostgresUserRepository::new(
@@ -90,7 +116,7 @@ async fn setup_test_app(pool: PgPool) -> Router {
         role_repo.clone(),
         login_attempt_repo,
         one_time_token_repo,
-        email_service,
+        email_service.clone(),
         &test_config,
     ));
     let admin_service = Arc::new(AdminService::new(
@@ -99,6 +125,14 @@ async fn setup_test_app(pool: PgPool) -> Router {
         permission_repo,
     ));
     let user_service = Arc::new(UserService::new(user_repo.clone()));
+    let notification_service = Arc::new(NotificationService::new(
+        Arc::new(
+            backend::repositories::PostgresNotificationPreferenceRepository::new(pool.clone()),
+        ),
+        user_repo.clone(),
+        email_service,
+        &test_config.email,
+    ));
     let post_service = Arc::new(PostService::new(
         Arc::new(backend::repositories::PostgresPostRepository::new(
             pool.clone(),
@@ -110,6 +144,20 @@ async fn setup_test_app(pool: PgPool) -> Router {
             pool.clone(),
         )),
         user_repo.clone(),
+        notification_service.clone(),
+        &test_config.markdown,
+    ));
+
+    let report_service = Arc::new(ReportService::new(
+        Arc::new(backend::repositories::PostgresPostReportRepository::new(
+            pool.clone(),
+        )),
+        Arc::new(backend::repositories::PostgresPostRepository::new(
+            pool.clone(),
+        )),
+        post_service.clone(),
+        &test_config.reports,
+        &test_config.auth,
     ));
 
     let app_state = AppState {
@@ -123,6 +171,66 @@ async fn setup_test_app(pool: PgPool) -> Router {
         auth_service,
         admin_service,
         user_service,
+        draft_comment_service: Arc::new(DraftCommentService::new(
+            Arc::new(backend::repositories::PostgresDraftCommentRepository::new(
+                pool.clone(),
+            )),
+            Arc::new(backend::repositories::PostgresPostRepository::new(
+                pool.clone(),
+            )),
+        )),
+        autosave_service: Arc::new(AutosaveService::new(
+            Arc::new(backend::repositories::PostgresPostAutosaveRepository::new(
+                pool.clone(),
+            )),
+            Arc::new(backend::repositories::PostgresPostRepository::new(
+                pool.clone(),
+            )),
+            &test_config.autosave,
+        )),
+        trash_service: Arc::new(TrashService::new(
+            Arc::new(backend::repositories::PostgresPostRepository::new(
+                pool.clone(),
+            )),
+            Arc::new(backend::repositories::PostgresCategoryRepository::new(
+                pool.clone(),
+            )),
+            Arc::new(backend::repositories::PostgresTagRepository::new(
+                pool.clone(),
+            )),
+            &test_config.trash,
+        )),
+        preview_link_service: Arc::new(PreviewLinkService::new(
+            Arc::new(backend::repositories::PostgresPostPreviewLinkRepository::new(pool.clone())),
+            Arc::new(backend::repositories::PostgresPostRepository::new(
+                pool.clone(),
+            )),
+            &test_config.auth,
+        )),
+        notification_service,
+        report_service,
+        post_template_service: Arc::new(PostTemplateService::new(
+            Arc::new(backend::repositories::PostgresPostTemplateRepository::new(
+                pool.clone(),
+            )),
+            Arc::new(backend::repositories::PostgresCategoryRepository::new(
+                pool.clone(),
+            )),
+            Arc::new(backend::repositories::PostgresTagRepository::new(
+                pool.clone(),
+            )),
+        )),
+        taxonomy_suggestion_service: Arc::new(TaxonomySuggestionService::new(
+            Arc::new(backend::repositories::PostgresPostRepository::new(
+                pool.clone(),
+            )),
+            Arc::new(backend::repositories::PostgresTagRepository::new(
+                pool.clone(),
+            )),
+            Arc::new(backend::repositories::PostgresCategoryRepository::new(
+                pool.clone(),
+            )),
+        )),
     };
 
     create_router(app_state)
@@ -5,20 +5,28 @@ use axum::{
     Router,
 };
 use backend::{
-    config::{AppConfig, AuthConfig, DatabaseConfig, DraftPolicy, EmailConfig, ServerConfig},
+    config::{
+        AppConfig, AuthConfig, AutosaveConfig, DatabaseConfig, DraftPolicy, EmailConfig,
+        MarkdownConfig, ReportConfig, ServerConfig, TrashConfig, default_render_profiles,
+        default_shortcodes,
+    },
     dtos::category::{CreateCategoryPayload, UpdateCategoryPayload},
     handlers::AppState,
     models::{Category, Role, User},
     repositories::{
         CategoryRepository, LoginAttemptRepository, OneTimeTokenRepository, PermissionRepository,
-        PostRepository, PostgresCategoryRepository, PostgresLoginAttemptRepository,
-        PostgresOneTimeTokenRepository, PostgresPermissionRepository, PostgresPostRepository,
-        PostgresRoleRepository, PostgresTagRepository, PostgresUserRepository, RoleRepository,
-        TagRepository, UserRepository,
+        PostRepository, PostgresCategoryRepository, PostgresDraftCommentRepository, PostgresPostAutosaveRepository,
+        PostgresPostPreviewLinkRepository, PostgresPostReportRepository, PostgresPostTemplateRepository,
+        PostgresLoginAttemptRepository, PostgresNotificationPreferenceRepository,
+        PostgresOneTimeTokenRepository,
+        PostgresPermissionRepository, PostgresPostRepository, PostgresRoleRepository,
+        PostgresTagRepository, PostgresUserRepository, RoleRepository, TagRepository,
+        UserRepository,
     },
     routes::create_router,
     services::{
-        AdminService, AuthService, CategoryService, EmailService, PostService, TagService,
+        AdminService, AuthService, AutosaveService, CategoryService, DraftCommentService, EmailService,
+        NotificationService, PostService, PostTemplateService, PreviewLinkService, ReportService, TagService, TaxonomySuggestionService, TrashService,
         UserService,
     },
     utils::hash_password,
@@ -70,12 +78,32 @@ async fn setup_test_app(pool: PgPool) -> Router {
             smtp_user: "".to_string(),
             smtp_pass: "".to_string(),
             from_address: "test@example.com".to_string(),
+            admin_base_url: "http://localhost:5173".to_string(),
         },
         draft_policy: DraftPolicy {
             mode: "private".to_string(),
             admin_access_all_drafts: false,
             audit_draft_access: true,
         },
+        autosave: AutosaveConfig {
+            retention_days: 30,
+            purge_interval_minutes: 60,
+        },
+        trash: TrashConfig {
+            retention_days: 30,
+            purge_interval_minutes: 60,
+        },
+        reports: ReportConfig {
+            max_reports_per_hour: 3,
+            trust_forwarded_for: true,
+        },
+        markdown: MarkdownConfig {
+            highlight_languages: vec!["rust".to_string(), "python".to_string()],
+            line_numbers: false,
+            shortcodes: default_shortcodes(),
+            default_profile: "standard".to_string(),
+            profiles: default_render_profiles(),
+        },
     };
     let user_repo: Arc<dyn UserRepository> = Arc::new(PostgresUserRepository::new(pool.clone()));
     let role_repo: Arc<dyn RoleRepository> = Arc::new(PostgresRoleRepository::new(pool.clone()));
@@ -90,12 +118,18 @@ async fn setup_test_app(pool: PgPool) -> Router {
     let tag_repo: Arc<dyn TagRepository> = Arc::new(PostgresTagRepository::new(pool.clone()));
     let post_repo: Arc<dyn PostRepository> = Arc::new(PostgresPostRepository::new(pool.clone()));
     let email_service = Arc::new(EmailService::new(test_config.email.clone()));
+    let notification_service = Arc::new(NotificationService::new(
+        Arc::new(PostgresNotificationPreferenceRepository::new(pool.clone())),
+        user_repo.clone(),
+        email_service.clone(),
+        &test_config.email,
+    ));
     let auth_service = Arc::new(AuthService::new(
         user_repo.clone(),
         role_repo.clone(),
         login_attempt_repo,
         one_time_token_repo,
-        email_service,
+        email_service.clone(),
         &test_config,
     ));
     let admin_service = Arc::new(AdminService::new(
@@ -111,6 +145,45 @@ async fn setup_test_app(pool: PgPool) -> Router {
         category_repo.clone(),
         tag_repo.clone(),
         user_repo.clone(),
+        notification_service.clone(),
+        &test_config.markdown,
+    ));
+    let draft_comment_service = Arc::new(DraftCommentService::new(
+        Arc::new(PostgresDraftCommentRepository::new(pool.clone())),
+        post_repo.clone(),
+    ));
+    let autosave_service = Arc::new(AutosaveService::new(
+        Arc::new(PostgresPostAutosaveRepository::new(pool.clone())),
+        post_repo.clone(),
+        &test_config.autosave,
+    ));
+    let trash_service = Arc::new(TrashService::new(
+        post_repo.clone(),
+        category_repo.clone(),
+        tag_repo.clone(),
+        &test_config.trash,
+    ));
+    let preview_link_service = Arc::new(PreviewLinkService::new(
+        Arc::new(PostgresPostPreviewLinkRepository::new(pool.clone())),
+        post_repo.clone(),
+        &test_config.auth,
+    ));
+    let report_service = Arc::new(ReportService::new(
+        Arc::new(PostgresPostReportRepository::new(pool.clone())),
+        post_repo.clone(),
+        post_service.clone(),
+        &test_config.reports,
+        &test_config.auth,
+    ));
+    let post_template_service = Arc::new(PostTemplateService::new(
+        Arc::new(PostgresPostTemplateRepository::new(pool.clone())),
+        category_repo.clone(),
+        tag_repo.clone(),
+    ));
+    let taxonomy_suggestion_service = Arc::new(TaxonomySuggestionService::new(
+        post_repo.clone(),
+        tag_repo.clone(),
+        category_repo.clone(),
     ));
     let app_state = AppState {
         post_service,
@@ -119,6 +192,14 @@ async fn setup_test_app(pool: PgPool) -> Router {
         auth_service,
         admin_service,
         user_service,
+        draft_comment_service,
+        autosave_service,
+        trash_service,
+        preview_link_service,
+        notification_service,
+        report_service,
+        post_template_service,
+        taxonomy_suggestion_service,
     };
     create_router(app_state)
 }
@@ -219,7 +300,7 @@ async fn register_and_login_new_user(app: &Router) -> Result<(String, Uuid)> {
 async fn seed_one_category(pool: &PgPool, name: &str) -> Result<Category> {
     sqlx::query_as!(
         Category,
-        r#"INSERT INTO categories (id, name, slug) VALUES ($1, $2, $3) RETURNING *"#,
+        r#"INSERT INTO categories (id, name, slug) VALUES ($1, $2, $3) RETURNING id, name, slug, parent_id, created_at, updated_at"#,
         Uuid::new_v4(),
         name,
         slugify(name)
@@ -283,6 +364,7 @@ async fn test_create_category_as_editor_success(pool: PgPool) -> Result<()> {
     let token = get_token_for_user(&app, &editor.username, "StrongPassword123!").await?;
     let payload = CreateCategoryPayload {
         name: Some("由编辑创建".to_string()),
+        parent_id: None,
     };
 
     // 执行
@@ -308,6 +390,7 @@ async fn test_update_category_as_editor_success(pool: PgPool) -> Result<()> {
     let category = seed_one_category(&pool, "旧分类").await?;
     let payload = UpdateCategoryPayload {
         name: Some("新分类".to_string()),
+        ..Default::default()
     };
 
     // 执行
@@ -315,6 +398,10 @@ async fn test_update_category_as_editor_success(pool: PgPool) -> Result<()> {
         .method(Method::PUT)
         .uri(format!("/categories/{}", category.id))
         .header("Content-Type", "application/json")
+        .header(
+            "If-Match",
+            format!("\"{}\"", category.updated_at.timestamp_micros()),
+        )
         .header("Authorization", format!("Bearer {}", token))
         .body(Body::from(serde_json::to_vec(&payload)?))?;
     let response = app.oneshot(request).await?;
@@ -348,6 +435,205 @@ async fn test_delete_category_as_editor_success(pool: PgPool) -> Result<()> {
     Ok(())
 }
 
+#[sqlx::test]
+async fn test_category_hierarchy(pool: PgPool) -> Result<()> {
+    // 准备: Docs > Guides > Getting Started 三级分类
+    let app = setup_test_app(pool.clone()).await;
+    let editor = seed_user_with_role(&pool, "editor_for_cat_tree", "editor").await?;
+    let token = get_token_for_user(&app, &editor.username, "StrongPassword123!").await?;
+
+    let send = |method: Method, uri: String, payload: Option<serde_json::Value>| {
+        let builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token));
+        match payload {
+            Some(payload) => builder.body(Body::from(serde_json::to_vec(&payload).unwrap())),
+            None => builder.body(Body::empty()),
+        }
+        .unwrap()
+    };
+
+    let mut categories = Vec::new();
+    let mut parent_id: Option<Uuid> = None;
+    for name in ["Docs", "Guides", "Getting Started"] {
+        let payload = serde_json::json!({ "name": name, "parent_id": parent_id });
+        let response = app
+            .clone()
+            .oneshot(send(Method::POST, "/categories".to_string(), Some(payload)))
+            .await?;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body_bytes = response.into_body().collect().await?.to_bytes();
+        let category: Category = serde_json::from_slice(&body_bytes)?;
+        assert_eq!(category.parent_id, parent_id);
+        parent_id = Some(category.id);
+        categories.push(category);
+    }
+    let (docs, guides, getting_started) = (categories[0].id, categories[1].id, categories[2].id);
+
+    // 1. 不能把分类移到自己的子分类下
+    let payload = serde_json::json!({ "parent_id": getting_started });
+    let mut request = send(Method::PUT, format!("/categories/{}", docs), Some(payload));
+    request.headers_mut().insert(
+        "If-Match",
+        format!("\"{}\"", categories[0].updated_at.timestamp_micros()).parse()?,
+    );
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 2. 分类树按层级嵌套
+    let response = app
+        .clone()
+        .oneshot(send(Method::GET, "/categories/tree".to_string(), None))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let tree: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(tree.as_array().context("分类树应为数组")?.len(), 1);
+    assert_eq!(tree[0]["name"], "Docs");
+    assert_eq!(tree[0]["children"][0]["name"], "Guides");
+    assert_eq!(tree[0]["children"][0]["children"][0]["name"], "Getting Started");
+
+    // 3. 文章的分类带有面包屑，按分类筛选时可以包括子分类
+    let post_id = Uuid::new_v4();
+    sqlx::query!(
+        "INSERT INTO posts (id, slug, title, content, author_id, published_at) VALUES ($1, 'tree-post', 'Tree Post', '正文', $2, NOW())",
+        post_id,
+        editor.id
+    )
+    .execute(&pool)
+    .await?;
+    sqlx::query!(
+        "INSERT INTO post_categories (post_id, category_id) VALUES ($1, $2)",
+        post_id,
+        getting_started
+    )
+    .execute(&pool)
+    .await?;
+
+    let response = app
+        .clone()
+        .oneshot(send(Method::GET, format!("/blog/posts/{}", post_id), None))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let post: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let breadcrumbs: Vec<&str> = post["categories"][0]["breadcrumbs"]
+        .as_array()
+        .context("缺少面包屑")?
+        .iter()
+        .filter_map(|b| b["name"].as_str())
+        .collect();
+    assert_eq!(breadcrumbs, ["Docs", "Guides"]);
+
+    for (query, expected) in [("", 0), ("?include_descendants=true", 1)] {
+        let response = app
+            .clone()
+            .oneshot(send(Method::GET, format!("/blog/categories/docs/posts{}", query), None))
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body_bytes = response.into_body().collect().await?.to_bytes();
+        let page: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+        assert_eq!(page["total_items"], expected, "{}", query);
+    }
+
+    // 4. 目标分类是源分类的后代时拒绝合并
+    let payload = serde_json::json!({ "target_category_id": getting_started, "source_category_ids": [docs] });
+    let response = app
+        .clone()
+        .oneshot(send(Method::POST, "/admin/categories/merge-enhanced".to_string(), Some(payload)))
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 5. 批量删除：选择 Refuse 时拒绝，默认把子分类移到最近的上级分类下
+    let payload = serde_json::json!({ "category_ids": [guides], "child_categories": "Refuse" });
+    let response = app
+        .clone()
+        .oneshot(send(Method::POST, "/admin/categories/batch-delete".to_string(), Some(payload)))
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let payload = serde_json::json!({ "category_ids": [guides] });
+    let response = app
+        .clone()
+        .oneshot(send(Method::POST, "/admin/categories/batch-delete".to_string(), Some(payload)))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let new_parent = sqlx::query_scalar!("SELECT parent_id FROM categories WHERE id = $1", getting_started)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(new_parent, Some(docs));
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_batch_delete_categories_auto_suggest(pool: PgPool) -> Result<()> {
+    // 准备: 删除 "Rust" 分类，其中一篇文章与 "编程" 分类相关，另一篇无法推荐
+    let app = setup_test_app(pool.clone()).await;
+    let editor = seed_user_with_role(&pool, "editor_for_cat_suggest", "editor").await?;
+    let token = get_token_for_user(&app, &editor.username, "StrongPassword123!").await?;
+    let rust = seed_one_category(&pool, "Rust").await?;
+    let programming = seed_one_category(&pool, "编程").await?;
+
+    let mut post_ids = Vec::new();
+    for (slug, title, content, category_id) in [
+        ("kept", "编程入门", "变量 函数 循环", programming.id),
+        ("suggested", "学习编程", "变量 函数 循环 borrow checker", rust.id),
+        ("unmatched", "周末", "天气 散步", rust.id),
+    ] {
+        let post_id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO posts (id, slug, title, content, author_id) VALUES ($1, $2, $3, $4, $5)",
+            post_id,
+            slug,
+            title,
+            content,
+            editor.id
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query!(
+            "INSERT INTO post_categories (post_id, category_id) VALUES ($1, $2)",
+            post_id,
+            category_id
+        )
+        .execute(&pool)
+        .await?;
+        post_ids.push(post_id);
+    }
+
+    // 执行
+    let payload = serde_json::json!({ "category_ids": [rust.id], "handle_orphaned_posts": "AutoSuggestCategories" });
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/admin/categories/batch-delete")
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+
+    // 断言: 能推荐的文章归入推荐的分类，其余归入 "未分类"
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let result: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(result["deleted_count"], 1);
+    assert_eq!(result["auto_assigned_post_count"], 1);
+
+    let category_names = |post_id: Uuid| {
+        sqlx::query_scalar!(
+            "SELECT c.name FROM post_categories pc JOIN categories c ON c.id = pc.category_id WHERE pc.post_id = $1",
+            post_id
+        )
+        .fetch_all(&pool)
+    };
+    assert_eq!(category_names(post_ids[1]).await?, ["编程"]);
+    assert_eq!(category_names(post_ids[2]).await?, ["未分类"]);
+
+    Ok(())
+}
+
 // == 授权失败测试
 #[sqlx::test]
 async fn test_create_category_no_token_fails(pool: PgPool) -> Result<()> {
@@ -355,6 +641,7 @@ async fn test_create_category_no_token_fails(pool: PgPool) -> Result<()> {
     let app = setup_test_app(pool.clone()).await;
     let payload = CreateCategoryPayload {
         name: Some("无Token分类".to_string()),
+        parent_id: None,
     };
 
     // 执行
@@ -6,24 +6,32 @@ use axum::{
 };
 use backend::utils::hash_password;
 use backend::{
-    config::{AppConfig, AuthConfig, DatabaseConfig, DraftPolicy, EmailConfig, ServerConfig},
+    config::{
+        AppConfig, AuthConfig, AutosaveConfig, DatabaseConfig, DraftPolicy, EmailConfig,
+        MarkdownConfig, ReportConfig, ServerConfig, TrashConfig, default_render_profiles,
+        default_shortcodes,
+    },
     dtos::{
         PaginatedResponse,
-        post::{CreatePostPayload, PostDetailDto, UpdatePostPayload},
+        post::{BulkPostResponse, CreatePostPayload, PostDetailDto, UpdatePostPayload},
     },
     handlers::AppState,
     models::{Category, Post, Role, Tag, User},
     repositories::{
         CategoryRepository, LoginAttemptRepository, OneTimeTokenRepository, PermissionRepository,
-        PostRepository, PostgresCategoryRepository, PostgresLoginAttemptRepository,
-        PostgresOneTimeTokenRepository, PostgresPermissionRepository, PostgresPostRepository,
+        PostRepository, PostgresCategoryRepository, PostgresDraftCommentRepository,
+        PostgresLoginAttemptRepository, PostgresNotificationPreferenceRepository,
+        PostgresOneTimeTokenRepository, PostgresPermissionRepository,
+        PostgresPostAutosaveRepository, PostgresPostPreviewLinkRepository,
+        PostgresPostReportRepository, PostgresPostRepository, PostgresPostTemplateRepository,
         PostgresRoleRepository, PostgresTagRepository, PostgresUserRepository, RoleRepository,
         TagRepository, UserRepository,
     },
     routes::create_router,
     services::{
-        AdminService, AuthService, CategoryService, EmailService, PostService, TagService,
-        UserService,
+        AdminService, AuthService, AutosaveService, CategoryService, DraftCommentService,
+        EmailService, NotificationService, PostService, PostTemplateService, PreviewLinkService,
+        ReportService, TagService, TaxonomySuggestionService, TrashService, UserService,
     },
 };
 use http_body_util::BodyExt;
@@ -78,12 +86,32 @@ async fn setup_test_app(pool: PgPool) -> Router {
             smtp_user: "".to_string(),
             smtp_pass: "".to_string(),
             from_address: "test@example.com".to_string(),
+            admin_base_url: "http://localhost:5173".to_string(),
         },
         draft_policy: DraftPolicy {
             mode: "private".to_string(),
             admin_access_all_drafts: false,
             audit_draft_access: true,
         },
+        autosave: AutosaveConfig {
+            retention_days: 30,
+            purge_interval_minutes: 60,
+        },
+        trash: TrashConfig {
+            retention_days: 30,
+            purge_interval_minutes: 60,
+        },
+        reports: ReportConfig {
+            max_reports_per_hour: 3,
+            trust_forwarded_for: true,
+        },
+        markdown: MarkdownConfig {
+            highlight_languages: vec!["rust".to_string(), "python".to_string()],
+            line_numbers: false,
+            shortcodes: default_shortcodes(),
+            default_profile: "standard".to_string(),
+            profiles: default_render_profiles(),
+        },
     };
 
     // 2. 实例化所有 Repositories
@@ -102,12 +130,18 @@ async fn setup_test_app(pool: PgPool) -> Router {
 
     // 3. 实例化所有 Services
     let email_service = Arc::new(EmailService::new(test_config.email.clone()));
+    let notification_service = Arc::new(NotificationService::new(
+        Arc::new(PostgresNotificationPreferenceRepository::new(pool.clone())),
+        user_repo.clone(),
+        email_service.clone(),
+        &test_config.email,
+    ));
     let auth_service = Arc::new(AuthService::new(
         user_repo.clone(),
         role_repo.clone(),
         login_attempt_repo,
         one_time_token_repo,
-        email_service,
+        email_service.clone(),
         &test_config,
     ));
     let admin_service = Arc::new(AdminService::new(
@@ -123,9 +157,48 @@ async fn setup_test_app(pool: PgPool) -> Router {
         category_repo.clone(),
         tag_repo.clone(),
         user_repo.clone(),
+        notification_service.clone(),
+        &test_config.markdown,
+    ));
+    let draft_comment_service = Arc::new(DraftCommentService::new(
+        Arc::new(PostgresDraftCommentRepository::new(pool.clone())),
+        post_repo.clone(),
+    ));
+    let autosave_service = Arc::new(AutosaveService::new(
+        Arc::new(PostgresPostAutosaveRepository::new(pool.clone())),
+        post_repo.clone(),
+        &test_config.autosave,
+    ));
+    let trash_service = Arc::new(TrashService::new(
+        post_repo.clone(),
+        category_repo.clone(),
+        tag_repo.clone(),
+        &test_config.trash,
+    ));
+    let preview_link_service = Arc::new(PreviewLinkService::new(
+        Arc::new(PostgresPostPreviewLinkRepository::new(pool.clone())),
+        post_repo.clone(),
+        &test_config.auth,
+    ));
+    let report_service = Arc::new(ReportService::new(
+        Arc::new(PostgresPostReportRepository::new(pool.clone())),
+        post_repo.clone(),
+        post_service.clone(),
+        &test_config.reports,
+        &test_config.auth,
+    ));
+    let post_template_service = Arc::new(PostTemplateService::new(
+        Arc::new(PostgresPostTemplateRepository::new(pool.clone())),
+        category_repo.clone(),
+        tag_repo.clone(),
     ));
 
     // 4. 创建完整的 AppState
+    let taxonomy_suggestion_service = Arc::new(TaxonomySuggestionService::new(
+        post_repo.clone(),
+        tag_repo.clone(),
+        category_repo.clone(),
+    ));
     let app_state = AppState {
         post_service,
         category_service,
@@ -133,6 +206,14 @@ async fn setup_test_app(pool: PgPool) -> Router {
         auth_service,
         admin_service,
         user_service,
+        draft_comment_service,
+        autosave_service,
+        trash_service,
+        preview_link_service,
+        notification_service,
+        report_service,
+        post_template_service,
+        taxonomy_suggestion_service,
     };
 
     // 5. 创建 Router
@@ -269,9 +350,9 @@ async fn seed_one_post(
     let post = sqlx::query_as!(
         Post,
         r#"
-        INSERT INTO posts (id, slug, title, content, author_id, published_at, draft_shared_with, is_draft_public,is_banned)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-        RETURNING id, slug, title, content, author_id, created_at, updated_at, published_at, draft_shared_with, is_draft_public,is_banned
+        INSERT INTO posts (id, slug, title, content, author_id, published_at, is_draft_public,is_banned)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, slug, title, content, author_id, created_at, updated_at, published_at, NULL::uuid[] as draft_shared_with, is_draft_public,is_banned,render_profile
         "#,
         Uuid::new_v4(),
         slug,
@@ -283,7 +364,6 @@ async fn seed_one_post(
         } else {
             None
         },
-        None::<&[Uuid]>, // draft_shared_with
         Some(false),      // is_draft_public
         false             // is_banned
     )
@@ -296,7 +376,7 @@ async fn seed_one_post(
 async fn seed_one_category(pool: &PgPool, name: &str) -> Result<Category> {
     sqlx::query_as!(
         Category,
-        r#"INSERT INTO categories (id, name, slug) VALUES ($1, $2, $3) RETURNING *;"#,
+        r#"INSERT INTO categories (id, name, slug) VALUES ($1, $2, $3) RETURNING id, name, slug, parent_id, created_at, updated_at;"#,
         Uuid::new_v4(),
         name,
         slugify(name)
@@ -309,7 +389,7 @@ async fn seed_one_category(pool: &PgPool, name: &str) -> Result<Category> {
 async fn seed_one_tag(pool: &PgPool, name: &str) -> Result<Tag> {
     sqlx::query_as!(
         Tag,
-        r#"INSERT INTO tags (id, name, slug) VALUES ($1, $2, $3) RETURNING *;"#,
+        r#"INSERT INTO tags (id, name, slug) VALUES ($1, $2, $3) RETURNING id, name, slug, created_at, updated_at;"#,
         Uuid::new_v4(),
         name,
         slugify(name)
@@ -336,6 +416,8 @@ async fn test_create_post_as_user_success(pool: PgPool) -> Result<()> {
         tag_ids: None,
         draft_shared_with: None,
         is_draft_public: None,
+        render_profile: None,
+        template_id: None,
     };
 
     // 执行
@@ -362,9 +444,15 @@ async fn test_create_post_as_user_success(pool: PgPool) -> Result<()> {
     assert_eq!(created_post.author_id, Some(user_id));
 
     // 从数据库验证作者ID是否正确
-    let db_post = sqlx::query_as!(Post, "SELECT * FROM posts WHERE id = $1", created_post.id)
-        .fetch_one(&pool)
-        .await?;
+    let db_post = sqlx::query_as!(
+        Post,
+        r#"SELECT id, slug, title, content, author_id, created_at, updated_at, published_at,
+                  NULL::uuid[] as draft_shared_with, is_draft_public, is_banned, render_profile
+           FROM posts WHERE id = $1"#,
+        created_post.id
+    )
+    .fetch_one(&pool)
+    .await?;
     assert_eq!(db_post.author_id, Some(user_id));
 
     Ok(())
@@ -384,6 +472,8 @@ async fn test_create_post_with_associations_success(pool: PgPool) -> Result<()>
         tag_ids: Some(vec![tag.id]),
         draft_shared_with: None,
         is_draft_public: None,
+        render_profile: None,
+        template_id: None,
     };
 
     let request = Request::builder()
@@ -418,6 +508,8 @@ async fn test_create_post_no_token_fails(pool: PgPool) -> Result<()> {
         tag_ids: None,
         draft_shared_with: None,
         is_draft_public: None,
+        render_profile: None,
+        template_id: None,
     };
 
     let request = Request::builder()
@@ -514,6 +606,10 @@ async fn test_update_own_post_success(pool: PgPool) -> Result<()> {
         .method(Method::PUT)
         .uri(format!("/posts/{}", original_post.id))
         .header("Content-Type", "application/json")
+        .header(
+            "If-Match",
+            format!("\"{}\"", original_post.updated_at.timestamp_micros()),
+        )
         .header("Authorization", format!("Bearer {}", token))
         .body(Body::from(serde_json::to_vec(&payload)?))?;
     let response = app.oneshot(request).await?;
@@ -580,6 +676,10 @@ async fn test_update_post_as_editor_success(pool: PgPool) -> Result<()> {
         .method(Method::PUT)
         .uri(format!("/posts/{}", post_to_update.id))
         .header("Content-Type", "application/json")
+        .header(
+            "If-Match",
+            format!("\"{}\"", post_to_update.updated_at.timestamp_micros()),
+        )
         .header("Authorization", format!("Bearer {}", editor_token))
         .body(Body::from(serde_json::to_vec(&payload)?))?;
     let response = app.oneshot(request).await?;
@@ -589,6 +689,75 @@ async fn test_update_post_as_editor_success(pool: PgPool) -> Result<()> {
     Ok(())
 }
 
+#[sqlx::test]
+async fn test_update_post_with_stale_version_fails(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (token, user_id) = register_and_login_new_user(&app).await?;
+    let post = seed_one_post(&pool, user_id, "并发编辑的帖子", "原始内容", false).await?;
+
+    // 1. 读取帖子，ETag 中带有当前版本
+    let request = Request::builder()
+        .uri(format!("/posts/{}", post.id))
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response.headers()["etag"].to_str()?.to_string();
+
+    let update_request = |if_match: Option<&str>, title: &str| -> Result<Request<Body>> {
+        let payload = UpdatePostPayload {
+            title: Some(title.to_string()),
+            ..Default::default()
+        };
+        let mut builder = Request::builder()
+            .method(Method::PUT)
+            .uri(format!("/posts/{}", post.id))
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token));
+        if let Some(if_match) = if_match {
+            builder = builder.header("If-Match", if_match);
+        }
+        Ok(builder.body(Body::from(serde_json::to_vec(&payload)?))?)
+    };
+
+    // 2. 第一个编辑者带着读取时的版本保存成功，得到新版本
+    let response = app
+        .clone()
+        .oneshot(update_request(Some(&etag), "第一个编辑者的标题")?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let new_etag = response.headers()["etag"].to_str()?.to_string();
+    assert_ne!(new_etag, etag);
+
+    // 3. 第二个编辑者仍持有旧版本，保存被拒绝并拿到服务器当前版本
+    let response = app
+        .clone()
+        .oneshot(update_request(Some(&etag), "第二个编辑者的标题")?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(
+        format!("\"{}\"", body["current_version"].as_str().unwrap()),
+        new_etag
+    );
+
+    // 4. 不带 If-Match 的更新请求被拒绝
+    let response = app
+        .clone()
+        .oneshot(update_request(None, "没有版本的标题")?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+
+    // 第一个编辑者的修改没有被覆盖
+    let title = sqlx::query_scalar!("SELECT title FROM posts WHERE id = $1", post.id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(title, "第一个编辑者的标题");
+
+    Ok(())
+}
+
 // == 删除帖子 (DELETE /posts/{id})
 
 #[sqlx::test]
@@ -606,14 +775,23 @@ async fn test_delete_own_post_success(pool: PgPool) -> Result<()> {
 
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
 
+    // 删除是软删除：记录仍在，但标记了 deleted_at
     let count = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM posts WHERE id = $1",
+        "SELECT COUNT(*) FROM posts WHERE id = $1 AND deleted_at IS NULL",
         post_to_delete.id
     )
     .fetch_one(&pool)
     .await?;
     assert_eq!(count.unwrap_or(0), 0);
 
+    let deleted_at = sqlx::query_scalar!(
+        "SELECT deleted_at FROM posts WHERE id = $1",
+        post_to_delete.id
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert!(deleted_at.is_some());
+
     Ok(())
 }
 
@@ -635,3 +813,2171 @@ async fn test_delete_post_not_owner_fails(pool: PgPool) -> Result<()> {
 
     Ok(())
 }
+
+// == 编辑审核工作流 (/posts/{id}/review/*)
+
+#[sqlx::test]
+async fn test_author_publish_requires_approved_review(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    let draft = seed_one_post(&pool, author_id, "需要审核的草稿", "内容", false).await?;
+    let editor = seed_user_with_role(&pool, "review_editor", "editor").await?;
+    let editor_token = get_token_for_user(&app, &editor.username, "StrongPassword123!").await?;
+
+    // 作者未经审核直接发布，应被拒绝
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/publish", draft.id))
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 作者提交审核并指定审核人
+    let submit_payload = serde_json::json!({ "reviewer_id": editor.id, "comment": "请帮忙审核" });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/review/submit", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&submit_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 审核人通过审核
+    let review_payload = serde_json::json!({ "decision": "approve", "comment": "没有问题" });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/review/decision", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", editor_token))
+        .body(Body::from(serde_json::to_vec(&review_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let review: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(review["status"], "approved");
+    assert_eq!(review["transitions"].as_array().map(Vec::len), Some(2));
+    assert_eq!(review["transitions"][1]["comment"], "没有问题");
+
+    // 通过审核后修改正文，审核状态退回草稿，不能再直接发布
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}", draft.id))
+        .header("Content-Type", "application/json")
+        .header("If-Match", "*")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(
+            &serde_json::json!({ "content": "审核通过后偷偷修改的内容" }),
+        )?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/publish", draft.id))
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let transitions = sqlx::query!(
+        "SELECT from_status, to_status, actor_id FROM post_review_transitions WHERE post_id = $1 ORDER BY created_at",
+        draft.id
+    )
+    .fetch_all(&pool)
+    .await?;
+    let last = transitions.last().context("缺少审核流转记录")?;
+    assert_eq!(
+        (last.from_status.as_str(), last.to_status.as_str()),
+        ("approved", "draft")
+    );
+    assert_eq!(last.actor_id, Some(author_id));
+
+    // 重新提交并通过审核
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/review/submit", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&submit_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/review/decision", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", editor_token))
+        .body(Body::from(serde_json::to_vec(&review_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 通过审核后作者可以发布
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/publish", draft.id))
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let status = sqlx::query_scalar!(
+        "SELECT status FROM post_reviews WHERE post_id = $1",
+        draft.id
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(status, "published");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_request_changes_requires_comment(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    let draft = seed_one_post(&pool, author_id, "待修改的草稿", "内容", false).await?;
+    let editor = seed_user_with_role(&pool, "review_editor_2", "editor").await?;
+    let editor_token = get_token_for_user(&app, &editor.username, "StrongPassword123!").await?;
+
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/review/submit", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&serde_json::json!({}))?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 要求修改但没有审核意见
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/review/decision", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", editor_token))
+        .body(Body::from(serde_json::to_vec(
+            &serde_json::json!({ "decision": "request_changes" }),
+        )?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 作者不能审核自己的文章
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/review/decision", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(
+            &serde_json::json!({ "decision": "approve" }),
+        )?))?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+// == 草稿评审意见 (/posts/{id}/comments)
+
+#[sqlx::test]
+async fn test_draft_comments_thread_on_shared_draft(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    let (reviewer_token, reviewer_id) = register_and_login_new_user(&app).await?;
+    let (outsider_token, _outsider_id) = register_and_login_new_user(&app).await?;
+    let draft = seed_one_post(
+        &pool,
+        author_id,
+        "共享草稿",
+        "# 引言\n\n这是一段需要评审的文字。",
+        false,
+    )
+    .await?;
+
+    // 作者把草稿分享给评审人
+    let share_payload = serde_json::json!({ "shared_with": [reviewer_id], "is_public": false });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/share", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&share_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 评审人锚定到标题添加评论
+    let comment_payload = serde_json::json!({
+        "body": "引言可以再精简一些",
+        "anchor": { "type": "heading", "text": "引言" }
+    });
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/posts/{}/comments", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", reviewer_token))
+        .body(Body::from(serde_json::to_vec(&comment_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let comment: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let comment_id = comment["id"]
+        .as_str()
+        .context("comment id missing")?
+        .to_string();
+    assert_eq!(comment["quoted_text"], "引言");
+
+    // 作者回复并解决该讨论串
+    let reply_payload = serde_json::json!({ "body": "已修改", "parent_id": comment_id });
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/posts/{}/comments", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&reply_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!(
+            "/posts/{}/comments/{}/resolve",
+            draft.id, comment_id
+        ))
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let resolved: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(resolved["is_resolved"], true);
+    assert_eq!(resolved["replies"].as_array().map(Vec::len), Some(1));
+
+    // 未被分享的用户无法查看评论
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(format!("/posts/{}/comments", draft.id))
+        .header("Authorization", format!("Bearer {}", outsider_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 每次评论访问都记录在草稿访问日志中
+    let logged = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM draft_access_logs WHERE post_id = $1 AND access_type LIKE 'comment_%'",
+        draft.id
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(logged, Some(3));
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_draft_share_roles(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    let (editor_token, editor_id) = register_and_login_new_user(&app).await?;
+    let (viewer_token, viewer_id) = register_and_login_new_user(&app).await?;
+    let (expired_token, expired_id) = register_and_login_new_user(&app).await?;
+    let draft = seed_one_post(&pool, author_id, "多人协作草稿", "初稿内容", false).await?;
+
+    // 不能把过期时间设置在过去
+    let share_payload = serde_json::json!({
+        "shares": [{ "user_id": viewer_id, "role": "viewer", "expires_at": "2000-01-01T00:00:00Z" }],
+        "is_public": false
+    });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/share", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&share_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let share_payload = serde_json::json!({
+        "shares": [
+            { "user_id": editor_id, "role": "editor" },
+            { "user_id": viewer_id, "role": "viewer" },
+            { "user_id": expired_id, "role": "editor" }
+        ],
+        "is_public": false
+    });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/share", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&share_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 模拟分享到期
+    sqlx::query!(
+        "UPDATE draft_shares SET expires_at = NOW() - INTERVAL '1 minute' WHERE post_id = $1 AND user_id = $2",
+        draft.id,
+        expired_id
+    )
+    .execute(&pool)
+    .await?;
+
+    // 作者查看草稿时只列出仍然有效的分享接收人
+    let request = Request::builder()
+        .uri(format!("/posts/{}", draft.id))
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let detail: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(
+        detail["draft_shared_with"],
+        serde_json::json!([editor_id, viewer_id])
+    );
+
+    // 编辑者可以查看并修改草稿内容
+    let request = Request::builder()
+        .uri(format!("/posts/{}", draft.id))
+        .header("Authorization", format!("Bearer {}", editor_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let detail: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(detail["share_role"], "editor");
+    assert_eq!(detail["can_edit"], true);
+
+    let payload = serde_json::json!({ "content": "编辑者修改后的内容" });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}", draft.id))
+        .header("Content-Type", "application/json")
+        .header("If-Match", "*")
+        .header("Authorization", format!("Bearer {}", editor_token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 编辑者不能修改发布状态和分享设置
+    let payload = serde_json::json!({ "is_draft_public": true });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}", draft.id))
+        .header("Content-Type", "application/json")
+        .header("If-Match", "*")
+        .header("Authorization", format!("Bearer {}", editor_token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 查看者只能阅读，既不能修改也不能添加评审意见
+    let request = Request::builder()
+        .uri(format!("/posts/{}", draft.id))
+        .header("Authorization", format!("Bearer {}", viewer_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let payload = serde_json::json!({ "content": "查看者的修改" });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}", draft.id))
+        .header("Content-Type", "application/json")
+        .header("If-Match", "*")
+        .header("Authorization", format!("Bearer {}", viewer_token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let comment_payload = serde_json::json!({ "body": "查看者的意见" });
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/posts/{}/comments", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", viewer_token))
+        .body(Body::from(serde_json::to_vec(&comment_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 过期的分享不再授予访问权限
+    let request = Request::builder()
+        .uri(format!("/posts/{}", draft.id))
+        .header("Authorization", format!("Bearer {}", expired_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // “与我共享”列表展示草稿及角色
+    let request = Request::builder()
+        .uri("/me/shared-drafts")
+        .header("Authorization", format!("Bearer {}", editor_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let shared: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(shared["total_items"], 1);
+    assert_eq!(shared["items"][0]["post_id"], draft.id.to_string());
+    assert_eq!(shared["items"][0]["role"], "editor");
+
+    let request = Request::builder()
+        .uri("/me/shared-drafts")
+        .header("Authorization", format!("Bearer {}", expired_token))
+        .body(Body::empty())?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let shared: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(shared["total_items"], 0);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_share_notification_preferences(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    let (reviewer_token, reviewer_id) = register_and_login_new_user(&app).await?;
+    let draft = seed_one_post(&pool, author_id, "请帮忙看看", "草稿内容", false).await?;
+
+    // 默认接收草稿分享通知
+    let request = Request::builder()
+        .uri("/me/notification-preferences")
+        .header("Authorization", format!("Bearer {}", reviewer_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let preferences: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(preferences["draft_shared"], true);
+
+    // 关闭后保持关闭
+    let payload = serde_json::json!({ "draft_shared": false });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri("/me/notification-preferences")
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", reviewer_token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri("/me/notification-preferences")
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", reviewer_token))
+        .body(Body::from("{}"))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let preferences: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(preferences["draft_shared"], false);
+
+    // 邮件在后台发送，即使邮件服务不可用，分享请求也照常成功
+    let share_payload = serde_json::json!({
+        "shares": [{ "user_id": reviewer_id, "role": "commenter" }],
+        "is_public": false,
+        "message": "<b>周五前</b>帮我看一下"
+    });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/share", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&share_payload)?))?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+// == 文章封禁与申诉 (/posts/{id}/ban, /ban-appeals)
+
+#[sqlx::test]
+async fn test_ban_records_and_appeal_flow(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    let moderator = seed_user_with_role(&pool, "ban_moderator", "admin").await?;
+    let moderator_token =
+        get_token_for_user(&app, &moderator.username, "StrongPassword123!").await?;
+    let post = seed_one_post(&pool, author_id, "有争议的文章", "内容", true).await?;
+
+    // 版主封禁文章并填写原因
+    let ban_payload = serde_json::json!({ "reason": "包含未经授权的转载内容" });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/ban", post.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", moderator_token))
+        .body(Body::from(serde_json::to_vec(&ban_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 作者在自己的文章列表中能看到封禁原因
+    let request = Request::builder()
+        .uri("/posts")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let list: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let ban = &list["items"][0]["ban"];
+    assert_eq!(ban["reason"], "包含未经授权的转载内容");
+    assert_eq!(ban["banned_by_username"], "ban_moderator");
+
+    // 只有作者本人可以申诉，且每次封禁只能申诉一次
+    let appeal_payload = serde_json::json!({ "message": "转载已获得原作者授权" });
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/posts/{}/ban/appeal", post.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", moderator_token))
+        .body(Body::from(serde_json::to_vec(&appeal_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/posts/{}/ban/appeal", post.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&appeal_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let appeal: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let appeal_id = appeal["id"].as_str().context("响应中缺少 id")?.to_string();
+    assert_eq!(appeal["status"], "pending");
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/posts/{}/ban/appeal", post.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&appeal_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // 普通用户看不到申诉队列
+    let request = Request::builder()
+        .uri("/ban-appeals")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let request = Request::builder()
+        .uri("/ban-appeals")
+        .header("Authorization", format!("Bearer {}", moderator_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let queue: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(queue["total_items"], 1);
+    assert_eq!(queue["items"][0]["ban_reason"], "包含未经授权的转载内容");
+
+    // 版主同意申诉后文章解封，申诉不能再次处理
+    let decision = serde_json::json!({ "decision": "unban", "note": "已核实授权" });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/ban-appeals/{}/decision", appeal_id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", moderator_token))
+        .body(Body::from(serde_json::to_vec(&decision)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let resolved: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(resolved["status"], "unbanned");
+
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/ban-appeals/{}/decision", appeal_id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", moderator_token))
+        .body(Body::from(serde_json::to_vec(&decision)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let is_banned = sqlx::query_scalar!("SELECT is_banned FROM posts WHERE id = $1", post.id)
+        .fetch_one(&pool)
+        .await?;
+    assert!(!is_banned);
+    let lifted = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM post_bans WHERE post_id = $1 AND lifted_by = $2",
+        post.id,
+        moderator.id
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(lifted, Some(1));
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_reader_reports_queue(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (reader_token, author_id) = register_and_login_new_user(&app).await?;
+    let moderator = seed_user_with_role(&pool, "report_moderator", "admin").await?;
+    let moderator_token =
+        get_token_for_user(&app, &moderator.username, "StrongPassword123!").await?;
+    let post = seed_one_post(&pool, author_id, "被举报的文章", "内容", true).await?;
+    let other_post = seed_one_post(&pool, author_id, "另一篇文章", "内容", true).await?;
+    let third_post = seed_one_post(&pool, author_id, "第三篇文章", "内容", true).await?;
+
+    let report = |post_id: uuid::Uuid, ip: &str, payload: serde_json::Value| {
+        Request::builder()
+            .method(Method::POST)
+            .uri(format!("/blog/posts/{}/report", post_id))
+            .header("Content-Type", "application/json")
+            .header("X-Forwarded-For", ip)
+            .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+            .unwrap()
+    };
+
+    // 游客可以举报，同一来源对同一篇文章重复举报返回 409
+    let payload = serde_json::json!({ "category": "spam", "details": "广告链接" });
+    let response = app
+        .clone()
+        .oneshot(report(post.id, "203.0.113.1", payload.clone()))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let response = app
+        .clone()
+        .oneshot(report(post.id, "203.0.113.1", payload.clone()))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    // 同一来源一小时内最多举报 3 次，超过后返回 429
+    for (post_id, category) in [
+        (post.id, "abuse"),
+        (other_post.id, "other"),
+        (third_post.id, "copyright"),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(report(
+                post_id,
+                "203.0.113.2",
+                serde_json::json!({ "category": category }),
+            ))
+            .await?;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+    let response = app
+        .clone()
+        .oneshot(report(post.id, "203.0.113.2", serde_json::json!({})))
+        .await?;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // 普通用户不能查看举报队列
+    let request = Request::builder()
+        .uri("/reports")
+        .header("Authorization", format!("Bearer {}", reader_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 版主查看按文章聚合的举报队列
+    let request = Request::builder()
+        .uri("/reports")
+        .header("Authorization", format!("Bearer {}", moderator_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let queue: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(queue["total_items"], 3);
+    let first = &queue["items"][0];
+    assert_eq!(first["post_id"], post.id.to_string());
+    assert_eq!(first["report_count"], 2);
+    let categories = first["categories"].as_array().context("缺少 categories")?;
+    assert!(
+        categories
+            .iter()
+            .any(|c| c["category"] == "spam" && c["count"] == 1)
+    );
+
+    // 驳回另一篇文章的举报后，再驳回一次返回 404
+    let dismiss = |post_id: uuid::Uuid| {
+        Request::builder()
+            .method(Method::PUT)
+            .uri(format!("/reports/posts/{}/dismiss", post_id))
+            .header("Authorization", format!("Bearer {}", moderator_token))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let response = app.clone().oneshot(dismiss(other_post.id)).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app.clone().oneshot(dismiss(other_post.id)).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // 从举报队列直接封禁文章，所有举报随之关闭
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/reports/posts/{}/ban", post.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", moderator_token))
+        .body(Body::from(serde_json::to_vec(
+            &serde_json::json!({ "reason": "垃圾广告" }),
+        )?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let resolution: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(resolution["resolved_reports"], 2);
+    assert_eq!(resolution["ban"]["reason"], "垃圾广告");
+
+    // 被封禁的文章不能再被举报
+    let response = app
+        .clone()
+        .oneshot(report(post.id, "198.51.100.7", serde_json::json!({})))
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_report_rate_limit_under_concurrency(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (_, author_id) = register_and_login_new_user(&app).await?;
+
+    let mut posts = Vec::new();
+    for i in 0..6 {
+        posts
+            .push(seed_one_post(&pool, author_id, &format!("并发举报 {}", i), "内容", true).await?);
+    }
+
+    // 同一来源并发举报 6 篇不同的文章，频率限制仍然只放行 3 次
+    let mut handles = Vec::new();
+    for post in posts {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/blog/posts/{}/report", post.id))
+            .header("Content-Type", "application/json")
+            .header("X-Forwarded-For", "203.0.113.9")
+            .body(Body::from(serde_json::to_vec(
+                &serde_json::json!({ "category": "spam" }),
+            )?))?;
+        let app = app.clone();
+        handles.push(tokio::spawn(async move { app.oneshot(request).await }));
+    }
+
+    let mut created = 0;
+    let mut limited = 0;
+    for handle in handles {
+        match handle.await??.status() {
+            StatusCode::CREATED => created += 1,
+            StatusCode::TOO_MANY_REQUESTS => limited += 1,
+            status => panic!("意外的状态码: {}", status),
+        }
+    }
+    assert_eq!(created, 3);
+    assert_eq!(limited, 3);
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_wiki_links_and_backlinks(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    seed_one_post(&pool, author_id, "Wiki Target", "目标文章", true).await?;
+    seed_one_post(&pool, author_id, "Wiki Draft", "草稿", false).await?;
+
+    // 作者创建引用其他文章的文章，无法解析的链接会提示给作者
+    let payload = serde_json::json!({
+        "title": "Wiki Source",
+        "content": "参见 [[wiki-target]]、[[wiki-draft|这篇草稿]] 和 [[missing-post]]。\n\n`[[not-a-link]]`",
+        "category_ids": null,
+        "tag_ids": null
+    });
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/posts")
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let source: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let html = source["content_html"]
+        .as_str()
+        .context("缺少 content_html")?;
+    assert!(html.contains("<a href=\"/posts/wiki-target\" data-wikilink=\"true\">wiki-target</a>"));
+    assert!(html.contains("这篇草稿"));
+    assert!(!html.contains("/posts/wiki-draft"));
+    assert!(!html.contains("/posts/missing-post"));
+    assert!(html.contains("<code>[[not-a-link]]</code>"));
+    assert_eq!(
+        source["broken_wiki_links"],
+        serde_json::json!([
+            { "slug": "wiki-draft", "issue": "unpublished" },
+            { "slug": "missing-post", "issue": "missing" }
+        ])
+    );
+
+    // 来源文章未发布时不出现在反向链接中
+    let get_target = || {
+        Request::builder()
+            .uri("/blog/posts/wiki-target")
+            .body(Body::empty())
+            .unwrap()
+    };
+    let response = app.clone().oneshot(get_target()).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let target: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(target["backlinks"], serde_json::json!([]));
+
+    sqlx::query!("UPDATE posts SET published_at = NOW() WHERE slug = 'wiki-source'")
+        .execute(&pool)
+        .await?;
+
+    let response = app.clone().oneshot(get_target()).await?;
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let target: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let backlinks = target["backlinks"].as_array().context("缺少 backlinks")?;
+    assert_eq!(backlinks.len(), 1);
+    assert_eq!(backlinks[0]["slug"], "wiki-source");
+    assert_eq!(backlinks[0]["title"], "Wiki Source");
+
+    // 读者看不到无法解析的链接提示，链接按普通文本显示
+    let request = Request::builder()
+        .uri("/blog/posts/wiki-source")
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let source: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert!(source.get("broken_wiki_links").is_none());
+    assert!(
+        source["content_html"]
+            .as_str()
+            .is_some_and(|html| html.contains("和 missing-post。"))
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_render_profiles(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (user_token, user_id) = register_and_login_new_user(&app).await?;
+    seed_user_with_role(&pool, "profile_editor", "editor").await?;
+    let editor_token = get_token_for_user(&app, "profile_editor", "StrongPassword123!").await?;
+
+    let create = |token: &str, profile: &str| {
+        let payload = serde_json::json!({
+            "title": format!("Profile {}", profile),
+            "content": "第一行\n第二行 <strong onclick=\"alert(1)\">加粗</strong>",
+            "render_profile": profile
+        });
+        Request::builder()
+            .method(Method::POST)
+            .uri("/posts")
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+            .unwrap()
+    };
+
+    // 未定义的方案无效，受限方案只开放给指定角色
+    let response = app.clone().oneshot(create(&user_token, "removed")).await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = app.clone().oneshot(create(&user_token, "trusted")).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app.clone().oneshot(create(&user_token, "casual")).await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let casual: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(casual["render_profile"], "casual");
+    let html = casual["content_html"]
+        .as_str()
+        .context("缺少 content_html")?;
+    assert!(html.contains("第一行<br />"));
+    assert!(!html.contains("<strong"));
+
+    // 编辑可以使用允许 HTML 的方案，HTML 经过白名单清理
+    let response = app
+        .clone()
+        .oneshot(create(&editor_token, "trusted"))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let trusted: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let html = trusted["content_html"]
+        .as_str()
+        .context("缺少 content_html")?;
+    assert!(html.contains("<strong>加粗</strong>"));
+    assert!(!html.contains("onclick"));
+
+    // 文章使用受限方案后，普通作者不能再修改正文
+    let post = seed_one_post(&pool, user_id, "Profile Locked", "正文", false).await?;
+    sqlx::query!(
+        "UPDATE posts SET render_profile = 'trusted' WHERE id = $1",
+        post.id
+    )
+    .execute(&pool)
+    .await?;
+    let payload = UpdatePostPayload {
+        content: Some("<b>新正文</b>".to_string()),
+        ..Default::default()
+    };
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}", post.id))
+        .header("Content-Type", "application/json")
+        .header(
+            "If-Match",
+            format!("\"{}\"", post.updated_at.timestamp_micros()),
+        )
+        .header("Authorization", format!("Bearer {}", user_token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_clone_post_and_templates(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (user_token, _) = register_and_login_new_user(&app).await?;
+    let (other_token, other_id) = register_and_login_new_user(&app).await?;
+    seed_user_with_role(&pool, "template_editor", "editor").await?;
+    seed_user_with_role(&pool, "clone_admin", "admin").await?;
+    let admin_token = get_token_for_user(&app, "clone_admin", "StrongPassword123!").await?;
+    let editor_token = get_token_for_user(&app, "template_editor", "StrongPassword123!").await?;
+    let category = seed_one_category(&pool, "Template Category").await?;
+    let tag = seed_one_tag(&pool, "Template Tag").await?;
+
+    let json_request = |method: Method, uri: &str, token: &str, payload: serde_json::Value| {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+            .unwrap()
+    };
+
+    // 1. 复制自己的文章：生成草稿副本，保留分类和标签
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            "/posts",
+            &user_token,
+            serde_json::json!({
+                "title": "Clone Source",
+                "content": "原文",
+                "category_ids": [category.id],
+                "tag_ids": [tag.id]
+            }),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let source: PostDetailDto = serde_json::from_slice(&body_bytes)?;
+
+    let clone_uri = format!("/posts/{}/clone", source.id);
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            &clone_uri,
+            &user_token,
+            serde_json::json!({}),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let copy: PostDetailDto = serde_json::from_slice(&body_bytes)?;
+    assert_ne!(copy.id, source.id);
+    assert_eq!(copy.title, "Clone Source (副本)");
+    assert_eq!(copy.slug, format!("{}-copy", source.slug));
+    assert!(copy.published_at.is_none());
+    assert_eq!(copy.categories.context("缺少分类")?[0].id, category.id);
+    assert_eq!(copy.tags.context("缺少标签")?[0].id, tag.id);
+
+    // 再次复制时 slug 自动追加序号
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            &clone_uri,
+            &user_token,
+            serde_json::json!({}),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let second: PostDetailDto = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(second.slug, format!("{}-copy-2", source.slug));
+
+    // 不能复制他人的草稿
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            &clone_uri,
+            &other_token,
+            serde_json::json!({}),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 管理员也不能复制他人的私有草稿（与查看详情的规则一致）
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            &clone_uri,
+            &admin_token,
+            serde_json::json!({}),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 草稿分享给其他用户后，对方可以复制
+    sqlx::query!(
+        "INSERT INTO draft_shares (post_id, user_id, role) VALUES ($1, $2, 'viewer')",
+        source.id,
+        other_id
+    )
+    .execute(&pool)
+    .await?;
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            &clone_uri,
+            &other_token,
+            serde_json::json!({}),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // 2. 个人模板：创建后用 template_id 新建文章
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            "/post-templates",
+            &user_token,
+            serde_json::json!({
+                "name": "周报",
+                "title_pattern": "周报 {year}-W{week}",
+                "content": "## 本周进展\n",
+                "category_ids": [category.id],
+                "tag_ids": [tag.id]
+            }),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let template: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(template["is_global"], false);
+    let template_id = template["id"].as_str().context("缺少模板 ID")?;
+
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            "/posts",
+            &user_token,
+            serde_json::json!({ "template_id": template_id }),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let from_template: PostDetailDto = serde_json::from_slice(&body_bytes)?;
+    assert!(from_template.title.starts_with("周报 20"));
+    assert!(!from_template.title.contains('{'));
+    assert_eq!(from_template.content_markdown, "## 本周进展\n");
+    assert_eq!(
+        from_template.categories.context("缺少分类")?[0].id,
+        category.id
+    );
+
+    // 他人的个人模板不可见
+    let template_uri = format!("/post-templates/{}", template_id);
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(&template_uri)
+        .header("Authorization", format!("Bearer {}", other_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // 3. 全局模板需要额外权限
+    let global_payload =
+        serde_json::json!({ "name": "公告", "content": "公告正文", "global": true });
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            "/post-templates",
+            &user_token,
+            global_payload.clone(),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = app
+        .clone()
+        .oneshot(json_request(
+            Method::POST,
+            "/post-templates",
+            &editor_token,
+            global_payload,
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // 全局模板对所有作者可见
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri("/post-templates")
+        .header("Authorization", format!("Bearer {}", other_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let templates: Vec<serde_json::Value> = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0]["is_global"], true);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_bulk_post_operations(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let author = seed_user_with_role(&pool, "bulk_author", "author").await?;
+    let admin = seed_user_with_role(&pool, "bulk_admin", "admin").await?;
+    let author_token = get_token_for_user(&app, "bulk_author", "StrongPassword123!").await?;
+    let admin_token = get_token_for_user(&app, "bulk_admin", "StrongPassword123!").await?;
+    let first = seed_one_post(&pool, author.id, "Bulk First", "正文", false).await?;
+    let second = seed_one_post(&pool, author.id, "Bulk Second", "正文", false).await?;
+    let other = seed_one_post(&pool, admin.id, "Bulk Other", "正文", false).await?;
+    let tag = seed_one_tag(&pool, "Bulk Tag").await?;
+    let category = seed_one_category(&pool, "Bulk Category").await?;
+
+    let bulk = |token: &str, payload: serde_json::Value| {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/posts/bulk")
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+            .unwrap()
+    };
+    let read_results = |body: &[u8]| -> Result<Vec<(Uuid, bool)>> {
+        let response: serde_json::Value = serde_json::from_slice(body)?;
+        response["results"]
+            .as_array()
+            .context("缺少 results")?
+            .iter()
+            .map(|item| {
+                let id = item["post_id"].as_str().context("缺少 post_id")?.parse()?;
+                Ok((id, item["success"].as_bool().unwrap_or(false)))
+            })
+            .collect()
+    };
+
+    // 1. 作者只能给自己的文章加标签，重复的 ID 只处理一次
+    let response = app
+        .clone()
+        .oneshot(bulk(
+            &author_token,
+            serde_json::json!({
+                "post_ids": [first.id, second.id, other.id, first.id],
+                "action": { "type": "add_tags", "tag_ids": [tag.id] }
+            }),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    assert_eq!(
+        read_results(&body_bytes)?,
+        vec![(first.id, true), (second.id, true), (other.id, false)]
+    );
+    let tagged = sqlx::query_scalar!("SELECT COUNT(*) FROM post_tags WHERE tag_id = $1", tag.id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(tagged, Some(2));
+
+    // 2. 需要审核的角色不能直接发布未通过审核的文章
+    let response = app
+        .clone()
+        .oneshot(bulk(
+            &author_token,
+            serde_json::json!({ "post_ids": [first.id], "action": { "type": "publish" } }),
+        ))
+        .await?;
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    assert_eq!(read_results(&body_bytes)?, vec![(first.id, false)]);
+
+    // 3. 管理员批量设置分类、发布，不存在的文章单独失败
+    let response = app
+        .clone()
+        .oneshot(bulk(
+            &admin_token,
+            serde_json::json!({
+                "post_ids": [first.id, other.id],
+                "action": { "type": "set_categories", "category_ids": [category.id] }
+            }),
+        ))
+        .await?;
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let response: BulkPostResponse = serde_json::from_slice(&body_bytes)?;
+    assert_eq!((response.succeeded, response.failed), (2, 0));
+
+    let missing = Uuid::new_v4();
+    let response = app
+        .clone()
+        .oneshot(bulk(
+            &admin_token,
+            serde_json::json!({
+                "post_ids": [first.id, missing, second.id],
+                "action": { "type": "publish" }
+            }),
+        ))
+        .await?;
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    assert_eq!(
+        read_results(&body_bytes)?,
+        vec![(first.id, true), (missing, false), (second.id, true)]
+    );
+    let review_status = sqlx::query_scalar!(
+        "SELECT status FROM post_reviews WHERE post_id = $1",
+        first.id
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(review_status, "published");
+
+    // 4. 只能封禁已发布的文章
+    let response = app
+        .clone()
+        .oneshot(bulk(
+            &admin_token,
+            serde_json::json!({
+                "post_ids": [first.id, other.id],
+                "action": { "type": "ban", "reason": "批量封禁" }
+            }),
+        ))
+        .await?;
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    assert_eq!(
+        read_results(&body_bytes)?,
+        vec![(first.id, true), (other.id, false)]
+    );
+    let banned = sqlx::query_scalar!("SELECT is_banned FROM posts WHERE id = $1", first.id)
+        .fetch_one(&pool)
+        .await?;
+    assert!(banned);
+
+    // 5. 作者删除自己的文章；空列表无效
+    let response = app
+        .clone()
+        .oneshot(bulk(
+            &author_token,
+            serde_json::json!({ "post_ids": [second.id], "action": { "type": "delete" } }),
+        ))
+        .await?;
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    assert_eq!(read_results(&body_bytes)?, vec![(second.id, true)]);
+    let response = app
+        .clone()
+        .oneshot(bulk(
+            &author_token,
+            serde_json::json!({ "post_ids": [], "action": { "type": "delete" } }),
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_post_co_authors(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let primary = seed_user_with_role(&pool, "primary_author", "author").await?;
+    let co_author = seed_user_with_role(&pool, "co_author", "author").await?;
+    let primary_token = get_token_for_user(&app, "primary_author", "StrongPassword123!").await?;
+    let co_token = get_token_for_user(&app, "co_author", "StrongPassword123!").await?;
+    let post = seed_one_post(&pool, primary.id, "Co-written", "正文", false).await?;
+
+    let get_post = |token: &str| {
+        Request::builder()
+            .method(Method::GET)
+            .uri(format!("/posts/{}", post.id))
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let set_authors = |token: &str, author_ids: Vec<Uuid>| {
+        Request::builder()
+            .method(Method::PUT)
+            .uri(format!("/posts/{}/authors", post.id))
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(
+                serde_json::to_vec(&serde_json::json!({ "author_ids": author_ids })).unwrap(),
+            ))
+            .unwrap()
+    };
+
+    // 1. 加入作者列表之前不能查看他人的草稿
+    let response = app.clone().oneshot(get_post(&co_token)).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 2. 主作者添加合著者；合著者不能管理作者，主作者不能自行更换主作者
+    let response = app
+        .clone()
+        .oneshot(set_authors(&primary_token, vec![primary.id, co_author.id]))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let authors: Vec<serde_json::Value> = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(authors.len(), 2);
+    assert_eq!(authors[1]["username"], "co_author");
+
+    let response = app
+        .clone()
+        .oneshot(set_authors(&co_token, vec![primary.id]))
+        .await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = app
+        .clone()
+        .oneshot(set_authors(&primary_token, vec![co_author.id, primary.id]))
+        .await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = app
+        .clone()
+        .oneshot(set_authors(&primary_token, vec![primary.id, primary.id]))
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 3. 合著者可以查看和编辑，但不能删除或发布
+    let response = app.clone().oneshot(get_post(&co_token)).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let detail: PostDetailDto = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(detail.author.context("缺少主作者")?.id, primary.id);
+    let author_ids: Vec<Uuid> = detail.authors.iter().map(|a| a.id).collect();
+    assert_eq!(author_ids, vec![primary.id, co_author.id]);
+    assert!(detail.can_edit);
+    assert!(!detail.can_delete);
+
+    let payload = UpdatePostPayload {
+        content: Some("合著者修改的正文".to_string()),
+        ..Default::default()
+    };
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}", post.id))
+        .header("Content-Type", "application/json")
+        .header(
+            "If-Match",
+            format!("\"{}\"", detail.updated_at.timestamp_micros()),
+        )
+        .header("Authorization", format!("Bearer {}", co_token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/publish", post.id))
+        .header("Authorization", format!("Bearer {}", co_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 批量修改标签与单篇编辑一致，合著者可以操作；批量删除同样只有主作者可以
+    let tag = seed_one_tag(&pool, "合写").await?;
+    let bulk = |action: serde_json::Value| {
+        let payload = serde_json::json!({ "post_ids": [post.id], "action": action });
+        Request::builder()
+            .method(Method::POST)
+            .uri("/posts/bulk")
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", co_token))
+            .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+            .unwrap()
+    };
+    for (action, succeeded) in [
+        (
+            serde_json::json!({ "type": "add_tags", "tag_ids": [tag.id] }),
+            1,
+        ),
+        (serde_json::json!({ "type": "delete" }), 0),
+    ] {
+        let response = app.clone().oneshot(bulk(action)).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body_bytes = response.into_body().collect().await?.to_bytes();
+        let result: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+        assert_eq!(result["succeeded"], succeeded);
+    }
+
+    // 合著者不能审核自己参与写作的文章
+    let co_editor = seed_user_with_role(&pool, "co_editor", "editor").await?;
+    let co_editor_token = get_token_for_user(&app, "co_editor", "StrongPassword123!").await?;
+    let response = app
+        .clone()
+        .oneshot(set_authors(
+            &primary_token,
+            vec![primary.id, co_author.id, co_editor.id],
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let submit_review = |token: &str, reviewer_id: Option<Uuid>| {
+        let payload = serde_json::json!({ "reviewer_id": reviewer_id });
+        Request::builder()
+            .method(Method::PUT)
+            .uri(format!("/posts/{}/review/submit", post.id))
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+            .unwrap()
+    };
+    let response = app
+        .clone()
+        .oneshot(submit_review(&primary_token, Some(co_editor.id)))
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    // 合著者可以提交审核，也可以查看审核记录
+    let response = app.clone().oneshot(submit_review(&co_token, None)).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(format!("/posts/{}/review", post.id))
+        .header("Authorization", format!("Bearer {}", co_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let review: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(review["status"], "in_review");
+
+    // 合写的文章不出现在合著者自己的待审核列表中
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri("/reviews/pending")
+        .header("Authorization", format!("Bearer {}", co_editor_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let pending: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(pending["total_items"], 0);
+    assert_eq!(pending["items"].as_array().map(Vec::len), Some(0));
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/review/decision", post.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", co_editor_token))
+        .body(Body::from(serde_json::to_vec(
+            &serde_json::json!({ "decision": "approve" }),
+        )?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 4. /me/stats 统计合写的文章
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri("/me/stats")
+        .header("Authorization", format!("Bearer {}", co_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let stats: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(stats["total_posts"], 1);
+    assert_eq!(stats["draft_posts"], 1);
+    assert_eq!(stats["co_authored_posts"], 1);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_public_author_pages(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let author = seed_user_with_role(&pool, "public_author", "author").await?;
+    let partner = seed_user_with_role(&pool, "public_partner", "author").await?;
+    seed_user_with_role(&pool, "silent_author", "author").await?;
+    let own = seed_one_post(&pool, author.id, "Author Own", "正文", true).await?;
+    seed_one_post(&pool, author.id, "Author Draft", "正文", false).await?;
+    let shared = seed_one_post(&pool, partner.id, "Author Shared", "正文", true).await?;
+    sqlx::query!(
+        "INSERT INTO post_authors (post_id, user_id, position) VALUES ($1, $2, 1)",
+        shared.id,
+        author.id
+    )
+    .execute(&pool)
+    .await?;
+
+    let get = |uri: String| {
+        Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    // 1. 公开资料只统计已发布的文章（包括合写的文章），不返回邮箱
+    let response = app
+        .clone()
+        .oneshot(get("/blog/authors/public_author".to_string()))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let profile: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(profile["username"], "public_author");
+    assert_eq!(profile["published_post_count"], 2);
+    assert!(profile.get("email").is_none());
+
+    // 2. 文章列表分页，作者信息不包含邮箱
+    let response = app
+        .clone()
+        .oneshot(get(
+            "/blog/authors/public_author/posts?page=1&page_size=1".to_string()
+        ))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let page: PaginatedResponse<PostDetailDto> = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(page.total_items, 2);
+    assert_eq!(page.items.len(), 1);
+    assert!(!String::from_utf8_lossy(&body_bytes).contains(&author.email));
+
+    // 已发布文章详情同样不包含作者邮箱
+    let response = app
+        .clone()
+        .oneshot(get(format!("/blog/posts/{}", own.id)))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let detail: PostDetailDto = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(detail.author.context("缺少作者")?.username, "public_author");
+    assert!(!String::from_utf8_lossy(&body_bytes).contains(&author.email));
+
+    // 3. 没有已发布文章的用户和不存在的用户都返回 404
+    for uri in [
+        "/blog/authors/silent_author",
+        "/blog/authors/silent_author/posts",
+        "/blog/authors/nobody",
+    ] {
+        let response = app.clone().oneshot(get(uri.to_string())).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", uri);
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_user_profile_fields(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let author = seed_user_with_role(&pool, "profile_author", "author").await?;
+    let token = get_token_for_user(&app, &author.username, "StrongPassword123!").await?;
+    let post = seed_one_post(&pool, author.id, "Profile Post", "正文", true).await?;
+
+    let put_profile = |payload: serde_json::Value| {
+        Request::builder()
+            .method(Method::PUT)
+            .uri("/me")
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+            .unwrap()
+    };
+
+    // 1. 更新资料，简介按 Markdown 渲染，社交平台名称统一为小写
+    let response = app
+        .clone()
+        .oneshot(put_profile(serde_json::json!({
+            "display_name": "  Profile Author  ",
+            "bio": "写 **Rust** 的作者 <script>alert(1)</script>",
+            "website": "https://example.com/blog",
+            "avatar_url": "https://cdn.example.com/a.png",
+            "social_links": [{ "platform": "GitHub", "url": "https://github.com/profile" }]
+        })))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let me: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(me["username"], "profile_author");
+    assert_eq!(me["display_name"], "Profile Author");
+    let bio_html = me["bio_html"].as_str().context("缺少 bio_html")?;
+    assert!(bio_html.contains("<strong>Rust</strong>"));
+    assert!(!bio_html.contains("<script>"));
+    assert_eq!(me["social_links"][0]["platform"], "github");
+
+    // 2. 无效的地址、平台和过长的显示名称都返回 400，资料保持不变
+    for payload in [
+        serde_json::json!({ "website": "javascript:alert(1)" }),
+        serde_json::json!({ "avatar_url": "ftp://example.com/a.png" }),
+        serde_json::json!({ "social_links": [{ "platform": "myspace", "url": "https://myspace.com/a" }] }),
+        serde_json::json!({ "social_links": [
+            { "platform": "github", "url": "https://github.com/a" },
+            { "platform": "github", "url": "https://github.com/b" }
+        ] }),
+        serde_json::json!({ "display_name": "名".repeat(51) }),
+    ] {
+        let response = app.clone().oneshot(put_profile(payload.clone())).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", payload);
+    }
+
+    // 3. 文章的作者信息带有署名资料，公开接口同样不返回邮箱
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri(format!("/blog/posts/{}", post.id))
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let detail: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(detail["author"]["display_name"], "Profile Author");
+    assert_eq!(detail["authors"][0]["website"], "https://example.com/blog");
+    assert!(detail["author"].get("email").is_none());
+
+    // 4. 上传头像：按文件内容识别格式，资料中的头像地址指向头像接口
+    let upload = |data: &[u8]| {
+        let boundary = "zincbloom-avatar-boundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"avatar\"; filename=\"a.png\"\r\nContent-Type: image/png\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(data);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        Request::builder()
+            .method(Method::PUT)
+            .uri("/me/avatar")
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(body))
+            .unwrap()
+    };
+    let response = app.clone().oneshot(upload(b"not an image")).await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let png = b"\x89PNG\r\n\x1a\nfake-png-body";
+    let response = app.clone().oneshot(upload(png)).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let me: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let avatar_url = me["avatar_url"].as_str().context("缺少头像地址")?;
+    assert!(avatar_url.starts_with(&format!("/users/{}/avatar", author.id)));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri(avatar_url)
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    assert_eq!(&body_bytes[..], &png[..]);
+
+    // 5. 清除头像地址时删除已上传的头像
+    let response = app
+        .clone()
+        .oneshot(put_profile(serde_json::json!({ "avatar_url": "" })))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let me: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert!(me["avatar_url"].is_null());
+    assert_eq!(me["display_name"], "Profile Author");
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri(format!("/users/{}/avatar", author.id))
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+// == 标签和分类推荐 (/posts/{id}/suggest-taxonomy)
+
+#[sqlx::test]
+async fn test_suggest_taxonomy_for_post(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    let (other_token, _) = register_and_login_new_user(&app).await?;
+
+    let rust = seed_one_tag(&pool, "Rust").await?;
+    let cooking = seed_one_tag(&pool, "美食").await?;
+    let python = seed_one_tag(&pool, "Python").await?;
+    let programming = seed_one_category(&pool, "编程").await?;
+
+    // 已有文章：两篇 Rust 文章，一篇美食文章
+    let tagged = [
+        ("所有权入门", "borrow checker lifetimes ownership", rust.id),
+        ("异步运行时", "tokio futures borrow checker", rust.id),
+        ("红烧肉做法", "火候 调味 食谱", cooking.id),
+    ];
+    for (title, content, tag_id) in tagged {
+        let post = seed_one_post(&pool, author_id, title, content, true).await?;
+        sqlx::query!(
+            "INSERT INTO post_tags (post_id, tag_id) VALUES ($1, $2)",
+            post.id,
+            tag_id
+        )
+        .execute(&pool)
+        .await?;
+        if tag_id == rust.id {
+            sqlx::query!(
+                "INSERT INTO post_categories (post_id, category_id) VALUES ($1, $2)",
+                post.id,
+                programming.id
+            )
+            .execute(&pool)
+            .await?;
+        }
+    }
+
+    let draft = seed_one_post(&pool, author_id, "草稿", "已保存的内容", false).await?;
+    let suggest = |token: &str, payload: serde_json::Value| {
+        Request::builder()
+            .method(Method::POST)
+            .uri(format!("/posts/{}/suggest-taxonomy", draft.id))
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+            .unwrap()
+    };
+
+    // 1. 编辑器提交的内容与 Rust 文章相似，标题中提到了 Python
+    let payload = serde_json::json!({
+        "title": "Python 与所有权",
+        "content": "the borrow checker enforces lifetimes"
+    });
+    let response = app.clone().oneshot(suggest(&author_token, payload)).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let suggestions: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+
+    let tags = suggestions["tags"].as_array().context("缺少标签推荐")?;
+    let tag_names: Vec<&str> = tags
+        .iter()
+        .filter_map(|s| s["tag"]["name"].as_str())
+        .collect();
+    assert!(tag_names.contains(&"Rust"));
+    assert!(tag_names.contains(&"Python"));
+    assert!(!tag_names.contains(&"美食"));
+    let python_suggestion = tags
+        .iter()
+        .find(|s| s["tag"]["id"] == python.id.to_string())
+        .context("缺少 Python 推荐")?;
+    assert_eq!(python_suggestion["reasons"][0], "标题中提到了 'Python'");
+
+    let categories = suggestions["categories"]
+        .as_array()
+        .context("缺少分类推荐")?;
+    assert_eq!(categories.len(), 1);
+    assert_eq!(categories[0]["category"]["name"], "编程");
+
+    // 2. 已经打上的标签不再推荐
+    sqlx::query!(
+        "INSERT INTO post_tags (post_id, tag_id) VALUES ($1, $2)",
+        draft.id,
+        rust.id
+    )
+    .execute(&pool)
+    .await?;
+    let payload = serde_json::json!({ "content": "the borrow checker enforces lifetimes" });
+    let response = app.clone().oneshot(suggest(&author_token, payload)).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let suggestions: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert!(
+        suggestions["tags"]
+            .as_array()
+            .context("缺少标签推荐")?
+            .iter()
+            .all(|s| s["tag"]["id"] != rust.id.to_string())
+    );
+
+    // 3. 模型已缓存，新建的别名仍然立即生效
+    sqlx::query!(
+        "INSERT INTO tag_aliases (tag_id, name, slug) VALUES ($1, '美味', 'mei-wei')",
+        cooking.id
+    )
+    .execute(&pool)
+    .await?;
+    let payload = serde_json::json!({ "title": "一道美味的菜", "content": "" });
+    let response = app.clone().oneshot(suggest(&author_token, payload)).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let suggestions: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let cooking_suggestion = suggestions["tags"]
+        .as_array()
+        .context("缺少标签推荐")?
+        .iter()
+        .find(|s| s["tag"]["id"] == cooking.id.to_string())
+        .context("缺少美食推荐")?;
+    assert_eq!(cooking_suggestion["reasons"][0], "标题中提到了 '美味'");
+
+    // 4. 不能为别人的文章获取推荐
+    let response = app
+        .clone()
+        .oneshot(suggest(&other_token, serde_json::json!({})))
+        .await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+// == 自动保存 (/posts/{id}/autosave)
+
+#[sqlx::test]
+async fn test_autosave_is_private_and_cleared_on_save(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    let (viewer_token, viewer_id) = register_and_login_new_user(&app).await?;
+    let (editor_token, editor_id) = register_and_login_new_user(&app).await?;
+    let draft = seed_one_post(&pool, author_id, "自动保存的草稿", "已保存的内容", false).await?;
+
+    // 作者把草稿分享给查看者和共享编辑者
+    let share_payload = serde_json::json!({
+        "shares": [
+            { "user_id": viewer_id, "role": "viewer" },
+            { "user_id": editor_id, "role": "editor" }
+        ],
+        "is_public": false
+    });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/share", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&share_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 作者自动保存，文章已保存的内容不变
+    let autosave_payload =
+        serde_json::json!({ "title": "自动保存的草稿", "content": "正在编辑的内容" });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/autosave", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&autosave_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let content = sqlx::query_scalar!("SELECT content FROM posts WHERE id = $1", draft.id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(content, "已保存的内容");
+
+    // 作者可以取回自己的自动保存
+    let request = Request::builder()
+        .uri(format!("/posts/{}/autosave", draft.id))
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let autosave: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(autosave["content"], "正在编辑的内容");
+
+    // 分享草稿的查看者看不到作者的自动保存，也不能写入自动保存
+    let request = Request::builder()
+        .uri(format!("/posts/{}/autosave", draft.id))
+        .header("Authorization", format!("Bearer {}", viewer_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/autosave", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", viewer_token))
+        .body(Body::from(serde_json::to_vec(&autosave_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 共享编辑者可以写入自己的自动保存，取回的也只是自己的那一份
+    let editor_payload =
+        serde_json::json!({ "title": "自动保存的草稿", "content": "共享编辑者的内容" });
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}/autosave", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", editor_token))
+        .body(Body::from(serde_json::to_vec(&editor_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    for (token, expected) in [
+        (&editor_token, "共享编辑者的内容"),
+        (&author_token, "正在编辑的内容"),
+    ] {
+        let request = Request::builder()
+            .uri(format!("/posts/{}/autosave", draft.id))
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::empty())?;
+        let response = app.clone().oneshot(request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body_bytes = response.into_body().collect().await?.to_bytes();
+        let autosave: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+        assert_eq!(autosave["content"], expected);
+    }
+
+    // 查看者仍然看不到任何人的自动保存
+    let request = Request::builder()
+        .uri(format!("/posts/{}/autosave", draft.id))
+        .header("Authorization", format!("Bearer {}", viewer_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // 显式保存后，自动保存被删除
+    let current_version = sqlx::query_scalar!(
+        r#"SELECT updated_at as "updated_at!" FROM posts WHERE id = $1"#,
+        draft.id
+    )
+    .fetch_one(&pool)
+    .await?;
+    let update_payload = UpdatePostPayload {
+        content: Some("正在编辑的内容".to_string()),
+        ..Default::default()
+    };
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/posts/{}", draft.id))
+        .header("Content-Type", "application/json")
+        .header(
+            "If-Match",
+            format!("\"{}\"", current_version.timestamp_micros()),
+        )
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&update_payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .uri(format!("/posts/{}/autosave", draft.id))
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+// == 回收站 (/trash)
+
+#[sqlx::test]
+async fn test_deleted_post_goes_to_trash_and_can_be_restored(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (owner_token, owner_id) = register_and_login_new_user(&app).await?;
+    let (other_token, _other_id) = register_and_login_new_user(&app).await?;
+    let post = seed_one_post(&pool, owner_id, "进入回收站的帖子", "c", true).await?;
+
+    let request = Request::builder()
+        .method(Method::DELETE)
+        .uri(format!("/posts/{}", post.id))
+        .header("Authorization", format!("Bearer {}", owner_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // 已删除的帖子在常规查询中不可见
+    let request = Request::builder()
+        .uri(format!("/posts/{}", post.id))
+        .header("Authorization", format!("Bearer {}", owner_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // 作者能在回收站中看到它，其他用户看不到
+    let request = Request::builder()
+        .uri("/trash")
+        .header("Authorization", format!("Bearer {}", owner_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let trash: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(trash["posts"].as_array().map(|p| p.len()), Some(1));
+    assert_eq!(trash["posts"][0]["id"], post.id.to_string());
+
+    let request = Request::builder()
+        .uri("/trash")
+        .header("Authorization", format!("Bearer {}", other_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let trash: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(trash["posts"].as_array().map(|p| p.len()), Some(0));
+
+    // 其他用户不能恢复
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/trash/posts/{}/restore", post.id))
+        .header("Authorization", format!("Bearer {}", other_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 作者恢复后帖子重新可见
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/trash/posts/{}/restore", post.id))
+        .header("Authorization", format!("Bearer {}", owner_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let request = Request::builder()
+        .uri(format!("/posts/{}", post.id))
+        .header("Authorization", format!("Bearer {}", owner_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 已恢复的帖子不在回收站中，再次恢复返回 404
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/trash/posts/{}/restore", post.id))
+        .header("Authorization", format!("Bearer {}", owner_token))
+        .body(Body::empty())?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+// == 草稿预览链接 (/posts/{id}/preview-links, /blog/preview/{token})
+
+#[sqlx::test]
+async fn test_preview_link_lifecycle(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let (author_token, author_id) = register_and_login_new_user(&app).await?;
+    let (other_token, other_id) = register_and_login_new_user(&app).await?;
+    let draft = seed_one_post(&pool, author_id, "给客户预览的草稿", "草稿内容", false).await?;
+    sqlx::query!(
+        "INSERT INTO draft_shares (post_id, user_id, role) VALUES ($1, $2, 'viewer')",
+        draft.id,
+        other_id
+    )
+    .execute(&pool)
+    .await?;
+
+    // 其他用户不能为别人的草稿创建预览链接
+    let payload = serde_json::json!({ "label": "客户审阅", "expires_in_hours": 24 });
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/posts/{}/preview-links", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", other_token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/posts/{}/preview-links", draft.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let link: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    let token = link["token"]
+        .as_str()
+        .context("响应中缺少 token")?
+        .to_string();
+    let link_id = link["id"].as_str().context("响应中缺少 id")?.to_string();
+
+    // 没有账号也能通过链接只读查看草稿
+    let request = Request::builder()
+        .uri(format!("/blog/preview/{}", token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let preview: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(preview["title"], "给客户预览的草稿");
+    // 匿名访问者看不到草稿的分享设置
+    assert!(preview.get("draft_shared_with").is_none());
+    assert!(preview.get("is_draft_public").is_none());
+
+    // 篡改过的令牌无效
+    let last = if token.ends_with('0') { '1' } else { '0' };
+    let tampered = format!("{}{}", &token[..token.len() - 1], last);
+    let request = Request::builder()
+        .uri(format!("/blog/preview/{}", tampered))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // 每次使用都记录在草稿访问日志中
+    let logged = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM draft_access_logs WHERE post_id = $1 AND access_type = 'preview_link' AND accessed_by IS NULL",
+        draft.id
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(logged, Some(1));
+
+    // 作者可以列出有效链接
+    let request = Request::builder()
+        .uri(format!("/posts/{}/preview-links", draft.id))
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let links: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(links.as_array().map(|l| l.len()), Some(1));
+    assert_eq!(links[0]["use_count"], 1);
+
+    // 撤销后链接立即失效
+    let request = Request::builder()
+        .method(Method::DELETE)
+        .uri(format!("/posts/{}/preview-links/{}", draft.id, link_id))
+        .header("Authorization", format!("Bearer {}", author_token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let request = Request::builder()
+        .uri(format!("/blog/preview/{}", token))
+        .body(Body::empty())?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
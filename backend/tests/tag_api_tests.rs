@@ -5,21 +5,29 @@ use axum::{
     http::{Method, Request, StatusCode},
 };
 use backend::{
-    config::{AppConfig, AuthConfig, DatabaseConfig, DraftPolicy, EmailConfig, ServerConfig},
-    dtos::tag::{CreateTagPayload, UpdateTagPayload},
+    config::{
+        AppConfig, AuthConfig, AutosaveConfig, DatabaseConfig, DraftPolicy, EmailConfig,
+        MarkdownConfig, ReportConfig, ServerConfig, TrashConfig, default_render_profiles,
+        default_shortcodes,
+    },
+    dtos::tag::{CreateTagAliasPayload, CreateTagPayload, MergeTagsPayload, UpdateTagPayload},
     handlers::AppState,
-    models::{Role, Tag, User},
+    models::{Role, Tag, TagAlias, User},
     repositories::{
         CategoryRepository, LoginAttemptRepository, OneTimeTokenRepository, PermissionRepository,
-        PostRepository, PostgresCategoryRepository, PostgresLoginAttemptRepository,
-        PostgresOneTimeTokenRepository, PostgresPermissionRepository, PostgresPostRepository,
+        PostRepository, PostgresCategoryRepository, PostgresDraftCommentRepository,
+        PostgresLoginAttemptRepository, PostgresNotificationPreferenceRepository,
+        PostgresOneTimeTokenRepository, PostgresPermissionRepository,
+        PostgresPostAutosaveRepository, PostgresPostPreviewLinkRepository,
+        PostgresPostReportRepository, PostgresPostRepository, PostgresPostTemplateRepository,
         PostgresRoleRepository, PostgresTagRepository, PostgresUserRepository, RoleRepository,
         TagRepository, UserRepository,
     },
     routes::create_router,
     services::{
-        AdminService, AuthService, CategoryService, EmailService, PostService, TagService,
-        UserService,
+        AdminService, AuthService, AutosaveService, CategoryService, DraftCommentService,
+        EmailService, NotificationService, PostService, PostTemplateService, PreviewLinkService,
+        ReportService, TagService, TaxonomySuggestionService, TrashService, UserService,
     },
     utils::hash_password,
 };
@@ -70,12 +78,32 @@ async fn setup_test_app(pool: PgPool) -> Router {
             smtp_user: "".to_string(),
             smtp_pass: "".to_string(),
             from_address: "test@example.com".to_string(),
+            admin_base_url: "http://localhost:5173".to_string(),
         },
         draft_policy: DraftPolicy {
             mode: "private".to_string(),
             admin_access_all_drafts: false,
             audit_draft_access: true,
         },
+        autosave: AutosaveConfig {
+            retention_days: 30,
+            purge_interval_minutes: 60,
+        },
+        trash: TrashConfig {
+            retention_days: 30,
+            purge_interval_minutes: 60,
+        },
+        reports: ReportConfig {
+            max_reports_per_hour: 3,
+            trust_forwarded_for: true,
+        },
+        markdown: MarkdownConfig {
+            highlight_languages: vec!["rust".to_string(), "python".to_string()],
+            line_numbers: false,
+            shortcodes: default_shortcodes(),
+            default_profile: "standard".to_string(),
+            profiles: default_render_profiles(),
+        },
     };
     let user_repo: Arc<dyn UserRepository> = Arc::new(PostgresUserRepository::new(pool.clone()));
     let role_repo: Arc<dyn RoleRepository> = Arc::new(PostgresRoleRepository::new(pool.clone()));
@@ -90,12 +118,18 @@ async fn setup_test_app(pool: PgPool) -> Router {
     let tag_repo: Arc<dyn TagRepository> = Arc::new(PostgresTagRepository::new(pool.clone()));
     let post_repo: Arc<dyn PostRepository> = Arc::new(PostgresPostRepository::new(pool.clone()));
     let email_service = Arc::new(EmailService::new(test_config.email.clone()));
+    let notification_service = Arc::new(NotificationService::new(
+        Arc::new(PostgresNotificationPreferenceRepository::new(pool.clone())),
+        user_repo.clone(),
+        email_service.clone(),
+        &test_config.email,
+    ));
     let auth_service = Arc::new(AuthService::new(
         user_repo.clone(),
         role_repo.clone(),
         login_attempt_repo,
         one_time_token_repo,
-        email_service,
+        email_service.clone(),
         &test_config,
     ));
     let admin_service = Arc::new(AdminService::new(
@@ -111,6 +145,45 @@ async fn setup_test_app(pool: PgPool) -> Router {
         category_repo.clone(),
         tag_repo.clone(),
         user_repo.clone(),
+        notification_service.clone(),
+        &test_config.markdown,
+    ));
+    let draft_comment_service = Arc::new(DraftCommentService::new(
+        Arc::new(PostgresDraftCommentRepository::new(pool.clone())),
+        post_repo.clone(),
+    ));
+    let autosave_service = Arc::new(AutosaveService::new(
+        Arc::new(PostgresPostAutosaveRepository::new(pool.clone())),
+        post_repo.clone(),
+        &test_config.autosave,
+    ));
+    let trash_service = Arc::new(TrashService::new(
+        post_repo.clone(),
+        category_repo.clone(),
+        tag_repo.clone(),
+        &test_config.trash,
+    ));
+    let preview_link_service = Arc::new(PreviewLinkService::new(
+        Arc::new(PostgresPostPreviewLinkRepository::new(pool.clone())),
+        post_repo.clone(),
+        &test_config.auth,
+    ));
+    let report_service = Arc::new(ReportService::new(
+        Arc::new(PostgresPostReportRepository::new(pool.clone())),
+        post_repo.clone(),
+        post_service.clone(),
+        &test_config.reports,
+        &test_config.auth,
+    ));
+    let post_template_service = Arc::new(PostTemplateService::new(
+        Arc::new(PostgresPostTemplateRepository::new(pool.clone())),
+        category_repo.clone(),
+        tag_repo.clone(),
+    ));
+    let taxonomy_suggestion_service = Arc::new(TaxonomySuggestionService::new(
+        post_repo.clone(),
+        tag_repo.clone(),
+        category_repo.clone(),
     ));
     let app_state = AppState {
         post_service,
@@ -119,6 +192,14 @@ async fn setup_test_app(pool: PgPool) -> Router {
         auth_service,
         admin_service,
         user_service,
+        draft_comment_service,
+        autosave_service,
+        trash_service,
+        preview_link_service,
+        notification_service,
+        report_service,
+        post_template_service,
+        taxonomy_suggestion_service,
     };
     create_router(app_state)
 }
@@ -214,7 +295,7 @@ async fn register_and_login_new_user(app: &Router) -> Result<(String, Uuid)> {
 async fn seed_one_tag(pool: &PgPool, name: &str) -> Result<Tag> {
     sqlx::query_as!(
         Tag,
-        r#"INSERT INTO tags (id, name, slug) VALUES ($1, $2, $3) RETURNING *"#,
+        r#"INSERT INTO tags (id, name, slug) VALUES ($1, $2, $3) RETURNING id, name, slug, created_at, updated_at"#,
         Uuid::new_v4(),
         name,
         slugify(name)
@@ -288,6 +369,10 @@ async fn test_update_tag_as_editor_success(pool: PgPool) -> Result<()> {
         .method(Method::PUT)
         .uri(format!("/tags/{}", tag.id))
         .header("Content-Type", "application/json")
+        .header(
+            "If-Match",
+            format!("\"{}\"", tag.updated_at.timestamp_micros()),
+        )
         .header("Authorization", format!("Bearer {}", token))
         .body(Body::from(serde_json::to_vec(&payload)?))?;
     let response = app.oneshot(request).await?;
@@ -314,6 +399,214 @@ async fn test_delete_tag_as_editor_success(pool: PgPool) -> Result<()> {
     Ok(())
 }
 
+#[sqlx::test]
+async fn test_recreate_trashed_tag_and_restore_conflict(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let editor = seed_user_with_role(&pool, "editor_for_tag_trash", "editor").await?;
+    let token = get_token_for_user(&app, &editor.username, "StrongPassword123!").await?;
+    let trashed = seed_one_tag(&pool, "rust").await?;
+    let send = |method: Method, uri: String, payload: Option<serde_json::Value>| {
+        let builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token));
+        match payload {
+            Some(payload) => builder.body(Body::from(serde_json::to_vec(&payload).unwrap())),
+            None => builder.body(Body::empty()),
+        }
+        .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(send(Method::DELETE, format!("/tags/{}", trashed.id), None))
+        .await?;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // 回收站中的同名标签不妨碍重新创建
+    let payload = serde_json::json!({ "name": "rust" });
+    let response = app
+        .clone()
+        .oneshot(send(Method::POST, "/tags".to_string(), Some(payload)))
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // 名称已被占用时拒绝恢复
+    let uri = format!("/trash/tags/{}/restore", trashed.id);
+    let response = app.clone().oneshot(send(Method::PUT, uri, None)).await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let error: serde_json::Value = serde_json::from_slice(&body_bytes)?;
+    assert!(
+        error["error"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("已存在")
+    );
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_tag_aliases(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let editor = seed_user_with_role(&pool, "editor_for_tag_aliases", "editor").await?;
+    let token = get_token_for_user(&app, &editor.username, "StrongPassword123!").await?;
+    let rust = seed_one_tag(&pool, "Rust").await?;
+    let rust_lang = seed_one_tag(&pool, "rust-lang").await?;
+
+    // 合并后源标签的名称成为目标标签的别名
+    let payload = MergeTagsPayload {
+        target_tag_id: rust.id,
+        source_tag_ids: vec![rust_lang.id],
+        new_target_name: None,
+    };
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/admin/tags/merge")
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .uri("/tags/rust-lang")
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let fetched: Tag = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(fetched.id, rust.id);
+
+    // 按别名或大小写不同的名称创建标签时返回规范标签
+    for name in ["Rust-Lang", "rust"] {
+        let payload = CreateTagPayload {
+            name: name.to_string(),
+        };
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/tags")
+            .header("Content-Type", "application/json")
+            .header("Authorization", format!("Bearer {}", token))
+            .body(Body::from(serde_json::to_vec(&payload)?))?;
+        let response = app.clone().oneshot(request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body_bytes = response.into_body().collect().await?.to_bytes();
+        let resolved: Tag = serde_json::from_slice(&body_bytes)?;
+        assert_eq!(resolved.id, rust.id);
+    }
+
+    // 直接管理别名
+    let payload = CreateTagAliasPayload {
+        name: "RustLang".to_string(),
+    };
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/tags/{}/aliases", rust.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let alias: TagAlias = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(alias.slug, "rustlang");
+
+    // 已存在的别名不能重复添加
+    let payload = CreateTagAliasPayload {
+        name: "Rust Lang".to_string(),
+    };
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/tags/{}/aliases", rust.id))
+        .header("Content-Type", "application/json")
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::from(serde_json::to_vec(&payload)?))?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let request = Request::builder()
+        .uri(format!("/tags/{}/aliases", rust.id))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let aliases: Vec<TagAlias> = serde_json::from_slice(&body_bytes)?;
+    assert_eq!(aliases.len(), 2);
+
+    let request = Request::builder()
+        .method(Method::DELETE)
+        .uri(format!("/tags/{}/aliases/{}", rust.id, alias.id))
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::empty())?;
+    let response = app.clone().oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let request = Request::builder()
+        .uri("/tags/rustlang")
+        .body(Body::empty())?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_find_similar_tags_clusters_with_confidence(pool: PgPool) -> Result<()> {
+    let app = setup_test_app(pool.clone()).await;
+    let editor = seed_user_with_role(&pool, "editor_for_similar_tags", "editor").await?;
+    let token = get_token_for_user(&app, &editor.username, "StrongPassword123!").await?;
+    for name in [
+        "rustlang",
+        "Rust-Lang",
+        "javascript",
+        "javscript",
+        "Java",
+        "编程",
+        "biancheng",
+    ] {
+        seed_one_tag(&pool, name).await?;
+    }
+
+    let request = Request::builder()
+        .uri("/admin/tags/similar")
+        .header("Authorization", format!("Bearer {}", token))
+        .body(Body::empty())?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = response.into_body().collect().await?.to_bytes();
+    let groups: Vec<serde_json::Value> = serde_json::from_slice(&body_bytes)?;
+
+    let mut names: Vec<Vec<String>> = groups
+        .iter()
+        .map(|group| {
+            let mut names: Vec<String> = group["tags"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|tag| tag["name"].as_str().unwrap().to_string())
+                .collect();
+            names.sort();
+            names
+        })
+        .collect();
+    names.sort();
+    // Java 与 javascript 不相似；大小写、标点和拼音写法不同的名称归为一组
+    assert_eq!(
+        names,
+        vec![
+            vec!["Rust-Lang", "rustlang"],
+            vec!["biancheng", "编程"],
+            vec!["javascript", "javscript"],
+        ]
+    );
+    for group in &groups {
+        let confidence = group["confidence"].as_f64().unwrap();
+        assert!(confidence >= 0.6 && confidence <= 1.0);
+    }
+    Ok(())
+}
+
 // == 授权失败测试
 #[sqlx::test]
 async fn test_create_tag_no_token_fails(pool: PgPool) -> Result<()> {